@@ -3,6 +3,8 @@ pub mod oas30;
 #[cfg(feature = "oas31")]
 pub mod oas31;
 
+#[cfg(test)]
+mod conformance;
 mod util;
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
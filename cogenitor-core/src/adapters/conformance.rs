@@ -0,0 +1,413 @@
+//! A conformance suite for the [Spec] adapters. The checks are generic over
+//! the adapter and run against equivalent OAS 3.0 and 3.1 documents
+//! (`test-data/conformance-3.0.yaml` and `test-data/conformance-3.1.yaml`),
+//! one test per feature and adapter, so that the tests of both adapters
+//! show which features an adapter supports. The OAS 3.1 tests run with
+//! the `oas31` feature enabled.
+
+use std::collections::HashSet;
+
+use crate::types::{
+    BooleanOrSchema, Format, Header, MediaType, Operation, Parameter, ParameterLocation, PathItem,
+    RefOr, Reference, RequestBody, Response, Schema, Spec, StatusSpec,
+};
+
+/// The named schema `name`, resolved
+fn named_schema<S: Spec>(spec: &S, name: &str) -> S::Schema {
+    let (_, schema) = spec
+        .schemata_iter()
+        .find(|(schema_name, _)| schema_name == name)
+        .unwrap_or_else(|| panic!("schema {name} not found"));
+    schema.resolve_fully()
+}
+
+fn property<S: Schema>(schema: &S, name: &str) -> RefOr<S> {
+    schema
+        .properties()
+        .shift_remove(name)
+        .unwrap_or_else(|| panic!("property {name} not found"))
+}
+
+fn operation<S: Spec>(spec: &S, path: &str, method: http::Method) -> S::Operation {
+    let (_, path_item) = spec
+        .paths()
+        .find(|(p, _)| p == path)
+        .unwrap_or_else(|| panic!("path {path} not found"));
+    path_item
+        .operations_iter()
+        .find(|(m, _)| *m == method)
+        .map(|(_, operation)| operation)
+        .unwrap_or_else(|| panic!("{method} {path} not found"))
+}
+
+fn response<S: Spec>(operation: &S::Operation, status: StatusSpec) -> S::Response {
+    operation
+        .responses()
+        .find(|(status_spec, _)| *status_spec == status)
+        .map(|(_, response)| response.resolve_fully())
+        .unwrap_or_else(|| panic!("response {status:?} not found"))
+}
+
+/// The URI of a reference, failing for inline objects
+fn reference_uri<O: crate::types::ByReference>(ro: &RefOr<O>) -> String {
+    match ro {
+        RefOr::Reference(r) => r.uri().to_string(),
+        RefOr::Object(_) => panic!("expected a reference"),
+    }
+}
+
+pub fn check_schema_iteration<S: Spec>(spec: &S) {
+    let names = spec
+        .schemata_iter()
+        .map(|(name, _)| name)
+        .collect::<HashSet<_>>();
+    assert_eq!(
+        names,
+        HashSet::from(["Pet", "Owner", "PetList", "Error"].map(String::from))
+    );
+    assert_eq!(named_schema(spec, "Pet").name(), Some("Pet"));
+    assert!(spec.components().is_some());
+}
+
+pub fn check_ref_resolution<S: Spec>(spec: &S) {
+    let owner = property(&named_schema(spec, "Pet"), "owner");
+    assert_eq!(reference_uri(&owner), "#/components/schemas/Owner");
+    assert_eq!(owner.resolve_fully().name(), Some("Owner"));
+
+    // parameters, responses and request bodies may be references as well
+    let show_pet = operation(spec, "/pets/{petId}", http::Method::GET);
+    let (_, path_item) = spec.paths().find(|(p, _)| p == "/pets/{petId}").unwrap();
+    let params = path_item.parameters().collect::<Vec<_>>();
+    assert_eq!(params.len(), 1);
+    assert_eq!(reference_uri(&params[0]), "#/components/parameters/PetId");
+    assert_eq!(params[0].resolve_fully().name(), "petId");
+    let (_, error) = show_pet
+        .responses()
+        .find(|(status, _)| *status == StatusSpec::Default)
+        .unwrap();
+    assert_eq!(reference_uri(&error), "#/components/responses/Error");
+    assert!(
+        error
+            .resolve_fully()
+            .content()
+            .contains_key("application/json")
+    );
+    let update_pet = operation(spec, "/pets", http::Method::PUT);
+    let body = update_pet.request_body().unwrap();
+    assert_eq!(reference_uri(&body), "#/components/requestBodies/Pet");
+    assert!(body.resolve_fully().required());
+}
+
+pub fn check_properties<S: Spec>(spec: &S) {
+    let pet = named_schema(spec, "Pet");
+    assert_eq!(pet.title(), Some("A pet"));
+    assert_eq!(pet.description(), Some("A pet in the store"));
+    let names = pet.properties().into_keys().collect::<HashSet<_>>();
+    assert_eq!(
+        names,
+        HashSet::from(["id", "name", "status", "metadata", "owner"].map(String::from))
+    );
+
+    let mut required = pet.required().unwrap();
+    required.sort();
+    assert_eq!(required, vec!["id", "name"]);
+
+    let id = property(&pet, "id").resolve_fully();
+    assert_eq!(id.format(), Some(Format::Int64));
+    assert!(id.is_integer());
+    let name = property(&pet, "name").resolve_fully();
+    assert_eq!(name.min_length(), Some(1));
+    assert_eq!(name.max_length(), Some(64));
+    assert_eq!(name.format(), None);
+}
+
+pub fn check_enums<S: Spec>(spec: &S) {
+    let status = property(&named_schema(spec, "Pet"), "status").resolve_fully();
+    let values = status.enum_().unwrap();
+    assert_eq!(
+        values,
+        vec![
+            json::JsonValue::from("available"),
+            json::JsonValue::from("pending"),
+            json::JsonValue::from("sold"),
+        ]
+    );
+    assert_eq!(named_schema(spec, "Owner").enum_(), None);
+}
+
+pub fn check_items<S: Spec>(spec: &S) {
+    let items = named_schema(spec, "PetList").items().unwrap();
+    assert_eq!(items.len(), 1);
+    assert_eq!(reference_uri(&items[0]), "#/components/schemas/Pet");
+    assert!(named_schema(spec, "Pet").items().is_none());
+}
+
+pub fn check_additional_properties<S: Spec>(spec: &S) {
+    let metadata = property(&named_schema(spec, "Pet"), "metadata").resolve_fully();
+    match metadata.addtional_properties() {
+        BooleanOrSchema::Schema(values) => assert!(values.type_().is_some()),
+        BooleanOrSchema::Boolean(_) => panic!("expected a schema for additionalProperties"),
+    }
+    assert!(matches!(
+        named_schema(spec, "Owner").addtional_properties(),
+        BooleanOrSchema::Boolean(false)
+    ));
+    // absent means any additional property is allowed
+    assert!(matches!(
+        named_schema(spec, "Error").addtional_properties(),
+        BooleanOrSchema::Boolean(true)
+    ));
+}
+
+pub fn check_operations<S: Spec>(spec: &S) {
+    let paths = spec.paths().map(|(path, _)| path).collect::<Vec<_>>();
+    assert_eq!(paths, vec!["/pets", "/pets/{petId}"]);
+    let (_, pets) = spec.paths().next().unwrap();
+    let methods = pets
+        .operations_iter()
+        .map(|(method, _)| method)
+        .collect::<HashSet<_>>();
+    assert_eq!(
+        methods,
+        HashSet::from([http::Method::GET, http::Method::POST, http::Method::PUT])
+    );
+
+    let list_pets = operation(spec, "/pets", http::Method::GET);
+    assert_eq!(list_pets.operation_id(), Some("listPets"));
+    assert_eq!(list_pets.summary(), Some("List all pets"));
+    assert_eq!(
+        list_pets.description(),
+        Some("Lists the pets, a page at a time")
+    );
+    assert_eq!(list_pets.tags().collect::<Vec<_>>(), vec!["pets"]);
+    assert!(list_pets.request_body().is_none());
+}
+
+pub fn check_parameters<S: Spec>(spec: &S) {
+    let locations_of = |params: Vec<RefOr<S::Parameter>>| {
+        params
+            .into_iter()
+            .map(|param| {
+                let param = param.resolve_fully();
+                (param.name().to_string(), param.in_(), param.required())
+            })
+            .collect::<Vec<_>>()
+    };
+
+    let (_, pets) = spec.paths().find(|(p, _)| p == "/pets").unwrap();
+    assert_eq!(
+        locations_of(pets.parameters().collect()),
+        vec![("X-Request-Id".to_string(), ParameterLocation::Header, false)]
+    );
+    let list_pets = operation(spec, "/pets", http::Method::GET);
+    assert_eq!(
+        locations_of(list_pets.parameters().collect()),
+        vec![
+            ("limit".to_string(), ParameterLocation::Query, false),
+            ("session".to_string(), ParameterLocation::Cookie, true),
+        ]
+    );
+    let (_, pet) = spec.paths().find(|(p, _)| p == "/pets/{petId}").unwrap();
+    assert_eq!(
+        locations_of(pet.parameters().collect()),
+        vec![("petId".to_string(), ParameterLocation::Path, true)]
+    );
+
+    let limit = list_pets.parameters().next().unwrap().resolve_fully();
+    assert_eq!(
+        limit.schema().unwrap().resolve_fully().format(),
+        Some(Format::Int32)
+    );
+    assert!(limit.content().is_none());
+}
+
+pub fn check_request_bodies<S: Spec>(spec: &S) {
+    let create_pet = operation(spec, "/pets", http::Method::POST);
+    let body = create_pet.request_body().unwrap().resolve_fully();
+    assert!(body.required());
+    assert_eq!(body.description(), Some("the pet to create"));
+    let media_types = body.content().into_keys().collect::<HashSet<_>>();
+    assert_eq!(
+        media_types,
+        HashSet::from(["application/json", "application/xml"].map(String::from))
+    );
+}
+
+pub fn check_responses<S: Spec>(spec: &S) {
+    let show_pet = operation(spec, "/pets/{petId}", http::Method::GET);
+    let statuses = show_pet
+        .responses()
+        .map(|(status, _)| status)
+        .collect::<Vec<_>>();
+    for status in [
+        StatusSpec::Informational(101),
+        StatusSpec::Informational1XX,
+        StatusSpec::Success(200),
+        StatusSpec::Success2XX,
+        StatusSpec::Redirection(301),
+        StatusSpec::Redirection3XX,
+        StatusSpec::ClientError(404),
+        StatusSpec::ClientError4XX,
+        StatusSpec::ServerError(500),
+        StatusSpec::ServerError5XX,
+        StatusSpec::Default,
+    ] {
+        assert!(statuses.contains(&status), "{status:?} missing");
+    }
+    assert_eq!(statuses.len(), 11);
+
+    let list_pets = operation(spec, "/pets", http::Method::GET);
+    let page = response::<S>(&list_pets, StatusSpec::Success(200));
+    let headers = page.headers();
+    let total = headers["X-Total-Count"].resolve_fully();
+    assert!(total.required());
+    assert!(total.schema().unwrap().resolve_fully().is_integer());
+    assert!(page.links().is_empty());
+}
+
+pub fn check_content<S: Spec>(spec: &S) {
+    let list_pets = operation(spec, "/pets", http::Method::GET);
+    let content = response::<S>(&list_pets, StatusSpec::Success(200)).content();
+    assert_eq!(content.len(), 1);
+    let schema = content["application/json"].schema().unwrap();
+    assert_eq!(reference_uri(&schema), "#/components/schemas/PetList");
+
+    let show_pet = operation(spec, "/pets/{petId}", http::Method::GET);
+    assert!(
+        response::<S>(&show_pet, StatusSpec::ClientError(404))
+            .content()
+            .is_empty()
+    );
+}
+
+#[cfg(feature = "oas30")]
+mod oas30 {
+    use std::str::FromStr;
+
+    use crate::adapters::oas30::OAS30Spec;
+
+    fn spec() -> OAS30Spec {
+        OAS30Spec::from_str(include_str!("../../../test-data/conformance-3.0.yaml")).unwrap()
+    }
+
+    #[test]
+    fn test_schema_iteration() {
+        super::check_schema_iteration(&spec());
+    }
+
+    #[test]
+    fn test_ref_resolution() {
+        super::check_ref_resolution(&spec());
+    }
+
+    #[test]
+    fn test_properties() {
+        super::check_properties(&spec());
+    }
+
+    #[test]
+    fn test_enums() {
+        super::check_enums(&spec());
+    }
+
+    #[test]
+    fn test_items() {
+        super::check_items(&spec());
+    }
+
+    #[test]
+    fn test_additional_properties() {
+        super::check_additional_properties(&spec());
+    }
+
+    #[test]
+    fn test_operations() {
+        super::check_operations(&spec());
+    }
+
+    #[test]
+    fn test_parameters() {
+        super::check_parameters(&spec());
+    }
+
+    #[test]
+    fn test_request_bodies() {
+        super::check_request_bodies(&spec());
+    }
+
+    #[test]
+    fn test_responses() {
+        super::check_responses(&spec());
+    }
+
+    #[test]
+    fn test_content() {
+        super::check_content(&spec());
+    }
+}
+
+#[cfg(feature = "oas31")]
+mod oas31 {
+    use std::str::FromStr;
+
+    use crate::adapters::oas31::OAS31Spec;
+
+    fn spec() -> OAS31Spec {
+        OAS31Spec::from_str(include_str!("../../../test-data/conformance-3.1.yaml")).unwrap()
+    }
+
+    #[test]
+    fn test_schema_iteration() {
+        super::check_schema_iteration(&spec());
+    }
+
+    #[test]
+    fn test_ref_resolution() {
+        super::check_ref_resolution(&spec());
+    }
+
+    #[test]
+    fn test_properties() {
+        super::check_properties(&spec());
+    }
+
+    #[test]
+    fn test_enums() {
+        super::check_enums(&spec());
+    }
+
+    #[test]
+    fn test_items() {
+        super::check_items(&spec());
+    }
+
+    #[test]
+    fn test_additional_properties() {
+        super::check_additional_properties(&spec());
+    }
+
+    #[test]
+    fn test_operations() {
+        super::check_operations(&spec());
+    }
+
+    #[test]
+    fn test_parameters() {
+        super::check_parameters(&spec());
+    }
+
+    #[test]
+    fn test_request_bodies() {
+        super::check_request_bodies(&spec());
+    }
+
+    #[test]
+    fn test_responses() {
+        super::check_responses(&spec());
+    }
+
+    #[test]
+    fn test_content() {
+        super::check_content(&spec());
+    }
+}
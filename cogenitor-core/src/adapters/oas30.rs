@@ -1,4 +1,5 @@
 mod obj;
+mod validate;
 
 #[cfg(test)]
 mod test;
@@ -99,7 +100,7 @@ impl<S: OAS30Source> OAS30Pointer<S> {
     }
 }
 
-#[derive(Clone, PartialEq)]
+#[derive(Clone)]
 pub struct OAS30Reference {
     openapi: Rc<OpenAPI>,
     uri: String,
@@ -121,6 +122,14 @@ impl Hash for OAS30Reference {
     }
 }
 
+/// References are compared by URI, like they are hashed, instead of
+/// comparing the whole documents they belong to
+impl PartialEq for OAS30Reference {
+    fn eq(&self, other: &Self) -> bool {
+        self.uri == other.uri
+    }
+}
+
 impl Eq for OAS30Reference {}
 
 trait SourceFromUri {
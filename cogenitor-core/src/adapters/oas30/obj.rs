@@ -1,6 +1,7 @@
 //! Holds implementations for the OAS object definitions. Each module implements a single OAS object.
 
 mod components;
+mod header;
 mod media_type;
 mod operation;
 mod parameter;
@@ -11,6 +12,7 @@ mod schema;
 mod spec;
 
 pub use components::*;
+pub use header::*;
 pub use media_type::*;
 pub use operation::*;
 pub use parameter::*;
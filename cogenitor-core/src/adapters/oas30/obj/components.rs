@@ -1,7 +1,7 @@
 use super::super::{OAS30Pointer, OAS30Source, into_ref_or};
 use super::OAS30Spec;
-use super::SchemaSource;
-use crate::types::{Components, RefOr};
+use super::{HeaderSource, SchemaSource};
+use crate::types::{ApiKeyLocation, Components, RefOr, SecurityScheme};
 use std::fmt::Debug;
 use std::hash::Hash;
 
@@ -21,6 +21,8 @@ impl OAS30Source for ComponentsSource {
 
 impl Components<OAS30Spec> for OAS30Pointer<ComponentsSource> {
     fn schemas(&self) -> impl Iterator<Item = (String, RefOr<OAS30Pointer<SchemaSource>>)> {
+        // openapiv3 keeps the schemas in an IndexMap, so they are yielded
+        // in declaration order
         self.inner().schemas.iter().map(|(name, schema_ro)| {
             (
                 name.clone(),
@@ -30,4 +32,49 @@ impl Components<OAS30Spec> for OAS30Pointer<ComponentsSource> {
             )
         })
     }
+
+    fn headers(&self) -> impl Iterator<Item = (String, RefOr<OAS30Pointer<HeaderSource>>)> {
+        self.inner().headers.iter().map(|(name, header_ro)| {
+            (
+                name.clone(),
+                into_ref_or(header_ro, self, |_| HeaderSource::Uri {
+                    uri: format!("#/components/headers/{name}"),
+                }),
+            )
+        })
+    }
+
+    fn security_schemes(&self) -> impl Iterator<Item = (String, SecurityScheme)> {
+        self.inner()
+            .security_schemes
+            .iter()
+            .filter_map(|(name, scheme_ro)| {
+                // references would point to other security schemes in the
+                // same section, which we yield anyway
+                let openapiv3::ReferenceOr::Item(scheme) = scheme_ro else {
+                    return None;
+                };
+                let scheme = match scheme {
+                    openapiv3::SecurityScheme::APIKey { location, name, .. } => {
+                        let location = match location {
+                            openapiv3::APIKeyLocation::Query => ApiKeyLocation::Query,
+                            openapiv3::APIKeyLocation::Header => ApiKeyLocation::Header,
+                            openapiv3::APIKeyLocation::Cookie => ApiKeyLocation::Cookie,
+                        };
+                        SecurityScheme::ApiKey {
+                            name: name.clone(),
+                            location,
+                        }
+                    }
+                    openapiv3::SecurityScheme::HTTP { scheme, .. } => SecurityScheme::Http {
+                        scheme: scheme.clone(),
+                    },
+                    openapiv3::SecurityScheme::OAuth2 { .. } => SecurityScheme::OAuth2,
+                    openapiv3::SecurityScheme::OpenIDConnect { .. } => {
+                        SecurityScheme::OpenIdConnect
+                    }
+                };
+                Some((name.clone(), scheme))
+            })
+    }
 }
@@ -0,0 +1,81 @@
+use std::fmt::Debug;
+use std::hash::Hash;
+
+use openapiv3::ParameterSchemaOrContent;
+
+use crate::adapters::oas30::{
+    OAS3Resolver, OAS30Pointer, OAS30Source, OAS30Spec, ResponseSource, SchemaSource,
+    SourceFromUri, into_ref_or,
+};
+use crate::types::{Header, RefOr};
+
+impl OAS3Resolver<openapiv3::Header> for openapiv3::OpenAPI {
+    fn prefix(&self) -> &'static str {
+        "#/components/headers/"
+    }
+
+    fn resolve_reference(&self, reference: &str) -> Option<&openapiv3::Header> {
+        let ro = self.components.as_ref()?.headers.get(reference)?;
+        self.resolve(ro)
+    }
+}
+
+#[derive(Clone, Debug, Hash, PartialEq)]
+pub enum HeaderSource {
+    Uri {
+        uri: String,
+    },
+    Response {
+        ref_source: ResponseSource,
+        header_name: String,
+    },
+}
+
+impl OAS30Source for HeaderSource {
+    type OAS30Type = openapiv3::Header;
+
+    fn inner<'a, 'b>(&'a self, openapi: &'b openapiv3::OpenAPI) -> &'b Self::OAS30Type
+    where
+        'a: 'b,
+    {
+        match self {
+            HeaderSource::Uri { uri } => {
+                let header_name = uri
+                    .strip_prefix(OAS3Resolver::<openapiv3::Header>::prefix(openapi))
+                    .unwrap();
+                openapi.resolve_reference(header_name).unwrap()
+            }
+            HeaderSource::Response {
+                ref_source,
+                header_name,
+            } => {
+                let ro = ref_source.inner(openapi).headers.get(header_name).unwrap();
+                openapi.resolve(ro).unwrap()
+            }
+        }
+    }
+}
+
+impl SourceFromUri for HeaderSource {
+    fn from_uri(uri: &str) -> Self {
+        HeaderSource::Uri {
+            uri: uri.to_string(),
+        }
+    }
+}
+
+impl Header<OAS30Spec> for OAS30Pointer<HeaderSource> {
+    fn required(&self) -> bool {
+        self.inner().required
+    }
+
+    fn schema(&self) -> Option<RefOr<OAS30Pointer<SchemaSource>>> {
+        if let ParameterSchemaOrContent::Schema(schema_ref) = &self.inner().format {
+            Some(into_ref_or(schema_ref, self, |src| {
+                SchemaSource::Header(Box::new(src.clone()))
+            }))
+        } else {
+            None
+        }
+    }
+}
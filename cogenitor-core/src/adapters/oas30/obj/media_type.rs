@@ -1,4 +1,3 @@
-use std::collections::HashMap;
 use std::fmt::Debug;
 use std::hash::Hash;
 
@@ -61,7 +60,7 @@ impl OAS30Source for MediaTypeSource {
 pub fn into_oas30_content(
     content: &IndexMap<String, openapiv3::MediaType>,
     src_fn: impl Fn(usize) -> OAS30Pointer<MediaTypeSource>,
-) -> HashMap<String, OAS30Pointer<MediaTypeSource>> {
+) -> IndexMap<String, OAS30Pointer<MediaTypeSource>> {
     content
         .as_slice()
         .iter()
@@ -5,9 +5,9 @@ use http::Method;
 
 use super::super::{OAS30Pointer, OAS30Source, ResponseSource, into_ref_or, to_parameters_iter};
 use super::{OAS30Spec, ParameterSource, PathItemSource, RequestBodySource};
-use crate::types::{Operation, RefOr, Spec, StatusSpec};
+use crate::types::{Operation, RefOr, SecurityRequirement, Spec, StatusSpec};
 
-#[derive(Debug, Clone, Hash, PartialEq)]
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
 pub struct OperationSource {
     pub(crate) path_item: PathItemSource,
     pub(crate) method: http::Method,
@@ -52,6 +52,53 @@ impl Operation<OAS30Spec> for OAS30Pointer<OperationSource> {
         self.inner().operation_id.as_deref()
     }
 
+    fn summary(&self) -> Option<&str> {
+        self.inner().summary.as_deref()
+    }
+
+    fn description(&self) -> Option<&str> {
+        self.inner().description.as_deref()
+    }
+
+    fn tags(&self) -> impl Iterator<Item = &str> {
+        self.inner().tags.iter().map(String::as_str)
+    }
+
+    fn security(&self) -> Option<Vec<SecurityRequirement>> {
+        self.inner().security.clone()
+    }
+
+    fn callbacks(
+        &self,
+    ) -> impl Iterator<Item = (String, Vec<(String, OAS30Pointer<PathItemSource>)>)> {
+        let operation = Box::new(self.ref_source.clone());
+        self.inner()
+            .callbacks
+            .iter()
+            .map(|(name, callback)| {
+                let path_items = callback
+                    .keys()
+                    .map(|expression| {
+                        let ref_source = PathItemSource::Callback {
+                            operation: operation.clone(),
+                            name: name.clone(),
+                            expression: expression.clone(),
+                        };
+                        (
+                            expression.clone(),
+                            OAS30Pointer {
+                                openapi: self.openapi.clone(),
+                                ref_source,
+                            },
+                        )
+                    })
+                    .collect();
+                (name.clone(), path_items)
+            })
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
     fn request_body(&self) -> Option<RefOr<OAS30Pointer<RequestBodySource>>> {
         self.inner().request_body.as_ref().map(|request_body| {
             into_ref_or(request_body, self, |src| RequestBodySource::Operation {
@@ -68,8 +115,20 @@ impl Operation<OAS30Spec> for OAS30Pointer<OperationSource> {
             RefOr<<OAS30Spec as Spec>::Response>,
         ),
     > {
-        self.inner().responses.responses.iter().enumerate().map(
-            |(content_index, (status, ro_response))| {
+        let responses = &self.inner().responses;
+        let default = responses.default.as_ref().map(|ro_response| {
+            (
+                StatusSpec::Default,
+                into_ref_or(ro_response, self, |src| ResponseSource::OperationDefault {
+                    ref_source: src.clone(),
+                }),
+            )
+        });
+        responses
+            .responses
+            .iter()
+            .enumerate()
+            .map(|(content_index, (status, ro_response))| {
                 let status = StatusSpec::try_from(status).unwrap();
                 (
                     status.clone(),
@@ -78,7 +137,7 @@ impl Operation<OAS30Spec> for OAS30Pointer<OperationSource> {
                         ref_source: src.clone(),
                     }),
                 )
-            },
-        )
+            })
+            .chain(default)
     }
 }
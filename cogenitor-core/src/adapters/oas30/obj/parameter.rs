@@ -1,7 +1,7 @@
-use std::collections::HashMap;
 use std::fmt::Debug;
 use std::hash::Hash;
 
+use indexmap::IndexMap;
 use openapiv3::{ParameterSchemaOrContent, ReferenceOr};
 
 use super::{OAS30Spec, OperationSource, PathItemSource, SchemaSource, into_oas30_content};
@@ -21,6 +21,17 @@ impl OAS3Resolver<openapiv3::Parameter> for openapiv3::OpenAPI {
     }
 }
 
+impl OAS3Resolver<openapiv3::Example> for openapiv3::OpenAPI {
+    fn prefix(&self) -> &'static str {
+        "#/components/examples/"
+    }
+
+    fn resolve_reference(&self, reference: &str) -> Option<&openapiv3::Example> {
+        let ro = self.components.as_ref()?.examples.get(reference)?;
+        self.resolve(ro)
+    }
+}
+
 pub fn to_parameters_iter(
     parent: &OAS30Pointer<impl OAS30Source>,
     oas30_parameters: &Vec<openapiv3::ReferenceOr<openapiv3::Parameter>>,
@@ -111,7 +122,12 @@ impl OAS30Source for ParameterSource {
         'a: 'b,
     {
         match self {
-            ParameterSource::Uri { uri } => openapi.resolve_reference(uri).unwrap(),
+            ParameterSource::Uri { uri } => {
+                let parameter_name = uri
+                    .strip_prefix(OAS3Resolver::<openapiv3::Parameter>::prefix(openapi))
+                    .unwrap();
+                openapi.resolve_reference(parameter_name).unwrap()
+            }
             ParameterSource::Operation {
                 source_ref,
                 param_id,
@@ -137,6 +153,13 @@ impl Parameter<OAS30Spec> for OAS30Pointer<ParameterSource> {
             .name
     }
 
+    fn required(&self) -> bool {
+        self.ref_source
+            .inner(&self.openapi)
+            .parameter_data_ref()
+            .required
+    }
+
     fn schema(&self) -> Option<RefOr<OAS30Pointer<SchemaSource>>> {
         if let ParameterSchemaOrContent::Schema(schema_ref) =
             &self.inner().parameter_data_ref().format
@@ -149,7 +172,7 @@ impl Parameter<OAS30Spec> for OAS30Pointer<ParameterSource> {
         }
     }
 
-    fn content(&self) -> Option<HashMap<String, OAS30Pointer<MediaTypeSource>>> {
+    fn content(&self) -> Option<IndexMap<String, OAS30Pointer<MediaTypeSource>>> {
         match &self.inner().parameter_data_ref().format {
             ParameterSchemaOrContent::Schema(_reference_or) => None,
             ParameterSchemaOrContent::Content(index_map) => {
@@ -165,4 +188,16 @@ impl Parameter<OAS30Spec> for OAS30Pointer<ParameterSource> {
             }
         }
     }
+
+    fn example(&self) -> Option<json::JsonValue> {
+        let parameter_data = self.inner().parameter_data_ref();
+        let example = parameter_data.example.as_ref().or_else(|| {
+            parameter_data
+                .examples
+                .values()
+                .filter_map(|ro| OAS3Resolver::<openapiv3::Example>::resolve(&*self.openapi, ro))
+                .find_map(|example| example.value.as_ref())
+        })?;
+        json::parse(&example.to_string()).ok()
+    }
 }
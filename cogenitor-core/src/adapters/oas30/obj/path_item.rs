@@ -13,15 +13,25 @@ impl OAS3Resolver<openapiv3::PathItem> for openapiv3::OpenAPI {
     }
 
     fn resolve_reference(&self, reference: &str) -> Option<&openapiv3::PathItem> {
-        let ro = self.paths.paths.get(reference)?;
+        // paths are JSON pointer escaped in references, like `~1pets`
+        let path = reference.replace("~1", "/").replace("~0", "~");
+        let ro = self.paths.paths.get(&path)?;
         self.resolve(ro)
     }
 }
 
 // OAS30 PathItem Implementation
 #[derive(Clone, Debug, PartialEq, Hash, Eq)]
-pub struct PathItemSource {
-    pub(crate) path: String,
+pub enum PathItemSource {
+    /// a path item in `#/paths`, by its path
+    Path(String),
+    /// a path item of an operation's callback, by the callback's name and
+    /// the runtime expression of the callback URL
+    Callback {
+        operation: Box<OperationSource>,
+        name: String,
+        expression: String,
+    },
 }
 
 impl OAS30Source for PathItemSource {
@@ -31,8 +41,17 @@ impl OAS30Source for PathItemSource {
     where
         'a: 'b,
     {
-        let ro_opt = openapi.paths.paths.get(&self.path);
-        ro_opt.and_then(|ro| openapi.resolve(ro)).unwrap()
+        match self {
+            PathItemSource::Path(path) => {
+                let ro_opt = openapi.paths.paths.get(path);
+                ro_opt.and_then(|ro| openapi.resolve(ro)).unwrap()
+            }
+            PathItemSource::Callback {
+                operation,
+                name,
+                expression,
+            } => &operation.inner(openapi).callbacks[name][expression],
+        }
     }
 }
 
@@ -1,7 +1,7 @@
-use std::collections::HashMap;
 use std::fmt::Debug;
 use std::hash::Hash;
 
+use indexmap::IndexMap;
 use openapiv3::ReferenceOr;
 
 use super::super::{
@@ -43,7 +43,12 @@ impl OAS30Source for RequestBodySource {
         'a: 'b,
     {
         match self {
-            RequestBodySource::Uri { uri } => openapi.resolve_reference(uri).unwrap(),
+            RequestBodySource::Uri { uri } => {
+                let request_body_name = uri
+                    .strip_prefix(OAS3Resolver::<openapiv3::RequestBody>::prefix(openapi))
+                    .unwrap();
+                openapi.resolve_reference(request_body_name).unwrap()
+            }
             RequestBodySource::Operation { source_ref } => source_ref
                 .inner(openapi)
                 .request_body
@@ -55,7 +60,7 @@ impl OAS30Source for RequestBodySource {
 }
 
 impl RequestBody<OAS30Spec> for OAS30Pointer<RequestBodySource> {
-    fn content(&self) -> HashMap<String, OAS30Pointer<MediaTypeSource>> {
+    fn content(&self) -> IndexMap<String, OAS30Pointer<MediaTypeSource>> {
         into_oas30_content(&self.inner().content, |content_index| OAS30Pointer {
             openapi: self.openapi.clone(),
             ref_source: MediaTypeSource::RequestBody {
@@ -67,4 +72,7 @@ impl RequestBody<OAS30Spec> for OAS30Pointer<RequestBodySource> {
     fn required(&self) -> bool {
         self.inner().required
     }
+    fn description(&self) -> Option<&str> {
+        self.inner().description.as_deref()
+    }
 }
@@ -1,15 +1,27 @@
-use std::collections::HashMap;
 use std::fmt::Debug;
 use std::hash::Hash;
 
+use indexmap::IndexMap;
+
 use crate::{
     adapters::oas30::{
-        MediaTypeSource, OAS3Resolver, OAS30Pointer, OAS30Source, OAS30Spec, OperationSource,
-        SourceFromUri, into_oas30_content,
+        HeaderSource, MediaTypeSource, OAS3Resolver, OAS30Pointer, OAS30Source, OAS30Spec,
+        OperationSource, SourceFromUri, into_oas30_content, into_ref_or,
     },
-    types::{Response, Spec},
+    types::{Link, RefOr, Response, Spec},
 };
 
+impl OAS3Resolver<openapiv3::Link> for openapiv3::OpenAPI {
+    fn prefix(&self) -> &'static str {
+        "#/components/links/"
+    }
+
+    fn resolve_reference(&self, reference: &str) -> Option<&openapiv3::Link> {
+        let ro = self.components.as_ref()?.links.get(reference)?;
+        self.resolve(ro)
+    }
+}
+
 impl OAS3Resolver<openapiv3::Response> for openapiv3::OpenAPI {
     fn prefix(&self) -> &'static str {
         "#/components/responses/"
@@ -30,6 +42,10 @@ pub enum ResponseSource {
         content_index: usize,
         ref_source: OperationSource,
     },
+    /// the `default` response of an operation
+    OperationDefault {
+        ref_source: OperationSource,
+    },
 }
 
 impl OAS30Source for ResponseSource {
@@ -40,7 +56,12 @@ impl OAS30Source for ResponseSource {
         'a: 'b,
     {
         match self {
-            ResponseSource::Uri { uri } => openapi.resolve_reference(uri).unwrap(),
+            ResponseSource::Uri { uri } => {
+                let response_name = uri
+                    .strip_prefix(OAS3Resolver::<openapiv3::Response>::prefix(openapi))
+                    .unwrap();
+                openapi.resolve_reference(response_name).unwrap()
+            }
             ResponseSource::Operation {
                 content_index,
                 ref_source,
@@ -54,12 +75,21 @@ impl OAS30Source for ResponseSource {
                     .1;
                 openapi.resolve(ro).unwrap()
             }
+            ResponseSource::OperationDefault { ref_source } => {
+                let ro = ref_source
+                    .inner(openapi)
+                    .responses
+                    .default
+                    .as_ref()
+                    .unwrap();
+                openapi.resolve(ro).unwrap()
+            }
         }
     }
 }
 
 impl Response<OAS30Spec> for OAS30Pointer<ResponseSource> {
-    fn content(&self) -> HashMap<String, <OAS30Spec as Spec>::MediaType> {
+    fn content(&self) -> IndexMap<String, <OAS30Spec as Spec>::MediaType> {
         into_oas30_content(&self.inner().content, |content_index| OAS30Pointer {
             openapi: self.openapi.clone(),
             ref_source: MediaTypeSource::Response {
@@ -68,6 +98,50 @@ impl Response<OAS30Spec> for OAS30Pointer<ResponseSource> {
             },
         })
     }
+
+    fn headers(&self) -> IndexMap<String, RefOr<<OAS30Spec as Spec>::Header>> {
+        self.inner()
+            .headers
+            .iter()
+            .map(|(name, header_ro)| {
+                let header = into_ref_or(header_ro, self, |src| HeaderSource::Response {
+                    ref_source: src.clone(),
+                    header_name: name.clone(),
+                });
+                (name.clone(), header)
+            })
+            .collect()
+    }
+
+    fn links(&self) -> IndexMap<String, Link> {
+        self.inner()
+            .links
+            .iter()
+            .filter_map(|(name, link_ro)| {
+                let link: &openapiv3::Link = self.openapi.resolve(link_ro)?;
+                let operation_id = match &link.operation {
+                    openapiv3::LinkOperation::OperationId(id) => Some(id.clone()),
+                    openapiv3::LinkOperation::OperationRef(_) => None,
+                };
+                let parameters = link
+                    .parameters
+                    .iter()
+                    .map(|(name, value)| {
+                        let value = json::parse(&value.to_string())
+                            .expect("serde_json values are valid JSON");
+                        (name.clone(), value)
+                    })
+                    .collect();
+                Some((
+                    name.clone(),
+                    Link {
+                        operation_id,
+                        parameters,
+                    },
+                ))
+            })
+            .collect()
+    }
 }
 
 impl SourceFromUri for ResponseSource {
@@ -1,10 +1,11 @@
+use std::borrow::Borrow;
 use std::hash::Hash;
-use std::{borrow::Borrow, collections::HashMap};
 
+use indexmap::IndexMap;
 use openapiv3::{ParameterSchemaOrContent, ReferenceOr, Type};
 
 use super::super::{MediaTypeSource, OAS3Resolver, OAS30Pointer, OAS30Source, SourceFromUri};
-use super::ParameterSource;
+use super::{HeaderSource, ParameterSource};
 use crate::types::{BooleanOrSchema, RefOr, Schema};
 
 use super::super::into_ref_or;
@@ -27,6 +28,10 @@ pub enum SchemaSource {
     Items(Box<SchemaSource>),
     OperationParam(Box<ParameterSource>),
     MediaType(Box<MediaTypeSource>),
+    Header(Box<HeaderSource>),
+    AllOf((Box<SchemaSource>, usize)),
+    AnyOf((Box<SchemaSource>, usize)),
+    OneOf((Box<SchemaSource>, usize)),
 }
 
 impl OAS30Source for SchemaSource {
@@ -70,6 +75,21 @@ impl OAS30Source for SchemaSource {
                     )
                 }
             }
+            SchemaSource::Header(header_source) => {
+                if let ParameterSchemaOrContent::Schema(schema_ro) =
+                    &header_source.inner(openapi).format
+                {
+                    openapi.resolve(schema_ro).unwrap()
+                } else {
+                    panic!("source created for schema from header where there is none defined")
+                }
+            }
+            SchemaSource::AllOf((schema_ref, index))
+            | SchemaSource::AnyOf((schema_ref, index))
+            | SchemaSource::OneOf((schema_ref, index)) => {
+                let ro = schema_from_composition(schema_ref.inner(openapi), self, *index).unwrap();
+                openapi.resolve(ro).unwrap()
+            }
         }
     }
 }
@@ -97,6 +117,18 @@ impl std::fmt::Debug for SchemaSource {
                 f.write_fmt(format_args!("{mediatype_source:?}.schema"))
             }
             SchemaSource::OperationParam(_) => f.write_str("InlineSchema"),
+            SchemaSource::Header(header_source) => {
+                f.write_fmt(format_args!("{header_source:?}.schema"))
+            }
+            SchemaSource::AllOf((oas30_schema_ref, index)) => {
+                f.write_fmt(format_args!("{oas30_schema_ref:?}.allOf[{index}]"))
+            }
+            SchemaSource::AnyOf((oas30_schema_ref, index)) => {
+                f.write_fmt(format_args!("{oas30_schema_ref:?}.anyOf[{index}]"))
+            }
+            SchemaSource::OneOf((oas30_schema_ref, index)) => {
+                f.write_fmt(format_args!("{oas30_schema_ref:?}.oneOf[{index}]"))
+            }
         }
     }
 }
@@ -118,16 +150,33 @@ impl Hash for SchemaSource {
                 state.write("i".as_bytes());
                 r.hash(state);
             }
-            SchemaSource::OperationParam(_) => {
-                state.write("inline".as_bytes());
-                // Note: We can't hash the schema content easily, so we just use a constant
-                // This means inline schemas will hash to the same value, which is not ideal
-                // but should work for basic functionality
+            SchemaSource::OperationParam(p) => {
+                state.write("o".as_bytes());
+                p.hash(state);
             }
             SchemaSource::MediaType(p) => {
                 state.write("m".as_bytes());
                 p.hash(state);
             }
+            SchemaSource::Header(h) => {
+                state.write("h".as_bytes());
+                h.hash(state);
+            }
+            SchemaSource::AllOf(c) => {
+                state.write("allOf".as_bytes());
+                c.0.hash(state);
+                c.1.hash(state);
+            }
+            SchemaSource::AnyOf(c) => {
+                state.write("anyOf".as_bytes());
+                c.0.hash(state);
+                c.1.hash(state);
+            }
+            SchemaSource::OneOf(c) => {
+                state.write("oneOf".as_bytes());
+                c.0.hash(state);
+                c.1.hash(state);
+            }
         }
     }
 }
@@ -142,14 +191,12 @@ impl PartialEq for SchemaSource {
                 s.eq(o)
             }
             (SchemaSource::Items(s), SchemaSource::Items(o)) => s.eq(o),
-            (SchemaSource::OperationParam(_), SchemaSource::OperationParam(_)) => {
-                todo!("this is broken, needs to compare path properly");
-                // For simplicity, we consider all inline schemas as different
-                // A proper implementation would compare schema content
-                #[allow(unreachable_code)]
-                false
-            }
+            (SchemaSource::OperationParam(s), SchemaSource::OperationParam(o)) => s.eq(o),
             (SchemaSource::MediaType(s), SchemaSource::MediaType(o)) => s.eq(o),
+            (SchemaSource::Header(s), SchemaSource::Header(o)) => s.eq(o),
+            (SchemaSource::AllOf(s), SchemaSource::AllOf(o)) => s.eq(o),
+            (SchemaSource::AnyOf(s), SchemaSource::AnyOf(o)) => s.eq(o),
+            (SchemaSource::OneOf(s), SchemaSource::OneOf(o)) => s.eq(o),
             _ => false,
         }
     }
@@ -189,6 +236,23 @@ fn schema_from_property<'a, 'b>(
     }
 }
 
+/// Yields the `index`th subschema of the composition (`allOf`, `anyOf` or `oneOf`)
+/// that `source` designates, provided `oas_schema` is of that composition kind.
+fn schema_from_composition<'a>(
+    oas_schema: &'a openapiv3::Schema,
+    source: &SchemaSource,
+    index: usize,
+) -> Option<&'a ReferenceOr<openapiv3::Schema>> {
+    use openapiv3::*;
+    let subschemas = match (&oas_schema.schema_kind, source) {
+        (SchemaKind::AllOf { all_of }, SchemaSource::AllOf(_)) => all_of,
+        (SchemaKind::AnyOf { any_of }, SchemaSource::AnyOf(_)) => any_of,
+        (SchemaKind::OneOf { one_of }, SchemaSource::OneOf(_)) => one_of,
+        _ => return None,
+    };
+    subschemas.get(index)
+}
+
 pub type OAS30SchemaPointer = OAS30Pointer<SchemaSource>;
 
 fn schema_name_of_reference_or(
@@ -204,10 +268,36 @@ fn schema_name_of_reference_or(
     }
 }
 
+impl OAS30Pointer<SchemaSource> {
+    /// Create pointers to the subschemas of a composition. Referenced subschemas
+    /// point to their location under `#/components/schemas`, inlined ones
+    /// are addressed by their index within the composition.
+    fn composition_pointers(
+        &self,
+        subschemas: &[ReferenceOr<openapiv3::Schema>],
+        variant: fn((Box<SchemaSource>, usize)) -> SchemaSource,
+    ) -> Vec<OAS30SchemaPointer> {
+        subschemas
+            .iter()
+            .enumerate()
+            .map(|(index, ro)| {
+                let ref_source = match ro {
+                    ReferenceOr::Reference { reference } => SchemaSource::Uri(reference.clone()),
+                    ReferenceOr::Item(_) => variant((Box::new(self.ref_source.clone()), index)),
+                };
+                OAS30Pointer {
+                    openapi: self.openapi.clone(),
+                    ref_source,
+                }
+            })
+            .collect()
+    }
+}
+
 impl Schema for OAS30Pointer<SchemaSource> {
     fn name(&self) -> Option<&str> {
         match &self.ref_source {
-            SchemaSource::Uri(uri) => uri.rsplit('/').last(),
+            SchemaSource::Uri(uri) => uri.rsplit('/').next(),
             SchemaSource::SchemaProperty((ref_source, name)) => {
                 // the name of a schema referenced via a property of
                 // onother schema is either tne name in the reference
@@ -251,13 +341,36 @@ impl Schema for OAS30Pointer<SchemaSource> {
                 .as_ref()
                 .and_then(|ro| schema_name_of_reference_or(ro)),
             SchemaSource::OperationParam(_) => None,
+            SchemaSource::Header(header_source) => {
+                match &header_source.inner(&self.openapi).format {
+                    ParameterSchemaOrContent::Schema(ro) => schema_name_of_reference_or(ro),
+                    ParameterSchemaOrContent::Content(_) => None,
+                }
+            }
+            SchemaSource::AllOf((schema_ref, index))
+            | SchemaSource::AnyOf((schema_ref, index))
+            | SchemaSource::OneOf((schema_ref, index)) => {
+                let ro = schema_from_composition(
+                    schema_ref.inner(&self.openapi),
+                    &self.ref_source,
+                    *index,
+                )?;
+                schema_name_of_reference_or(ro)
+            }
         }
     }
 
+    fn schema_dialect(&self) -> Option<&str> {
+        // OAS 3.0 schemas cannot declare a dialect
+        None
+    }
+
     fn type_(&self) -> Option<Vec<crate::types::Type>> {
         match &(self.inner().schema_kind) {
             openapiv3::SchemaKind::Type(t) => Some(vec![t.into()]),
-            _ => unimplemented!(),
+            // compositions are exposed via all_of()/any_of()/one_of(), and
+            // 'Any' schemas do not constrain the type
+            _ => None,
         }
     }
 
@@ -272,7 +385,10 @@ impl Schema for OAS30Pointer<SchemaSource> {
                     };
                     Some(fmt)
                 }
-                _ => None,
+                VariantOrUnknownOrEmpty::Unknown(ref format) => {
+                    Some(crate::types::Format::parse(format))
+                }
+                VariantOrUnknownOrEmpty::Empty => None,
             },
             SchemaKind::Type(Type::Integer(integer_type)) => match integer_type.format {
                 VariantOrUnknownOrEmpty::Item(integer_format) => {
@@ -282,7 +398,10 @@ impl Schema for OAS30Pointer<SchemaSource> {
                     };
                     Some(fmt)
                 }
-                _ => None,
+                VariantOrUnknownOrEmpty::Unknown(ref format) => {
+                    Some(crate::types::Format::parse(format))
+                }
+                VariantOrUnknownOrEmpty::Empty => None,
             },
             SchemaKind::Type(Type::String(string_type)) => match string_type.format {
                 VariantOrUnknownOrEmpty::Item(string_format) => {
@@ -295,19 +414,58 @@ impl Schema for OAS30Pointer<SchemaSource> {
                     };
                     Some(fmt)
                 }
-                VariantOrUnknownOrEmpty::Unknown(_) => todo!(),
-                VariantOrUnknownOrEmpty::Empty => todo!(),
+                // integer formats on strings are used for numbers that
+                // JSON cannot represent precisely (e.g. 64 bit IDs)
+                VariantOrUnknownOrEmpty::Unknown(ref format) => {
+                    Some(crate::types::Format::parse(format))
+                }
+                VariantOrUnknownOrEmpty::Empty => None,
             },
             _ => None,
         }
     }
 
+    fn is_integer(&self) -> bool {
+        matches!(
+            self.inner().schema_kind,
+            openapiv3::SchemaKind::Type(openapiv3::Type::Integer(_))
+        )
+    }
+
     fn title(&self) -> Option<&str> {
-        todo!()
+        self.inner().schema_data.title.as_deref()
     }
 
     fn description(&self) -> Option<&str> {
-        todo!()
+        self.inner().schema_data.description.as_deref()
+    }
+
+    fn min_length(&self) -> Option<usize> {
+        match &self.inner().schema_kind {
+            openapiv3::SchemaKind::Type(Type::String(string_type)) => string_type.min_length,
+            _ => None,
+        }
+    }
+
+    fn max_length(&self) -> Option<usize> {
+        match &self.inner().schema_kind {
+            openapiv3::SchemaKind::Type(Type::String(string_type)) => string_type.max_length,
+            _ => None,
+        }
+    }
+
+    fn min_properties(&self) -> Option<usize> {
+        match &self.inner().schema_kind {
+            openapiv3::SchemaKind::Type(Type::Object(object_type)) => object_type.min_properties,
+            _ => None,
+        }
+    }
+
+    fn max_properties(&self) -> Option<usize> {
+        match &self.inner().schema_kind {
+            openapiv3::SchemaKind::Type(Type::Object(object_type)) => object_type.max_properties,
+            _ => None,
+        }
     }
 
     fn required(&self) -> Option<Vec<&str>> {
@@ -319,25 +477,101 @@ impl Schema for OAS30Pointer<SchemaSource> {
         }
     }
 
-    fn all_of(&self) -> Option<Vec<impl Schema>> {
-        Option::<Vec<OAS30SchemaPointer>>::None
+    fn all_of(&self) -> Option<Vec<RefOr<Self>>> {
+        match &self.inner().schema_kind {
+            openapiv3::SchemaKind::AllOf { all_of } => Some(
+                all_of
+                    .iter()
+                    .enumerate()
+                    .map(|(index, ro)| {
+                        into_ref_or(ro, self, |src| {
+                            SchemaSource::AllOf((Box::new(src.clone()), index))
+                        })
+                    })
+                    .collect(),
+            ),
+            _ => None,
+        }
     }
 
     fn any_of(&self) -> Option<Vec<impl Schema>> {
-        Option::<Vec<OAS30SchemaPointer>>::None
+        match &self.inner().schema_kind {
+            openapiv3::SchemaKind::AnyOf { any_of } => {
+                Some(self.composition_pointers(any_of, SchemaSource::AnyOf))
+            }
+            _ => None,
+        }
     }
 
     fn one_of(&self) -> Option<Vec<impl Schema>> {
-        Option::<Vec<OAS30SchemaPointer>>::None
+        match &self.inner().schema_kind {
+            openapiv3::SchemaKind::OneOf { one_of } => {
+                Some(self.composition_pointers(one_of, SchemaSource::OneOf))
+            }
+            _ => None,
+        }
     }
 
     fn enum_(&self) -> Option<Vec<json::JsonValue>> {
-        todo!()
+        use openapiv3::*;
+        // `null` entries are kept, like in OAS 3.1 specs
+        let values: Vec<json::JsonValue> = match &self.inner().schema_kind {
+            SchemaKind::Type(Type::String(t)) => {
+                t.enumeration.iter().map(|v| v.clone().into()).collect()
+            }
+            SchemaKind::Type(Type::Number(t)) => {
+                t.enumeration.iter().map(|v| (*v).into()).collect()
+            }
+            SchemaKind::Type(Type::Integer(t)) => {
+                t.enumeration.iter().map(|v| (*v).into()).collect()
+            }
+            SchemaKind::Type(Type::Boolean(t)) => {
+                t.enumeration.iter().map(|v| (*v).into()).collect()
+            }
+            _ => Vec::new(),
+        };
+        if values.is_empty() {
+            None
+        } else {
+            Some(values)
+        }
+    }
+
+    fn const_value(&self) -> Option<json::JsonValue> {
+        // 'const' is not part of the OAS 3.0 schema object
+        None
+    }
+
+    fn default_value(&self) -> Option<json::JsonValue> {
+        let default = self.inner().schema_data.default.as_ref()?;
+        json::parse(&default.to_string()).ok()
+    }
+
+    fn example(&self) -> Option<json::JsonValue> {
+        let example = self.inner().schema_data.example.as_ref()?;
+        json::parse(&example.to_string()).ok()
+    }
+
+    fn extension_value(&self, key: &str) -> Option<json::JsonValue> {
+        let value = self.inner().schema_data.extensions.get(key)?;
+        json::parse(&value.to_string()).ok()
     }
 
-    fn properties(&self) -> std::collections::HashMap<String, RefOr<Self>> {
+    fn read_only(&self) -> bool {
+        self.inner().schema_data.read_only
+    }
+
+    fn write_only(&self) -> bool {
+        self.inner().schema_data.write_only
+    }
+
+    fn nullable(&self) -> bool {
+        self.inner().schema_data.nullable
+    }
+
+    fn properties(&self) -> IndexMap<String, RefOr<Self>> {
         use openapiv3::*;
-        let mut m = HashMap::new();
+        let mut m = IndexMap::new();
         match &self.inner().schema_kind {
             SchemaKind::Type(Type::Object(t)) => {
                 for (k, v) in t.properties.iter() {
@@ -352,11 +586,12 @@ impl Schema for OAS30Pointer<SchemaSource> {
         m
     }
 
-    fn pattern_properties(&self) -> std::collections::HashMap<String, RefOr<impl Schema>> {
-        HashMap::<_, RefOr<OAS30SchemaPointer>>::new()
+    fn pattern_properties(&self) -> IndexMap<String, RefOr<Self>> {
+        // 'patternProperties' is not part of OAS 3.0 (and not modeled by openapiv3)
+        IndexMap::new()
     }
 
-    fn addtional_properties(&self) -> crate::types::BooleanOrSchema<impl Schema> {
+    fn addtional_properties(&self) -> crate::types::BooleanOrSchema<Self> {
         use openapiv3::*;
         let inner = self.inner();
         match &inner.schema_kind {
@@ -364,18 +599,21 @@ impl Schema for OAS30Pointer<SchemaSource> {
                 additional_properties: Some(AdditionalProperties::Any(any)),
                 ..
             })) => BooleanOrSchema::Boolean(*any),
-            SchemaKind::Type(Type::Object(_)) => {
-                if schema_from_additional_properties(inner).is_some() {
-                    BooleanOrSchema::<Self>::Schema(Self {
-                        openapi: self.openapi.clone(),
-                        ref_source: SchemaSource::AdditionalProperties(Box::new(
-                            self.ref_source.clone(),
-                        )),
-                    })
-                } else {
-                    BooleanOrSchema::<Self>::Boolean(true)
-                }
-            }
+            SchemaKind::Type(Type::Object(_)) => match schema_from_additional_properties(inner) {
+                // referenced schemas are pointed to by their URI, so that they
+                // map to the types generated for them
+                Some(ReferenceOr::Reference { reference }) => BooleanOrSchema::Schema(Self {
+                    openapi: self.openapi.clone(),
+                    ref_source: SchemaSource::Uri(reference.clone()),
+                }),
+                Some(ReferenceOr::Item(_)) => BooleanOrSchema::Schema(Self {
+                    openapi: self.openapi.clone(),
+                    ref_source: SchemaSource::AdditionalProperties(Box::new(
+                        self.ref_source.clone(),
+                    )),
+                }),
+                None => BooleanOrSchema::Boolean(true),
+            },
             _ => BooleanOrSchema::<Self>::Boolean(true),
         }
     }
@@ -1,3 +1,4 @@
+use indexmap::IndexMap;
 use std::io::BufReader;
 use std::rc::Rc;
 use std::str::FromStr;
@@ -6,21 +7,100 @@ use openapiv3::{OpenAPI, ReferenceOr};
 
 use super::super::{MediaTypeSource, OAS30Pointer, ResponseSource};
 use super::{
-    ComponentsSource, OperationSource, ParameterSource, PathItemSource, RequestBodySource,
-    SchemaSource,
+    ComponentsSource, HeaderSource, OperationSource, ParameterSource, PathItemSource,
+    RequestBodySource, SchemaSource,
+};
+use crate::types::{
+    Components, Contact, License, RefOr, SecurityRequirement, ServerVariable, ValidationError,
 };
-use crate::types::{Components, RefOr};
 
 pub struct OAS30Spec {
     openapi: Rc<OpenAPI>,
+    /// Operations under methods `openapiv3` drops while parsing, as pairs
+    /// of path and method
+    unsupported_operations: Vec<(String, String)>,
 }
 
 impl FromStr for OAS30Spec {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> Result<Self, anyhow::Error> {
-        let openapi: OpenAPI = serde_yaml::from_str(s)?;
-        Ok(openapi.into())
+        parse_openapi(serde_yaml::from_str(s)?)
+    }
+}
+
+const OPERATION_METHODS: [&str; 8] = [
+    "get", "put", "post", "delete", "options", "head", "patch", "trace",
+];
+
+/// The fixed fields of a path item that are not operations
+const PATH_ITEM_FIELDS: [&str; 5] = ["$ref", "summary", "description", "servers", "parameters"];
+
+/// Parses an OAS 3.0 document, tolerating operations without `responses`.
+/// The spec requires them, but they are omitted often enough, so we treat
+/// them as empty instead of failing.
+///
+/// Path item keys that are neither fields, extensions nor one of the
+/// [OPERATION_METHODS] (like `query`) are recorded as unsupported
+/// operations, since `openapiv3` silently drops them.
+fn parse_openapi(mut document: serde_yaml::Value) -> anyhow::Result<OAS30Spec> {
+    let mut unsupported_operations = Vec::new();
+    let path_items = document
+        .get_mut("paths")
+        .and_then(|paths| paths.as_mapping_mut())
+        .into_iter()
+        .flat_map(|paths| paths.iter_mut());
+    for (path, path_item) in path_items {
+        let path = path.as_str().unwrap_or_default();
+        let methods = path_item
+            .as_mapping()
+            .into_iter()
+            .flat_map(|path_item| path_item.keys())
+            .filter_map(|key| key.as_str())
+            .filter(|key| {
+                !key.starts_with("x-")
+                    && !PATH_ITEM_FIELDS.contains(key)
+                    && !OPERATION_METHODS.contains(key)
+            });
+        for method in methods {
+            unsupported_operations.push((path.to_string(), method.to_string()));
+        }
+        for method in OPERATION_METHODS {
+            if let Some(operation) = path_item
+                .get_mut(method)
+                .and_then(|operation| operation.as_mapping_mut())
+                && !operation.contains_key("responses")
+            {
+                operation.insert("responses".into(), serde_yaml::Mapping::new().into());
+            }
+        }
+    }
+    let openapi: OpenAPI = serde_yaml::from_value(document)?;
+    Ok(OAS30Spec {
+        openapi: Rc::new(openapi),
+        unsupported_operations,
+    })
+}
+
+impl OAS30Spec {
+    /// The path item a path item's `$ref` points to. OAS 3.0 has no
+    /// `#/components/pathItems`, so only references to other entries of
+    /// `#/paths` (like `#/paths/~1pets`) can be resolved; chains of
+    /// references are followed, unless they are cyclic.
+    fn resolve_path_item_ref(&self, reference: &str) -> Option<&openapiv3::PathItem> {
+        let paths = &self.openapi.paths.paths;
+        let mut reference = reference;
+        for _ in 0..paths.len() {
+            let path = reference
+                .strip_prefix("#/paths/")?
+                .replace("~1", "/")
+                .replace("~0", "~");
+            match paths.get(&path)? {
+                ReferenceOr::Item(path_item) => return Some(path_item),
+                ReferenceOr::Reference { reference: next } => reference = next,
+            }
+        }
+        None
     }
 }
 
@@ -28,6 +108,7 @@ impl From<OpenAPI> for OAS30Spec {
     fn from(openapi: OpenAPI) -> Self {
         OAS30Spec {
             openapi: Rc::new(openapi),
+            unsupported_operations: Vec::new(),
         }
     }
 }
@@ -41,11 +122,11 @@ impl crate::Spec for OAS30Spec {
     type Operation = OAS30Pointer<OperationSource>;
     type RequestBody = OAS30Pointer<RequestBodySource>;
     type Response = OAS30Pointer<ResponseSource>;
+    type Header = OAS30Pointer<HeaderSource>;
 
     fn from_reader(r: impl std::io::Read) -> anyhow::Result<impl crate::Spec> {
         let r = BufReader::new(r);
-        let openapi: OpenAPI = serde_yaml::from_reader(r)?;
-        Ok(OAS30Spec::from(openapi))
+        parse_openapi(serde_yaml::from_reader(r)?)
     }
 
     fn schemata_iter(&self) -> impl Iterator<Item = (String, RefOr<Self::Schema>)> {
@@ -62,12 +143,11 @@ impl crate::Spec for OAS30Spec {
             .paths
             .paths
             .iter()
-            .filter_map(|(path, path_item_ref)| {
-                if let ReferenceOr::Item(_path_item) = path_item_ref {
-                    Some(path.clone())
-                } else {
-                    None
-                }
+            .filter_map(|(path, path_item_ref)| match path_item_ref {
+                ReferenceOr::Item(_path_item) => Some(path.clone()),
+                ReferenceOr::Reference { reference } => self
+                    .resolve_path_item_ref(reference)
+                    .map(|_path_item| path.clone()),
             })
             .collect();
 
@@ -78,12 +158,85 @@ impl crate::Spec for OAS30Spec {
         }
     }
 
+    fn unresolved_path_item_refs(&self) -> Vec<(String, String)> {
+        self.openapi
+            .paths
+            .paths
+            .iter()
+            .filter_map(|(path, path_item_ref)| match path_item_ref {
+                ReferenceOr::Reference { reference }
+                    if self.resolve_path_item_ref(reference).is_none() =>
+                {
+                    Some((path.clone(), reference.clone()))
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn unsupported_operations(&self) -> Vec<(String, String)> {
+        self.unsupported_operations.clone()
+    }
+
     fn components(&self) -> Option<OAS30Pointer<ComponentsSource>> {
         self.openapi.components.as_ref().map(|_| OAS30Pointer {
             openapi: self.openapi.clone(),
             ref_source: ComponentsSource {},
         })
     }
+
+    fn contact(&self) -> Option<Contact> {
+        self.openapi.info.contact.as_ref().map(|c| Contact {
+            name: c.name.clone(),
+            url: c.url.clone(),
+            email: c.email.clone(),
+        })
+    }
+
+    fn license(&self) -> Option<License> {
+        self.openapi.info.license.as_ref().map(|l| License {
+            name: l.name.clone(),
+            url: l.url.clone(),
+        })
+    }
+
+    fn terms_of_service(&self) -> Option<String> {
+        self.openapi.info.terms_of_service.clone()
+    }
+
+    fn security(&self) -> Vec<SecurityRequirement> {
+        self.openapi.security.clone().unwrap_or_default()
+    }
+
+    fn server_url(&self) -> Option<String> {
+        self.openapi.servers.first().map(|s| s.url.clone())
+    }
+
+    fn validate_references(&self) -> Vec<ValidationError> {
+        super::super::validate::validate_references(&self.openapi)
+    }
+
+    fn server_variables(&self) -> IndexMap<String, ServerVariable> {
+        let Some(variables) = self
+            .openapi
+            .servers
+            .first()
+            .and_then(|s| s.variables.as_ref())
+        else {
+            return IndexMap::new();
+        };
+        variables
+            .iter()
+            .map(|(name, v)| {
+                let variable = ServerVariable {
+                    default: v.default.clone(),
+                    enum_: v.enumeration.clone(),
+                    description: v.description.clone(),
+                };
+                (name.clone(), variable)
+            })
+            .collect()
+    }
 }
 
 // Path Iterator Implementation
@@ -103,7 +256,7 @@ impl Iterator for PathIterator {
             return Some((
                 path.clone(),
                 OAS30Pointer {
-                    ref_source: PathItemSource { path: path.clone() },
+                    ref_source: PathItemSource::Path(path.clone()),
                     openapi: self.openapi.clone(),
                 },
             ));
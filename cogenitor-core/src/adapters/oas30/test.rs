@@ -51,6 +51,15 @@ components:
                 integer_int32:
                     type: integer
                     format: int32
+                integer_uint64:
+                    type: integer
+                    format: uint64
+                integer_int8:
+                    type: integer
+                    format: int8
+                integer_bogus:
+                    type: integer
+                    format: bogus
 ";
     println!("parsing {oas}");
     let spec = OAS30Spec::from_str(oas).unwrap();
@@ -78,6 +87,19 @@ components:
     let schema = nf_props.get("integer_int32").unwrap().resolve_fully();
     assert_eq!(type_of(&schema), Some(crate::types::Type::Number));
     assert_eq!(schema.format(), Some(Format::Int32));
+    assert!(schema.is_integer());
+
+    let schema = nf_props.get("integer_uint64").unwrap().resolve_fully();
+    assert_eq!(schema.format(), Some(Format::UInt64));
+
+    let schema = nf_props.get("integer_int8").unwrap().resolve_fully();
+    assert_eq!(schema.format(), Some(Format::Int8));
+
+    let schema = nf_props.get("integer_bogus").unwrap().resolve_fully();
+    assert_eq!(schema.format(), Some(Format::Custom("bogus".to_string())));
+
+    let schema = nf_props.get("number_double").unwrap().resolve_fully();
+    assert!(!schema.is_integer());
 }
 
 fn type_of(s: &impl Schema) -> Option<crate::types::Type> {
@@ -268,3 +290,297 @@ fn test_path_parameters_impl(spec: impl Spec) {
     assert_eq!(param.name(), "with_foo");
     assert_eq!(param.in_(), ParameterLocation::Query);
 }
+
+static SHARED_HEADER_YAML: &str = r"
+openapi: 3.0.0
+info:
+    title: Test shared response headers
+    version: v1
+paths:
+    '/foo':
+        get:
+            responses:
+                200:
+                    description: 'a foo'
+                    headers:
+                        X-RateLimit:
+                            $ref: '#/components/headers/X-RateLimit'
+                        X-Request-Id:
+                            schema:
+                                type: string
+    '/bar':
+        get:
+            responses:
+                200:
+                    description: 'a bar'
+                    headers:
+                        X-RateLimit:
+                            $ref: '#/components/headers/X-RateLimit'
+components:
+    headers:
+        X-RateLimit:
+            required: true
+            schema:
+                type: object
+                properties:
+                    remaining:
+                        type: integer
+                    reset:
+                        type: integer
+";
+
+#[test]
+fn test_component_headers() {
+    use crate::types::{Components, Header, Operation, PathItem, RefOr, Response};
+
+    let spec = OAS30Spec::from_str(SHARED_HEADER_YAML).unwrap();
+
+    let headers: Vec<_> = spec.components().unwrap().headers().collect();
+    assert_eq!(headers.len(), 1);
+    let (name, header) = &headers[0];
+    assert_eq!(name, "X-RateLimit");
+    let header = header.resolve_fully();
+    assert!(header.required());
+    let schema = header.schema().unwrap().resolve_fully();
+    assert_eq!(type_of(&schema), Some(crate::types::Type::Object));
+    assert_eq!(schema.properties().len(), 2);
+
+    let (_, foo) = spec.paths().find(|(path, _)| path == "/foo").unwrap();
+    let (_, foo_get) = foo.operations_iter().next().unwrap();
+    let (_, response) = foo_get.responses().next().unwrap();
+    let response_headers = response.resolve_fully().headers();
+    assert_eq!(response_headers.len(), 2);
+
+    // the shared header is a reference to the component
+    match response_headers.get("X-RateLimit").unwrap() {
+        RefOr::Reference(r) => assert_eq!(r.uri, "#/components/headers/X-RateLimit"),
+        RefOr::Object(o) => panic!("reference expected, found {o:?}"),
+    }
+
+    // the inline header is resolved from the response
+    let request_id = response_headers
+        .get("X-Request-Id")
+        .unwrap()
+        .as_object()
+        .unwrap();
+    assert!(!request_id.required());
+    let schema = request_id.schema().unwrap().resolve_fully();
+    assert_eq!(type_of(&schema), Some(crate::types::Type::String));
+}
+
+#[test]
+fn test_parameter_content() {
+    use crate::types::{MediaType, Operation, PathItem, RefOr};
+
+    let oas = r"
+openapi: 3.0.0
+info:
+    title: Parameter content
+    version: v1
+paths:
+    /pets:
+        get:
+            parameters:
+                -   name: filter
+                    in: query
+                    content:
+                        application/json:
+                            schema:
+                                type: object
+                                properties:
+                                    color:
+                                        type: string
+            responses: {}
+";
+    let spec = OAS30Spec::from_str(oas).unwrap();
+    let (_, pets) = spec.paths().next().unwrap();
+    let (_, pets_get) = pets.operations_iter().next().unwrap();
+    let param = match pets_get.parameters().next().unwrap() {
+        RefOr::Object(p) => p,
+        RefOr::Reference(r) => panic!("inline parameter expected, found {r:?}"),
+    };
+    assert_eq!(param.name(), "filter");
+    assert!(param.schema().is_none());
+
+    let content = param.content().unwrap();
+    assert_eq!(content.len(), 1);
+    let schema = content
+        .get("application/json")
+        .unwrap()
+        .schema()
+        .unwrap()
+        .resolve_fully();
+    assert_eq!(type_of(&schema), Some(crate::types::Type::Object));
+    assert!(schema.properties().contains_key("color"));
+}
+
+const ONE_OF_YAML: &str = r"
+openapi: 3.0.0
+info:
+    title: Composition
+    version: v1
+paths: {}
+components:
+    schemas:
+        Cat:
+            type: object
+            properties:
+                meows:
+                    type: boolean
+        Pet:
+            oneOf:
+                - $ref: '#/components/schemas/Cat'
+                - type: object
+                  properties:
+                      barks:
+                          type: boolean
+";
+
+#[test]
+fn test_one_of() {
+    let spec = OAS30Spec::from_str(ONE_OF_YAML).unwrap();
+    let (_, pet) = spec
+        .schemata_iter()
+        .find(|(name, _)| name == "Pet")
+        .unwrap();
+    let pet = pet.resolve_fully();
+
+    // compositions carry no type of their own
+    assert_eq!(pet.type_(), None);
+    assert!(pet.all_of().is_none());
+    assert!(pet.any_of().is_none());
+
+    let one_of = pet.one_of().unwrap();
+    assert_eq!(one_of.len(), 2);
+
+    // the referenced subschema is named after its component
+    assert_eq!(one_of[0].name(), Some("Cat"));
+    assert!(one_of[0].properties().contains_key("meows"));
+
+    // the inlined subschema has no name
+    assert_eq!(one_of[1].name(), None);
+    assert_eq!(type_of(&one_of[1]), Some(crate::types::Type::Object));
+    assert!(one_of[1].properties().contains_key("barks"));
+}
+
+static DANGLING_REFERENCES_YAML: &str = r"
+openapi: 3.0.0
+info:
+    title: test for references that do not resolve
+    version: v1
+paths:
+    /pets:
+        get:
+            parameters:
+                -   $ref: '#/components/parameters/limit'
+            responses:
+                '200':
+                    description: the pets
+                    content:
+                        application/json:
+                            schema:
+                                type: array
+                                items:
+                                    $ref: '#/components/schemas/Pet'
+components:
+    schemas:
+        Pet:
+            type: object
+            properties:
+                owner:
+                    $ref: '#/components/schemas/NonExistent'
+                name:
+                    type: string
+";
+
+#[test]
+fn test_validate_references() {
+    use crate::types::ValidationError;
+
+    let spec = OAS30Spec::from_str(DANGLING_REFERENCES_YAML).unwrap();
+    let errors = spec.validate_references();
+    assert_eq!(
+        errors,
+        vec![
+            ValidationError {
+                location: "#/components/schemas/Pet/properties/owner".to_string(),
+                reference: "#/components/schemas/NonExistent".to_string(),
+            },
+            ValidationError {
+                location: "#/paths/~1pets/get/parameters/0".to_string(),
+                reference: "#/components/parameters/limit".to_string(),
+            },
+        ]
+    );
+
+    // a spec without dangling references passes
+    let spec = OAS30Spec::from_str(ONE_OF_YAML).unwrap();
+    assert!(spec.validate_references().is_empty());
+}
+
+#[test]
+fn test_schemata_declaration_order() {
+    let oas = r"
+openapi: 3.0.0
+info:
+    title: Schema order
+    version: v1
+paths: {}
+components:
+    schemas:
+        Zebra:
+            type: string
+        Apple:
+            type: string
+        Mango:
+            $ref: '#/components/schemas/Apple'
+        Banana:
+            type: object
+";
+    // parse repeatedly, as hash maps would be seeded differently each time
+    for _ in 0..8 {
+        let spec = OAS30Spec::from_str(oas).unwrap();
+        let names = spec
+            .schemata_iter()
+            .map(|(name, _)| name)
+            .collect::<Vec<_>>();
+        assert_eq!(vec!["Zebra", "Apple", "Mango", "Banana"], names);
+    }
+}
+
+#[test]
+fn test_callbacks() {
+    use crate::types::{MediaType, Operation, PathItem, RequestBody};
+    use http::Method;
+
+    let oas = include_str!("../../../../test-data/callbacks.yaml");
+    let spec = OAS30Spec::from_str(oas).unwrap();
+    let (_, path_item) = spec.paths().next().unwrap();
+    let (_, operation) = path_item.operations_iter().next().unwrap();
+
+    let callbacks = operation.callbacks().collect::<Vec<_>>();
+    assert_eq!(1, callbacks.len());
+    let (name, callback_items) = &callbacks[0];
+    assert_eq!("orderStatusChanged", name);
+    assert_eq!(1, callback_items.len());
+    let (expression, callback_item) = &callback_items[0];
+    assert_eq!("{$request.body#/callbackUrl}", expression);
+
+    let operations = callback_item.operations_iter().collect::<Vec<_>>();
+    assert_eq!(1, operations.len());
+    let (method, callback_op) = &operations[0];
+    assert_eq!(Method::POST, method);
+    let content = callback_op
+        .request_body()
+        .unwrap()
+        .resolve_fully()
+        .content();
+    let schema = content["application/json"]
+        .schema()
+        .unwrap()
+        .resolve_fully();
+    assert_eq!(
+        vec!["orderId", "status"],
+        schema.required().unwrap_or_default()
+    );
+}
@@ -0,0 +1,255 @@
+//! Checks of the `$ref` URIs in an OAS 3.0 document. Our resolvers
+//! (see [OAS3Resolver]) panic on references that do not resolve, so
+//! these checks need to work on the `openapiv3` objects directly.
+
+use std::borrow::Borrow;
+
+use openapiv3::{
+    Components, Content, Header, OpenAPI, Parameter, ParameterSchemaOrContent, ReferenceOr,
+    RequestBody, Response, Schema, SchemaKind, Type,
+};
+
+use super::OAS3Resolver;
+use crate::types::ValidationError;
+
+/// OAS objects that can be referenced in `#/components`
+trait ComponentsEntry: Sized {
+    fn is_in(components: &Components, name: &str) -> bool;
+}
+
+impl ComponentsEntry for Schema {
+    fn is_in(components: &Components, name: &str) -> bool {
+        components.schemas.contains_key(name)
+    }
+}
+
+impl ComponentsEntry for Parameter {
+    fn is_in(components: &Components, name: &str) -> bool {
+        components.parameters.contains_key(name)
+    }
+}
+
+impl ComponentsEntry for RequestBody {
+    fn is_in(components: &Components, name: &str) -> bool {
+        components.request_bodies.contains_key(name)
+    }
+}
+
+impl ComponentsEntry for Response {
+    fn is_in(components: &Components, name: &str) -> bool {
+        components.responses.contains_key(name)
+    }
+}
+
+impl ComponentsEntry for Header {
+    fn is_in(components: &Components, name: &str) -> bool {
+        components.headers.contains_key(name)
+    }
+}
+
+/// Escapes a name for use as JSON pointer segment, see
+/// https://datatracker.ietf.org/doc/html/rfc6901#section-3
+fn escape(name: &str) -> String {
+    name.replace('~', "~0").replace('/', "~1")
+}
+
+/// Checks all references in schemas, parameters, request bodies, responses
+/// and headers, both in `#/components` and in the operations below `#/paths`
+pub(super) fn validate_references(openapi: &OpenAPI) -> Vec<ValidationError> {
+    let mut validator = Validator {
+        openapi,
+        errors: Vec::new(),
+    };
+    validator.components();
+    validator.paths();
+    validator.errors
+}
+
+struct Validator<'a> {
+    openapi: &'a OpenAPI,
+    errors: Vec<ValidationError>,
+}
+
+impl<'a> Validator<'a> {
+    /// Records an error if `ro` is a reference that does not resolve,
+    /// yields the inlined object otherwise.
+    fn check<T, B>(&mut self, location: &str, ro: &'a ReferenceOr<B>) -> Option<&'a T>
+    where
+        T: ComponentsEntry,
+        B: Borrow<T>,
+        OpenAPI: OAS3Resolver<T>,
+    {
+        match ro {
+            ReferenceOr::Reference { reference } => {
+                let prefix = OAS3Resolver::<T>::prefix(self.openapi);
+                let resolves = reference.strip_prefix(prefix).is_some_and(|name| {
+                    self.openapi
+                        .components
+                        .as_ref()
+                        .is_some_and(|c| T::is_in(c, name))
+                });
+                if !resolves {
+                    self.errors.push(ValidationError {
+                        location: location.to_string(),
+                        reference: reference.clone(),
+                    });
+                }
+                None
+            }
+            ReferenceOr::Item(item) => Some(item.borrow()),
+        }
+    }
+
+    fn components(&mut self) {
+        let Some(components) = &self.openapi.components else {
+            return;
+        };
+        for (name, schema) in &components.schemas {
+            self.schema(&format!("#/components/schemas/{}", escape(name)), schema);
+        }
+        for (name, parameter) in &components.parameters {
+            self.parameter(
+                &format!("#/components/parameters/{}", escape(name)),
+                parameter,
+            );
+        }
+        for (name, request_body) in &components.request_bodies {
+            let location = format!("#/components/requestBodies/{}", escape(name));
+            self.request_body(&location, request_body);
+        }
+        for (name, response) in &components.responses {
+            self.response(
+                &format!("#/components/responses/{}", escape(name)),
+                response,
+            );
+        }
+        for (name, header) in &components.headers {
+            self.header(&format!("#/components/headers/{}", escape(name)), header);
+        }
+    }
+
+    fn paths(&mut self) {
+        for (path, path_item) in self.openapi.paths.iter() {
+            // path items referenced via $ref are not supported in code generation
+            let ReferenceOr::Item(path_item) = path_item else {
+                continue;
+            };
+            let location = format!("#/paths/{}", escape(path));
+            for (i, parameter) in path_item.parameters.iter().enumerate() {
+                self.parameter(&format!("{location}/parameters/{i}"), parameter);
+            }
+            for (method, operation) in path_item.iter() {
+                let location = format!("{location}/{method}");
+                for (i, parameter) in operation.parameters.iter().enumerate() {
+                    self.parameter(&format!("{location}/parameters/{i}"), parameter);
+                }
+                if let Some(request_body) = &operation.request_body {
+                    self.request_body(&format!("{location}/requestBody"), request_body);
+                }
+                if let Some(response) = &operation.responses.default {
+                    self.response(&format!("{location}/responses/default"), response);
+                }
+                for (status, response) in &operation.responses.responses {
+                    self.response(&format!("{location}/responses/{status}"), response);
+                }
+            }
+        }
+    }
+
+    fn schema(&mut self, location: &str, ro: &'a ReferenceOr<impl Borrow<Schema>>) {
+        let Some(schema) = self.check::<Schema, _>(location, ro) else {
+            return;
+        };
+        match &schema.schema_kind {
+            SchemaKind::Type(Type::Object(o)) => {
+                for (name, property) in &o.properties {
+                    self.schema(&format!("{location}/properties/{}", escape(name)), property);
+                }
+                if let Some(openapiv3::AdditionalProperties::Schema(s)) = &o.additional_properties {
+                    self.schema(&format!("{location}/additionalProperties"), s.as_ref());
+                }
+            }
+            SchemaKind::Type(Type::Array(a)) => {
+                if let Some(items) = &a.items {
+                    self.schema(&format!("{location}/items"), items);
+                }
+            }
+            SchemaKind::Type(_) => (),
+            SchemaKind::OneOf { one_of } => self.subschemas(location, "oneOf", one_of),
+            SchemaKind::AllOf { all_of } => self.subschemas(location, "allOf", all_of),
+            SchemaKind::AnyOf { any_of } => self.subschemas(location, "anyOf", any_of),
+            SchemaKind::Not { not } => self.schema(&format!("{location}/not"), not.as_ref()),
+            SchemaKind::Any(any) => {
+                for (name, property) in &any.properties {
+                    self.schema(&format!("{location}/properties/{}", escape(name)), property);
+                }
+                if let Some(openapiv3::AdditionalProperties::Schema(s)) = &any.additional_properties
+                {
+                    self.schema(&format!("{location}/additionalProperties"), s.as_ref());
+                }
+                if let Some(items) = &any.items {
+                    self.schema(&format!("{location}/items"), items);
+                }
+                self.subschemas(location, "oneOf", &any.one_of);
+                self.subschemas(location, "allOf", &any.all_of);
+                self.subschemas(location, "anyOf", &any.any_of);
+                if let Some(not) = &any.not {
+                    self.schema(&format!("{location}/not"), not.as_ref());
+                }
+            }
+        }
+    }
+
+    fn subschemas(&mut self, location: &str, keyword: &str, schemas: &'a [ReferenceOr<Schema>]) {
+        for (i, schema) in schemas.iter().enumerate() {
+            self.schema(&format!("{location}/{keyword}/{i}"), schema);
+        }
+    }
+
+    fn schema_or_content(&mut self, location: &str, format: &'a ParameterSchemaOrContent) {
+        match format {
+            ParameterSchemaOrContent::Schema(schema) => {
+                self.schema(&format!("{location}/schema"), schema)
+            }
+            ParameterSchemaOrContent::Content(content) => {
+                self.content(&format!("{location}/content"), content)
+            }
+        }
+    }
+
+    fn content(&mut self, location: &str, content: &'a Content) {
+        for (media_type_name, media_type) in content {
+            if let Some(schema) = &media_type.schema {
+                let location = format!("{location}/{}/schema", escape(media_type_name));
+                self.schema(&location, schema);
+            }
+        }
+    }
+
+    fn parameter(&mut self, location: &str, ro: &'a ReferenceOr<Parameter>) {
+        if let Some(parameter) = self.check::<Parameter, _>(location, ro) {
+            self.schema_or_content(location, &parameter.parameter_data_ref().format);
+        }
+    }
+
+    fn request_body(&mut self, location: &str, ro: &'a ReferenceOr<RequestBody>) {
+        if let Some(request_body) = self.check::<RequestBody, _>(location, ro) {
+            self.content(&format!("{location}/content"), &request_body.content);
+        }
+    }
+
+    fn response(&mut self, location: &str, ro: &'a ReferenceOr<Response>) {
+        if let Some(response) = self.check::<Response, _>(location, ro) {
+            for (name, header) in &response.headers {
+                self.header(&format!("{location}/headers/{}", escape(name)), header);
+            }
+            self.content(&format!("{location}/content"), &response.content);
+        }
+    }
+
+    fn header(&mut self, location: &str, ro: &'a ReferenceOr<Header>) {
+        if let Some(header) = self.check::<Header, _>(location, ro) {
+            self.schema_or_content(location, &header.format);
+        }
+    }
+}
@@ -0,0 +1,87 @@
+use super::super::{OAS31Pointer, OAS31Source, into_ref_or};
+use super::OAS31Spec;
+use super::{HeaderSource, SchemaSource};
+use crate::types::{ApiKeyLocation, Components, RefOr, SecurityScheme};
+use oas3::spec::ObjectOrReference;
+use std::fmt::Debug;
+use std::hash::Hash;
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct ComponentsSource;
+
+impl OAS31Source for ComponentsSource {
+    type OAS31Type = oas3::spec::Components;
+
+    fn inner<'a>(&'a self, spec: &'a oas3::spec::Spec) -> Option<&'a Self::OAS31Type> {
+        spec.components.as_ref()
+    }
+}
+
+impl Components<OAS31Spec> for OAS31Pointer<ComponentsSource> {
+    fn schemas(&self) -> impl Iterator<Item = (String, RefOr<OAS31Pointer<SchemaSource>>)> {
+        // oas3 keeps the schemas in a BTreeMap, so they are yielded in
+        // alphabetical order
+        self.inner()
+            .into_iter()
+            .flat_map(|components| components.schemas.iter())
+            .map(|(name, schema_ro)| {
+                (
+                    name.clone(),
+                    into_ref_or(schema_ro, self, |_| {
+                        SchemaSource::Uri(format!("#/components/schemas/{name}"))
+                    }),
+                )
+            })
+    }
+
+    fn headers(&self) -> impl Iterator<Item = (String, RefOr<OAS31Pointer<HeaderSource>>)> {
+        self.inner()
+            .into_iter()
+            .flat_map(|components| components.headers.iter())
+            .map(|(name, header_ro)| {
+                (
+                    name.clone(),
+                    into_ref_or(header_ro, self, |_| HeaderSource::Uri {
+                        uri: format!("#/components/headers/{name}"),
+                    }),
+                )
+            })
+    }
+
+    fn security_schemes(&self) -> impl Iterator<Item = (String, SecurityScheme)> {
+        self.inner()
+            .into_iter()
+            .flat_map(|components| components.security_schemes.iter())
+            .filter_map(|(name, scheme_ro)| {
+                // references would point to other security schemes in the
+                // same section, which we yield anyway
+                let ObjectOrReference::Object(scheme) = scheme_ro else {
+                    return None;
+                };
+                let scheme = match scheme {
+                    oas3::spec::SecurityScheme::ApiKey { name, location, .. } => {
+                        let location = match location.as_str() {
+                            "query" => ApiKeyLocation::Query,
+                            "header" => ApiKeyLocation::Header,
+                            "cookie" => ApiKeyLocation::Cookie,
+                            _ => return None,
+                        };
+                        SecurityScheme::ApiKey {
+                            name: name.clone(),
+                            location,
+                        }
+                    }
+                    oas3::spec::SecurityScheme::Http { scheme, .. } => SecurityScheme::Http {
+                        scheme: scheme.clone(),
+                    },
+                    oas3::spec::SecurityScheme::OAuth2 { .. } => SecurityScheme::OAuth2,
+                    oas3::spec::SecurityScheme::OpenIdConnect { .. } => {
+                        SecurityScheme::OpenIdConnect
+                    }
+                    // mutual TLS is configured on the transport
+                    oas3::spec::SecurityScheme::MutualTls { .. } => return None,
+                };
+                Some((name.clone(), scheme))
+            })
+    }
+}
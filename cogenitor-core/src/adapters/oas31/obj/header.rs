@@ -0,0 +1,68 @@
+use std::hash::Hash;
+
+use crate::adapters::oas31::{
+    OAS31Pointer, OAS31Resolver, OAS31Source, OAS31Spec, ResponseSource, SchemaSource,
+    SourceFromUri, into_ref_or,
+};
+use crate::types::{Header, RefOr};
+
+impl OAS31Resolver<oas3::spec::Header> for oas3::spec::Spec {
+    fn prefix(&self) -> &'static str {
+        "#/components/headers/"
+    }
+
+    fn resolve_reference(&self, reference: &str) -> Option<&oas3::spec::Header> {
+        // a header in the components section may itself reference another
+        // one, which resolve() follows
+        let ro = self.components.as_ref()?.headers.get(reference)?;
+        self.resolve(ro)
+    }
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub enum HeaderSource {
+    Uri {
+        uri: String,
+    },
+    Response {
+        ref_source: ResponseSource,
+        header_name: String,
+    },
+}
+
+impl OAS31Source for HeaderSource {
+    type OAS31Type = oas3::spec::Header;
+
+    fn inner<'a>(&'a self, spec: &'a oas3::spec::Spec) -> Option<&'a Self::OAS31Type> {
+        match self {
+            HeaderSource::Uri { uri } => {
+                OAS31Resolver::<oas3::spec::Header>::resolve_uri(spec, uri)
+            }
+            HeaderSource::Response {
+                ref_source,
+                header_name,
+            } => spec.resolve(ref_source.inner(spec)?.headers.get(header_name)?),
+        }
+    }
+}
+
+impl SourceFromUri for HeaderSource {
+    fn from_uri(uri: &str) -> Self {
+        HeaderSource::Uri {
+            uri: uri.to_string(),
+        }
+    }
+}
+
+impl Header<OAS31Spec> for OAS31Pointer<HeaderSource> {
+    fn required(&self) -> bool {
+        self.inner().and_then(|h| h.required).unwrap_or(false)
+    }
+
+    fn schema(&self) -> Option<RefOr<OAS31Pointer<SchemaSource>>> {
+        let schema_ref = self.inner()?.schema.as_ref()?;
+        Some(into_ref_or(schema_ref, self, |src| {
+            SchemaSource::Header(Box::new(src.clone()))
+        }))
+    }
+}
@@ -0,0 +1,68 @@
+use std::collections::BTreeMap;
+use std::hash::Hash;
+
+use indexmap::IndexMap;
+
+use crate::{
+    adapters::oas31::{OAS31Pointer, OAS31Source, ResponseSource, into_ref_or},
+    types::{MediaType, RefOr},
+};
+
+use super::{OAS31Spec, ParameterSource, RequestBodySource, SchemaSource};
+
+/// A media type object of a content map, by its media type key
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+pub enum MediaTypeSource {
+    Parameter {
+        ref_source: ParameterSource,
+        media_type: String,
+    },
+    RequestBody {
+        ref_source: RequestBodySource,
+        media_type: String,
+    },
+    Response {
+        ref_source: ResponseSource,
+        media_type: String,
+    },
+}
+
+impl OAS31Source for MediaTypeSource {
+    type OAS31Type = oas3::spec::MediaType;
+
+    fn inner<'a>(&'a self, spec: &'a oas3::spec::Spec) -> Option<&'a Self::OAS31Type> {
+        match self {
+            MediaTypeSource::Parameter {
+                ref_source,
+                media_type,
+            } => ref_source.inner(spec)?.content.as_ref()?.get(media_type),
+            MediaTypeSource::RequestBody {
+                ref_source,
+                media_type,
+            } => ref_source.inner(spec)?.content.get(media_type),
+            MediaTypeSource::Response {
+                ref_source,
+                media_type,
+            } => ref_source.inner(spec)?.content.get(media_type),
+        }
+    }
+}
+
+pub fn into_oas31_content(
+    content: &BTreeMap<String, oas3::spec::MediaType>,
+    src_fn: impl Fn(String) -> OAS31Pointer<MediaTypeSource>,
+) -> IndexMap<String, OAS31Pointer<MediaTypeSource>> {
+    content
+        .keys()
+        .map(|media_type| (media_type.clone(), src_fn(media_type.clone())))
+        .collect()
+}
+
+impl MediaType<OAS31Spec> for OAS31Pointer<MediaTypeSource> {
+    fn schema(&self) -> Option<RefOr<OAS31Pointer<SchemaSource>>> {
+        let schema_ref = self.inner()?.schema.as_ref()?;
+        Some(into_ref_or(schema_ref, self, |src| {
+            SchemaSource::MediaType(Box::new(src.clone()))
+        }))
+    }
+}
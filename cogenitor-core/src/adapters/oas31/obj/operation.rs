@@ -0,0 +1,158 @@
+use std::fmt::Debug;
+use std::hash::Hash;
+use std::rc::Rc;
+use std::str::FromStr;
+
+use http::Method;
+
+use super::super::{OAS31Pointer, OAS31Source, ResponseSource, into_ref_or, to_parameters_iter};
+use super::{OAS31Spec, ParameterSource, PathItemSource, RequestBodySource};
+use crate::types::{Operation, RefOr, SecurityRequirement, Spec, StatusSpec};
+
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+pub struct OperationSource {
+    pub(crate) path_item: PathItemSource,
+    pub(crate) method: http::Method,
+}
+
+impl OAS31Source for OperationSource {
+    type OAS31Type = oas3::spec::Operation;
+
+    fn inner<'a>(&'a self, spec: &'a oas3::spec::Spec) -> Option<&'a Self::OAS31Type> {
+        let path_item = self.path_item.inner(spec)?;
+
+        let op = match self.method {
+            Method::GET => &path_item.get,
+            Method::DELETE => &path_item.delete,
+            Method::HEAD => &path_item.head,
+            Method::OPTIONS => &path_item.options,
+            Method::PATCH => &path_item.patch,
+            Method::POST => &path_item.post,
+            Method::PUT => &path_item.put,
+            Method::TRACE => &path_item.trace,
+            _ => return None,
+        };
+        op.as_ref()
+    }
+}
+
+impl Operation<OAS31Spec> for OAS31Pointer<OperationSource> {
+    fn parameters(&self) -> impl Iterator<Item = RefOr<OAS31Pointer<ParameterSource>>> {
+        let source_ref = &self.ref_source;
+        let parameters = self
+            .inner()
+            .map(|operation| operation.parameters.as_slice())
+            .unwrap_or_default();
+        to_parameters_iter(self, parameters, |index| ParameterSource::Operation {
+            source_ref: source_ref.clone(),
+            index,
+        })
+    }
+
+    fn operation_id(&self) -> Option<&str> {
+        self.inner()?.operation_id.as_deref()
+    }
+
+    fn summary(&self) -> Option<&str> {
+        self.inner()?.summary.as_deref()
+    }
+
+    fn description(&self) -> Option<&str> {
+        self.inner()?.description.as_deref()
+    }
+
+    fn tags(&self) -> impl Iterator<Item = &str> {
+        self.inner()
+            .into_iter()
+            .flat_map(|operation| operation.tags.iter().map(String::as_str))
+    }
+
+    fn security(&self) -> Option<Vec<SecurityRequirement>> {
+        // the oas3 crate does not parse security requirements yet
+        None
+    }
+
+    fn callbacks(
+        &self,
+    ) -> impl Iterator<Item = (String, Vec<(String, OAS31Pointer<PathItemSource>)>)> {
+        let operation = Box::new(self.ref_source.clone());
+        let callbacks = self
+            .inner()
+            .into_iter()
+            .flat_map(|operation| operation.callbacks.iter());
+        callbacks
+            .map(|(name, callback)| {
+                // the oas3 crate keeps callbacks as raw JSON, mapping the
+                // URL expressions to path items
+                let callback = serde_json::to_value(callback).unwrap_or_default();
+                let path_items = callback
+                    .as_object()
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|(expression, path_item)| {
+                        let path_item = match serde_json::from_value(path_item.clone()) {
+                            Ok(path_item) => path_item,
+                            Err(e) => {
+                                log::warn!(
+                                    "ignoring path item '{expression}' of callback '{name}': {e}"
+                                );
+                                return None;
+                            }
+                        };
+                        let ref_source = PathItemSource::Callback {
+                            operation: operation.clone(),
+                            name: name.clone(),
+                            expression: expression.clone(),
+                            path_item: Rc::new(path_item),
+                        };
+                        Some((expression.clone(), self.with_source(ref_source)))
+                    })
+                    .collect();
+                (name.clone(), path_items)
+            })
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
+    fn request_body(&self) -> Option<RefOr<OAS31Pointer<RequestBodySource>>> {
+        self.inner()?.request_body.as_ref().map(|request_body| {
+            into_ref_or(request_body, self, |src| RequestBodySource::Operation {
+                source_ref: src.clone(),
+            })
+        })
+    }
+
+    fn responses(
+        &self,
+    ) -> impl Iterator<
+        Item = (
+            crate::types::StatusSpec,
+            RefOr<<OAS31Spec as Spec>::Response>,
+        ),
+    > {
+        // `default` sorts after all status codes and ranges, so it comes
+        // last like in the OAS 3.0 adapter
+        let responses = self
+            .inner()
+            .and_then(|operation| operation.responses.as_ref());
+        responses
+            .into_iter()
+            .flatten()
+            .filter_map(|(status, ro_response)| {
+                let status_spec = match StatusSpec::from_str(status) {
+                    Ok(status_spec) => status_spec,
+                    Err(e) => {
+                        log::warn!("ignoring response with invalid status '{status}': {e:?}");
+                        return None;
+                    }
+                };
+                let response = into_ref_or(ro_response, self, |src| ResponseSource::Operation {
+                    ref_source: src.clone(),
+                    status: status.clone(),
+                });
+                Some((status_spec, response))
+            })
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+}
@@ -0,0 +1,150 @@
+use std::hash::Hash;
+
+use indexmap::IndexMap;
+use oas3::spec::ObjectOrReference;
+
+use super::{OAS31Spec, OperationSource, PathItemSource, SchemaSource, into_oas31_content};
+use crate::adapters::oas31::{
+    MediaTypeSource, OAS31Pointer, OAS31Resolver, OAS31Source, SourceFromUri, into_ref_or,
+    to_json_value,
+};
+use crate::types::{Parameter, ParameterLocation, RefOr};
+
+impl OAS31Resolver<oas3::spec::Parameter> for oas3::spec::Spec {
+    fn prefix(&self) -> &'static str {
+        "#/components/parameters/"
+    }
+
+    fn resolve_reference(&self, reference: &str) -> Option<&oas3::spec::Parameter> {
+        let ro = self.components.as_ref()?.parameters.get(reference)?;
+        self.resolve(ro)
+    }
+}
+
+impl OAS31Resolver<oas3::spec::Example> for oas3::spec::Spec {
+    fn prefix(&self) -> &'static str {
+        "#/components/examples/"
+    }
+
+    fn resolve_reference(&self, reference: &str) -> Option<&oas3::spec::Example> {
+        let ro = self.components.as_ref()?.examples.get(reference)?;
+        self.resolve(ro)
+    }
+}
+
+/// The parameters of a path item or operation. Inlined parameters are
+/// addressed by their index in the parameter list.
+pub fn to_parameters_iter(
+    parent: &OAS31Pointer<impl OAS31Source>,
+    oas31_parameters: &[ObjectOrReference<oas3::spec::Parameter>],
+    parameter_source_factory: impl Fn(usize) -> ParameterSource,
+) -> impl Iterator<Item = RefOr<OAS31Pointer<ParameterSource>>> {
+    oas31_parameters
+        .iter()
+        .enumerate()
+        .map(|(index, param_ref)| {
+            into_ref_or(param_ref, parent, |_src| parameter_source_factory(index))
+        })
+        .collect::<Vec<_>>()
+        .into_iter()
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub enum ParameterSource {
+    Uri {
+        uri: String,
+    },
+    Operation {
+        source_ref: OperationSource,
+        index: usize,
+    },
+    PathItem {
+        source_ref: PathItemSource,
+        index: usize,
+    },
+}
+
+impl SourceFromUri for ParameterSource {
+    fn from_uri(uri: &str) -> Self {
+        ParameterSource::Uri {
+            uri: uri.to_string(),
+        }
+    }
+}
+
+fn extract_location(param: &oas3::spec::Parameter) -> ParameterLocation {
+    match param.location {
+        oas3::spec::ParameterIn::Query => ParameterLocation::Query,
+        oas3::spec::ParameterIn::Header => ParameterLocation::Header,
+        oas3::spec::ParameterIn::Path => ParameterLocation::Path,
+        oas3::spec::ParameterIn::Cookie => ParameterLocation::Cookie,
+    }
+}
+
+impl OAS31Source for ParameterSource {
+    type OAS31Type = oas3::spec::Parameter;
+
+    fn inner<'a>(&'a self, spec: &'a oas3::spec::Spec) -> Option<&'a Self::OAS31Type> {
+        match self {
+            ParameterSource::Uri { uri } => {
+                OAS31Resolver::<oas3::spec::Parameter>::resolve_uri(spec, uri)
+            }
+            ParameterSource::Operation { source_ref, index } => {
+                spec.resolve(source_ref.inner(spec)?.parameters.get(*index)?)
+            }
+            ParameterSource::PathItem { source_ref, index } => {
+                spec.resolve(source_ref.inner(spec)?.parameters.get(*index)?)
+            }
+        }
+    }
+}
+
+impl Parameter<OAS31Spec> for OAS31Pointer<ParameterSource> {
+    fn in_(&self) -> ParameterLocation {
+        // unresolvable parameters are reported by validate_references(),
+        // so this default is never observed during code generation
+        self.inner()
+            .map(extract_location)
+            .unwrap_or(ParameterLocation::Query)
+    }
+
+    fn name(&self) -> &str {
+        self.inner().map(|p| p.name.as_str()).unwrap_or_default()
+    }
+
+    fn required(&self) -> bool {
+        // path parameters are always required, even if the document
+        // omits the flag
+        self.in_() == ParameterLocation::Path
+            || self.inner().and_then(|p| p.required).unwrap_or(false)
+    }
+
+    fn schema(&self) -> Option<RefOr<OAS31Pointer<SchemaSource>>> {
+        let schema_ref = self.inner()?.schema.as_ref()?;
+        Some(into_ref_or(schema_ref, self, |src| {
+            SchemaSource::Parameter(Box::new(src.clone()))
+        }))
+    }
+
+    fn content(&self) -> Option<IndexMap<String, OAS31Pointer<MediaTypeSource>>> {
+        let content = self.inner()?.content.as_ref()?;
+        Some(into_oas31_content(content, |media_type| {
+            self.with_source(MediaTypeSource::Parameter {
+                ref_source: self.ref_source.clone(),
+                media_type,
+            })
+        }))
+    }
+
+    fn example(&self) -> Option<json::JsonValue> {
+        let parameter = self.inner()?;
+        let example = parameter.example.as_ref().or_else(|| {
+            parameter
+                .examples
+                .values()
+                .filter_map(|ro| OAS31Resolver::<oas3::spec::Example>::resolve(&*self.spec, ro))
+                .find_map(|example| example.value.as_ref())
+        })?;
+        to_json_value(example)
+    }
+}
@@ -0,0 +1,171 @@
+use std::hash::Hash;
+use std::rc::Rc;
+
+use http::Method;
+
+use super::super::{OAS31Pointer, OAS31Resolver, OAS31Source, to_parameters_iter};
+use super::{OAS31Spec, OperationSource, ParameterSource};
+use crate::types::{PathItem, RefOr};
+
+impl OAS31Resolver<oas3::spec::PathItem> for oas3::spec::Spec {
+    fn prefix(&self) -> &'static str {
+        "#/components/pathItems/"
+    }
+
+    fn resolve_reference(&self, reference: &str) -> Option<&oas3::spec::PathItem> {
+        let ro = self.components.as_ref()?.path_items.get(reference)?;
+        self.resolve(ro)
+    }
+}
+
+/// The path item a path item's `$ref` points to, or the path item itself
+/// if it has no `$ref`. Only references to `#/components/pathItems` can
+/// be resolved.
+pub(crate) fn resolve_path_item<'a>(
+    spec: &'a oas3::spec::Spec,
+    path_item: &'a oas3::spec::PathItem,
+) -> Option<&'a oas3::spec::PathItem> {
+    match &path_item.reference {
+        Some(reference) => OAS31Resolver::<oas3::spec::PathItem>::resolve_uri(spec, reference),
+        None => Some(path_item),
+    }
+}
+
+// OAS31 PathItem Implementation
+#[derive(Clone)]
+pub enum PathItemSource {
+    /// a path item in `#/paths`, by its path
+    Path(String),
+    /// a path item of an operation's callback, by the callback's name and
+    /// the runtime expression of the callback URL. The `oas3` crate keeps
+    /// callbacks as raw JSON, so the path item is parsed when the callback
+    /// is visited and kept here.
+    Callback {
+        operation: Box<OperationSource>,
+        name: String,
+        expression: String,
+        path_item: Rc<oas3::spec::PathItem>,
+    },
+}
+
+impl std::fmt::Debug for PathItemSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PathItemSource::Path(path) => f.debug_tuple("Path").field(path).finish(),
+            PathItemSource::Callback {
+                operation,
+                name,
+                expression,
+                ..
+            } => f
+                .debug_struct("Callback")
+                .field("operation", operation)
+                .field("name", name)
+                .field("expression", expression)
+                .finish(),
+        }
+    }
+}
+
+/// Callback path items are identified by their location, like all other
+/// objects, not by their contents
+impl Hash for PathItemSource {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        core::mem::discriminant(self).hash(state);
+        match self {
+            PathItemSource::Path(path) => path.hash(state),
+            PathItemSource::Callback {
+                operation,
+                name,
+                expression,
+                ..
+            } => {
+                operation.hash(state);
+                name.hash(state);
+                expression.hash(state);
+            }
+        }
+    }
+}
+
+impl PartialEq for PathItemSource {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (PathItemSource::Path(s), PathItemSource::Path(o)) => s == o,
+            (
+                PathItemSource::Callback {
+                    operation,
+                    name,
+                    expression,
+                    ..
+                },
+                PathItemSource::Callback {
+                    operation: other_operation,
+                    name: other_name,
+                    expression: other_expression,
+                    ..
+                },
+            ) => {
+                operation == other_operation && name == other_name && expression == other_expression
+            }
+            _ => false,
+        }
+    }
+}
+
+impl Eq for PathItemSource {}
+
+impl OAS31Source for PathItemSource {
+    type OAS31Type = oas3::spec::PathItem;
+
+    fn inner<'a>(&'a self, spec: &'a oas3::spec::Spec) -> Option<&'a Self::OAS31Type> {
+        match self {
+            PathItemSource::Path(path) => {
+                let path_item = spec.paths.as_ref()?.get(path)?;
+                resolve_path_item(spec, path_item)
+            }
+            PathItemSource::Callback { path_item, .. } => Some(path_item),
+        }
+    }
+}
+
+impl PathItem<OAS31Spec> for OAS31Pointer<PathItemSource> {
+    fn operations_iter(&self) -> impl Iterator<Item = (Method, OAS31Pointer<OperationSource>)> {
+        let operations = self
+            .inner()
+            .map(|path_item| {
+                vec![
+                    (Method::GET, &path_item.get),
+                    (Method::PUT, &path_item.put),
+                    (Method::POST, &path_item.post),
+                    (Method::DELETE, &path_item.delete),
+                    (Method::OPTIONS, &path_item.options),
+                    (Method::HEAD, &path_item.head),
+                    (Method::PATCH, &path_item.patch),
+                    (Method::TRACE, &path_item.trace),
+                ]
+            })
+            .unwrap_or_default();
+        operations
+            .into_iter()
+            .filter_map(|(method, operation_opt)| operation_opt.as_ref().map(|_operation| method))
+            .map(|method| {
+                let ref_source = OperationSource {
+                    path_item: self.ref_source.clone(),
+                    method: method.clone(),
+                };
+                (method, self.with_source(ref_source))
+            })
+    }
+
+    fn parameters(&self) -> impl Iterator<Item = RefOr<OAS31Pointer<ParameterSource>>> {
+        let parameters = self
+            .inner()
+            .map(|path_item| path_item.parameters.as_slice())
+            .unwrap_or_default();
+        to_parameters_iter(self, parameters, |index| ParameterSource::PathItem {
+            source_ref: self.ref_source.clone(),
+            index,
+        })
+    }
+}
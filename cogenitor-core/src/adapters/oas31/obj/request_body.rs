@@ -0,0 +1,71 @@
+use std::hash::Hash;
+
+use indexmap::IndexMap;
+
+use super::super::{
+    MediaTypeSource, OAS31Pointer, OAS31Resolver, OAS31Source, SourceFromUri, into_oas31_content,
+};
+use super::{OAS31Spec, OperationSource};
+use crate::types::RequestBody;
+
+impl OAS31Resolver<oas3::spec::RequestBody> for oas3::spec::Spec {
+    fn prefix(&self) -> &'static str {
+        "#/components/requestBodies/"
+    }
+
+    fn resolve_reference(&self, reference: &str) -> Option<&oas3::spec::RequestBody> {
+        let ro = self.components.as_ref()?.request_bodies.get(reference)?;
+        self.resolve(ro)
+    }
+}
+
+impl SourceFromUri for RequestBodySource {
+    fn from_uri(uri: &str) -> Self {
+        RequestBodySource::Uri {
+            uri: uri.to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+pub enum RequestBodySource {
+    Uri { uri: String },
+    Operation { source_ref: OperationSource },
+}
+
+impl OAS31Source for RequestBodySource {
+    type OAS31Type = oas3::spec::RequestBody;
+
+    fn inner<'a>(&'a self, spec: &'a oas3::spec::Spec) -> Option<&'a Self::OAS31Type> {
+        match self {
+            RequestBodySource::Uri { uri } => {
+                OAS31Resolver::<oas3::spec::RequestBody>::resolve_uri(spec, uri)
+            }
+            RequestBodySource::Operation { source_ref } => {
+                spec.resolve(source_ref.inner(spec)?.request_body.as_ref()?)
+            }
+        }
+    }
+}
+
+impl RequestBody<OAS31Spec> for OAS31Pointer<RequestBodySource> {
+    fn content(&self) -> IndexMap<String, OAS31Pointer<MediaTypeSource>> {
+        let Some(request_body) = self.inner() else {
+            return IndexMap::new();
+        };
+        into_oas31_content(&request_body.content, |media_type| {
+            self.with_source(MediaTypeSource::RequestBody {
+                ref_source: self.ref_source.clone(),
+                media_type,
+            })
+        })
+    }
+
+    fn required(&self) -> bool {
+        self.inner().and_then(|b| b.required).unwrap_or(false)
+    }
+
+    fn description(&self) -> Option<&str> {
+        self.inner()?.description.as_deref()
+    }
+}
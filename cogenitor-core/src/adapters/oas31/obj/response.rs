@@ -0,0 +1,135 @@
+use std::hash::Hash;
+
+use indexmap::IndexMap;
+
+use crate::{
+    adapters::oas31::{
+        HeaderSource, MediaTypeSource, OAS31Pointer, OAS31Resolver, OAS31Source, OAS31Spec,
+        OperationSource, SourceFromUri, into_oas31_content, into_ref_or,
+    },
+    types::{Link, RefOr, Response, Spec},
+};
+
+impl OAS31Resolver<oas3::spec::Link> for oas3::spec::Spec {
+    fn prefix(&self) -> &'static str {
+        "#/components/links/"
+    }
+
+    fn resolve_reference(&self, reference: &str) -> Option<&oas3::spec::Link> {
+        let ro = self.components.as_ref()?.links.get(reference)?;
+        self.resolve(ro)
+    }
+}
+
+impl OAS31Resolver<oas3::spec::Response> for oas3::spec::Spec {
+    fn prefix(&self) -> &'static str {
+        "#/components/responses/"
+    }
+
+    fn resolve_reference(&self, reference: &str) -> Option<&oas3::spec::Response> {
+        let ro = self.components.as_ref()?.responses.get(reference)?;
+        self.resolve(ro)
+    }
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub enum ResponseSource {
+    Uri {
+        uri: String,
+    },
+    /// a response of an operation, by its status key (like `200`, `4XX`
+    /// or `default`)
+    Operation {
+        ref_source: OperationSource,
+        status: String,
+    },
+}
+
+impl OAS31Source for ResponseSource {
+    type OAS31Type = oas3::spec::Response;
+
+    fn inner<'a>(&'a self, spec: &'a oas3::spec::Spec) -> Option<&'a Self::OAS31Type> {
+        match self {
+            ResponseSource::Uri { uri } => {
+                OAS31Resolver::<oas3::spec::Response>::resolve_uri(spec, uri)
+            }
+            ResponseSource::Operation { ref_source, status } => {
+                let responses = ref_source.inner(spec)?.responses.as_ref()?;
+                spec.resolve(responses.get(status)?)
+            }
+        }
+    }
+}
+
+impl Response<OAS31Spec> for OAS31Pointer<ResponseSource> {
+    fn content(&self) -> IndexMap<String, <OAS31Spec as Spec>::MediaType> {
+        let Some(response) = self.inner() else {
+            return IndexMap::new();
+        };
+        into_oas31_content(&response.content, |media_type| {
+            self.with_source(MediaTypeSource::Response {
+                ref_source: self.ref_source.clone(),
+                media_type,
+            })
+        })
+    }
+
+    fn headers(&self) -> IndexMap<String, RefOr<<OAS31Spec as Spec>::Header>> {
+        let Some(response) = self.inner() else {
+            return IndexMap::new();
+        };
+        response
+            .headers
+            .iter()
+            .map(|(name, header_ro)| {
+                let header = into_ref_or(header_ro, self, |src| HeaderSource::Response {
+                    ref_source: src.clone(),
+                    header_name: name.clone(),
+                });
+                (name.clone(), header)
+            })
+            .collect()
+    }
+
+    fn links(&self) -> IndexMap<String, Link> {
+        let Some(response) = self.inner() else {
+            return IndexMap::new();
+        };
+        response
+            .links
+            .iter()
+            .filter_map(|(name, link_ro)| {
+                let link: &oas3::spec::Link = self.spec.resolve(link_ro)?;
+                let (operation_id, parameters) = match link {
+                    oas3::spec::Link::Id {
+                        operation_id,
+                        parameters,
+                        ..
+                    } => (Some(operation_id.clone()), parameters),
+                    oas3::spec::Link::Ref { parameters, .. } => (None, parameters),
+                };
+                // the oas3 crate only keeps runtime expressions and
+                // constants given as strings
+                let parameters = parameters
+                    .iter()
+                    .map(|(name, value)| (name.clone(), json::JsonValue::from(value.as_str())))
+                    .collect();
+                Some((
+                    name.clone(),
+                    Link {
+                        operation_id,
+                        parameters,
+                    },
+                ))
+            })
+            .collect()
+    }
+}
+
+impl SourceFromUri for ResponseSource {
+    fn from_uri(uri: &str) -> Self {
+        ResponseSource::Uri {
+            uri: uri.to_string(),
+        }
+    }
+}
@@ -0,0 +1,350 @@
+use std::hash::Hash;
+
+use indexmap::IndexMap;
+use oas3::spec::{ObjectOrReference, ObjectSchema, SchemaType, SchemaTypeSet};
+
+use super::super::{
+    MediaTypeSource, OAS31Pointer, OAS31Resolver, OAS31Source, SourceFromUri, into_ref_or,
+    to_json_value,
+};
+use super::{HeaderSource, ParameterSource};
+use crate::types::{BooleanOrSchema, RefOr, Schema};
+
+impl OAS31Resolver<ObjectSchema> for oas3::spec::Spec {
+    fn prefix(&self) -> &'static str {
+        "#/components/schemas/"
+    }
+
+    fn resolve_reference(&self, reference: &str) -> Option<&ObjectSchema> {
+        let ro = self.components.as_ref()?.schemas.get(reference)?;
+        self.resolve(ro)
+    }
+}
+
+#[derive(Clone, Hash, PartialEq, Eq)]
+pub enum SchemaSource {
+    Uri(String),
+    SchemaProperty((Box<SchemaSource>, String)),
+    AdditionalProperties(Box<SchemaSource>),
+    Items(Box<SchemaSource>),
+    Parameter(Box<ParameterSource>),
+    MediaType(Box<MediaTypeSource>),
+    Header(Box<HeaderSource>),
+    AllOf((Box<SchemaSource>, usize)),
+    AnyOf((Box<SchemaSource>, usize)),
+    OneOf((Box<SchemaSource>, usize)),
+}
+
+impl OAS31Source for SchemaSource {
+    type OAS31Type = ObjectSchema;
+
+    fn inner<'a>(&'a self, spec: &'a oas3::spec::Spec) -> Option<&'a Self::OAS31Type> {
+        match self {
+            SchemaSource::Uri(uri) => OAS31Resolver::<ObjectSchema>::resolve_uri(spec, uri),
+            SchemaSource::SchemaProperty((schema_ref, name)) => {
+                spec.resolve(schema_ref.inner(spec)?.properties.get(name)?)
+            }
+            SchemaSource::AdditionalProperties(schema_ref) => {
+                match schema_ref.inner(spec)?.additional_properties.as_ref()? {
+                    oas3::spec::Schema::Object(ro) => spec.resolve(ro),
+                    oas3::spec::Schema::Boolean(_) => None,
+                }
+            }
+            SchemaSource::Items(schema_ref) => {
+                spec.resolve(schema_ref.inner(spec)?.items.as_ref()?)
+            }
+            SchemaSource::Parameter(parameter_source) => {
+                spec.resolve(parameter_source.inner(spec)?.schema.as_ref()?)
+            }
+            SchemaSource::MediaType(mediatype_source) => {
+                spec.resolve(mediatype_source.inner(spec)?.schema.as_ref()?)
+            }
+            SchemaSource::Header(header_source) => {
+                spec.resolve(header_source.inner(spec)?.schema.as_ref()?)
+            }
+            SchemaSource::AllOf((schema_ref, index)) => {
+                spec.resolve(schema_ref.inner(spec)?.all_of.get(*index)?)
+            }
+            SchemaSource::AnyOf((schema_ref, index)) => {
+                spec.resolve(schema_ref.inner(spec)?.any_of.get(*index)?)
+            }
+            SchemaSource::OneOf((schema_ref, index)) => {
+                spec.resolve(schema_ref.inner(spec)?.one_of.get(*index)?)
+            }
+        }
+    }
+}
+
+impl SourceFromUri for SchemaSource {
+    fn from_uri(uri: &str) -> Self {
+        SchemaSource::Uri(uri.to_string())
+    }
+}
+
+impl std::fmt::Debug for SchemaSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SchemaSource::Uri(uri) => f.write_fmt(format_args!("'{uri}'")),
+            SchemaSource::SchemaProperty((schema_ref, name)) => {
+                f.write_fmt(format_args!("{schema_ref:?}.properties.{name}"))
+            }
+            SchemaSource::AdditionalProperties(schema_ref) => {
+                f.write_fmt(format_args!("{schema_ref:?}.additionalProperties"))
+            }
+            SchemaSource::Items(schema_ref) => f.write_fmt(format_args!("{schema_ref:?}.items")),
+            SchemaSource::Parameter(parameter_source) => {
+                f.write_fmt(format_args!("{parameter_source:?}.schema"))
+            }
+            SchemaSource::MediaType(mediatype_source) => {
+                f.write_fmt(format_args!("{mediatype_source:?}.schema"))
+            }
+            SchemaSource::Header(header_source) => {
+                f.write_fmt(format_args!("{header_source:?}.schema"))
+            }
+            SchemaSource::AllOf((schema_ref, index)) => {
+                f.write_fmt(format_args!("{schema_ref:?}.allOf[{index}]"))
+            }
+            SchemaSource::AnyOf((schema_ref, index)) => {
+                f.write_fmt(format_args!("{schema_ref:?}.anyOf[{index}]"))
+            }
+            SchemaSource::OneOf((schema_ref, index)) => {
+                f.write_fmt(format_args!("{schema_ref:?}.oneOf[{index}]"))
+            }
+        }
+    }
+}
+
+pub type OAS31SchemaPointer = OAS31Pointer<SchemaSource>;
+
+impl OAS31Pointer<SchemaSource> {
+    /// Create pointers to the subschemas of a composition. Referenced subschemas
+    /// point to their location under `#/components/schemas`, inlined ones
+    /// are addressed by their index within the composition.
+    fn composition_pointers(
+        &self,
+        subschemas: &[ObjectOrReference<ObjectSchema>],
+        variant: fn((Box<SchemaSource>, usize)) -> SchemaSource,
+    ) -> Vec<OAS31SchemaPointer> {
+        subschemas
+            .iter()
+            .enumerate()
+            .map(|(index, ro)| {
+                let ref_source = match ro {
+                    ObjectOrReference::Ref { ref_path } => SchemaSource::Uri(ref_path.clone()),
+                    ObjectOrReference::Object(_) => {
+                        variant((Box::new(self.ref_source.clone()), index))
+                    }
+                };
+                self.with_source(ref_source)
+            })
+            .collect()
+    }
+
+    /// The types listed by the `type` keyword, which may be a single type
+    /// or an array of types in OAS 3.1
+    fn schema_types(&self) -> Vec<SchemaType> {
+        match self.inner().and_then(|s| s.schema_type.as_ref()) {
+            Some(SchemaTypeSet::Single(t)) => vec![*t],
+            Some(SchemaTypeSet::Multiple(types)) => types.clone(),
+            None => Vec::new(),
+        }
+    }
+}
+
+impl Schema for OAS31Pointer<SchemaSource> {
+    fn name(&self) -> Option<&str> {
+        // referenced schemas are always pointed to by their URI, all others
+        // are inlined and therefore have no name
+        match &self.ref_source {
+            SchemaSource::Uri(uri) => uri.rsplit('/').next(),
+            _ => None,
+        }
+    }
+
+    fn schema_dialect(&self) -> Option<&str> {
+        // `$schema` is only read for the schemas in the components
+        // section, which are the ones the dialect lint checks
+        let declared = match &self.ref_source {
+            SchemaSource::Uri(uri) => uri
+                .strip_prefix("#/components/schemas/")
+                .and_then(|name| self.dialects.components.get(name)),
+            _ => None,
+        };
+        declared
+            .or(self.dialects.default.as_ref())
+            .map(String::as_str)
+    }
+
+    fn type_(&self) -> Option<Vec<crate::types::Type>> {
+        let types = self.schema_types();
+        if types.is_empty() {
+            None
+        } else {
+            Some(types.into_iter().map(Into::into).collect())
+        }
+    }
+
+    fn format(&self) -> Option<crate::types::Format> {
+        let format = self.inner()?.format.as_deref()?;
+        Some(crate::types::Format::parse(format))
+    }
+
+    fn is_integer(&self) -> bool {
+        self.schema_types().contains(&SchemaType::Integer)
+    }
+
+    fn title(&self) -> Option<&str> {
+        self.inner()?.title.as_deref()
+    }
+
+    fn description(&self) -> Option<&str> {
+        self.inner()?.description.as_deref()
+    }
+
+    fn min_length(&self) -> Option<usize> {
+        self.inner()?.min_length.map(|l| l as usize)
+    }
+
+    fn max_length(&self) -> Option<usize> {
+        self.inner()?.max_length.map(|l| l as usize)
+    }
+
+    fn min_properties(&self) -> Option<usize> {
+        self.inner()?.min_properties.map(|l| l as usize)
+    }
+
+    fn max_properties(&self) -> Option<usize> {
+        self.inner()?.max_properties.map(|l| l as usize)
+    }
+
+    fn required(&self) -> Option<Vec<&str>> {
+        let required = &self.inner()?.required;
+        if required.is_empty() {
+            None
+        } else {
+            Some(required.iter().map(String::as_str).collect())
+        }
+    }
+
+    fn all_of(&self) -> Option<Vec<RefOr<Self>>> {
+        let all_of = &self.inner()?.all_of;
+        if all_of.is_empty() {
+            return None;
+        }
+        let subschemas = all_of
+            .iter()
+            .enumerate()
+            .map(|(index, ro)| {
+                into_ref_or(ro, self, |src| {
+                    SchemaSource::AllOf((Box::new(src.clone()), index))
+                })
+            })
+            .collect();
+        Some(subschemas)
+    }
+
+    fn any_of(&self) -> Option<Vec<impl Schema>> {
+        let any_of = &self.inner()?.any_of;
+        if any_of.is_empty() {
+            None
+        } else {
+            Some(self.composition_pointers(any_of, SchemaSource::AnyOf))
+        }
+    }
+
+    fn one_of(&self) -> Option<Vec<impl Schema>> {
+        let one_of = &self.inner()?.one_of;
+        if one_of.is_empty() {
+            None
+        } else {
+            Some(self.composition_pointers(one_of, SchemaSource::OneOf))
+        }
+    }
+
+    fn enum_(&self) -> Option<Vec<json::JsonValue>> {
+        let enum_values = &self.inner()?.enum_values;
+        if enum_values.is_empty() {
+            None
+        } else {
+            Some(enum_values.iter().filter_map(to_json_value).collect())
+        }
+    }
+
+    fn const_value(&self) -> Option<json::JsonValue> {
+        to_json_value(self.inner()?.const_value.as_ref()?)
+    }
+
+    fn default_value(&self) -> Option<json::JsonValue> {
+        to_json_value(self.inner()?.default.as_ref()?)
+    }
+
+    fn example(&self) -> Option<json::JsonValue> {
+        // 'example' is deprecated in OAS 3.1 in favor of 'examples'
+        let inner = self.inner()?;
+        to_json_value(inner.examples.first().or(inner.example.as_ref())?)
+    }
+
+    fn extension_value(&self, key: &str) -> Option<json::JsonValue> {
+        // the oas3 crate keeps extensions without their `x-` prefix
+        let key = key.strip_prefix("x-")?;
+        to_json_value(self.inner()?.extensions.get(key)?)
+    }
+
+    fn read_only(&self) -> bool {
+        self.inner().and_then(|s| s.read_only).unwrap_or(false)
+    }
+
+    fn write_only(&self) -> bool {
+        self.inner().and_then(|s| s.write_only).unwrap_or(false)
+    }
+
+    fn nullable(&self) -> bool {
+        self.schema_types().contains(&SchemaType::Null)
+    }
+
+    fn properties(&self) -> IndexMap<String, RefOr<Self>> {
+        let Some(inner) = self.inner() else {
+            return IndexMap::new();
+        };
+        inner
+            .properties
+            .iter()
+            .map(|(name, ro)| {
+                let property = into_ref_or(ro, self, |src| {
+                    SchemaSource::SchemaProperty((Box::new(src.clone()), name.clone()))
+                });
+                (name.clone(), property)
+            })
+            .collect()
+    }
+
+    fn pattern_properties(&self) -> IndexMap<String, RefOr<Self>> {
+        // TODO: oas3 does not model 'patternProperties' yet (see the TODO on
+        // oas3::spec::ObjectSchema), so we cannot report them
+        IndexMap::new()
+    }
+
+    fn addtional_properties(&self) -> BooleanOrSchema<Self> {
+        match self.inner().and_then(|s| s.additional_properties.as_ref()) {
+            Some(oas3::spec::Schema::Boolean(b)) => BooleanOrSchema::Boolean(b.0),
+            // referenced schemas are pointed to by their URI, so that they
+            // map to the types generated for them
+            Some(oas3::spec::Schema::Object(ro)) => match ro.as_ref() {
+                ObjectOrReference::Ref { ref_path } => {
+                    BooleanOrSchema::Schema(self.with_source(SchemaSource::Uri(ref_path.clone())))
+                }
+                ObjectOrReference::Object(_) => BooleanOrSchema::Schema(self.with_source(
+                    SchemaSource::AdditionalProperties(Box::new(self.ref_source.clone())),
+                )),
+            },
+            None => BooleanOrSchema::Boolean(true),
+        }
+    }
+
+    fn items(&self) -> Option<Vec<RefOr<Self>>> {
+        let items = self.inner()?.items.as_ref()?;
+        Some(vec![into_ref_or(items, self, |src| {
+            SchemaSource::Items(Box::new(src.clone()))
+        })])
+    }
+}
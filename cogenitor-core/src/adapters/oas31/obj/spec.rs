@@ -0,0 +1,192 @@
+use indexmap::IndexMap;
+use std::io::BufReader;
+use std::rc::Rc;
+use std::str::FromStr;
+
+use oas3::spec::Spec;
+
+use super::super::{MediaTypeSource, OAS31Pointer, ResponseSource, SchemaDialects};
+use super::{
+    ComponentsSource, HeaderSource, OperationSource, ParameterSource, PathItemSource,
+    RequestBodySource, SchemaSource, resolve_path_item,
+};
+use crate::types::{
+    Components, Contact, License, RefOr, SecurityRequirement, ServerVariable, ValidationError,
+};
+
+pub struct OAS31Spec {
+    spec: Rc<Spec>,
+    dialects: Rc<SchemaDialects>,
+}
+
+impl FromStr for OAS31Spec {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, anyhow::Error> {
+        parse_spec(serde_yaml::from_str(s)?)
+    }
+}
+
+/// Parses an OAS 3.1 document, keeping the JSON Schema dialects it
+/// declares, which `oas3` drops
+fn parse_spec(document: serde_yaml::Value) -> anyhow::Result<OAS31Spec> {
+    let dialects = SchemaDialects {
+        default: document
+            .get("jsonSchemaDialect")
+            .and_then(|dialect| dialect.as_str())
+            .map(str::to_string),
+        components: document
+            .get("components")
+            .and_then(|components| components.get("schemas"))
+            .and_then(|schemas| schemas.as_mapping())
+            .into_iter()
+            .flatten()
+            .filter_map(|(name, schema)| {
+                let dialect = schema.get("$schema")?.as_str()?;
+                Some((name.as_str()?.to_string(), dialect.to_string()))
+            })
+            .collect(),
+    };
+    let spec: Spec = serde_yaml::from_value(document)?;
+    Ok(OAS31Spec {
+        spec: Rc::new(spec),
+        dialects: Rc::new(dialects),
+    })
+}
+
+impl From<Spec> for OAS31Spec {
+    fn from(spec: Spec) -> Self {
+        OAS31Spec {
+            spec: Rc::new(spec),
+            dialects: Rc::new(SchemaDialects::default()),
+        }
+    }
+}
+
+impl crate::Spec for OAS31Spec {
+    type Schema = OAS31Pointer<SchemaSource>;
+    type Components = OAS31Pointer<ComponentsSource>;
+    type PathItem = OAS31Pointer<PathItemSource>;
+    type Parameter = OAS31Pointer<ParameterSource>;
+    type MediaType = OAS31Pointer<MediaTypeSource>;
+    type Operation = OAS31Pointer<OperationSource>;
+    type RequestBody = OAS31Pointer<RequestBodySource>;
+    type Response = OAS31Pointer<ResponseSource>;
+    type Header = OAS31Pointer<HeaderSource>;
+
+    fn from_reader(r: impl std::io::Read) -> anyhow::Result<impl crate::Spec> {
+        let r = BufReader::new(r);
+        parse_spec(serde_yaml::from_reader(r)?)
+    }
+
+    fn schemata_iter(&self) -> impl Iterator<Item = (String, RefOr<Self::Schema>)> {
+        self.components()
+            .iter()
+            .flat_map(|c| c.schemas())
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
+    fn paths(&self) -> impl Iterator<Item = (String, Self::PathItem)> {
+        let paths: Vec<String> = self
+            .spec
+            .paths
+            .iter()
+            .flatten()
+            .filter(|(_, path_item)| resolve_path_item(&self.spec, path_item).is_some())
+            .map(|(path, _)| path.clone())
+            .collect();
+        let spec = self.spec.clone();
+        let dialects = self.dialects.clone();
+        paths.into_iter().map(move |path| {
+            let path_item = OAS31Pointer {
+                spec: spec.clone(),
+                dialects: dialects.clone(),
+                ref_source: PathItemSource::Path(path.clone()),
+            };
+            (path, path_item)
+        })
+    }
+
+    fn unresolved_path_item_refs(&self) -> Vec<(String, String)> {
+        self.spec
+            .paths
+            .iter()
+            .flatten()
+            .filter(|(_, path_item)| resolve_path_item(&self.spec, path_item).is_none())
+            .filter_map(|(path, path_item)| {
+                path_item
+                    .reference
+                    .as_ref()
+                    .map(|reference| (path.clone(), reference.clone()))
+            })
+            .collect()
+    }
+
+    fn unsupported_operations(&self) -> Vec<(String, String)> {
+        // the `oas3` crate gives no access to unknown path item keys
+        Vec::new()
+    }
+
+    fn components(&self) -> Option<OAS31Pointer<ComponentsSource>> {
+        self.spec.components.as_ref().map(|_| OAS31Pointer {
+            spec: self.spec.clone(),
+            dialects: self.dialects.clone(),
+            ref_source: ComponentsSource,
+        })
+    }
+
+    fn contact(&self) -> Option<Contact> {
+        self.spec.info.contact.as_ref().map(|c| Contact {
+            name: c.name.clone(),
+            url: c.url.as_ref().map(|url| url.to_string()),
+            email: c.email.clone(),
+        })
+    }
+
+    fn license(&self) -> Option<License> {
+        self.spec.info.license.as_ref().map(|l| License {
+            name: l.name.clone(),
+            url: l.url.as_ref().map(|url| url.to_string()),
+        })
+    }
+
+    fn terms_of_service(&self) -> Option<String> {
+        self.spec
+            .info
+            .terms_of_service
+            .as_ref()
+            .map(|url| url.to_string())
+    }
+
+    fn security(&self) -> Vec<SecurityRequirement> {
+        // the oas3 crate does not parse security requirements yet
+        Vec::new()
+    }
+
+    fn server_url(&self) -> Option<String> {
+        self.spec.servers.first().map(|s| s.url.clone())
+    }
+
+    fn validate_references(&self) -> Vec<ValidationError> {
+        super::super::validate::validate_references(&self.spec)
+    }
+
+    fn server_variables(&self) -> IndexMap<String, ServerVariable> {
+        let Some(server) = self.spec.servers.first() else {
+            return IndexMap::new();
+        };
+        server
+            .variables
+            .iter()
+            .map(|(name, v)| {
+                let variable = ServerVariable {
+                    default: v.default.clone(),
+                    enum_: v.substitutions_enum.clone(),
+                    description: v.description.clone(),
+                };
+                (name.clone(), variable)
+            })
+            .collect()
+    }
+}
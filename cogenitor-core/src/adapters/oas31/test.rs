@@ -0,0 +1,509 @@
+//! Tests for OAS 3.1 adapter implementation
+
+use std::str::FromStr;
+
+use crate::{
+    adapters::oas31::OAS31Spec,
+    types::{Operation, Parameter, ParameterLocation, PathItem, Schema, Spec},
+};
+
+#[test]
+fn test_empty() {
+    let oas = r"
+openapi: 3.1.0
+info:
+    title: Empty API
+    version: v1
+paths: {}";
+    println!("parsing {oas}");
+    let spec = OAS31Spec::from_str(oas).unwrap();
+    assert!(spec.schemata_iter().next().is_none());
+}
+
+#[test]
+fn test_path_item_refs() {
+    let oas = r"
+openapi: 3.1.0
+info:
+    title: Path item references
+    version: v1
+paths:
+    /things:
+        $ref: '#/components/pathItems/Things'
+    /external:
+        $ref: 'other.yaml#/paths/~1external'
+components:
+    pathItems:
+        Things:
+            get:
+                responses:
+                    '200':
+                        description: the things";
+
+    let spec = OAS31Spec::from_str(oas).unwrap();
+    let paths: Vec<_> = spec.paths().collect();
+    assert_eq!(paths.len(), 1);
+    let (path, path_item) = &paths[0];
+    assert_eq!(path, "/things");
+    assert_eq!(path_item.operations_iter().count(), 1);
+    assert_eq!(
+        spec.unresolved_path_item_refs(),
+        vec![(
+            "/external".to_string(),
+            "other.yaml#/paths/~1external".to_string()
+        )]
+    );
+}
+
+#[test]
+fn test_number_formats() {
+    let oas = r"
+openapi: 3.1.0
+info:
+    title: Number Formats
+    version: v1
+paths: {}
+components:
+    schemas:
+        NumberFormats:
+            type: object
+            properties:
+                int32_field:
+                    type: integer
+                    format: int32
+                int64_field:
+                    type: integer
+                    format: int64
+                float_field:
+                    type: number
+                    format: float
+                double_field:
+                    type: number
+                    format: double";
+
+    let spec = OAS31Spec::from_str(oas).unwrap();
+    let mut schemas: Vec<_> = spec.schemata_iter().collect();
+    assert_eq!(schemas.len(), 1);
+
+    let (name, schema) = schemas.pop().unwrap();
+    assert_eq!(name, "NumberFormats");
+
+    let resolved_schema = schema.resolve_fully();
+    let properties = resolved_schema.properties();
+    assert_eq!(properties.len(), 4);
+
+    let int32_field = properties.get("int32_field").unwrap().resolve_fully();
+    assert_eq!(int32_field.format(), Some(crate::types::Format::Int32));
+}
+
+#[test]
+fn test_const_value() {
+    let oas = r"
+openapi: 3.1.0
+info:
+    title: Const
+    version: v1
+paths: {}
+components:
+    schemas:
+        AuthScheme:
+            type: string
+            const: Bearer";
+
+    let spec = OAS31Spec::from_str(oas).unwrap();
+    let (name, schema) = spec.schemata_iter().next().unwrap();
+    assert_eq!(name, "AuthScheme");
+    assert_eq!(
+        schema.resolve_fully().const_value(),
+        Some(json::JsonValue::from("Bearer"))
+    );
+}
+
+#[test]
+fn test_inline_const_properties() {
+    let oas = r"
+openapi: 3.1.0
+info:
+    title: Inline constants
+    version: v1
+paths: {}
+components:
+    schemas:
+        Dog:
+            type: object
+            required:
+                - kind
+                - legs
+            properties:
+                kind:
+                    type: string
+                    const: dog
+                legs:
+                    type: integer
+                    format: int32
+                    const: 4
+                family:
+                    type: string
+                    const: canidae";
+
+    let code = crate::generate_from_reader(&crate::ApiConfig::default(), oas.as_bytes())
+        .unwrap()
+        .to_string();
+    // string constants are mapped like single value enums
+    assert!(code.contains("pub kind : DogMarker"), "{code}");
+    assert!(
+        code.contains(r#"# [doc = " Always `\"canidae\"`"] pub family : Option < String >"#),
+        "{code}"
+    );
+    // other constants are documented only
+    assert!(
+        code.contains(r#"# [doc = " Always `4`"] pub legs : i32"#),
+        "{code}"
+    );
+}
+
+#[test]
+fn test_simple_paths() {
+    let oas = r"
+openapi: 3.1.0
+info:
+    title: Simple Paths
+    version: v1
+paths:
+    /users:
+        get:
+            operationId: getUsers
+        post:
+            operationId: createUser
+    /users/{id}:
+        parameters:
+            - name: id
+              in: path
+              required: true
+              schema:
+                  type: string
+        get:
+            operationId: getUser";
+
+    let spec = OAS31Spec::from_str(oas).unwrap();
+    let paths: Vec<_> = spec.paths().collect();
+    assert_eq!(paths.len(), 2);
+
+    // Check first path
+    let (path, path_item) = &paths[0];
+    assert_eq!(path, "/users");
+    let operations: Vec<_> = path_item.operations_iter().collect();
+    assert_eq!(operations.len(), 2);
+
+    // Check second path
+    let (path, path_item) = &paths[1];
+    assert_eq!(path, "/users/{id}");
+    let operations: Vec<_> = path_item.operations_iter().collect();
+    assert_eq!(operations.len(), 1);
+
+    let parameters: Vec<_> = path_item.parameters().collect();
+    assert_eq!(parameters.len(), 1);
+
+    let param = &parameters[0];
+    assert_eq!(param.resolve_fully().name(), "id");
+    assert_eq!(param.resolve_fully().in_(), ParameterLocation::Path);
+}
+
+#[test]
+fn test_comprehensive_oas31_spec() {
+    let oas = r"
+openapi: 3.1.0
+info:
+    title: Comprehensive OAS 3.1 Test
+    version: 1.0.0
+    description: A comprehensive test of OpenAPI 3.1 features
+paths:
+    /pets:
+        get:
+            summary: List all pets
+            operationId: listPets
+            parameters:
+                - name: limit
+                  in: query
+                  description: How many items to return at one time (max 100)
+                  required: false
+                  schema:
+                      type: integer
+                      format: int32
+        post:
+            summary: Create a pet
+            operationId: createPets
+    /pets/{petId}:
+        parameters:
+            - name: petId
+              in: path
+              required: true
+              description: The id of the pet to retrieve
+              schema:
+                  type: string
+        get:
+            summary: Info for a specific pet
+            operationId: showPetById
+components:
+    schemas:
+        Pet:
+            type: object
+            required:
+                - id
+                - name
+            properties:
+                id:
+                    type: integer
+                    format: int64
+                name:
+                    type: string
+                    examples:
+                        - Fluffy
+                        - Buddy
+                tag:
+                    type: string
+                    description: Pet category
+                status:
+                    type: string
+                    enum:
+                        - available
+                        - pending
+                        - sold
+                    default: available
+                metadata:
+                    type: object
+                    additionalProperties:
+                        type: string
+                    description: Additional metadata
+        Error:
+            type: object
+            properties:
+                code:
+                    type: integer
+                    format: int32
+                message:
+                    type: string
+            required:
+                - code
+                - message
+        PetList:
+            type: array
+            items:
+                $ref: '#/components/schemas/Pet'
+            description: A list of pets";
+
+    let spec = OAS31Spec::from_str(oas).unwrap();
+    test_comprehensive_spec_impl(spec);
+}
+
+pub fn test_comprehensive_spec_impl(spec: impl crate::types::Spec) {
+    // Test schema iteration
+    let schemas: Vec<_> = spec.schemata_iter().collect();
+    assert_eq!(schemas.len(), 3);
+
+    let schema_names: Vec<&str> = schemas.iter().map(|(name, _)| name.as_str()).collect();
+    assert!(schema_names.contains(&"Pet"));
+    assert!(schema_names.contains(&"Error"));
+    assert!(schema_names.contains(&"PetList"));
+
+    // Test Pet schema details
+    let pet_schema = schemas
+        .iter()
+        .find(|(name, _)| name == "Pet")
+        .unwrap()
+        .1
+        .resolve_fully();
+    assert_eq!(pet_schema.name(), Some("Pet"));
+
+    let properties = pet_schema.properties();
+    assert_eq!(properties.len(), 5); // id, name, tag, status, metadata
+    assert!(properties.contains_key("id"));
+    assert!(properties.contains_key("name"));
+    assert!(properties.contains_key("tag"));
+    assert!(properties.contains_key("status"));
+    assert!(properties.contains_key("metadata"));
+
+    // Test required fields
+    let required = pet_schema.required().unwrap();
+    assert_eq!(required.len(), 2);
+    assert!(required.contains(&"id"));
+    assert!(required.contains(&"name"));
+
+    // Test id field format
+    let id_field = properties.get("id").unwrap().resolve_fully();
+    assert_eq!(id_field.format(), Some(crate::types::Format::Int64));
+
+    // Test status field enum
+    let status_field = properties.get("status").unwrap().resolve_fully();
+    let enum_values = status_field.enum_();
+    assert!(enum_values.is_some());
+    let enum_vals = enum_values.unwrap();
+    assert_eq!(enum_vals.len(), 3);
+
+    // Test metadata field additional properties
+    let metadata_field = properties.get("metadata").unwrap().resolve_fully();
+    match metadata_field.addtional_properties() {
+        crate::types::BooleanOrSchema::Schema(_) => {} // Expected
+        crate::types::BooleanOrSchema::Boolean(_) => {
+            panic!("Expected schema for additional properties")
+        }
+    }
+
+    // Test PetList array items
+    let pet_list_schema = schemas
+        .iter()
+        .find(|(name, _)| name == "PetList")
+        .unwrap()
+        .1
+        .resolve_fully();
+    let items = pet_list_schema.items();
+    assert!(items.is_some());
+    assert_eq!(items.unwrap().len(), 1);
+
+    // Test path iteration
+    let paths: Vec<_> = spec.paths().collect();
+    assert_eq!(paths.len(), 2);
+
+    // Check /pets path
+    let pets_path = paths.iter().find(|(path, _)| path == "/pets").unwrap();
+    let operations: Vec<_> = pets_path.1.operations_iter().collect();
+    assert_eq!(operations.len(), 2); // GET and POST
+
+    // Check GET operation parameters
+    let get_op = operations
+        .iter()
+        .find(|(method, _)| *method == http::Method::GET)
+        .unwrap();
+    let params: Vec<_> = get_op.1.parameters().collect();
+    assert_eq!(params.len(), 1);
+    assert_eq!(params[0].resolve_fully().name(), "limit");
+    assert_eq!(params[0].resolve_fully().in_(), ParameterLocation::Query);
+
+    // Check /pets/{petId} path parameters
+    let pet_id_path = paths
+        .iter()
+        .find(|(path, _)| path == "/pets/{petId}")
+        .unwrap();
+    let path_params: Vec<_> = pet_id_path.1.parameters().collect();
+    assert_eq!(path_params.len(), 1);
+    assert_eq!(path_params[0].resolve_fully().name(), "petId");
+    assert_eq!(
+        path_params[0].resolve_fully().in_(),
+        ParameterLocation::Path
+    );
+}
+
+#[test]
+fn test_component_headers() {
+    use crate::types::{Components, Header, RefOr, Reference, Response};
+
+    let oas = r"
+openapi: 3.1.0
+info:
+    title: Headers
+    version: v1
+paths:
+    /things:
+        get:
+            responses:
+                '429':
+                    description: too many requests
+                    headers:
+                        X-RateLimit-Reset:
+                            $ref: '#/components/headers/X-RateLimit-Reset'
+components:
+    headers:
+        X-RateLimit-Reset:
+            required: true
+            schema:
+                type: integer
+                format: int64";
+
+    let spec = OAS31Spec::from_str(oas).unwrap();
+    let headers: Vec<_> = spec.components().unwrap().headers().collect();
+    assert_eq!(1, headers.len());
+    let (name, header) = &headers[0];
+    assert_eq!("X-RateLimit-Reset", name);
+    let header = header.resolve_fully();
+    assert!(header.required());
+    let schema = header.schema().unwrap().resolve_fully();
+    assert_eq!(Some(crate::types::Format::Int64), schema.format());
+
+    let (_path, path_item) = spec.paths().next().unwrap();
+    let (_method, operation) = path_item.operations_iter().next().unwrap();
+    let (_status, response) = operation.responses().next().unwrap();
+    let response_headers = response.resolve_fully().headers();
+    let RefOr::Reference(reference) = &response_headers["X-RateLimit-Reset"] else {
+        panic!("reference expected");
+    };
+    let uri = Reference::<<OAS31Spec as Spec>::Header>::uri(reference);
+    assert_eq!("#/components/headers/X-RateLimit-Reset", uri);
+}
+
+#[test]
+fn test_schema_dialects() {
+    use crate::lint::{SchemaDialectDiagnostic, lint_schema_dialects};
+
+    let oas = r#"
+openapi: 3.1.0
+info:
+    title: Dialects
+    version: v1
+paths: {}
+components:
+    schemas:
+        Pet:
+            $schema: "http://json-schema.org/draft-04/schema"
+            type: object
+            properties:
+                name:
+                    type: string
+        Tag:
+            type: object
+            properties:
+                label:
+                    type: string"#;
+
+    let spec = OAS31Spec::from_str(oas).unwrap();
+    let (_, pet) = spec.schemata_iter().next().unwrap();
+    assert_eq!(
+        Some("http://json-schema.org/draft-04/schema"),
+        pet.resolve_fully().schema_dialect()
+    );
+    assert_eq!(
+        vec![SchemaDialectDiagnostic {
+            schema_name: "Pet".to_string(),
+            dialect: "http://json-schema.org/draft-04/schema".to_string(),
+        }],
+        lint_schema_dialects(&spec)
+    );
+    // the dialect is only reported, code is still generated
+    let code = crate::generate_from_reader(&crate::ApiConfig::default(), oas.as_bytes())
+        .unwrap()
+        .to_string();
+    assert!(code.contains("struct Pet"), "{code}");
+
+    // the document's dialect applies to all schemas not declaring one
+    let oas = oas.replace(
+        "paths: {}",
+        "jsonSchemaDialect: https://json-schema.org/draft/2019-09/schema\npaths: {}",
+    );
+    let spec = OAS31Spec::from_str(&oas).unwrap();
+    let dialects: Vec<_> = lint_schema_dialects(&spec)
+        .into_iter()
+        .map(|d| (d.schema_name, d.dialect))
+        .collect();
+    assert_eq!(
+        vec![
+            (
+                "Pet".to_string(),
+                "http://json-schema.org/draft-04/schema".to_string()
+            ),
+            (
+                "Tag".to_string(),
+                "https://json-schema.org/draft/2019-09/schema".to_string()
+            ),
+        ],
+        dialects
+    );
+}
@@ -0,0 +1,194 @@
+//! Checks of the `$ref` URIs in an OAS 3.1 document. Our resolvers (see
+//! [OAS31Resolver]) yield `None` for references that do not resolve, which
+//! code generation would silently skip, so these checks report them before.
+
+use std::collections::BTreeMap;
+
+use oas3::spec::{
+    Header, MediaType, ObjectOrReference, ObjectSchema, Parameter, PathItem, RequestBody, Response,
+    Spec,
+};
+
+use super::OAS31Resolver;
+use crate::types::ValidationError;
+
+/// Escapes a name for use as JSON pointer segment, see
+/// https://datatracker.ietf.org/doc/html/rfc6901#section-3
+fn escape(name: &str) -> String {
+    name.replace('~', "~0").replace('/', "~1")
+}
+
+/// Checks all references in schemas, parameters, request bodies, responses
+/// and headers, both in `#/components` and in the operations below `#/paths`
+pub(super) fn validate_references(spec: &Spec) -> Vec<ValidationError> {
+    let mut validator = Validator {
+        spec,
+        errors: Vec::new(),
+    };
+    validator.components();
+    validator.paths();
+    validator.errors
+}
+
+struct Validator<'a> {
+    spec: &'a Spec,
+    errors: Vec<ValidationError>,
+}
+
+impl<'a> Validator<'a> {
+    /// Records an error if `ro` is a reference that does not resolve,
+    /// yields the inlined object otherwise.
+    fn check<T>(&mut self, location: &str, ro: &'a ObjectOrReference<T>) -> Option<&'a T>
+    where
+        Spec: OAS31Resolver<T>,
+    {
+        match ro {
+            ObjectOrReference::Ref { ref_path } => {
+                if OAS31Resolver::<T>::resolve_uri(self.spec, ref_path).is_none() {
+                    self.errors.push(ValidationError {
+                        location: location.to_string(),
+                        reference: ref_path.clone(),
+                    });
+                }
+                None
+            }
+            ObjectOrReference::Object(item) => Some(item),
+        }
+    }
+
+    fn components(&mut self) {
+        let Some(components) = &self.spec.components else {
+            return;
+        };
+        for (name, schema) in &components.schemas {
+            self.schema(&format!("#/components/schemas/{}", escape(name)), schema);
+        }
+        for (name, parameter) in &components.parameters {
+            self.parameter(
+                &format!("#/components/parameters/{}", escape(name)),
+                parameter,
+            );
+        }
+        for (name, request_body) in &components.request_bodies {
+            let location = format!("#/components/requestBodies/{}", escape(name));
+            self.request_body(&location, request_body);
+        }
+        for (name, response) in &components.responses {
+            self.response(
+                &format!("#/components/responses/{}", escape(name)),
+                response,
+            );
+        }
+        for (name, header) in &components.headers {
+            self.header(&format!("#/components/headers/{}", escape(name)), header);
+        }
+        for (name, path_item) in &components.path_items {
+            let location = format!("#/components/pathItems/{}", escape(name));
+            if let Some(path_item) = self.check::<PathItem>(&location, path_item) {
+                self.path_item(&location, path_item);
+            }
+        }
+    }
+
+    fn paths(&mut self) {
+        for (path, path_item) in self.spec.paths.iter().flatten() {
+            // path items referenced via $ref are checked in #/components,
+            // or reported as unresolved path item references
+            if path_item.reference.is_some() {
+                continue;
+            }
+            self.path_item(&format!("#/paths/{}", escape(path)), path_item);
+        }
+    }
+
+    fn path_item(&mut self, location: &str, path_item: &'a PathItem) {
+        for (i, parameter) in path_item.parameters.iter().enumerate() {
+            self.parameter(&format!("{location}/parameters/{i}"), parameter);
+        }
+        for (method, operation) in path_item.methods() {
+            let location = format!("{location}/{}", method.as_str().to_lowercase());
+            for (i, parameter) in operation.parameters.iter().enumerate() {
+                self.parameter(&format!("{location}/parameters/{i}"), parameter);
+            }
+            if let Some(request_body) = &operation.request_body {
+                self.request_body(&format!("{location}/requestBody"), request_body);
+            }
+            for (status, response) in operation.responses.iter().flatten() {
+                self.response(&format!("{location}/responses/{status}"), response);
+            }
+        }
+    }
+
+    fn schema(&mut self, location: &str, ro: &'a ObjectOrReference<ObjectSchema>) {
+        let Some(schema) = self.check::<ObjectSchema>(location, ro) else {
+            return;
+        };
+        for (name, property) in &schema.properties {
+            self.schema(&format!("{location}/properties/{}", escape(name)), property);
+        }
+        if let Some(oas3::spec::Schema::Object(s)) = &schema.additional_properties {
+            self.schema(&format!("{location}/additionalProperties"), s.as_ref());
+        }
+        if let Some(items) = &schema.items {
+            self.schema(&format!("{location}/items"), items.as_ref());
+        }
+        self.subschemas(location, "oneOf", &schema.one_of);
+        self.subschemas(location, "allOf", &schema.all_of);
+        self.subschemas(location, "anyOf", &schema.any_of);
+    }
+
+    fn subschemas(
+        &mut self,
+        location: &str,
+        keyword: &str,
+        schemas: &'a [ObjectOrReference<ObjectSchema>],
+    ) {
+        for (i, schema) in schemas.iter().enumerate() {
+            self.schema(&format!("{location}/{keyword}/{i}"), schema);
+        }
+    }
+
+    fn content(&mut self, location: &str, content: &'a BTreeMap<String, MediaType>) {
+        for (media_type_name, media_type) in content {
+            if let Some(schema) = &media_type.schema {
+                let location = format!("{location}/{}/schema", escape(media_type_name));
+                self.schema(&location, schema);
+            }
+        }
+    }
+
+    fn parameter(&mut self, location: &str, ro: &'a ObjectOrReference<Parameter>) {
+        let Some(parameter) = self.check::<Parameter>(location, ro) else {
+            return;
+        };
+        if let Some(schema) = &parameter.schema {
+            self.schema(&format!("{location}/schema"), schema);
+        }
+        if let Some(content) = &parameter.content {
+            self.content(&format!("{location}/content"), content);
+        }
+    }
+
+    fn request_body(&mut self, location: &str, ro: &'a ObjectOrReference<RequestBody>) {
+        if let Some(request_body) = self.check::<RequestBody>(location, ro) {
+            self.content(&format!("{location}/content"), &request_body.content);
+        }
+    }
+
+    fn response(&mut self, location: &str, ro: &'a ObjectOrReference<Response>) {
+        if let Some(response) = self.check::<Response>(location, ro) {
+            for (name, header) in &response.headers {
+                self.header(&format!("{location}/headers/{}", escape(name)), header);
+            }
+            self.content(&format!("{location}/content"), &response.content);
+        }
+    }
+
+    fn header(&mut self, location: &str, ro: &'a ObjectOrReference<Header>) {
+        if let Some(header) = self.check::<Header>(location, ro)
+            && let Some(schema) = &header.schema
+        {
+            self.schema(&format!("{location}/schema"), schema);
+        }
+    }
+}
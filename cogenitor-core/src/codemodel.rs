@@ -6,8 +6,14 @@ use std::{
 use fqtn::FQTN;
 use lazy_static::lazy_static;
 use proc_macro2::TokenStream;
+use quote::ToTokens;
 
-use crate::codemodel::{implementation::Implementation, simplepath::SimplePath, trait_::Trait};
+use crate::codemodel::{
+    function::FunctionBuilder,
+    implementation::Implementation,
+    simplepath::SimplePath,
+    trait_::{Trait, TraitBuilder},
+};
 
 pub mod fqtn;
 pub mod function;
@@ -20,8 +26,15 @@ pub trait Scope {
     fn find_module(&self, name: &str) -> Option<ModuleRef>;
 }
 
+/// The items generated for a spec. Items share each other via `Rc`, so a
+/// code model is neither `Send` nor `Sync`: it is built and written on a
+/// single thread. To generate code on other threads, hand the code back
+/// as a string (see [crate::generate_string]).
 pub struct Codemodel {
     crate_namespace: Namespace<ModuleRef>,
+    /// the `Option<T>` instances created by [Codemodel::optional], by the
+    /// name of `T`
+    optional_instances: HashMap<String, Vec<TypeRef>>,
 }
 
 lazy_static! {
@@ -35,6 +48,7 @@ impl Codemodel {
     pub fn new() -> Self {
         let mut cm = Codemodel {
             crate_namespace: Namespace::default(),
+            optional_instances: HashMap::new(),
         };
 
         Self::fill_std(&mut cm).unwrap();
@@ -82,6 +96,12 @@ impl Codemodel {
         self.crate_namespace.find_item(crate_name)
     }
 
+    /// Removes the crate with the given name from the codemodel and returns
+    /// it, or `None` if no such crate exists.
+    pub fn remove_crate(&mut self, crate_name: &str) -> Option<ModuleRef> {
+        self.crate_namespace.remove_item(crate_name)
+    }
+
     /** Create a reference to an _instance_ if a generic type (e.g. Vec<u8>) */
     pub fn type_instance(&mut self, generic_type: &TypeRef, type_params: &[TypeRef]) -> TypeRef {
         TypeRef::GenericInstance {
@@ -90,10 +110,46 @@ impl Codemodel {
         }
     }
 
+    /** `Option<T>` for the given type `T`, rendered as `Option<T>`.
+     * Repeated calls for the same type yield the same instance. */
+    pub fn optional(&mut self, type_ref: &TypeRef) -> TypeRef {
+        let option = self.type_option();
+        let instances = self
+            .optional_instances
+            .entry(type_ref.name().to_string())
+            .or_default();
+        let existing = instances.iter().find(|instance| match instance {
+            TypeRef::GenericInstance { type_parameter, .. } => {
+                match (&type_parameter[0], type_ref) {
+                    // builtins are created anew for each use
+                    (TypeRef::Builtin(lhs), TypeRef::Builtin(rhs)) => lhs.name() == rhs.name(),
+                    (lhs, rhs) => lhs == rhs,
+                }
+            }
+            _ => false,
+        });
+        if let Some(instance) = existing {
+            return instance.clone();
+        }
+        let instance = TypeRef::GenericInstance {
+            generic_type: Box::new(option),
+            type_parameter: vec![type_ref.clone()],
+        };
+        instances.push(instance.clone());
+        instance
+    }
+
+    /** Create a reference to a fixed-size array type (e.g. `[u8; 16]`) */
+    pub fn type_array(&self, elem: &TypeRef, len: usize) -> TypeRef {
+        TypeRef::Array {
+            elem: Box::new(elem.clone()),
+            len,
+        }
+    }
+
     pub fn type_unit(&self) -> TypeRef {
         TypeRef::Builtin(Rc::new(Builtin::Unit))
     }
-    #[allow(unused)]
     pub fn type_u8(&self) -> TypeRef {
         TypeRef::Builtin(Rc::new(Builtin::U8))
     }
@@ -105,7 +161,6 @@ impl Codemodel {
     pub fn type_u32(&self) -> TypeRef {
         TypeRef::Builtin(Rc::new(Builtin::U32))
     }
-    #[allow(unused)]
     pub fn type_u64(&self) -> TypeRef {
         TypeRef::Builtin(Rc::new(Builtin::U64))
     }
@@ -133,6 +188,10 @@ impl Codemodel {
         TypeRef::Builtin(Rc::new(Builtin::Bool))
     }
 
+    pub fn type_str(&self) -> TypeRef {
+        TypeRef::Builtin(Rc::new(Builtin::Str))
+    }
+
     pub fn type_string(&self) -> TypeRef {
         self.find_type(&STRING_TYPE_NAME).unwrap()
     }
@@ -168,6 +227,63 @@ impl Codemodel {
             lifetime: None,
         }
     }
+
+    /** A type outside of the code model, like `::std::fmt::Result`, written
+     * as the given path. Paths should be absolute, so that generated code
+     * does not need to import them. */
+    pub fn type_external(&self, path: &str) -> TypeRef {
+        TypeRef::External(Rc::from(path))
+    }
+
+    /** `&mut std::fmt::Formatter<'_>`, as taken by `std::fmt::Display::fmt()` */
+    pub fn type_ref_mut_fmt_formatter(&self) -> TypeRef {
+        TypeRef::Reference {
+            referenced_type: Box::new(self.type_external("::std::fmt::Formatter<'_>")),
+            mutable: true,
+            lifetime: None,
+        }
+    }
+
+    /** `std::fmt::Result`, as returned by `std::fmt::Display::fmt()` */
+    pub fn type_fmt_result(&self) -> TypeRef {
+        self.type_external("::std::fmt::Result")
+    }
+
+    /** `std::collections::HashMap`, to be instantiated via [Self::type_instance] */
+    pub fn type_hashmap(&self) -> TypeRef {
+        self.type_external("::std::collections::HashMap")
+    }
+
+    /** `serde_json::Value`, for JSON data without a more specific type */
+    pub fn type_json_value(&self) -> TypeRef {
+        self.type_external("::serde_json::Value")
+    }
+
+    pub fn trait_display(&self) -> Result<TraitRef, CodeError> {
+        let fmt_fn = FunctionBuilder::new("fmt".to_string(), self.type_fmt_result())
+            .param("self".to_string(), self.type_ref_self())
+            .param("f".to_string(), self.type_ref_mut_fmt_formatter())
+            .build();
+        let display = TraitBuilder::new("::std::fmt::Display")
+            .function(fmt_fn)
+            .build()?;
+        Ok(TraitRef {
+            trait_ref: Rc::new(display),
+        })
+    }
+
+    pub fn trait_debug(&self) -> Result<TraitRef, CodeError> {
+        let fmt_fn = FunctionBuilder::new("fmt".to_string(), self.type_fmt_result())
+            .param("self".to_string(), self.type_ref_self())
+            .param("f".to_string(), self.type_ref_mut_fmt_formatter())
+            .build();
+        let debug = TraitBuilder::new("::std::fmt::Debug")
+            .function(fmt_fn)
+            .build()?;
+        Ok(TraitRef {
+            trait_ref: Rc::new(debug),
+        })
+    }
 }
 
 pub enum FieldListBuilderError {
@@ -206,7 +322,7 @@ impl FieldListBuilder {
     }
 
     pub fn field(self, name: &str, type_ref: TypeRef) -> Result<Self, FieldListBuilderError> {
-        self.field_impl(name, TypeRefOrTokenStream::TypeRef(type_ref))
+        self.field_impl(name, TypeRefOrTokenStream::TypeRef(type_ref), Vec::new())
     }
 
     pub fn field_with_input(
@@ -214,13 +330,27 @@ impl FieldListBuilder {
         name: &str,
         input: TokenStream,
     ) -> Result<Self, FieldListBuilderError> {
-        self.field_impl(name, TypeRefOrTokenStream::TokenStream(input))
+        self.field_impl(name, TypeRefOrTokenStream::TokenStream(input), Vec::new())
+    }
+
+    fn field_with_attrs(
+        self,
+        name: &str,
+        type_ref: TypeRef,
+        attribute_list: Vec<Attr>,
+    ) -> Result<Self, FieldListBuilderError> {
+        self.field_impl(
+            name,
+            TypeRefOrTokenStream::TypeRef(type_ref),
+            attribute_list,
+        )
     }
 
     fn field_impl(
         mut self,
         name: &str,
         t_or_ts: TypeRefOrTokenStream,
+        attribute_list: Vec<Attr>,
     ) -> Result<Self, FieldListBuilderError> {
         if self.fields.iter().any(|f| f.name.eq(name)) {
             return Err(FieldListBuilderError::DuplicateFieldName);
@@ -228,6 +358,7 @@ impl FieldListBuilder {
         let field = Field {
             name: name.to_string(),
             type_ref_or_ts: t_or_ts,
+            attribute_list,
         };
         self.fields.push(field);
         Ok(self)
@@ -308,9 +439,114 @@ where
 
 impl<T> FunctionListBuilder for T where T: PushFunction {}
 
+/// Visibility of a generated item
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Visibility {
+    /// `pub`
+    #[default]
+    Public,
+    /// `pub(crate)`
+    Crate,
+    /// no visibility qualifier, so the item is private to its module
+    Private,
+}
+
+impl ToTokens for Visibility {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        match self {
+            Visibility::Public => tokens.extend(quote::quote!(pub)),
+            Visibility::Crate => tokens.extend(quote::quote!(pub(crate))),
+            Visibility::Private => (),
+        }
+    }
+}
+
+impl FromStr for Visibility {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "public" => Ok(Visibility::Public),
+            "crate" => Ok(Visibility::Crate),
+            "private" => Ok(Visibility::Private),
+            _ => Err(format!(
+                "unknown visibility '{s}', expected one of 'public', 'crate' or 'private'"
+            )),
+        }
+    }
+}
+
+/// The casing conventions serde's `rename_all` attribute supports, which
+/// map snake case field names to the names used on the wire
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SerdeRenameCase {
+    LowerCase,
+    UpperCase,
+    PascalCase,
+    CamelCase,
+    SnakeCase,
+    ScreamingSnakeCase,
+    KebabCase,
+    ScreamingKebabCase,
+}
+
+impl SerdeRenameCase {
+    /// The name of the convention in the attribute, like `camelCase`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SerdeRenameCase::LowerCase => "lowercase",
+            SerdeRenameCase::UpperCase => "UPPERCASE",
+            SerdeRenameCase::PascalCase => "PascalCase",
+            SerdeRenameCase::CamelCase => "camelCase",
+            SerdeRenameCase::SnakeCase => "snake_case",
+            SerdeRenameCase::ScreamingSnakeCase => "SCREAMING_SNAKE_CASE",
+            SerdeRenameCase::KebabCase => "kebab-case",
+            SerdeRenameCase::ScreamingKebabCase => "SCREAMING-KEBAB-CASE",
+        }
+    }
+
+    /// Renames a snake case field name the way serde does, so `pet_id`
+    /// becomes `petId` for [SerdeRenameCase::CamelCase]
+    pub fn apply(&self, field_name: &str) -> String {
+        match self {
+            SerdeRenameCase::LowerCase | SerdeRenameCase::SnakeCase => field_name.to_string(),
+            SerdeRenameCase::UpperCase | SerdeRenameCase::ScreamingSnakeCase => {
+                field_name.to_ascii_uppercase()
+            }
+            SerdeRenameCase::PascalCase => {
+                let mut pascal = String::new();
+                let mut capitalize = true;
+                for c in field_name.chars() {
+                    if c == '_' {
+                        capitalize = true;
+                    } else if capitalize {
+                        pascal.push(c.to_ascii_uppercase());
+                        capitalize = false;
+                    } else {
+                        pascal.push(c);
+                    }
+                }
+                pascal
+            }
+            SerdeRenameCase::CamelCase => {
+                let pascal = SerdeRenameCase::PascalCase.apply(field_name);
+                match pascal.char_indices().nth(1) {
+                    Some((i, _)) => pascal[..i].to_ascii_lowercase() + &pascal[i..],
+                    None => pascal.to_ascii_lowercase(),
+                }
+            }
+            SerdeRenameCase::KebabCase => field_name.replace('_', "-"),
+            SerdeRenameCase::ScreamingKebabCase => {
+                field_name.to_ascii_uppercase().replace('_', "-")
+            }
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct StructBuilder {
     name: String,
+    visibility: Visibility,
     field_builder: FieldListBuilder,
     attribute_list: Vec<Attr>,
 }
@@ -329,8 +565,18 @@ pub(crate) enum TypeRefOrTokenStream {
     TokenStream(TokenStream),
 }
 
+impl PartialEq for TypeRefOrTokenStream {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::TypeRef(lhs), Self::TypeRef(rhs)) => lhs == rhs,
+            (Self::TokenStream(lhs), Self::TokenStream(rhs)) => lhs.to_string() == rhs.to_string(),
+            _ => false,
+        }
+    }
+}
+
 /// Represents the data associated with an enum variant
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 pub(crate) enum EnumVariantData {
     /// Unit variant (e.g., `Red`)
     Unit,
@@ -341,16 +587,21 @@ pub(crate) enum EnumVariantData {
 }
 
 /// Represents a single variant of a Rust enum
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 pub struct EnumVariant {
     name: String,
     data: EnumVariantData,
+    attribute_list: Vec<Attr>,
 }
 
 impl EnumVariant {
     pub(crate) fn data(&self) -> &EnumVariantData {
         &self.data
     }
+
+    pub fn attr_iter(&self) -> impl Iterator<Item = &Attr> {
+        self.attribute_list.iter()
+    }
 }
 
 impl NamedItem for EnumVariant {
@@ -359,15 +610,21 @@ impl NamedItem for EnumVariant {
     }
 }
 
-/// Represents a Rust enum with its variants
-#[derive(Debug)]
+/// Represents a Rust enum with its variants.
+/// Equality is structural: same name, visibility, variants and attributes.
+#[derive(Debug, PartialEq)]
 pub struct Enum {
     name: String,
+    visibility: Visibility,
     variant_list: Vec<EnumVariant>,
     attribute_list: Vec<Attr>,
 }
 
 impl Enum {
+    pub fn visibility(&self) -> Visibility {
+        self.visibility
+    }
+
     pub fn variant_iter(&self) -> impl Iterator<Item = &EnumVariant> {
         self.variant_list.iter()
     }
@@ -387,6 +644,7 @@ impl NamedItem for Enum {
 #[derive(Debug)]
 pub struct EnumBuilder {
     name: String,
+    visibility: Visibility,
     variants: Vec<EnumVariant>,
     attribute_list: Vec<Attr>,
 }
@@ -405,11 +663,17 @@ impl StructBuilder {
     pub fn new(name: &str) -> Self {
         StructBuilder {
             name: name.to_string(),
+            visibility: Visibility::default(),
             field_builder: FieldListBuilder::new(),
             attribute_list: Vec::new(),
         }
     }
 
+    pub fn visibility(mut self, visibility: Visibility) -> Self {
+        self.visibility = visibility;
+        self
+    }
+
     /** Add new field with given name and type, referenced by name */
     pub fn field(mut self, name: &str, type_ref: TypeRef) -> Result<Self, StructBuilderError> {
         self.field_builder = self.field_builder.field(name, type_ref)?;
@@ -425,9 +689,55 @@ impl StructBuilder {
         Ok(self)
     }
 
+    /** Add new field like [Self::field], annotated with the given attribute
+    (e.g. `serde(with = "...")`) */
+    pub fn field_with_attr(
+        self,
+        name: &str,
+        type_ref: TypeRef,
+        attr_path: &str,
+        attr_input: TokenStream,
+    ) -> Result<Self, StructBuilderError> {
+        self.field_with_attrs(name, type_ref, vec![(attr_path, attr_input)])
+    }
+
+    /** Add new field like [Self::field], annotated with the given attributes
+    (e.g. `doc = "..."` and `serde(rename = "...")`) in order */
+    pub fn field_with_attrs(
+        mut self,
+        name: &str,
+        type_ref: TypeRef,
+        attrs: Vec<(&str, TokenStream)>,
+    ) -> Result<Self, StructBuilderError> {
+        let mut attribute_list = Vec::new();
+        for (attr_path, attr_input) in attrs {
+            attribute_list.push(Attr {
+                path: SimplePath::new(attr_path)
+                    .map_err(|_| StructBuilderError::AttrPathInvalid)?,
+                input: attr_input,
+            });
+        }
+        self.field_builder = self
+            .field_builder
+            .field_with_attrs(name, type_ref, attribute_list)?;
+        Ok(self)
+    }
+
+    /** Have serde rename all fields following the given casing convention,
+    with a `#[serde(rename_all = "...")]` attribute on the struct */
+    pub fn serde_rename_all(mut self, case: SerdeRenameCase) -> Self {
+        let case = case.as_str();
+        self.attribute_list.push(Attr {
+            path: SimplePath::new("serde").expect("'serde' is a valid path"),
+            input: quote::quote!((rename_all = #case)),
+        });
+        self
+    }
+
     pub fn build(self) -> Result<Struct, StructBuilderError> {
         Ok(Struct {
             name: self.name,
+            visibility: self.visibility,
             attribute_list: self.attribute_list,
             field_list: self.field_builder.build(),
         })
@@ -444,18 +754,42 @@ impl EnumBuilder {
     pub fn new(name: &str) -> Self {
         EnumBuilder {
             name: name.to_string(),
+            visibility: Visibility::default(),
             variants: Vec::new(),
             attribute_list: Vec::new(),
         }
     }
 
-    pub fn unit_variant(mut self, name: &str) -> Result<Self, EnumBuilderError> {
+    pub fn visibility(mut self, visibility: Visibility) -> Self {
+        self.visibility = visibility;
+        self
+    }
+
+    pub fn unit_variant(self, name: &str) -> Result<Self, EnumBuilderError> {
+        self.unit_variant_with_attrs(name, Vec::new())
+    }
+
+    /** Add a unit variant like [Self::unit_variant], annotated with the
+    given attributes (e.g. `serde(rename = "...")`) in order */
+    pub fn unit_variant_with_attrs(
+        mut self,
+        name: &str,
+        attrs: Vec<(&str, TokenStream)>,
+    ) -> Result<Self, EnumBuilderError> {
         if self.variants.iter().any(|v| v.name.eq(name)) {
             return Err(EnumBuilderError::DuplicateVariantName);
         }
+        let mut attribute_list = Vec::new();
+        for (attr_path, attr_input) in attrs {
+            attribute_list.push(Attr {
+                path: SimplePath::new(attr_path).map_err(|_| EnumBuilderError::AttrPathInvalid)?,
+                input: attr_input,
+            });
+        }
         let variant = EnumVariant {
             name: name.to_string(),
             data: EnumVariantData::Unit,
+            attribute_list,
         };
         self.variants.push(variant);
         Ok(self)
@@ -496,6 +830,7 @@ impl EnumBuilder {
         let variant = EnumVariant {
             name: name.to_string(),
             data: EnumVariantData::Tuple(types),
+            attribute_list: Vec::new(),
         };
         self.variants.push(variant);
         Ok(self)
@@ -519,14 +854,26 @@ impl EnumBuilder {
         let variant = EnumVariant {
             name: name.to_string(),
             data: EnumVariantData::Struct(completed_builder.build()),
+            attribute_list: Vec::new(),
         };
         self.variants.push(variant);
         Ok(self)
     }
 
+    /// Returns the number of variants added to this builder so far.
+    pub fn variant_count(&self) -> usize {
+        self.variants.len()
+    }
+
+    /// Returns `true` if at least one variant was added to this builder.
+    pub fn has_variants(&self) -> bool {
+        !self.variants.is_empty()
+    }
+
     pub fn build(self) -> Result<Enum, EnumBuilderError> {
         Ok(Enum {
             name: self.name,
+            visibility: self.visibility,
             variant_list: self.variants,
             attribute_list: self.attribute_list,
         })
@@ -552,6 +899,8 @@ pub enum Builtin {
     F32,
     F64,
     Bool,
+    /// `str`, to be used behind a reference
+    Str,
     Unit,
 }
 
@@ -569,6 +918,7 @@ impl Builtin {
             Builtin::F32 => "f32",
             Builtin::F64 => "f64",
             Builtin::Bool => "bool",
+            Builtin::Str => "str",
             Builtin::Unit => "()",
         }
     }
@@ -591,12 +941,23 @@ pub enum Indirection {
 pub struct Alias {
     name: String,
     target: TypeRef,
+    visibility: Visibility,
+    doc: Option<String>,
 }
 
 impl Alias {
     pub fn target(&self) -> &TypeRef {
         &self.target
     }
+
+    pub fn visibility(&self) -> Visibility {
+        self.visibility
+    }
+
+    /// The documentation of the alias, as the value of a `#[doc]` attribute
+    pub fn doc(&self) -> Option<&str> {
+        self.doc.as_deref()
+    }
 }
 
 impl NamedItem for Alias {
@@ -629,6 +990,15 @@ pub enum TypeRef {
         mutable: bool,
         lifetime: Option<String>,
     },
+    /// a fixed-size array `[T; N]`, e.g. for `[u8; 16]` `elem` refers to
+    /// `u8`'s `TypeRef` and `len` is `16`
+    Array {
+        elem: Box<TypeRef>,
+        len: usize,
+    },
+    /// a type defined outside of the code model, by its path, e.g.
+    /// `::std::fmt::Result` (see [Codemodel::type_external])
+    External(Rc<str>),
 }
 
 impl PartialEq for TypeRef {
@@ -667,6 +1037,17 @@ impl PartialEq for TypeRef {
                     && lhs_mutable == rhs_mutable
                     && lhs_lifetime == rhs_lifetime
             }
+            (
+                Array {
+                    elem: lhs_elem,
+                    len: lhs_len,
+                },
+                Array {
+                    elem: rhs_elem,
+                    len: rhs_len,
+                },
+            ) => lhs_elem == rhs_elem && lhs_len == rhs_len,
+            (External(lhs), External(rhs)) => lhs == rhs,
             _ => false,
         }
     }
@@ -709,6 +1090,8 @@ impl NamedItem for TypeRef {
                     .unwrap_or("".to_string());
                 Cow::Owned(format!("&{lifetime}{mutable}{type_name}"))
             }
+            TypeRef::Array { elem, len } => Cow::Owned(format!("[{}; {len}]", elem.name())),
+            TypeRef::External(path) => Cow::Borrowed(path),
         }
     }
 }
@@ -747,16 +1130,21 @@ pub trait NamedItem {
     fn name<'a>(&'a self) -> Cow<'a, str>;
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 pub(crate) struct Field {
     pub name: String,
     pub type_ref_or_ts: TypeRefOrTokenStream,
+    pub attribute_list: Vec<Attr>,
 }
 
 impl Field {
     pub fn type_(&self) -> &TypeRefOrTokenStream {
         &self.type_ref_or_ts
     }
+
+    pub fn attr_iter(&self) -> impl Iterator<Item = &Attr> {
+        self.attribute_list.iter()
+    }
 }
 impl NamedItem for Field {
     fn name<'a>(&'a self) -> Cow<'a, str> {
@@ -764,14 +1152,20 @@ impl NamedItem for Field {
     }
 }
 
-#[derive(Debug)]
+/// Equality is structural: same name, visibility, fields and attributes.
+#[derive(Debug, PartialEq)]
 pub struct Struct {
     attribute_list: Vec<Attr>,
     name: String,
+    visibility: Visibility,
     field_list: Vec<Field>,
 }
 
 impl Struct {
+    pub fn visibility(&self) -> Visibility {
+        self.visibility
+    }
+
     pub(crate) fn field_iter(&self) -> impl Iterator<Item = &Field> {
         self.field_list.iter()
     }
@@ -793,6 +1187,12 @@ pub struct Attr {
     input: TokenStream,
 }
 
+impl PartialEq for Attr {
+    fn eq(&self, other: &Self) -> bool {
+        self.path == other.path && self.input.to_string() == other.input.to_string()
+    }
+}
+
 impl Attr {
     pub fn path(&self) -> &SimplePath {
         &self.path
@@ -807,12 +1207,20 @@ impl Attr {
 pub enum CodeError {
     /** it was attempted to insert an element with a name that is already used */
     ItemAlreadyPresent,
+    /** an item with the same name but a different structure is already present */
+    ItemConflict { name: String, description: String },
 }
 
 impl Display for CodeError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             CodeError::ItemAlreadyPresent => f.write_str("item already present"),
+            CodeError::ItemConflict { name, description } => {
+                write!(
+                    f,
+                    "item '{name}' conflicts with existing item: {description}"
+                )
+            }
         }
     }
 }
@@ -857,6 +1265,12 @@ impl<T: NamedItem + Clone> Namespace<T> {
     fn find_item(&self, name: &str) -> Option<T> {
         self.item_map.get(name).map(Clone::clone)
     }
+
+    fn remove_item(&mut self, name: &str) -> Option<T> {
+        let item = self.item_map.remove(name)?;
+        self.item_list.retain(|i| i.name() != name);
+        Some(item)
+    }
 }
 
 impl<T> Default for Namespace<T> {
@@ -870,44 +1284,84 @@ impl<T> Default for Namespace<T> {
 
 #[derive(Clone, Debug)]
 enum ItemRef {
-    TypeRef(TypeRef),
-    TraitRef(TraitRef),
-    ModuleRef(ModuleRef),
+    Type(TypeRef),
+    Trait(TraitRef),
+    Module(ModuleRef),
+    Raw(RawItemRef),
 }
 
 impl From<TypeRef> for ItemRef {
     fn from(type_ref: TypeRef) -> Self {
-        ItemRef::TypeRef(type_ref)
+        ItemRef::Type(type_ref)
     }
 }
 
 impl From<TraitRef> for ItemRef {
     fn from(trait_ref: TraitRef) -> Self {
-        ItemRef::TraitRef(trait_ref)
+        ItemRef::Trait(trait_ref)
     }
 }
 
 impl From<ModuleRef> for ItemRef {
     fn from(module_ref: ModuleRef) -> Self {
-        ItemRef::ModuleRef(module_ref)
+        ItemRef::Module(module_ref)
+    }
+}
+
+impl From<RawItemRef> for ItemRef {
+    fn from(raw_item_ref: RawItemRef) -> Self {
+        ItemRef::Raw(raw_item_ref)
     }
 }
 
 impl NamedItem for ItemRef {
     fn name<'a>(&'a self) -> Cow<'a, str> {
         match self {
-            ItemRef::TypeRef(type_ref) => type_ref.name(),
-            ItemRef::TraitRef(trait_ref) => trait_ref.name(),
-            ItemRef::ModuleRef(module_ref) => module_ref.name(),
+            ItemRef::Type(type_ref) => type_ref.name(),
+            ItemRef::Trait(trait_ref) => trait_ref.name(),
+            ItemRef::Module(module_ref) => module_ref.name(),
+            ItemRef::Raw(raw_item_ref) => raw_item_ref.name(),
         }
     }
 }
 
+/**
+An item the codemodel cannot express (like a static, a free function or a
+macro invocation), given as a token stream that is written as is.
+The name is only used to detect duplicates within a module.
+*/
+#[derive(Debug)]
+pub struct RawItem {
+    name: String,
+    ts: TokenStream,
+}
+
+impl RawItem {
+    pub fn token_stream(&self) -> &TokenStream {
+        &self.ts
+    }
+}
+
+impl NamedItem for RawItem {
+    fn name<'a>(&'a self) -> Cow<'a, str> {
+        Cow::Borrowed(&self.name)
+    }
+}
+
+type RawItemRef = CodeModelRef<RawItem>;
+
+/// Either a type or a raw item of a module, see [Module::type_or_raw_item_iter]
+pub enum TypeOrRawItem<'a> {
+    Type(&'a TypeRef),
+    Raw(&'a RawItem),
+}
+
 #[derive(Debug)]
 pub struct Module {
     name: String,
     item_namespace: Namespace<ItemRef>,
     implementations: Vec<Implementation>,
+    attribute_list: Vec<Attr>,
 }
 /*
 impl std::fmt::Debug for Module {
@@ -970,15 +1424,34 @@ impl Module {
             name: name.to_string(),
             item_namespace: Default::default(),
             implementations: Vec::new(),
+            attribute_list: Vec::new(),
         }
     }
 
+    /// Adds an attribute applying to the module itself, which is written
+    /// as an inner attribute (e.g. `#![doc = "..."]`)
+    pub fn insert_attr_with_input(
+        &mut self,
+        item_path: &str,
+        input: TokenStream,
+    ) -> Result<(), AttrListBuilderError> {
+        self.attribute_list.push(Attr {
+            path: SimplePath::new(item_path).map_err(|_| AttrListBuilderError::AttrPathInvalid)?,
+            input,
+        });
+        Ok(())
+    }
+
+    pub fn attr_iter(&self) -> impl Iterator<Item = &Attr> {
+        self.attribute_list.iter()
+    }
+
     pub fn type_iter(&self) -> impl Iterator<Item = &TypeRef> {
         self.item_namespace
             .item_list
             .iter()
             .filter_map(|i| match i {
-                ItemRef::TypeRef(type_ref) => Some(type_ref),
+                ItemRef::Type(type_ref) => Some(type_ref),
                 _ => None,
             })
     }
@@ -988,7 +1461,7 @@ impl Module {
             .item_list
             .iter()
             .filter_map(|i| match i {
-                ItemRef::ModuleRef(module_ref) => Some(module_ref),
+                ItemRef::Module(module_ref) => Some(module_ref),
                 _ => None,
             })
     }
@@ -998,7 +1471,7 @@ impl Module {
             .item_list
             .iter()
             .filter_map(|i| match i {
-                ItemRef::TraitRef(trait_ref) => Some(trait_ref),
+                ItemRef::Trait(trait_ref) => Some(trait_ref),
                 _ => None,
             })
     }
@@ -1012,10 +1485,72 @@ impl Module {
         self.implementations.iter()
     }
 
+    /// Inserts an item the codemodel cannot express as is, see [RawItem].
+    /// Raw items are written in insertion order relative to the module's
+    /// types, and are not visible to `find_type()` and friends.
+    pub fn insert_raw_item(&mut self, name: &str, ts: TokenStream) -> Result<(), CodeError> {
+        let raw_item: RawItemRef = RawItem {
+            name: name.to_string(),
+            ts,
+        }
+        .into();
+        self.item_namespace.insert_item(raw_item)?;
+        Ok(())
+    }
+
+    /// Inserts a raw item declaring the given placeholder type, which
+    /// refers to the item by name. Like other types, the raw item may
+    /// replace a type stub of the same name, which then resolves to the
+    /// placeholder.
+    pub fn insert_raw_type(
+        &mut self,
+        placeholder: TypeRef,
+        ts: TokenStream,
+    ) -> Result<TypeRef, CodeError> {
+        let name = placeholder.name().to_string();
+        match self.item_namespace.find_item(&name) {
+            Some(ItemRef::Type(TypeRef::Indirection(i)))
+                if matches!(i.borrow().deref(), Indirection::Stub(_)) =>
+            {
+                i.replace(Indirection::Resolved(placeholder.clone()));
+                self.item_namespace.remove_item(&name);
+            }
+            Some(_item) => {
+                return Err(CodeError::ItemAlreadyPresent);
+            }
+            None => {}
+        }
+        self.insert_raw_item(&name, ts)?;
+        Ok(placeholder)
+    }
+
+    pub fn raw_item_iter(&self) -> impl Iterator<Item = &RawItem> {
+        self.item_namespace
+            .item_list
+            .iter()
+            .filter_map(|i| match i {
+                ItemRef::Raw(raw_item_ref) => Some(raw_item_ref.deref()),
+                _ => None,
+            })
+    }
+
+    /// Iterates the module's types and raw items in the order they were
+    /// inserted
+    pub fn type_or_raw_item_iter(&self) -> impl Iterator<Item = TypeOrRawItem<'_>> {
+        self.item_namespace
+            .item_list
+            .iter()
+            .filter_map(|i| match i {
+                ItemRef::Type(type_ref) => Some(TypeOrRawItem::Type(type_ref)),
+                ItemRef::Raw(raw_item_ref) => Some(TypeOrRawItem::Raw(raw_item_ref.deref())),
+                _ => None,
+            })
+    }
+
     fn insert_type_ref(&mut self, t: impl Into<TypeRef>) -> Result<TypeRef, CodeError> {
         let type_ref = t.into();
         match self.item_namespace.find_item(type_ref.name().as_ref()) {
-            Some(ItemRef::TypeRef(TypeRef::Indirection(i))) => {
+            Some(ItemRef::Type(TypeRef::Indirection(i))) => {
                 let is_stub = match i.borrow().deref() {
                     Indirection::Stub(_) => true,
                     _ => false,
@@ -1032,7 +1567,7 @@ impl Module {
             }
             _ => {
                 self.item_namespace
-                    .insert_item(ItemRef::TypeRef(type_ref.clone()))?;
+                    .insert_item(ItemRef::Type(type_ref.clone()))?;
             }
         }
         Ok(type_ref)
@@ -1046,6 +1581,54 @@ impl Module {
         self.insert_type_ref(e)
     }
 
+    /// Inserts the struct, unless a structurally identical struct of the same
+    /// name is already present, in which case that one is returned.
+    pub fn insert_struct_or_get(&mut self, s: Struct) -> Result<TypeRef, CodeError> {
+        let conflict = |description: &str| CodeError::ItemConflict {
+            name: s.name.clone(),
+            description: description.to_string(),
+        };
+        match self.find_type(&s.name) {
+            Some(TypeRef::Struct(existing)) => {
+                if existing.visibility != s.visibility {
+                    Err(conflict("visibilities differ"))
+                } else if existing.attribute_list != s.attribute_list {
+                    Err(conflict("attributes differ"))
+                } else if existing.field_list != s.field_list {
+                    Err(conflict("fields differ"))
+                } else {
+                    Ok(TypeRef::Struct(existing))
+                }
+            }
+            Some(_) => Err(conflict("existing item is not a struct")),
+            None => self.insert_struct(s),
+        }
+    }
+
+    /// Inserts the enum, unless a structurally identical enum of the same
+    /// name is already present, in which case that one is returned.
+    pub fn insert_enum_or_get(&mut self, e: Enum) -> Result<TypeRef, CodeError> {
+        let conflict = |description: &str| CodeError::ItemConflict {
+            name: e.name.clone(),
+            description: description.to_string(),
+        };
+        match self.find_type(&e.name) {
+            Some(TypeRef::Enum(existing)) => {
+                if existing.visibility != e.visibility {
+                    Err(conflict("visibilities differ"))
+                } else if existing.attribute_list != e.attribute_list {
+                    Err(conflict("attributes differ"))
+                } else if existing.variant_list != e.variant_list {
+                    Err(conflict("variants differ"))
+                } else {
+                    Ok(TypeRef::Enum(existing))
+                }
+            }
+            Some(_) => Err(conflict("existing item is not an enum")),
+            None => self.insert_enum(e),
+        }
+    }
+
     pub fn insert_trait(&mut self, t: Trait) -> Result<TraitRef, CodeError> {
         let trait_ref: TraitRef = TraitRef {
             trait_ref: Rc::new(t),
@@ -1062,12 +1645,74 @@ impl Module {
     }
 
     pub fn insert_type_alias(&mut self, name: &str, target: TypeRef) -> Result<TypeRef, CodeError> {
+        self.insert_type_alias_with_visibility(name, Visibility::Private, target)
+    }
+
+    pub fn insert_type_alias_with_visibility(
+        &mut self,
+        name: &str,
+        visibility: Visibility,
+        target: TypeRef,
+    ) -> Result<TypeRef, CodeError> {
         let type_alias = TypeRef::Alias(Rc::new(Alias {
             name: name.to_string(),
             target,
+            visibility,
+            doc: None,
         }));
-        self.item_namespace.insert_item(type_alias.clone())?;
-        Ok(type_alias)
+        // like structs and enums, aliases may replace type stubs
+        self.insert_type_ref(type_alias)
+    }
+
+    /// Inserts a private type alias documented by `doc`, the value of its
+    /// `#[doc]` attribute
+    pub fn insert_documented_type_alias(
+        &mut self,
+        name: &str,
+        target: TypeRef,
+        doc: &str,
+    ) -> Result<TypeRef, CodeError> {
+        let type_alias = TypeRef::Alias(Rc::new(Alias {
+            name: name.to_string(),
+            target,
+            visibility: Visibility::Private,
+            doc: Some(doc.to_string()),
+        }));
+        self.insert_type_ref(type_alias)
+    }
+
+    /// Inserts a `const` item with the given type and value expression.
+    pub fn insert_const(
+        &mut self,
+        name: &str,
+        visibility: Visibility,
+        type_: TokenStream,
+        value: TokenStream,
+    ) -> Result<(), CodeError> {
+        let ident = proc_macro2::Ident::new(name, proc_macro2::Span::call_site());
+        let visibility = visibility.to_token_stream();
+        self.insert_raw_item(
+            name,
+            quote::quote!(#visibility const #ident: #type_ = #value;),
+        )
+    }
+
+    /// Returns `true` if the module has an item of the given name,
+    /// including type stubs not replaced by a proper type yet.
+    pub fn contains_item(&self, name: &str) -> bool {
+        self.item_namespace.find_item(name).is_some()
+    }
+
+    /// Removes the type with the given name from the module and returns it.
+    /// Returns `None` if there is no such item or if the item is not a type.
+    pub fn remove_type(&mut self, name: &str) -> Option<TypeRef> {
+        match self.item_namespace.find_item(name)? {
+            ItemRef::Type(_) => match self.item_namespace.remove_item(name)? {
+                ItemRef::Type(type_ref) => Some(type_ref),
+                _ => None,
+            },
+            _ => None,
+        }
     }
 
     fn insert_module(&mut self, m: Module) -> Result<ModuleRef, CodeError> {
@@ -1086,7 +1731,7 @@ impl NamedItem for Module {
 impl Scope for Module {
     fn find_type(&self, name: &str) -> Option<TypeRef> {
         match self.item_namespace.find_item(name) {
-            Some(ItemRef::TypeRef(target)) => match target {
+            Some(ItemRef::Type(target)) => match target {
                 TypeRef::Indirection(i) => match i.borrow().deref() {
                     Indirection::Stub(_) => None,
                     Indirection::Resolved(inner_target) => Some(inner_target.clone()),
@@ -1098,7 +1743,7 @@ impl Scope for Module {
     }
 
     fn find_module(&self, name: &str) -> Option<ModuleRef> {
-        if let Some(ItemRef::ModuleRef(module_ref)) = self.item_namespace.find_item(name) {
+        if let Some(ItemRef::Module(module_ref)) = self.item_namespace.find_item(name) {
             Some(module_ref.clone())
         } else {
             None
@@ -1138,6 +1783,272 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_remove_crate() -> Result<(), anyhow::Error> {
+        let mut cm = Codemodel::new();
+
+        let crate_ref = cm.insert_crate(Module::new("crate"))?;
+        assert_eq!(Some(crate_ref), cm.remove_crate("crate"));
+        assert!(cm.find_crate("crate").is_none());
+
+        // removing it a second time yields nothing
+        assert!(cm.remove_crate("crate").is_none());
+
+        // the name is free again
+        cm.insert_crate(Module::new("crate"))?;
+        assert!(cm.find_crate("crate").is_some());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_remove_type() -> Result<(), anyhow::Error> {
+        let mut m = Module::new("crate");
+
+        m.insert_struct(StructBuilder::new("Foo").build()?)?;
+        m.insert_trait(trait_::TraitBuilder::new("Bar").build()?)?;
+
+        let foo = m.remove_type("Foo").expect("'Foo' not removed");
+        assert_eq!("Foo", foo.name());
+        assert!(m.find_type("Foo").is_none());
+        assert_eq!(0, m.type_iter().count());
+
+        // not existing
+        assert!(m.remove_type("Foo").is_none());
+        assert!(m.remove_type("Baz").is_none());
+
+        // traits are not types, so they remain in the module
+        assert!(m.remove_type("Bar").is_none());
+        assert_eq!(1, m.trait_iter().count());
+
+        // replace the removed type with a new one
+        m.insert_enum(EnumBuilder::new("Foo").unit_variant("A")?.build()?)?;
+        assert!(matches!(m.find_type("Foo"), Some(TypeRef::Enum(_))));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_type_array() {
+        let cm = Codemodel::new();
+
+        let u8_ = cm.type_u8();
+        let array = cm.type_array(&u8_, 16);
+        assert_eq!("[u8; 16]", array.name());
+        assert_eq!(array, cm.type_array(&u8_, 16));
+        assert_ne!(array, cm.type_array(&u8_, 32));
+    }
+
+    #[test]
+    fn test_optional() {
+        let mut cm = Codemodel::new();
+
+        let string = cm.optional(&cm.type_string());
+        assert_eq!("Option<String>", string.name());
+        assert_eq!(
+            "Option < String >",
+            crate::codewriter::syn_type_name_of(&string)
+                .unwrap()
+                .to_string()
+        );
+
+        // repeated calls share the instance, even for builtins, which are
+        // not shared otherwise
+        let TypeRef::GenericInstance {
+            generic_type,
+            type_parameter,
+        } = cm.optional(&cm.type_string())
+        else {
+            panic!("Option<String> is no generic instance");
+        };
+        let TypeRef::GenericInstance {
+            generic_type: first_generic_type,
+            type_parameter: first_type_parameter,
+        } = &string
+        else {
+            panic!("Option<String> is no generic instance");
+        };
+        assert!(matches!(
+            (&*generic_type, &**first_generic_type),
+            (TypeRef::Struct(lhs), TypeRef::Struct(rhs)) if Rc::ptr_eq(lhs, rhs)
+        ));
+        assert!(matches!(
+            (&type_parameter[0], &first_type_parameter[0]),
+            (TypeRef::Struct(lhs), TypeRef::Struct(rhs)) if Rc::ptr_eq(lhs, rhs)
+        ));
+        assert_ne!(cm.type_u64(), cm.type_u64());
+        assert_eq!(cm.optional(&cm.type_u64()), cm.optional(&cm.type_u64()));
+
+        // nesting works as for any other type
+        let nested = cm.optional(&string);
+        assert_eq!("Option<Option<String>>", nested.name());
+        let string = cm.optional(&cm.type_string());
+        assert_eq!(nested, cm.optional(&string));
+        assert_ne!(nested, string);
+    }
+
+    #[test]
+    fn test_insert_raw_item() -> Result<(), anyhow::Error> {
+        let mut m = Module::new("crate");
+
+        m.insert_raw_item(
+            "FOO",
+            quote::quote!(
+                const FOO: u8 = 1;
+            ),
+        )?;
+        assert_eq!(1, m.raw_item_iter().count());
+        // raw items are not types
+        assert!(m.find_type("FOO").is_none());
+
+        // names of raw items are unique within the module, like all items
+        assert!(matches!(
+            m.insert_raw_item(
+                "FOO",
+                quote::quote!(
+                    const FOO: u8 = 2;
+                )
+            ),
+            Err(CodeError::ItemAlreadyPresent)
+        ));
+        assert!(m.insert_struct(StructBuilder::new("FOO").build()?).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_insert_const() -> Result<(), anyhow::Error> {
+        let cm = Codemodel::new();
+        let mut m = Module::new("crate");
+
+        // aliases replace stubs, like other types do
+        let stub = m.insert_type_stub("AuthScheme")?;
+        m.insert_type_alias("AuthScheme", cm.type_string())?;
+        assert_eq!("AuthScheme", stub.name());
+        assert!(matches!(m.find_type("AuthScheme"), Some(TypeRef::Alias(_))));
+
+        m.insert_const(
+            "AUTH_SCHEME",
+            Visibility::Public,
+            quote::quote!(&str),
+            quote::quote!("Bearer"),
+        )?;
+        let raw_item = m.raw_item_iter().next().expect("const not inserted");
+        assert_eq!(
+            quote::quote!(
+                pub const AUTH_SCHEME: &str = "Bearer";
+            )
+            .to_string(),
+            raw_item.token_stream().to_string()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_type_external() {
+        let cm = Codemodel::new();
+        let result = cm.type_external("::std::fmt::Result");
+        assert_eq!("::std::fmt::Result", result.name());
+        assert_eq!(result, cm.type_fmt_result());
+        assert_ne!(result, cm.type_external("::std::io::Result<()>"));
+        assert_eq!(
+            "&mut ::std::fmt::Formatter<'_>",
+            cm.type_ref_mut_fmt_formatter().name()
+        );
+    }
+
+    #[test]
+    fn test_insert_or_get() -> Result<(), anyhow::Error> {
+        let cm = Codemodel::new();
+        let mut m = Module::new("crate");
+
+        let foo = || {
+            StructBuilder::new("Foo")
+                .attr_with_input("derive", quote!((Debug)))?
+                .field("name", cm.type_string())?
+                .build()
+        };
+        let first = m.insert_struct_or_get(foo()?)?;
+        let second = m.insert_struct_or_get(foo()?)?;
+        match (&first, &second) {
+            (TypeRef::Struct(first), TypeRef::Struct(second)) => {
+                assert!(Rc::ptr_eq(first, second))
+            }
+            _ => panic!("structs expected"),
+        }
+
+        // differing fields
+        let other_foo = StructBuilder::new("Foo")
+            .attr_with_input("derive", quote!((Debug)))?
+            .field("name", cm.type_u32())?
+            .build()?;
+        let err = m.insert_struct_or_get(other_foo).unwrap_err();
+        assert_eq!(
+            "item 'Foo' conflicts with existing item: fields differ",
+            err.to_string()
+        );
+
+        // differing attribute input
+        let other_foo = StructBuilder::new("Foo")
+            .attr_with_input("derive", quote!((Clone)))?
+            .field("name", cm.type_string())?
+            .build()?;
+        let err = m.insert_struct_or_get(other_foo).unwrap_err();
+        assert!(matches!(err, CodeError::ItemConflict { .. }), "{err}");
+
+        // differing visibility
+        let other_foo = StructBuilder::new("Foo")
+            .visibility(Visibility::Crate)
+            .attr_with_input("derive", quote!((Debug)))?
+            .field("name", cm.type_string())?
+            .build()?;
+        let err = m.insert_struct_or_get(other_foo).unwrap_err();
+        assert_eq!(
+            "item 'Foo' conflicts with existing item: visibilities differ",
+            err.to_string()
+        );
+
+        let bar = || {
+            EnumBuilder::new("Bar")
+                .unit_variant("A")?
+                .tuple_variant_with_input("B", vec![quote!(::std::vec::Vec<u8>)])?
+                .build()
+        };
+        let first = m.insert_enum_or_get(bar()?)?;
+        let second = m.insert_enum_or_get(bar()?)?;
+        assert_eq!(first, second);
+
+        let other_bar = EnumBuilder::new("Bar")
+            .visibility(Visibility::Private)
+            .unit_variant("A")?
+            .tuple_variant_with_input("B", vec![quote!(::std::vec::Vec<u8>)])?
+            .build()?;
+        let err = m.insert_enum_or_get(other_bar).unwrap_err();
+        assert_eq!(
+            "item 'Bar' conflicts with existing item: visibilities differ",
+            err.to_string()
+        );
+
+        let other_bar = EnumBuilder::new("Bar").unit_variant("A")?.build()?;
+        let err = m.insert_enum_or_get(other_bar).unwrap_err();
+        assert_eq!(
+            "item 'Bar' conflicts with existing item: variants differ",
+            err.to_string()
+        );
+
+        // same name, different kind of item
+        let err = m
+            .insert_enum_or_get(EnumBuilder::new("Foo").build()?)
+            .unwrap_err();
+        assert_eq!(
+            "item 'Foo' conflicts with existing item: existing item is not an enum",
+            err.to_string()
+        );
+
+        Ok(())
+    }
+
     #[test]
     fn test_stub() -> Result<(), anyhow::Error> {
         let mut m = Module::new("crate");
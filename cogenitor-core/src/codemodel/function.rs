@@ -2,24 +2,53 @@ use std::borrow::Cow;
 
 use proc_macro2::TokenStream;
 
-use crate::codemodel::{NamedItem, TypeRef};
+use crate::codemodel::{Attr, NamedItem, PushAttr, TypeRef, Visibility};
 
 #[derive(Debug)]
 pub struct Function {
     name: String,
+    visibility: Visibility,
+    lifetime_params: Vec<String>,
+    generic_params: Vec<GenericParam>,
     function_params: Vec<FunctionParam>,
     return_type: TypeRef,
     body: Option<TokenStream>,
+    attribute_list: Vec<Attr>,
 }
 
 impl Function {
+    /// Names of the lifetime parameters, without the leading `'`
+    pub fn lifetime_params_iter(&self) -> impl Iterator<Item = &str> {
+        self.lifetime_params.iter().map(|l| l.as_str())
+    }
+
+    pub fn generic_params_iter(&self) -> impl Iterator<Item = &GenericParam> {
+        self.generic_params.iter()
+    }
+
     pub fn function_params_iter(&self) -> impl Iterator<Item = &FunctionParam> {
         self.function_params.iter()
     }
 
+    /// Visibility of the function in inherent implementations. Trait
+    /// functions and functions implementing a trait have no visibility
+    /// qualifier, so it is ignored there.
+    pub fn visibility(&self) -> Visibility {
+        self.visibility
+    }
+
     pub fn return_type(&self) -> &TypeRef {
         &self.return_type
     }
+
+    /// The function's body, if one was provided
+    pub fn body(&self) -> Option<&TokenStream> {
+        self.body.as_ref()
+    }
+
+    pub fn attr_iter(&self) -> impl Iterator<Item = &Attr> {
+        self.attribute_list.iter()
+    }
 }
 
 impl NamedItem for Function {
@@ -28,6 +57,13 @@ impl NamedItem for Function {
     }
 }
 
+/// A generic type parameter of a function, like `W: std::io::Write`
+#[derive(Debug)]
+pub struct GenericParam {
+    pub name: String,
+    pub bounds: TokenStream,
+}
+
 #[derive(Debug)]
 pub struct FunctionParam {
     pub name: String,
@@ -36,19 +72,67 @@ pub struct FunctionParam {
 
 pub struct FunctionBuilder {
     name: String,
+    visibility: Visibility,
+    lifetime_params: Vec<String>,
+    generic_params: Vec<GenericParam>,
     function_params: Vec<FunctionParam>,
     return_type: TypeRef,
     body: Option<TokenStream>,
+    attribute_list: Vec<Attr>,
 }
 
 impl FunctionBuilder {
     pub fn new(name: String, return_type: TypeRef) -> Self {
         Self {
             name,
+            visibility: Visibility::default(),
+            lifetime_params: Default::default(),
+            generic_params: Default::default(),
             function_params: Default::default(),
             return_type,
             body: None,
+            attribute_list: Vec::new(),
+        }
+    }
+
+    pub fn visibility(mut self, visibility: Visibility) -> Self {
+        self.visibility = visibility;
+        self
+    }
+
+    /// Adds a generic type parameter, with `bounds` being the trait bounds
+    /// following the colon (e.g. `::std::io::Write`)
+    pub fn generic_param(mut self, name: String, bounds: TokenStream) -> Self {
+        self.generic_params.push(GenericParam { name, bounds });
+        self
+    }
+
+    /// Makes the function return a shared reference to `type_`, like `&T`
+    /// or `&'a T`. The lifetime is declared as parameter of the function,
+    /// unless it is `'static`.
+    pub fn return_ref(self, type_: TypeRef, lifetime: Option<String>) -> Self {
+        self.return_reference(type_, false, lifetime)
+    }
+
+    /// Makes the function return a mutable reference to `type_`, like
+    /// [Self::return_ref] does for shared references
+    pub fn return_mut_ref(self, type_: TypeRef, lifetime: Option<String>) -> Self {
+        self.return_reference(type_, true, lifetime)
+    }
+
+    fn return_reference(mut self, type_: TypeRef, mutable: bool, lifetime: Option<String>) -> Self {
+        let declare = lifetime
+            .as_ref()
+            .filter(|l| *l != "static" && !self.lifetime_params.contains(l));
+        if let Some(lifetime) = declare {
+            self.lifetime_params.push(lifetime.clone());
         }
+        self.return_type = TypeRef::Reference {
+            referenced_type: Box::new(type_),
+            mutable,
+            lifetime,
+        };
+        self
     }
 
     pub fn param(mut self, name: String, type_: TypeRef) -> Self {
@@ -56,12 +140,23 @@ impl FunctionBuilder {
         self
     }
 
+    /// Sets the statements making up the function's body (without the
+    /// enclosing braces)
+    pub fn body(mut self, body: TokenStream) -> Self {
+        self.body = Some(body);
+        self
+    }
+
     pub fn build(self) -> Function {
         Function {
             name: self.name,
+            visibility: self.visibility,
+            lifetime_params: self.lifetime_params,
+            generic_params: self.generic_params,
             function_params: self.function_params,
             return_type: self.return_type,
             body: self.body,
+            attribute_list: self.attribute_list,
         }
     }
 
@@ -72,3 +167,9 @@ impl FunctionBuilder {
             .collect::<Vec<&str>>()
     }
 }
+
+impl PushAttr for FunctionBuilder {
+    fn push_attr(&mut self, attr: Attr) {
+        self.attribute_list.push(attr);
+    }
+}
@@ -1,13 +1,18 @@
 use std::borrow::Cow;
 
-use crate::codemodel::{Attr, NamedItem, PushAttr};
+use proc_macro2::TokenStream;
+
+use crate::codemodel::{Attr, NamedItem, PushAttr, TypeRef, Visibility};
 
 use super::function::Function;
 
 #[derive(Debug)]
 pub struct Trait {
     name: String,
+    visibility: Visibility,
     pub associated_functions: Vec<Function>,
+    /// associated constants as name, type and default value
+    pub associated_consts: Vec<(String, TypeRef, TokenStream)>,
     attribute_list: Vec<Attr>,
 }
 
@@ -18,10 +23,18 @@ impl NamedItem for Trait {
 }
 
 impl Trait {
+    pub fn visibility(&self) -> Visibility {
+        self.visibility
+    }
+
     pub fn function_iter(&self) -> impl Iterator<Item = &Function> {
         self.associated_functions.iter()
     }
 
+    pub fn const_iter(&self) -> impl Iterator<Item = &(String, TypeRef, TokenStream)> {
+        self.associated_consts.iter()
+    }
+
     pub fn attr_iter(&self) -> impl Iterator<Item = &Attr> {
         self.attribute_list.iter()
     }
@@ -36,17 +49,37 @@ impl TraitBuilder {
         Self {
             inner: Trait {
                 name: name.to_string(),
+                visibility: Visibility::default(),
                 associated_functions: Vec::new(),
+                associated_consts: Vec::new(),
                 attribute_list: Vec::new(),
             },
         }
     }
 
+    pub fn visibility(mut self, visibility: Visibility) -> Self {
+        self.inner.visibility = visibility;
+        self
+    }
+
     pub fn function(mut self, function: Function) -> Self {
         self.inner.associated_functions.push(function);
         self
     }
 
+    /// Adds an associated constant, like `const BASE_PATH: &str = "/api/v2";`
+    pub fn associated_const(
+        mut self,
+        name: &str,
+        type_: TypeRef,
+        default_value: TokenStream,
+    ) -> Self {
+        self.inner
+            .associated_consts
+            .push((name.to_string(), type_, default_value));
+        self
+    }
+
     pub fn build(self) -> Result<Trait, crate::codemodel::CodeError> {
         Ok(self.inner)
     }
@@ -1,14 +1,16 @@
+use std::collections::HashSet;
 use std::ops::Deref;
 
 use anyhow::anyhow;
-use proc_macro2::{Ident, Span, TokenStream};
+use proc_macro2::{Ident, Span, TokenStream, TokenTree};
 use quote::{ToTokens, format_ident, quote};
 use rust_format::Formatter;
 use thiserror::Error;
 
 use crate::codemodel::{
     Attr, AttrListBuilder, Codemodel, EnumVariantData, FunctionListBuilder, Indirection, NamedItem,
-    TraitRef, TypeRef, TypeRefOrTokenStream, function::Function, implementation::Implementation,
+    TraitRef, TypeOrRawItem, TypeRef, TypeRefOrTokenStream, Visibility, function::Function,
+    implementation::Implementation,
 };
 
 // useful read on working with proc_macro2, quote and syn:
@@ -22,9 +24,13 @@ pub(crate) fn write_to_token_stream(
         .find_crate(crate_name)
         .ok_or(anyhow!(format!("crate {crate_name} not found")))?;
 
+    // raw items are written in between types, in insertion order
     let mut type_decls = Vec::new();
-    for t in mod_.type_iter() {
-        type_decls.push(write_type_decl(t)?);
+    for item in mod_.type_or_raw_item_iter() {
+        match item {
+            TypeOrRawItem::Type(t) => type_decls.push(write_type_decl(t)?),
+            TypeOrRawItem::Raw(raw_item) => type_decls.push(raw_item.token_stream().clone()),
+        }
     }
 
     let mut trait_decls = Vec::new();
@@ -37,13 +43,85 @@ pub(crate) fn write_to_token_stream(
         impl_decls.push(write_implementation(impl_block)?);
     }
 
-    let mut ts = TokenStream::new();
+    // module attributes apply to the enclosing module, so they come first
+    let mut ts = tokenize_inner_attrs(mod_.attr_iter());
     ts.extend(trait_decls);
     ts.extend(type_decls);
     ts.extend(impl_decls);
     Ok(ts)
 }
 
+/// Writes the trait `t` with just the functions accepted by `filter`, e.g.
+/// to show the client functions of a single operation
+pub(crate) fn write_trait_functions(
+    t: &TraitRef,
+    filter: impl Fn(&Function) -> bool,
+) -> anyhow::Result<TokenStream> {
+    let trait_name = format_ident!("{}", t.name());
+    let mut function_tokens = Vec::new();
+    for func in t.function_iter().filter(|func| filter(func)) {
+        function_tokens.push(write_trait_function(func)?);
+    }
+    let visibility = t.visibility();
+    Ok(quote!(
+        #visibility trait #trait_name {
+            #(#function_tokens)*
+        }
+    ))
+}
+
+/// Writes the declarations of the types of crate `crate_name` named in
+/// `names`, along with those of the types they refer to, in the order of
+/// the crate. Names of types not declared in the crate are ignored.
+pub(crate) fn write_type_decls_of(
+    cm: &Codemodel,
+    crate_name: &str,
+    names: &[String],
+) -> anyhow::Result<TokenStream> {
+    let mod_ = cm
+        .find_crate(crate_name)
+        .ok_or(anyhow!(format!("crate {crate_name} not found")))?;
+    let types = mod_
+        .type_or_raw_item_iter()
+        .filter_map(|item| match item {
+            TypeOrRawItem::Type(t) => Some(t),
+            TypeOrRawItem::Raw(_) => None,
+        })
+        .collect::<Vec<_>>();
+
+    // the types referred to are found by the identifiers in the written
+    // declarations, until no new ones turn up
+    let mut wanted: HashSet<String> = names.iter().cloned().collect();
+    let mut decls: Vec<Option<TokenStream>> = vec![None; types.len()];
+    loop {
+        let mut found = Vec::new();
+        for (t, decl) in types.iter().zip(decls.iter_mut()) {
+            if decl.is_none() && wanted.contains(t.name().as_ref()) {
+                let ts = write_type_decl(t)?;
+                collect_idents(ts.clone(), &mut found);
+                *decl = Some(ts);
+            }
+        }
+        let before = wanted.len();
+        wanted.extend(found);
+        if wanted.len() == before {
+            break;
+        }
+    }
+    Ok(decls.into_iter().flatten().collect())
+}
+
+/// Collects the names of all identifiers in `ts`, including nested groups
+pub(crate) fn collect_idents(ts: TokenStream, idents: &mut Vec<String>) {
+    for tt in ts {
+        match tt {
+            TokenTree::Ident(ident) => idents.push(ident.to_string()),
+            TokenTree::Group(group) => collect_idents(group.stream(), idents),
+            TokenTree::Punct(_) | TokenTree::Literal(_) => (),
+        }
+    }
+}
+
 fn tokenize_attrs<'a>(attr_iter: impl Iterator<Item = &'a Attr>) -> TokenStream {
     let mut ts = TokenStream::new();
     for attr in attr_iter {
@@ -54,10 +132,21 @@ fn tokenize_attrs<'a>(attr_iter: impl Iterator<Item = &'a Attr>) -> TokenStream
     ts
 }
 
+fn tokenize_inner_attrs<'a>(attr_iter: impl Iterator<Item = &'a Attr>) -> TokenStream {
+    let mut ts = TokenStream::new();
+    for attr in attr_iter {
+        let attr_name = syn::parse_str::<syn::Path>(attr.path().as_str()).unwrap();
+        let attr_input = attr.input();
+        ts.extend(quote!(#![#attr_name #attr_input]));
+    }
+    ts
+}
+
 fn write_type_decl(type_ref: &TypeRef) -> anyhow::Result<TokenStream> {
     let ts = match type_ref {
         TypeRef::Struct(s) => {
             let struct_name = format_ident!("{}", s.name());
+            let visibility = s.visibility();
             let mut struct_fields = Vec::new();
 
             let attrs = tokenize_attrs(s.attr_iter());
@@ -68,11 +157,12 @@ fn write_type_decl(type_ref: &TypeRef) -> anyhow::Result<TokenStream> {
                     TypeRefOrTokenStream::TokenStream(token_stream) => token_stream.clone(),
                 };
                 let field_type: TokenStream = syn_type_ref.to_token_stream();
-                struct_fields.push(quote!(pub #field_name: #field_type));
+                let field_attrs = tokenize_attrs(f.attr_iter());
+                struct_fields.push(quote!(#field_attrs pub #field_name: #field_type));
             }
             quote!(
                 #attrs
-                pub struct #struct_name {
+                #visibility struct #struct_name {
                 #(#struct_fields),*
             })
         }
@@ -82,9 +172,10 @@ fn write_type_decl(type_ref: &TypeRef) -> anyhow::Result<TokenStream> {
 
             for variant in e.variant_iter() {
                 let variant_name = format_ident!("{}", variant.name());
+                let variant_attrs = tokenize_attrs(variant.attr_iter());
                 match variant.data() {
                     EnumVariantData::Unit => {
-                        enum_variants.push(quote!(#variant_name));
+                        enum_variants.push(quote!(#variant_attrs #variant_name));
                     }
                     EnumVariantData::Tuple(types) => {
                         let mut variant_types = Vec::new();
@@ -120,9 +211,10 @@ fn write_type_decl(type_ref: &TypeRef) -> anyhow::Result<TokenStream> {
                 }
             }
             let attrs = tokenize_attrs(e.attr_iter());
+            let visibility = e.visibility();
             quote!(
                 #attrs
-                pub enum #enum_name {
+                #visibility enum #enum_name {
                     #(#enum_variants),*
                 }
             )
@@ -130,7 +222,9 @@ fn write_type_decl(type_ref: &TypeRef) -> anyhow::Result<TokenStream> {
         TypeRef::Alias(alias) => {
             let alias_name = Ident::new(&alias.name(), Span::call_site());
             let target_name = syn_type_name_of(alias.target())?;
-            quote!(type #alias_name = #target_name;)
+            let visibility = alias.visibility();
+            let doc = alias.doc().map(|doc| quote!(#[doc = #doc]));
+            quote!(#doc #visibility type #alias_name = #target_name;)
         }
         TypeRef::Indirection(ind) => match ind.borrow().deref() {
             Indirection::Stub(_) => todo!("unresolved stub {ind:?}"),
@@ -143,16 +237,24 @@ fn write_type_decl(type_ref: &TypeRef) -> anyhow::Result<TokenStream> {
 
 fn write_trait_decl(t: &TraitRef) -> anyhow::Result<TokenStream> {
     let trait_name = format_ident!("{}", t.name());
-    let mut function_tokens = Vec::new();
+    let mut const_tokens = Vec::new();
+    for (name, type_, default_value) in t.const_iter() {
+        let const_name = format_ident!("{}", name);
+        let const_type = syn_type_name_of(type_)?;
+        const_tokens.push(quote!(const #const_name: #const_type = #default_value;));
+    }
 
+    let mut function_tokens = Vec::new();
     for func in t.function_iter() {
         function_tokens.push(write_trait_function(func)?);
     }
 
     let attrs = tokenize_attrs(t.attr_iter());
+    let visibility = t.visibility();
     let ts = quote!(
         #attrs
-        pub trait #trait_name {
+        #visibility trait #trait_name {
+            #(#const_tokens)*
             #(#function_tokens)*
         }
     );
@@ -168,7 +270,7 @@ pub fn fmt_code(ts: proc_macro2::TokenStream) -> Result<String, FormattingError>
     Ok(formatter.format_tokens(ts)?)
 }
 
-fn syn_type_name_of(type_ref: &TypeRef) -> anyhow::Result<TokenStream> {
+pub(crate) fn syn_type_name_of(type_ref: &TypeRef) -> anyhow::Result<TokenStream> {
     let syn_type = syn::parse_str::<syn::Type>(&type_ref.name())?;
     let ts = syn_type.to_token_stream();
     Ok(ts)
@@ -178,13 +280,20 @@ fn write_implementation(impl_block: &Implementation) -> anyhow::Result<TokenStre
     let type_name = syn_type_name_of(&impl_block.implementing_type)?;
     let mut function_tokens = Vec::new();
 
+    // functions implementing a trait cannot have a visibility qualifier
+    let is_trait_impl = impl_block.impl_trait.is_some();
     for func in &impl_block.associated_functions {
-        function_tokens.push(write_function(func)?);
+        let visibility = if is_trait_impl {
+            Visibility::Private
+        } else {
+            func.visibility()
+        };
+        function_tokens.push(write_function(func, visibility)?);
     }
 
     match &impl_block.impl_trait {
         Some(trait_ref) => {
-            let trait_name = trait_ref.name();
+            let trait_name = syn::parse_str::<syn::Path>(&trait_ref.name())?;
             Ok(quote! {
                 impl #trait_name for #type_name {
                     #(#function_tokens)*
@@ -199,22 +308,42 @@ fn write_implementation(impl_block: &Implementation) -> anyhow::Result<TokenStre
     }
 }
 
-fn write_function(func: &Function) -> anyhow::Result<TokenStream> {
-    write_function_impl(func, true, Some(quote!(todo!())))
+fn write_function(func: &Function, visibility: Visibility) -> anyhow::Result<TokenStream> {
+    let body = func.body().cloned().unwrap_or(quote!(todo!()));
+    write_function_impl(func, visibility, Some(body))
 }
 
+/// Writes a trait function, with its body being the default implementation
+/// if it has one
 fn write_trait_function(func: &Function) -> anyhow::Result<TokenStream> {
-    write_function_impl(func, false, None)
+    write_function_impl(func, Visibility::Private, func.body().cloned())
 }
 
 fn write_function_impl(
     func: &Function,
-    is_pub: bool,
+    visibility: Visibility,
     body: Option<TokenStream>,
 ) -> anyhow::Result<TokenStream> {
     let func_name = format_ident!("{}", func.name());
     let return_type = syn_type_name_of(func.return_type())?;
 
+    // lifetime parameters must precede type parameters
+    let mut generic_params = Vec::new();
+    for lifetime in func.lifetime_params_iter() {
+        let lifetime = syn::Lifetime::new(&format!("'{lifetime}"), Span::call_site());
+        generic_params.push(quote!(#lifetime));
+    }
+    for generic_param in func.generic_params_iter() {
+        let param_name = format_ident!("{}", generic_param.name);
+        let bounds = &generic_param.bounds;
+        generic_params.push(quote!(#param_name: #bounds));
+    }
+    let generics = if generic_params.is_empty() {
+        TokenStream::new()
+    } else {
+        quote!(<#(#generic_params),*>)
+    };
+
     let mut params = Vec::new();
     for param in func.function_params_iter() {
         let param_name = format_ident!("{}", param.name);
@@ -222,14 +351,39 @@ fn write_function_impl(
         params.push(quote!(#param_name: #param_type));
     }
 
-    let access = if is_pub { Some(quote!(pub)) } else { None };
-
+    let attrs = tokenize_attrs(func.attr_iter());
     let body = body.map(|body| quote!({ #body })).unwrap_or(quote!(;));
     Ok(quote! {
-        #access fn #func_name(#(#params),*) -> #return_type #body
+        #attrs
+        #visibility fn #func_name #generics(#(#params),*) -> #return_type #body
     })
 }
 
+/// Writes an expression constructing the given HTTP method in generated code.
+/// Standard methods map to the associated constants of `::http::Method`, so
+/// generated code does not depend on any transport's re-export of the type.
+/// Extension methods (like `QUERY`) are constructed from their name instead.
+pub(crate) fn write_http_method(method: &http::Method) -> TokenStream {
+    let standard = [
+        http::Method::GET,
+        http::Method::PUT,
+        http::Method::POST,
+        http::Method::DELETE,
+        http::Method::OPTIONS,
+        http::Method::HEAD,
+        http::Method::PATCH,
+        http::Method::TRACE,
+        http::Method::CONNECT,
+    ];
+    if standard.contains(method) {
+        let method_ident = format_ident!("{}", method.as_str());
+        quote!(::http::Method::#method_ident)
+    } else {
+        let method_bytes = proc_macro2::Literal::byte_string(method.as_str().as_bytes());
+        quote!(::http::Method::from_bytes(#method_bytes).unwrap())
+    }
+}
+
 #[test]
 fn test_write_code() -> anyhow::Result<()> {
     use crate::codemodel::{Module, StructBuilder};
@@ -243,6 +397,7 @@ fn test_write_code() -> anyhow::Result<()> {
 
     // insert an alias to forward-declared 'Bar'
     let bar_alias_t = m.insert_type_alias("BarAlias", bar_t.clone())?;
+    m.insert_type_alias_with_visibility("BazAlias", Visibility::Crate, bar_t.clone())?;
 
     // insert 'Foo' that also references 'Bar';
     let foo_struct = StructBuilder::new("Foo")
@@ -272,6 +427,7 @@ fn test_write_code() -> anyhow::Result<()> {
             pub has_handles: bool,
         }
         type BarAlias = Bar;
+        pub(crate) type BazAlias = Bar;
         pub struct Foo {
             pub bar: Bar,
             pub bar_alias: BarAlias,
@@ -317,6 +473,42 @@ fn test_write_struct_with_serde() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_write_struct_with_serde_rename_all() -> anyhow::Result<()> {
+    use crate::codemodel::{Module, SerdeRenameCase, StructBuilder};
+    use assert_tokenstreams_eq::assert_tokenstreams_eq;
+
+    let mut cm = Codemodel::new();
+    let mut m = Module::new("crate");
+
+    let pet_struct = StructBuilder::new("Pet")
+        .attr_with_input("derive", quote!((serde::Deserialize)))?
+        .serde_rename_all(SerdeRenameCase::CamelCase)
+        .field("pet_id", cm.type_i64())?
+        .field("photo_urls", cm.type_string())?
+        .build()?;
+
+    m.insert_struct(pet_struct)?;
+
+    cm.insert_crate(m)?;
+
+    let ts = write_to_token_stream(&cm, "crate")?;
+    println!("{ts}");
+
+    assert_tokenstreams_eq!(
+        &ts,
+        &quote!(
+            #[derive(serde::Deserialize)]
+            #[serde(rename_all = "camelCase")]
+            pub struct Pet {
+                pub pet_id: i64,
+                pub photo_urls: String,
+            }
+        )
+    );
+    Ok(())
+}
+
 #[test]
 fn test_write_enum_code() -> anyhow::Result<()> {
     use crate::codemodel::{EnumBuilder, Module};
@@ -329,7 +521,7 @@ fn test_write_enum_code() -> anyhow::Result<()> {
     let color_enum = EnumBuilder::new("Color")
         .unit_variant("Red")?
         .unit_variant("Green")?
-        .unit_variant("Blue")?
+        .unit_variant_with_attrs("Blue", vec![("serde", quote!((rename = "blue")))])?
         .build()?;
     m.insert_enum(color_enum)?;
 
@@ -359,6 +551,7 @@ fn test_write_enum_code() -> anyhow::Result<()> {
         pub enum Color {
             Red,
             Green,
+            #[serde(rename = "blue")]
             Blue,
         }
         #[derive(Debug)]
@@ -471,7 +664,9 @@ fn test_write_implementation() -> anyhow::Result<()> {
 
 #[test]
 fn test_write_trait() -> anyhow::Result<()> {
-    use crate::codemodel::{Module, function::FunctionBuilder, trait_::TraitBuilder};
+    use crate::codemodel::{
+        Module, StructBuilder, function::FunctionBuilder, trait_::TraitBuilder,
+    };
     use assert_tokenstreams_eq::assert_tokenstreams_eq;
 
     let mut cm = Codemodel::new();
@@ -494,9 +689,19 @@ fn test_write_trait() -> anyhow::Result<()> {
 
     m.insert_trait(identifiable_trait)?;
 
-    // Create a simpler trait without attributes
+    // Create a simpler trait without attributes, but with a generic function
+    let writer_type = TypeRef::Reference {
+        referenced_type: Box::new(StructBuilder::new("W").build()?.into()),
+        mutable: true,
+        lifetime: None,
+    };
+    let write_to_fn = FunctionBuilder::new("write_to".to_string(), cm.type_u64())
+        .generic_param("W".to_string(), quote!(::std::io::Write))
+        .param("writer".to_string(), writer_type)
+        .build();
     let simple_trait = TraitBuilder::new("Simple")
         .function(FunctionBuilder::new("process".to_string(), cm.type_bool()).build())
+        .function(write_to_fn)
         .build()?;
 
     m.insert_trait(simple_trait)?;
@@ -515,8 +720,255 @@ fn test_write_trait() -> anyhow::Result<()> {
         }
         pub trait Simple {
             fn process() -> bool;
+            fn write_to<W: ::std::io::Write>(writer: &mut W) -> u64;
         }
     );
     assert_tokenstreams_eq!(&ts, &ts_reference);
     Ok(())
 }
+
+#[test]
+fn test_write_trait_associated_consts() -> anyhow::Result<()> {
+    use crate::codemodel::{Module, function::FunctionBuilder, trait_::TraitBuilder};
+    use assert_tokenstreams_eq::assert_tokenstreams_eq;
+
+    let mut cm = Codemodel::new();
+    let mut m = Module::new("crate");
+
+    let str_type = TypeRef::Reference {
+        referenced_type: Box::new(cm.type_str()),
+        mutable: false,
+        lifetime: None,
+    };
+    let api_trait = TraitBuilder::new("Api")
+        .associated_const("BASE_PATH", str_type, quote!("/api/v2"))
+        .associated_const("MAX_SIZE", cm.type_u32(), quote!(1024))
+        .function(FunctionBuilder::new("base_url".to_string(), cm.type_string()).build())
+        .build()?;
+    m.insert_trait(api_trait)?;
+    cm.insert_crate(m)?;
+
+    let ts = write_to_token_stream(&cm, "crate")?;
+    println!("{ts}");
+
+    let ts_reference = quote!(
+        pub trait Api {
+            const BASE_PATH: &str = "/api/v2";
+            const MAX_SIZE: u32 = 1024;
+            fn base_url() -> String;
+        }
+    );
+    assert_tokenstreams_eq!(&ts, &ts_reference);
+    // rustfmt rejects code that does not parse
+    fmt_code(ts)?;
+    Ok(())
+}
+
+#[test]
+fn test_write_http_method() {
+    let ts = write_http_method(&http::Method::GET);
+    assert_eq!(ts.to_string(), quote!(::http::Method::GET).to_string());
+
+    let ts = write_http_method(&http::Method::PATCH);
+    assert_eq!(ts.to_string(), quote!(::http::Method::PATCH).to_string());
+
+    let query = http::Method::from_bytes(b"QUERY").unwrap();
+    let ts = write_http_method(&query);
+    assert_eq!(
+        ts.to_string(),
+        quote!(::http::Method::from_bytes(b"QUERY").unwrap()).to_string()
+    );
+}
+
+#[test]
+fn test_write_raw_items() -> anyhow::Result<()> {
+    use crate::codemodel::{Module, StructBuilder};
+    use assert_tokenstreams_eq::assert_tokenstreams_eq;
+
+    let mut cm = Codemodel::new();
+    let mut m = Module::new("crate");
+
+    m.insert_raw_item(
+        "foo",
+        quote!(
+            macro_rules! foo {
+                () => {
+                    Foo {}
+                };
+            }
+        ),
+    )?;
+    m.insert_struct(StructBuilder::new("Foo").build()?)?;
+    m.insert_raw_item(
+        "MAX_FOOS",
+        quote!(
+            pub const MAX_FOOS: usize = 42;
+        ),
+    )?;
+
+    cm.insert_crate(m)?;
+
+    // raw items are written in insertion order relative to types
+    let ts = write_to_token_stream(&cm, "crate")?;
+    let ts_reference = quote!(
+        macro_rules! foo {
+            () => {
+                Foo {}
+            };
+        }
+        pub struct Foo {}
+        pub const MAX_FOOS: usize = 42;
+    );
+    assert_tokenstreams_eq!(&ts, &ts_reference);
+    Ok(())
+}
+
+#[test]
+fn test_write_trait_implementation() -> anyhow::Result<()> {
+    use crate::codemodel::{
+        Module, StructBuilder, function::FunctionBuilder, implementation::ImplementationBuilder,
+    };
+    use assert_tokenstreams_eq::assert_tokenstreams_eq;
+
+    let mut cm = Codemodel::new();
+    let mut m = Module::new("crate");
+
+    let foo_ref = m.insert_struct(StructBuilder::new("Foo").build()?)?;
+
+    let fmt_fn = FunctionBuilder::new("fmt".to_string(), cm.type_fmt_result())
+        .param("self".to_string(), cm.type_ref_self())
+        .param("f".to_string(), cm.type_ref_mut_fmt_formatter())
+        .body(quote!(::std::write!(f, "Foo")))
+        .build();
+    let foo_display = ImplementationBuilder::new_trait(cm.trait_display()?, foo_ref)
+        .function(fmt_fn)
+        .build();
+    m.insert_implementation(foo_display)?;
+
+    cm.insert_crate(m)?;
+
+    let ts = write_to_token_stream(&cm, "crate")?;
+    let ts_reference = quote!(
+        pub struct Foo {}
+        impl ::std::fmt::Display for Foo {
+            fn fmt(self: &Self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                ::std::write!(f, "Foo")
+            }
+        }
+    );
+    assert_tokenstreams_eq!(&ts, &ts_reference);
+    Ok(())
+}
+
+#[test]
+fn test_write_crate_visible_trait() -> anyhow::Result<()> {
+    use crate::codemodel::{
+        Module, StructBuilder, function::FunctionBuilder, implementation::ImplementationBuilder,
+        trait_::TraitBuilder,
+    };
+    use assert_tokenstreams_eq::assert_tokenstreams_eq;
+
+    let mut cm = Codemodel::new();
+    let mut m = Module::new("crate");
+
+    let helper_trait = TraitBuilder::new("Helper")
+        .visibility(Visibility::Crate)
+        .function(
+            FunctionBuilder::new("help".to_string(), cm.type_unit())
+                .param("self".to_string(), cm.type_ref_self())
+                .build(),
+        )
+        .build()?;
+    let helper_ref = m.insert_trait(helper_trait)?;
+    let foo_ref = m.insert_struct(
+        StructBuilder::new("Foo")
+            .visibility(Visibility::Crate)
+            .build()?,
+    )?;
+
+    // the function's visibility is dropped in the trait implementation...
+    let help_fn = FunctionBuilder::new("help".to_string(), cm.type_unit())
+        .visibility(Visibility::Crate)
+        .param("self".to_string(), cm.type_ref_self())
+        .build();
+    m.insert_implementation(
+        ImplementationBuilder::new_trait(helper_ref, foo_ref.clone())
+            .function(help_fn)
+            .build(),
+    )?;
+    // ...but kept in inherent implementations
+    let new_fn = FunctionBuilder::new("new".to_string(), cm.type_self())
+        .visibility(Visibility::Crate)
+        .body(quote!(Foo {}))
+        .build();
+    m.insert_implementation(
+        ImplementationBuilder::new_inherent(foo_ref)
+            .function(new_fn)
+            .build(),
+    )?;
+
+    cm.insert_crate(m)?;
+
+    let ts = write_to_token_stream(&cm, "crate")?;
+    let ts_reference = quote!(
+        pub(crate) trait Helper {
+            fn help(self: &Self) -> ();
+        }
+        pub(crate) struct Foo {}
+        impl Helper for Foo {
+            fn help(self: &Self) -> () {
+                todo!()
+            }
+        }
+        impl Foo {
+            pub(crate) fn new() -> Self {
+                Foo {}
+            }
+        }
+    );
+    assert_tokenstreams_eq!(&ts, &ts_reference);
+    Ok(())
+}
+
+#[test]
+fn test_write_function_return_ref() -> anyhow::Result<()> {
+    use crate::codemodel::function::FunctionBuilder;
+    use assert_tokenstreams_eq::assert_tokenstreams_eq;
+
+    let cm = Codemodel::new();
+
+    let name_fn = FunctionBuilder::new("name".to_string(), cm.type_str())
+        .return_ref(cm.type_str(), Some("a".to_string()))
+        .build();
+    let ts = write_trait_function(&name_fn)?;
+    assert_tokenstreams_eq!(
+        &ts,
+        &quote!(
+            fn name<'a>() -> &'a str;
+        )
+    );
+
+    // 'static is not declared, and lifetimes precede type parameters
+    let buffer_fn = FunctionBuilder::new("buffer".to_string(), cm.type_unit())
+        .generic_param("W".to_string(), quote!(::std::io::Write))
+        .return_mut_ref(cm.type_str(), Some("b".to_string()))
+        .build();
+    let ts = write_trait_function(&buffer_fn)?;
+    assert_tokenstreams_eq!(
+        &ts,
+        &quote!(
+            fn buffer<'b, W: ::std::io::Write>() -> &'b mut str;
+        )
+    );
+    let static_fn = FunctionBuilder::new("version".to_string(), cm.type_unit())
+        .return_ref(cm.type_str(), Some("static".to_string()))
+        .build();
+    let ts = write_trait_function(&static_fn)?;
+    assert_tokenstreams_eq!(
+        &ts,
+        &quote!(
+            fn version() -> &'static str;
+        )
+    );
+    Ok(())
+}
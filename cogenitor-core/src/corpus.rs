@@ -0,0 +1,257 @@
+//! Runs code generation over a directory tree of specs, like a mirror of
+//! the apis.guru directory, to find specs the generator fails on. Each
+//! spec is generated on its own thread, so that panics (which adapters
+//! still raise for some constructs) and specs taking too long are
+//! reported instead of ending the run. Threads of specs taking too long
+//! cannot be stopped, so only a few of them may keep running (see
+//! [MAX_RUNNING_TIMEOUTS]).
+
+use std::fmt::Display;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+use crate::ApiConfig;
+
+/// The number of timed out specs whose threads may still be running.
+/// Once reached, the remaining specs are skipped until one of them ends.
+pub const MAX_RUNNING_TIMEOUTS: usize = 4;
+
+/// How generating code for a spec ended
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CorpusOutcome {
+    Success,
+    /// generation failed with the given error
+    Error(String),
+    /// generation panicked with the given message
+    Panic(String),
+    /// generation did not finish within the timeout
+    Timeout,
+    /// generation was not started, as too many timed out generations were
+    /// still running
+    Skipped,
+}
+
+impl CorpusOutcome {
+    fn label(&self) -> &'static str {
+        match self {
+            CorpusOutcome::Success => "success",
+            CorpusOutcome::Error(_) => "error",
+            CorpusOutcome::Panic(_) => "panic",
+            CorpusOutcome::Timeout => "timeout",
+            CorpusOutcome::Skipped => "skipped",
+        }
+    }
+
+    fn message(&self) -> Option<&str> {
+        match self {
+            CorpusOutcome::Error(message) | CorpusOutcome::Panic(message) => Some(message),
+            CorpusOutcome::Success | CorpusOutcome::Timeout | CorpusOutcome::Skipped => None,
+        }
+    }
+}
+
+/// The outcome of generating code for a single spec of the corpus
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CorpusEntry {
+    pub path: PathBuf,
+    pub outcome: CorpusOutcome,
+    pub duration: Duration,
+}
+
+/// The outcomes of a [run_corpus] run, by spec path. The [Display]
+/// implementation renders a table for the console.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CorpusReport {
+    pub entries: Vec<CorpusEntry>,
+}
+
+impl CorpusReport {
+    /// The number of specs whose generation ended with an outcome of the
+    /// same kind as `outcome`, ignoring messages
+    pub fn count(&self, outcome: &CorpusOutcome) -> usize {
+        self.entries
+            .iter()
+            .filter(|entry| entry.outcome.label() == outcome.label())
+            .count()
+    }
+
+    /// The report as JSON, with the counts per outcome in `summary` and
+    /// the outcome of each spec in `specs`
+    pub fn to_json(&self) -> serde_json::Value {
+        let specs = self
+            .entries
+            .iter()
+            .map(|entry| {
+                serde_json::json!({
+                    "path": entry.path.display().to_string(),
+                    "outcome": entry.outcome.label(),
+                    "message": entry.outcome.message(),
+                    "duration_ms": entry.duration.as_millis() as u64,
+                })
+            })
+            .collect::<Vec<_>>();
+        serde_json::json!({
+            "summary": {
+                "success": self.count(&CorpusOutcome::Success),
+                "error": self.count(&CorpusOutcome::Error(String::new())),
+                "panic": self.count(&CorpusOutcome::Panic(String::new())),
+                "timeout": self.count(&CorpusOutcome::Timeout),
+                "skipped": self.count(&CorpusOutcome::Skipped),
+            },
+            "specs": specs,
+        })
+    }
+}
+
+impl Display for CorpusReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let path_width = self
+            .entries
+            .iter()
+            .map(|entry| entry.path.display().to_string().len())
+            .max()
+            .unwrap_or(0)
+            .max("spec".len());
+        writeln!(
+            f,
+            "{:<path_width$}  {:<7}  {:>8}  message",
+            "spec", "outcome", "ms"
+        )?;
+        for entry in &self.entries {
+            // messages may span several lines, which would break the table
+            let message = entry.outcome.message().unwrap_or_default();
+            let message = message.lines().next().unwrap_or_default();
+            let row = format!(
+                "{:<path_width$}  {:<7}  {:>8}  {message}",
+                entry.path.display(),
+                entry.outcome.label(),
+                entry.duration.as_millis(),
+            );
+            writeln!(f, "{}", row.trim_end())?;
+        }
+        write!(
+            f,
+            "{} specs: {} success, {} error, {} panic, {} timeout, {} skipped",
+            self.entries.len(),
+            self.count(&CorpusOutcome::Success),
+            self.count(&CorpusOutcome::Error(String::new())),
+            self.count(&CorpusOutcome::Panic(String::new())),
+            self.count(&CorpusOutcome::Timeout),
+            self.count(&CorpusOutcome::Skipped),
+        )
+    }
+}
+
+/// Generates code for each spec (`.yaml`, `.yml` or `.json` file) found
+/// in the directory tree below `dir`, using `config` with the spec's path.
+/// Specs are processed in path order. A spec whose generation takes longer
+/// than `timeout` is reported as timed out; its thread cannot be stopped
+/// and is left to finish in the background. While [MAX_RUNNING_TIMEOUTS]
+/// of these are running, specs are skipped.
+pub fn run_corpus(
+    dir: &Path,
+    config: &ApiConfig,
+    timeout: Duration,
+) -> anyhow::Result<CorpusReport> {
+    run_corpus_with_limit(dir, config, timeout, MAX_RUNNING_TIMEOUTS)
+}
+
+/// Like [run_corpus], but with the given limit of running timed out specs
+pub(crate) fn run_corpus_with_limit(
+    dir: &Path,
+    config: &ApiConfig,
+    timeout: Duration,
+    max_running_timeouts: usize,
+) -> anyhow::Result<CorpusReport> {
+    let mut spec_paths = Vec::new();
+    collect_spec_paths(dir, &mut spec_paths)?;
+    spec_paths.sort();
+
+    let mut report = CorpusReport::default();
+    let mut running_timeouts = Vec::new();
+    for path in spec_paths {
+        running_timeouts.retain(|thread: &JoinHandle<()>| !thread.is_finished());
+        if running_timeouts.len() >= max_running_timeouts {
+            log::warn!(
+                "skipping {}, as {} timed out specs are still generating",
+                path.display(),
+                running_timeouts.len()
+            );
+            report.entries.push(CorpusEntry {
+                path,
+                outcome: CorpusOutcome::Skipped,
+                duration: Duration::ZERO,
+            });
+            continue;
+        }
+        log::info!("generating code for {}", path.display());
+        let config = ApiConfig {
+            path: Some(path.to_string_lossy().to_string()),
+            url: None,
+            ..config.clone()
+        };
+        let start = Instant::now();
+        let outcome = generate_isolated(config, timeout, &mut running_timeouts);
+        report.entries.push(CorpusEntry {
+            path,
+            outcome,
+            duration: start.elapsed(),
+        });
+    }
+    Ok(report)
+}
+
+fn collect_spec_paths(dir: &Path, spec_paths: &mut Vec<PathBuf>) -> anyhow::Result<()> {
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect_spec_paths(&path, spec_paths)?;
+        } else if path
+            .extension()
+            .is_some_and(|ext| ext == "yaml" || ext == "yml" || ext == "json")
+        {
+            spec_paths.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Generates code for the spec `config` points to on a separate thread,
+/// turning panics and timeouts into outcomes. The thread of a timed out
+/// spec is added to `running_timeouts`.
+fn generate_isolated(
+    config: ApiConfig,
+    timeout: Duration,
+    running_timeouts: &mut Vec<JoinHandle<()>>,
+) -> CorpusOutcome {
+    let (sender, receiver) = mpsc::channel();
+    let thread = std::thread::spawn(move || {
+        // the config is dropped with the thread, whatever state a naming
+        // policy is left in
+        let generate = std::panic::AssertUnwindSafe(|| crate::generate_token_stream(&config));
+        let result = std::panic::catch_unwind(generate);
+        let outcome = match result {
+            Ok(Ok(_)) => CorpusOutcome::Success,
+            Ok(Err(e)) => CorpusOutcome::Error(format!("{e:#}")),
+            Err(payload) => {
+                let message = payload
+                    .downcast_ref::<&str>()
+                    .map(|s| s.to_string())
+                    .or_else(|| payload.downcast_ref::<String>().cloned())
+                    .unwrap_or_else(|| "unknown panic payload".to_string());
+                CorpusOutcome::Panic(message)
+            }
+        };
+        // the receiver is gone if we timed out
+        let _ = sender.send(outcome);
+    });
+    match receiver.recv_timeout(timeout) {
+        Ok(outcome) => outcome,
+        Err(_) => {
+            running_timeouts.push(thread);
+            CorpusOutcome::Timeout
+        }
+    }
+}
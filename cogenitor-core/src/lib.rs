@@ -1,31 +1,57 @@
 use anyhow::anyhow;
 use codewriter::fmt_code;
+use indexmap::IndexMap;
 use proc_macro2::TokenStream;
-use quote::quote;
+use quote::{format_ident, quote};
 use rust_format::Formatter;
 use std::{
     collections::{HashMap, HashSet},
     fs::File,
-    io::{BufReader, Cursor, Read, Seek, Write},
+    io::{Cursor, Read, Write},
+    str::FromStr,
+    time::Instant,
 };
 use syn::Ident;
 
+pub use codemodel::Visibility;
 use codemodel::{AttrListBuilder, Codemodel, Module, StructBuilder, TypeRef};
-use types::{BooleanOrSchema, Schema, Spec};
+pub use corpus::{CorpusEntry, CorpusOutcome, CorpusReport, run_corpus};
+pub use modeldiff::{ItemDiff, ItemKind, MemberDiff, ModelDiff, diff};
+pub use naming::{DefaultNamingPolicy, NamingPolicy, NamingPolicyClone};
+pub use show::{OperationSelector, show_operation};
+pub use stats::GenerationStats;
+pub use types::StatusSpec;
+use types::{BooleanOrSchema, Components, Header, Schema, Spec};
+
+#[cfg(feature = "oas31")]
+use crate::adapters::oas31::OAS31Spec;
 
 use crate::{
     adapters::oas30::OAS30Spec,
     codemodel::{
-        EnumBuilder, FunctionListBuilder, function::FunctionBuilder, trait_::TraitBuilder,
+        EnumBuilder, FunctionListBuilder, Indirection, NamedItem,
+        function::{Function, FunctionBuilder},
+        implementation::{Implementation, ImplementationBuilder},
+        trait_::{Trait, TraitBuilder},
+    },
+    types::{
+        Link, MediaType, Operation, Parameter, ParameterLocation, PathItem, RefOr, Reference,
+        RequestBody, Response,
     },
-    types::{MediaType, Operation, Parameter, PathItem, RefOr, RequestBody, Response, StatusSpec},
 };
 
 pub mod codemodel;
 mod codewriter;
+mod corpus;
+mod lint;
+mod modeldiff;
+mod naming;
 mod oasprobe;
+mod show;
+mod stats;
 mod translate;
 mod types;
+mod usage;
 
 #[cfg(test)]
 mod test;
@@ -33,12 +59,403 @@ mod test;
 pub mod adapters;
 
 /// Configuration settings for OpenAPI code generation.
-#[derive(Default, Debug, PartialEq)]
+#[derive(Default, Debug, Clone, PartialEq)]
 pub struct ApiConfig {
     /// Path to the input OpenAPI spec from which we want to generate code from
     pub path: Option<String>,
+    /// Paths to further spec files whose paths and components are merged
+    /// into the spec at [ApiConfig::path], e.g. for APIs whose spec is split
+    /// by resource. Names declared in several files are an error. With the
+    /// `parallel` feature, the files are read and parsed in parallel.
+    pub additional_paths: Vec<String>,
+    /// HTTP(S) URL of the input OpenAPI spec, used if no [ApiConfig::path]
+    /// is given. The downloaded spec is cached in the temp directory (see
+    /// [ApiConfig::spec_cache_ttl]). Downloading requires the `url` feature.
+    pub url: Option<String>,
+    /// How long a spec downloaded from [ApiConfig::url] is used from the
+    /// cache before it is downloaded again. `None` (the default) keeps
+    /// using it, a zero duration bypasses the cache.
+    pub spec_cache_ttl: Option<std::time::Duration>,
     /// Name of the module into which the generated code should be placed
     pub module_name: Option<String>,
+    /// Number of inputs (parameters and request body) above which an
+    /// operation's inputs are bundled into a generated `{Operation}Params`
+    /// struct instead of being passed as individual function parameters.
+    /// `None` (the default) never bundles inputs.
+    pub params_struct_threshold: Option<usize>,
+    /// Visibility of the generated items. Use [Visibility::Crate] to keep
+    /// them out of the public API of the crate embedding them.
+    pub visibility: Visibility,
+    /// Map string schemas with an `int64` or `uint64` format (integers
+    /// stringified because JSON numbers cannot represent them precisely)
+    /// to the generated newtypes `Int64String` and `UInt64String`, which
+    /// (de)serialize from/to JSON strings. Otherwise, they map to `String`.
+    pub stringified_integers: bool,
+    /// Name the types generated for inline schemas after their location
+    /// in the spec only. Otherwise, an inline schema's `title` is used as
+    /// its type name, if present.
+    pub ignore_schema_titles: bool,
+    /// Generate a `make_test_client!` macro creating a `ClientImpl` for a
+    /// given base URL literal. The macro is only emitted in `#[cfg(test)]`
+    /// builds of the crate embedding the generated code.
+    pub generate_test_macro: bool,
+    /// Generate an `examples` test module with an ignored test per
+    /// operation, calling it with the examples of its parameters, as a
+    /// smoke test of a deployed API. The tests call `ClientImpl` for the
+    /// base URL in the environment variable `{PREFIX}_BASE_URL` (see
+    /// [ApiConfig::env_prefix]), so `ClientImpl` must implement `Client`.
+    /// Operations taking a request body or a params struct, or a
+    /// parameter without an example, get no test.
+    pub emit_examples: bool,
+    /// Generate a `#[cfg(test)]` module with a test per generated type
+    /// deriving serde's traits, serializing a value of it to JSON and back.
+    /// The values are built from the schemas' examples, from `Default`, or
+    /// from the schemas' required properties. The generated code then
+    /// requires the `serde_json` crate.
+    pub roundtrip_tests: bool,
+    /// Map properties holding `byte` or `binary` strings of fixed length
+    /// (`minLength` equal to `maxLength`) to `[u8; N]` arrays if their
+    /// length is at most this limit, and to `Vec<u8>` otherwise. The
+    /// arrays are (de)serialized by generated helpers rejecting values of
+    /// a different length. `byte` strings are (de)serialized as base64
+    /// without padding, so their length is that of the encoding, and `N`
+    /// the number of bytes encoded. `None` (the default) keeps them
+    /// `String`s.
+    pub fixed_size_bytes_limit: Option<usize>,
+    /// Map properties of formats with a dedicated Rust type to that type.
+    /// Currently, these are decimals (`format: decimal`, or
+    /// `x-format: decimal` for formats taken by other tools), mapped to
+    /// `rust_decimal::Decimal`: strings via `rust_decimal::serde::str` and
+    /// numbers via `rust_decimal::serde::float`, so the generated code then
+    /// requires the `rust_decimal` crate with the `serde-with-str` and
+    /// `serde-with-float` features. Otherwise, decimals map to `String` and
+    /// `f64`.
+    pub typed_formats: bool,
+    /// Media types to pick from request bodies and responses offering
+    /// several, most preferred first (e.g. `application/json`). Only used
+    /// with [MediaTypeStrategy::PreferSingle].
+    pub preferred_media_types: Vec<String>,
+    /// How to map request bodies and responses offering several media types
+    pub media_type_strategy: MediaTypeStrategy,
+    /// Generate conversions of the structs generated for schemas from and
+    /// to `serde_json::Value`, and a `decode_with_path()` function whose
+    /// errors carry the JSON path of the value that failed to deserialize.
+    /// The generated code then requires the `serde_json` and
+    /// `serde_path_to_error` crates.
+    pub json_value_conversions: bool,
+    /// Fail code generation for path templates with parameters that are
+    /// not declared as path parameters. Otherwise, a `String` parameter is
+    /// added for each of them, with a warning.
+    pub strict_path_params: bool,
+    /// Fail code generation for parameters whose `content` offers several
+    /// media types. Otherwise, the parameter type is generated for a single
+    /// one of them, with a warning: the first of the
+    /// [ApiConfig::preferred_media_types] offered, or else a JSON media type,
+    /// or else the first in alphabetical order.
+    pub strict_param_content: bool,
+    /// Fail code generation if a declared operation gets no function of
+    /// the `Client` trait, like operations under methods we do not support
+    /// (`query`), or shares its function with other operations. Otherwise,
+    /// the discrepancies are logged as errors.
+    pub strict_operations: bool,
+    /// Fail code generation for paths whose templates differ only in the
+    /// names of their parameters, like `/users/{id}` and `/users/{userId}`,
+    /// which the spec forbids as they match the same requests. Otherwise,
+    /// only the first of them is generated, with a warning.
+    pub strict_duplicate_paths: bool,
+    /// Derive `Debug` for structs holding secrets, like passwords, so that
+    /// they print as they are. Otherwise, such structs get a `Debug` impl
+    /// printing `***` for strings of format `password` and write-only
+    /// strings (see [is_secret_property]), and so do the credentials of
+    /// the `SecurityMiddleware`.
+    pub debug_secrets: bool,
+    /// Generate a `decode_{operation}_response()` function per operation
+    /// on `ClientImpl`, mapping an `http::Response<Vec<u8>>` to the
+    /// operation's result by its status code and media type. JSON bodies
+    /// are deserialized, plain text bodies decoded as UTF-8, and binary
+    /// bodies passed on as is. Errors deserializing JSON are
+    /// `DecodeError`s, reporting the path to the offending value. The
+    /// generated code then requires the `serde_json` and
+    /// `serde_path_to_error` crates.
+    pub response_decoders: bool,
+    /// Generate an `{operation}_events()` variant of operations responding
+    /// with server-sent events (`text/event-stream`), returning an iterator
+    /// of the `SseEvent`s parsed from the stream. Otherwise, event streams
+    /// are mapped to `Vec<u8>`, like binary content.
+    pub sse_events: bool,
+    /// Number of operations above which a spec is reported as too large
+    /// to generate code for in reasonable time. `None` means no limit.
+    pub max_operations: Option<usize>,
+    /// Number of schemas above which a spec is reported as too large.
+    /// `None` means no limit.
+    pub max_schemas: Option<usize>,
+    /// Fail code generation for specs exceeding [ApiConfig::max_operations]
+    /// or [ApiConfig::max_schemas], instead of warning about them
+    pub fail_on_limit: bool,
+    /// Map `int64` integer properties named like IDs (ending in `Id` or
+    /// `Identifier`, like `petId`) to generated newtypes (like
+    /// `PetId(pub i64)`), so that IDs of different things cannot be mixed
+    /// up. Properties of the same name share their newtype.
+    pub generate_id_newtypes: bool,
+    /// What operations declaring no responses at all return on success
+    pub missing_responses: MissingResponses,
+    /// Generate newtypes checking the validation keywords of a schema
+    /// (like `minProperties` and `maxProperties` of maps) on conversion
+    /// and deserialization. Otherwise, the constraints are only mentioned
+    /// in the doc comments of the generated types.
+    pub validation_newtypes: bool,
+    /// Name the client functions and the types of their responses after
+    /// the operations' `operationId`s (like `find_pets_by_status()` for
+    /// `findPetsByStatus`) instead of their path and method. Operations
+    /// without an `operationId` keep the names derived from path and method.
+    pub operation_id_names: bool,
+    /// Derive the names of client functions and their types from paths the
+    /// way earlier versions did, without separating the words of camel case
+    /// path segments (`pet_findbystatus_get()` instead of
+    /// `pet_find_by_status_get()` for `GET /pet/findByStatus`), so code
+    /// written against those names keeps compiling
+    pub legacy_path_names: bool,
+    /// How non-ASCII characters of `operationId`s are treated when naming
+    /// client functions after them, see [ApiConfig::operation_id_names]
+    pub non_ascii_names: NonAsciiHandling,
+    /// Instrument the response decoders generated with
+    /// [ApiConfig::response_decoders] with `tracing`: each decoder enters a
+    /// span (target `cogenitor::client`) carrying the operation's name,
+    /// method and path template, and records the response status and the
+    /// kind of error, if any, as events. The generated code then requires
+    /// the `tracing` crate.
+    pub emit_tracing: bool,
+    /// Cargo feature of the crate embedding the generated code that
+    /// enables the instrumentation of [ApiConfig::emit_tracing], so that
+    /// `tracing` can be an optional dependency. `None` (the default)
+    /// instruments the decoders unconditionally.
+    pub tracing_feature: Option<String>,
+    /// Map nullable properties that are not required to
+    /// `Option<Option<T>>`, so that an absent property (`None`) can be told
+    /// apart from an explicit `null` (`Some(None)`), as PATCH requests
+    /// following JSON merge patch semantics require. Otherwise, both map
+    /// to `None`.
+    pub double_option_for_patch: bool,
+    /// How string properties allowing a single value via `enum` (like
+    /// discriminators declared as `enum: ["dog"]`) are mapped
+    pub single_value_enums: SingleValueEnums,
+    /// Generate a `callbacks` module with a `parse_{callback}()` function
+    /// per callback declared by the operations, deserializing the JSON body
+    /// of an incoming callback request into the generated payload type. The
+    /// payload types are generated regardless. The generated code then
+    /// requires the `serde_json` and `serde_path_to_error` crates.
+    pub callback_parsers: bool,
+    /// Generate a `{Operation}Links` trait per operation whose success
+    /// response declares `links`, implemented for the response type, with
+    /// a `follow_{link}()` method per link calling the linked operation
+    /// with the parameters taken from the response. Only parameters given
+    /// as `$response.body#/{pointer}` expressions are supported. The
+    /// generated code then requires the `serde_json` crate.
+    pub link_helpers: bool,
+    /// Generate a `ClientExists` trait, implemented for all clients, with
+    /// an `{operation}_exists()` method per `GET` operation reading a
+    /// single resource, telling whether the resource exists: `true` on
+    /// success, `false` on `404`. A `HEAD` operation on the same path is
+    /// called instead, if the spec declares one. Only operations taking
+    /// a single path parameter, with a single success response and a
+    /// declared `404` response, get a method.
+    pub emit_exists_helpers: bool,
+    /// Generate `ClientImpl::from_env()`, creating a client from the
+    /// environment variables `{PREFIX}_BASE_URL` and, if the spec has API
+    /// key or bearer token security schemes, `{PREFIX}_API_KEY` or
+    /// `{PREFIX}_BEARER_TOKEN`. The variables are read when `from_env()` is
+    /// called, never at generation time.
+    pub env_constructor: bool,
+    /// The prefix of the environment variables read by
+    /// [ApiConfig::env_constructor] and [ApiConfig::emit_examples],
+    /// converted to SCREAMING_SNAKE_CASE.
+    /// Defaults to the module name.
+    pub env_prefix: Option<String>,
+    /// Generate aliases naming each operation's request body, success and
+    /// error types, so user code need not look up the generated names
+    pub operation_type_aliases: OperationTypeAliases,
+    /// The server framework to generate routing glue for, serving an
+    /// implementation of the `Client` trait. The generated code then
+    /// requires the framework's crate and `serde_json`, and for
+    /// [ServerFramework::Axum] `tokio`, whose blocking threads call the
+    /// `Client` implementation.
+    pub server_framework: ServerFramework,
+    /// The policy naming generated items after names in the spec, for
+    /// house conventions the other settings don't cover. Defaults to
+    /// [DefaultNamingPolicy]. Only available when generating from code,
+    /// like in `build.rs`.
+    pub naming_policy: Option<Box<dyn NamingPolicy>>,
+}
+
+/// How request bodies and responses offering several media types are mapped
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MediaTypeStrategy {
+    /// Generate an enum with a variant per media type
+    #[default]
+    EnumAll,
+    /// Generate code for the first of [ApiConfig::preferred_media_types]
+    /// offered only, dropping the others. Falls back to
+    /// [MediaTypeStrategy::EnumAll] if none of them is offered.
+    PreferSingle,
+    /// Generate the type all media types map to if they map to the same
+    /// type (like JSON and XML representations of the same schema), and
+    /// fall back to [MediaTypeStrategy::EnumAll] otherwise
+    CollapseIdentical,
+}
+
+impl FromStr for MediaTypeStrategy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "enum_all" => Ok(MediaTypeStrategy::EnumAll),
+            "prefer_single" => Ok(MediaTypeStrategy::PreferSingle),
+            "collapse_identical" => Ok(MediaTypeStrategy::CollapseIdentical),
+            _ => Err(format!(
+                "unknown media type strategy '{s}', expected one of 'enum_all', 'prefer_single' or 'collapse_identical'"
+            )),
+        }
+    }
+}
+
+/// The success type of operations declaring no responses, which the spec
+/// does not allow, but which are common nonetheless
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MissingResponses {
+    /// Return `()`, like for operations declaring error responses only
+    #[default]
+    Unit,
+    /// Return the raw `http::Response<Vec<u8>>`, so that callers still get
+    /// whatever the server responded with
+    RawResponse,
+}
+
+impl FromStr for MissingResponses {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "unit" => Ok(MissingResponses::Unit),
+            "raw_response" => Ok(MissingResponses::RawResponse),
+            _ => Err(format!(
+                "unknown missing responses type '{s}', expected one of 'unit' or 'raw_response'"
+            )),
+        }
+    }
+}
+
+/// The form of the aliases generated for the types of each operation, see
+/// [ApiConfig::operation_type_aliases]
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OperationTypeAliases {
+    /// Generate no aliases
+    #[default]
+    None,
+    /// Generate a module per operation, named like the operation's
+    /// function, with the aliases `Request`, `Response` and `Error` (like
+    /// `pet_put::Response`)
+    Modules,
+    /// Generate the aliases next to the other types, prefixed with the
+    /// operation's type name (like `PetPutResponse`). Aliases that would be
+    /// named like the type they alias (like `PetPutError`) are omitted.
+    Prefixed,
+}
+
+impl FromStr for OperationTypeAliases {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "none" => Ok(OperationTypeAliases::None),
+            "modules" => Ok(OperationTypeAliases::Modules),
+            "prefixed" => Ok(OperationTypeAliases::Prefixed),
+            _ => Err(format!(
+                "unknown operation type alias form '{s}', expected one of 'none', 'modules' or 'prefixed'"
+            )),
+        }
+    }
+}
+
+/// The server frameworks we generate routing glue for, see
+/// [ApiConfig::server_framework]
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ServerFramework {
+    /// Generate no server code
+    #[default]
+    None,
+    /// Generate a `router()` function returning an `axum::Router` (axum
+    /// 0.8) with a route per operation, passing requests on to an
+    /// implementation of the `Client` trait. Only JSON and binary bodies
+    /// are supported.
+    Axum,
+}
+
+impl FromStr for ServerFramework {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "none" => Ok(ServerFramework::None),
+            "axum" => Ok(ServerFramework::Axum),
+            _ => Err(format!(
+                "unknown server framework '{s}', expected one of 'none' or 'axum'"
+            )),
+        }
+    }
+}
+
+/// How non-ASCII characters are treated when turning names from the spec
+/// into Rust identifiers, see [ApiConfig::non_ascii_names]
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NonAsciiHandling {
+    /// Remove non-ASCII characters, logging a warning, so `cafés` becomes
+    /// `cafs`
+    #[default]
+    Strip,
+    /// Keep non-ASCII letters and digits, which Rust accepts in identifiers
+    Keep,
+}
+
+impl FromStr for NonAsciiHandling {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "strip" => Ok(NonAsciiHandling::Strip),
+            "keep" => Ok(NonAsciiHandling::Keep),
+            _ => Err(format!(
+                "unknown non-ASCII handling '{s}', expected one of 'strip' or 'keep'"
+            )),
+        }
+    }
+}
+
+/// How string properties allowing a single value via `enum` or `const` are
+/// mapped, instead of generating a one-variant enum for each of them
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SingleValueEnums {
+    /// Map required properties to a generated zero-sized marker type (like
+    /// `DogMarker` for `"dog"`), which always serializes to the value and
+    /// fails to deserialize from any other value. Properties that are not
+    /// required map to `String`, like with [SingleValueEnums::String].
+    #[default]
+    MarkerIfRequired,
+    /// Map them to `String`, documenting the value on the field
+    String,
+}
+
+impl FromStr for SingleValueEnums {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "marker_if_required" => Ok(SingleValueEnums::MarkerIfRequired),
+            "string" => Ok(SingleValueEnums::String),
+            _ => Err(format!(
+                "unknown single value enum mapping '{s}', expected one of 'marker_if_required' or 'string'"
+            )),
+        }
+    }
 }
 
 impl ApiConfig {
@@ -48,17 +465,46 @@ impl ApiConfig {
             ..Self::default()
         }
     }
+
+    pub fn new_from_url(url: String) -> Self {
+        Self {
+            url: Some(url),
+            ..Self::default()
+        }
+    }
+
+    /// The naming policy in effect
+    pub(crate) fn naming(&self) -> &dyn NamingPolicy {
+        match &self.naming_policy {
+            Some(policy) => policy.as_ref(),
+            None => &DefaultNamingPolicy,
+        }
+    }
 }
 
-pub fn generate_mod(config: &ApiConfig) -> anyhow::Result<TokenStream> {
-    let module_name = config
+fn module_name_of(config: &ApiConfig) -> String {
+    config
         .module_name
-        .as_ref()
-        .map(|s| s.clone())
-        .unwrap_or_else(|| "generated_api".to_string());
+        .clone()
+        .unwrap_or_else(|| "generated_api".to_string())
+}
+
+pub fn generate_mod(config: &ApiConfig) -> anyhow::Result<TokenStream> {
+    let (ts, _) = generate_mod_with_stats(config)?;
+    Ok(ts)
+}
+
+/// Like [generate_mod], but also yields statistics about the generated
+/// code, like the number of operations and types
+pub fn generate_mod_with_stats(
+    config: &ApiConfig,
+) -> anyhow::Result<(TokenStream, GenerationStats)> {
+    let start = Instant::now();
+    let module_name = module_name_of(config);
     let module_ident = Ident::new(&module_name, proc_macro2::Span::call_site());
 
-    let ts = generate_token_stream(&config)?;
+    let (codemodel, mut stats) = codemodel_with_stats(config)?;
+    let ts = write_code(&codemodel)?;
 
     let ts = quote! {
         pub mod #module_ident {
@@ -68,87 +514,616 @@ pub fn generate_mod(config: &ApiConfig) -> anyhow::Result<TokenStream> {
             #![allow(non_snake_case)]
             #![allow(non_camel_case_types)]
 
-            use std::path::Path;
-
+            // the generated code may start with inner attributes, so it
+            // must precede any items
             #ts
+
+            use std::path::Path;
         }
     }
     .into();
 
-    Ok(ts)
+    stats.duration = start.elapsed();
+    Ok((ts, stats))
+}
+
+/// Generates a module for each of the given configs, one after the other.
+/// Yields the module name and the module's code for each config, in the
+/// order of `configs`.
+pub fn generate_batch(configs: &[ApiConfig]) -> anyhow::Result<Vec<(String, TokenStream)>> {
+    configs
+        .iter()
+        .map(|config| Ok((module_name_of(config), generate_mod(config)?)))
+        .collect()
+}
+
+/// Like [generate_batch], but generates the modules in parallel. Requires
+/// the `parallel` feature.
+#[cfg(feature = "parallel")]
+pub fn generate_batch_parallel(
+    configs: &[ApiConfig],
+) -> anyhow::Result<Vec<(String, TokenStream)>> {
+    use rayon::prelude::*;
+
+    // TokenStreams are neither Send nor Sync, so each worker hands back
+    // its code as a string, which we parse again on this thread
+    let generated = configs
+        .par_iter()
+        .map(|config| Ok((module_name_of(config), generate_mod(config)?.to_string())))
+        .collect::<anyhow::Result<Vec<_>>>()?;
+
+    generated
+        .into_iter()
+        .map(|(module_name, code)| {
+            let ts = code
+                .parse::<TokenStream>()
+                .map_err(|e| anyhow!(e.to_string()))?;
+            Ok((module_name, ts))
+        })
+        .collect()
 }
 
 pub fn generate_token_stream(config: &ApiConfig) -> anyhow::Result<TokenStream> {
-    let path = config
-        .path
+    let codemodel = generate_codemodel(config)?;
+    write_code(&codemodel)
+}
+
+/// Like [generate_token_stream], but yields the code as a string. Neither
+/// token streams nor the [Codemodel] are `Send`, so this is what to call
+/// on other threads, parsing the code into a [TokenStream] again on the
+/// thread collecting it if needed. Generating independent specs on
+/// separate threads shares nothing.
+pub fn generate_string(config: &ApiConfig) -> anyhow::Result<String> {
+    Ok(generate_token_stream(config)?.to_string())
+}
+
+/// Builds the code model that [generate_token_stream] writes code for,
+/// e.g. to [diff] the models of two versions of a spec
+pub fn generate_codemodel(config: &ApiConfig) -> anyhow::Result<Codemodel> {
+    let (codemodel, _) = codemodel_with_stats(config)?;
+    Ok(codemodel)
+}
+
+fn codemodel_with_stats(config: &ApiConfig) -> anyhow::Result<(Codemodel, GenerationStats)> {
+    visit_config_spec(config, CheckedCodemodel(config))
+}
+
+/// Reads the spec of `config`, from its path merged with its additional
+/// paths or from its URL, and hands it to `visitor`
+fn visit_config_spec<V: SpecVisitor>(config: &ApiConfig, visitor: V) -> anyhow::Result<V::Output> {
+    if let Some(path) = &config.path {
+        if !config.additional_paths.is_empty() {
+            let spec = merged_spec(path, &config.additional_paths)?;
+            return visit_spec_reader(std::io::Cursor::new(spec), visitor);
+        }
+        let path = std::path::Path::new(&path);
+        let mut file = std::fs::File::open(path)?;
+        return visit_spec_reader(&mut file, visitor);
+    }
+    if !config.additional_paths.is_empty() {
+        return Err(anyhow!("additional spec paths require a spec path"));
+    }
+    let url = config
+        .url
         .as_ref()
-        .ok_or(anyhow!("no path to OpenAPI file specified"))?;
-    let path = std::path::Path::new(&path);
-    let mut file = std::fs::File::open(path)?;
+        .ok_or(anyhow!("no path or URL to OpenAPI file specified"))?;
+    let spec = fetch_spec(url, config.spec_cache_ttl)?;
 
-    generate_from_reader(&mut file)
+    visit_spec_reader(std::io::Cursor::new(spec), visitor)
 }
 
-pub fn generate_file(config: &ApiConfig, output_path: &std::path::Path) -> anyhow::Result<()> {
-    let ts = generate_mod(config)?;
-    let formatter = rust_format::RustFmt::default();
-    let code_string = formatter.format_tokens(ts)?;
+/// Reads the spec at `path` and the specs at `additional_paths`, and
+/// merges the latter into the former, yielding the merged spec's YAML.
+/// Only the merging is sequential, the files are loaded with
+/// [load_spec_documents].
+fn merged_spec(path: &str, additional_paths: &[String]) -> anyhow::Result<Vec<u8>> {
+    let paths = std::iter::once(path)
+        .chain(additional_paths.iter().map(String::as_str))
+        .collect::<Vec<_>>();
+    let mut documents = load_spec_documents(&paths)?.into_iter();
+    let mut spec = documents.next().expect("the spec at path is loaded");
+    for (additional_path, document) in additional_paths.iter().zip(documents) {
+        merge_spec_document(&mut spec, document)
+            .map_err(|e| anyhow!("cannot merge spec '{additional_path}': {e}"))?;
+    }
+    Ok(serde_yaml::to_string(&spec)?.into_bytes())
+}
+
+/// Reads and parses the specs at `paths`, in the order of `paths`
+#[cfg(not(feature = "parallel"))]
+fn load_spec_documents(paths: &[&str]) -> anyhow::Result<Vec<serde_yaml::Value>> {
+    paths.iter().map(|path| load_spec_document(path)).collect()
+}
+
+/// Reads and parses the specs at `paths` in parallel, yielding them in
+/// the order of `paths`. Parsing dominates loading large specs, and the
+/// files are independent of each other until they are merged.
+#[cfg(feature = "parallel")]
+fn load_spec_documents(paths: &[&str]) -> anyhow::Result<Vec<serde_yaml::Value>> {
+    use rayon::prelude::*;
+
+    paths
+        .par_iter()
+        .map(|path| load_spec_document(path))
+        .collect()
+}
+
+fn load_spec_document(path: &str) -> anyhow::Result<serde_yaml::Value> {
+    let bytes = std::fs::read(path).map_err(|e| anyhow!("cannot read spec '{path}': {e}"))?;
+    let input = bytes.strip_prefix(UTF8_BOM).unwrap_or(&bytes);
+    serde_yaml::from_slice(input).map_err(|e| anyhow!("cannot parse spec '{path}': {e}"))
+}
+
+/// Adds the paths and components of `document` to `spec`. The other
+/// sections of `document`, like its info and servers, are ignored.
+fn merge_spec_document(
+    spec: &mut serde_yaml::Value,
+    document: serde_yaml::Value,
+) -> anyhow::Result<()> {
+    use serde_yaml::{Mapping, Value};
+
+    fn merge_mapping(into: &mut Value, from: Value, section: &str) -> anyhow::Result<()> {
+        let Value::Mapping(from) = from else {
+            return Err(anyhow!("'{section}' is not a mapping"));
+        };
+        if into.is_null() {
+            *into = Value::Mapping(Mapping::new());
+        }
+        let into = into
+            .as_mapping_mut()
+            .ok_or(anyhow!("'{section}' of the spec is not a mapping"))?;
+        for (key, value) in from {
+            if into.contains_key(&key) {
+                let key = key.as_str().unwrap_or_default();
+                return Err(anyhow!("'{section}/{key}' is declared in the spec already"));
+            }
+            into.insert(key, value);
+        }
+        Ok(())
+    }
+
+    // patch versions may differ, like in 3.0.0 and 3.0.3
+    let version = |document: &Value| {
+        let version = document.get("openapi").and_then(Value::as_str);
+        version.map(|v| v.split('.').take(2).collect::<Vec<_>>().join("."))
+    };
+    if version(spec) != version(&document) {
+        return Err(anyhow!("the OpenAPI versions differ"));
+    }
+    let spec = spec
+        .as_mapping_mut()
+        .ok_or(anyhow!("the spec is not a mapping"))?;
+    let Value::Mapping(document) = document else {
+        return Err(anyhow!("the spec is not a mapping"));
+    };
+    for (key, value) in document {
+        match key.as_str() {
+            Some("paths") => {
+                let paths = spec.entry(key).or_insert(Value::Null);
+                merge_mapping(paths, value, "paths")?;
+            }
+            Some("components") => {
+                let Value::Mapping(components) = value else {
+                    return Err(anyhow!("'components' is not a mapping"));
+                };
+                let into = spec
+                    .entry(key)
+                    .or_insert(Value::Mapping(Mapping::new()))
+                    .as_mapping_mut()
+                    .ok_or(anyhow!("'components' of the spec is not a mapping"))?;
+                for (kind, value) in components {
+                    let section = format!("components/{}", kind.as_str().unwrap_or_default());
+                    let components = into.entry(kind).or_insert(Value::Null);
+                    merge_mapping(components, value, &section)?;
+                }
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+/// The file a spec downloaded from `url` is cached in
+fn spec_cache_path(url: &str) -> std::path::PathBuf {
+    use std::hash::{Hash, Hasher};
+
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    url.hash(&mut hasher);
+    std::env::temp_dir().join(format!("cogenitor-spec-{:016x}", hasher.finish()))
+}
+
+/// Reads the spec at `url` from the cache, downloading it if it isn't
+/// cached yet, or was cached longer than `ttl` ago
+fn fetch_spec(url: &str, ttl: Option<std::time::Duration>) -> anyhow::Result<Vec<u8>> {
+    if !(url.starts_with("http://") || url.starts_with("https://")) {
+        return Err(anyhow!(
+            "unsupported spec URL '{url}', expected an http or https URL"
+        ));
+    }
+    let cache_path = spec_cache_path(url);
+    let fresh = match ttl {
+        None => true,
+        Some(ttl) => std::fs::metadata(&cache_path)
+            .and_then(|metadata| metadata.modified())
+            .ok()
+            .and_then(|modified| modified.elapsed().ok())
+            .is_some_and(|age| age < ttl),
+    };
+    if fresh && let Ok(spec) = std::fs::read(&cache_path) {
+        log::debug!("using cached spec {} for {url}", cache_path.display());
+        return Ok(spec);
+    }
+
+    let spec = download_spec(url)?;
+    // a failing cache must not fail code generation
+    if let Err(e) = write_spec_cache(&cache_path, &spec) {
+        log::warn!("cannot cache spec in {}: {e}", cache_path.display());
+    }
+    Ok(spec)
+}
+
+/// Writes a downloaded spec to the cache. The spec is written to a file of
+/// its own first, and then renamed, so that generators running
+/// concurrently never read a partially written spec.
+fn write_spec_cache(cache_path: &std::path::Path, spec: &[u8]) -> std::io::Result<()> {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    static WRITES: AtomicUsize = AtomicUsize::new(0);
+    let write = WRITES.fetch_add(1, Ordering::Relaxed);
+    let mut tmp_path = cache_path.as_os_str().to_owned();
+    tmp_path.push(format!(".{}-{write}.tmp", std::process::id()));
+    std::fs::write(&tmp_path, spec)?;
+    std::fs::rename(&tmp_path, cache_path).inspect_err(|_| {
+        let _ = std::fs::remove_file(&tmp_path);
+    })
+}
+
+#[cfg(feature = "url")]
+fn download_spec(url: &str) -> anyhow::Result<Vec<u8>> {
+    log::info!("downloading spec from {url}");
+    let response = reqwest::blocking::get(url)?.error_for_status()?;
+    Ok(response.bytes()?.to_vec())
+}
 
+#[cfg(not(feature = "url"))]
+fn download_spec(url: &str) -> anyhow::Result<Vec<u8>> {
+    Err(anyhow!(
+        "cannot download spec from '{url}', enable the 'url' feature to download specs"
+    ))
+}
+
+/// Generates the code for `config` into the file at `output_path`. The
+/// file starts with a comment summarizing the [GenerationStats], which
+/// [generate_file_check] ignores. The time generation took is logged
+/// instead, so that the file only changes with the spec.
+pub fn generate_file(config: &ApiConfig, output_path: &std::path::Path) -> anyhow::Result<()> {
+    let contents = generate_file_contents(config)?;
     let mut file = File::create(output_path)?;
-    file.write(code_string.as_bytes())?;
+    file.write_all(contents.as_bytes())?;
     Ok(())
 }
 
+/// The contents of the file [generate_file] writes: the summary comment
+/// and the formatted code
+fn generate_file_contents(config: &ApiConfig) -> anyhow::Result<String> {
+    let (code_string, mut stats) = generate_formatted_with_stats(config)?;
+    stats.lines = Some(code_string.lines().count());
+    let source = config
+        .path
+        .as_ref()
+        .or(config.url.as_ref())
+        .map_or("an unknown spec", |source| source.as_str());
+    log::info!(
+        "generated code for {source} in {} ms",
+        stats.duration.as_millis()
+    );
+    Ok(stats.header_comment(source) + &code_string)
+}
+
+/// Like [generate_file], but leaves the file at `output_path` untouched if
+/// it already contains what [generate_file] would write, so that its
+/// modification time doesn't trigger needless recompilation (e.g. when
+/// called from a build script). Returns `true` if the file was written.
+pub fn generate_file_if_changed(
+    config: &ApiConfig,
+    output_path: &std::path::Path,
+) -> anyhow::Result<bool> {
+    let contents = generate_file_contents(config)?;
+
+    let existing = match std::fs::read_to_string(output_path) {
+        Ok(existing) => Some(existing),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+        Err(e) => return Err(e.into()),
+    };
+    if existing.as_deref() == Some(contents.as_str()) {
+        return Ok(false);
+    }
+    std::fs::write(output_path, contents)?;
+    Ok(true)
+}
+
+fn generate_formatted(config: &ApiConfig) -> anyhow::Result<String> {
+    let (code_string, _) = generate_formatted_with_stats(config)?;
+    Ok(code_string)
+}
+
+fn generate_formatted_with_stats(config: &ApiConfig) -> anyhow::Result<(String, GenerationStats)> {
+    let (ts, stats) = generate_mod_with_stats(config)?;
+    let formatter = rust_format::RustFmt::default();
+    Ok((formatter.format_tokens(ts)?, stats))
+}
+
+/// Outcome of [generate_file_check]
+#[derive(Debug, Clone, PartialEq)]
+pub enum GenerateCheck {
+    /// the existing file contains the code we'd generate
+    Unchanged,
+    /// the existing file differs from the code we'd generate.
+    /// `diff_summary` is a unified diff from the existing to the new code.
+    Changed { diff_summary: String },
+    /// there is no file at the given path
+    Missing,
+}
+
+/// Checks whether [generate_file] would change the file at `existing_path`,
+/// without writing it. Trailing whitespace on lines, trailing newlines and
+/// the summary comment at the top of the file are ignored. Use this (or
+/// `cogenitor --check <spec> <output>`) in CI to detect generated files
+/// that are stale relative to their spec.
+pub fn generate_file_check(
+    config: &ApiConfig,
+    existing_path: &std::path::Path,
+) -> anyhow::Result<GenerateCheck> {
+    let existing = match std::fs::read_to_string(existing_path) {
+        Ok(existing) => existing,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(GenerateCheck::Missing),
+        Err(e) => return Err(e.into()),
+    };
+    let generated = generate_formatted(config)?;
+
+    let normalize = |code: &str| -> Vec<String> {
+        let mut lines: Vec<String> = code.lines().map(|l| l.trim_end().to_string()).collect();
+        while lines.last().is_some_and(|l| l.is_empty()) {
+            lines.pop();
+        }
+        lines
+    };
+    // the generated code never starts with a comment
+    let existing = normalize(&existing)
+        .into_iter()
+        .skip_while(|line| line.starts_with("//"))
+        .collect::<Vec<_>>();
+    let generated = normalize(&generated);
+    if existing == generated {
+        Ok(GenerateCheck::Unchanged)
+    } else {
+        Ok(GenerateCheck::Changed {
+            diff_summary: line_diff(&existing, &generated),
+        })
+    }
+}
+
+/// A unified diff with a single hunk, spanning from the first to the
+/// last line that differs. This is coarser than a minimal diff, but
+/// good enough to show where a generated file went stale.
+fn line_diff(old: &[String], new: &[String]) -> String {
+    let prefix = old.iter().zip(new).take_while(|(o, n)| o == n).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(o, n)| o == n)
+        .count();
+    let old_changed = &old[prefix..old.len() - suffix];
+    let new_changed = &new[prefix..new.len() - suffix];
+
+    let mut diff = format!(
+        "@@ -{},{} +{},{} @@\n",
+        prefix + 1,
+        old_changed.len(),
+        prefix + 1,
+        new_changed.len()
+    );
+    for line in old_changed {
+        diff.push_str(&format!("-{line}\n"));
+    }
+    for line in new_changed {
+        diff.push_str(&format!("+{line}\n"));
+    }
+    diff
+}
+
 #[allow(unused)]
 fn generate_from_str<S: Spec>(s: &str) -> anyhow::Result<TokenStream> {
-    generate_from_reader(Cursor::new(s.as_bytes()))
+    generate_from_reader(&ApiConfig::default(), Cursor::new(s.as_bytes()))
+}
+
+fn generate_from_reader(config: &ApiConfig, input: impl Read) -> anyhow::Result<TokenStream> {
+    let (codemodel, _) = codemodel_from_reader(config, input)?;
+    write_code(&codemodel)
+}
+
+/// Specs saved on Windows often start with a byte order mark, which is
+/// not part of the YAML or JSON document
+const UTF8_BOM: &[u8] = b"\xEF\xBB\xBF";
+
+fn codemodel_from_reader(
+    config: &ApiConfig,
+    input: impl Read,
+) -> anyhow::Result<(Codemodel, GenerationStats)> {
+    visit_spec_reader(input, CheckedCodemodel(config))
+}
+
+/// Something done with a spec once it is read, whatever its OAS version
+trait SpecVisitor {
+    type Output;
+
+    fn visit<S: Spec>(self, spec: &S) -> anyhow::Result<Self::Output>;
 }
 
-fn generate_from_reader(input: impl Read + Seek) -> anyhow::Result<TokenStream> {
-    let mut input = BufReader::with_capacity(8192, input);
-    let version = oasprobe::probe_yaml_oas_version(&mut input).map_err(|e| anyhow!(e))?;
-    input.rewind()?;
+/// Builds the checked code model of a spec (see [checked_codemodel])
+struct CheckedCodemodel<'a>(&'a ApiConfig);
+
+impl SpecVisitor for CheckedCodemodel<'_> {
+    type Output = (Codemodel, GenerationStats);
+
+    fn visit<S: Spec>(self, spec: &S) -> anyhow::Result<Self::Output> {
+        checked_codemodel(self.0, spec)
+    }
+}
+
+/// Parses the spec read from `input` with the adapter for its OAS version,
+/// and hands it to `visitor`
+fn visit_spec_reader<V: SpecVisitor>(
+    mut input: impl Read,
+    visitor: V,
+) -> anyhow::Result<V::Output> {
+    // the spec is read into memory, so that it can be parsed after probing
+    // its version without seeking back
+    let mut bytes = Vec::new();
+    input.read_to_end(&mut bytes)?;
+    // removing the BOM keeps the line numbers of parse errors intact
+    let input = bytes.strip_prefix(UTF8_BOM).unwrap_or(&bytes);
+    let version = oasprobe::probe_yaml_oas_version(input).map_err(|e| anyhow!(e))?;
     match version {
         #[cfg(feature = "oas30")]
-        adapters::OASMajorVersion::OAS30 => read_and_gererate::<OAS30Spec>(input),
+        adapters::OASMajorVersion::OAS30 => visitor.visit(&OAS30Spec::from_reader(input)?),
         #[cfg(feature = "oas31")]
-        adapters::OASMajorVersion::OAS31 => read_and_gererate::<OAS31Spec>(input),
+        adapters::OASMajorVersion::OAS31 => visitor.visit(&OAS31Spec::from_reader(input)?),
     }
 }
 
-fn read_and_gererate<S: Spec>(input: impl Read) -> anyhow::Result<TokenStream> {
-    let spec = S::from_reader(input)?;
-    generate_code(&spec)
-}
-
 struct Context<S: Spec> {
+    config: ApiConfig,
     cm: Codemodel,
     m: Module,
     mapping: TypeMapping<S>,
+    /// The media types of content collapsed to a single type (see
+    /// [MediaTypeStrategy::CollapseIdentical]), by the name the content's
+    /// enum would have had. Responses are still accepted in any of them.
+    collapsed_media_types: HashMap<String, Vec<String>>,
+    /// The names of the marker types generated for single value enums (see
+    /// [SingleValueEnums::MarkerIfRequired]), by value
+    constant_markers: HashMap<String, String>,
+    /// Makes the names of the items in [Context::m] unique
+    names: translate::NameAllocator,
+    /// The statistics counted while building the code model
+    stats: GenerationStats,
+}
+
+#[cfg(test)]
+fn build_codemodel<S: Spec>(
+    config: &ApiConfig,
+    spec: &S,
+) -> anyhow::Result<(Codemodel, TypeMapping<S>)> {
+    let (cm, mapping, _) = build_codemodel_with_stats(config, spec)?;
+    Ok((cm, mapping))
 }
 
-fn build_codemodel<S: Spec>(spec: &S) -> anyhow::Result<(Codemodel, TypeMapping<S>)> {
+/// Like [build_codemodel], but also yields the statistics counted while
+/// building the code model. Lines and duration are left for the caller.
+fn build_codemodel_with_stats<S: Spec>(
+    config: &ApiConfig,
+    spec: &S,
+) -> anyhow::Result<(Codemodel, TypeMapping<S>, GenerationStats)> {
     let mut ctx = Context {
+        config: config.clone(),
+
         cm: Codemodel::new(),
 
         m: Module::new("crate"),
 
         mapping: TypeMapping::new(),
+
+        collapsed_media_types: HashMap::new(),
+
+        constant_markers: HashMap::new(),
+
+        names: translate::NameAllocator::new(),
+
+        stats: GenerationStats::default(),
     };
 
+    for diagnostic in lint::lint_oas_names(spec, config.naming()) {
+        log::warn!("{diagnostic}");
+    }
+    for diagnostic in lint::lint_schema_dialects(spec) {
+        log::warn!("{diagnostic}");
+    }
+    for diagnostic in lint::lint_missing_responses(spec) {
+        log::warn!("{diagnostic}");
+    }
+    for diagnostic in lint::lint_path_item_refs(spec) {
+        log::warn!("{diagnostic}");
+    }
+    for diagnostic in lint::lint_duplicate_operation_ids(spec) {
+        log::warn!("{diagnostic}");
+    }
+
+    ctx.mapping.usage = usage::schema_usage(spec);
     populate_types(&mut ctx, spec)?;
 
+    let mut stats = ctx.stats;
+    for type_ref in ctx.m.type_iter() {
+        let type_ref = match type_ref {
+            TypeRef::Indirection(indirection) => match &*indirection.borrow() {
+                Indirection::Resolved(type_ref) => type_ref.clone(),
+                Indirection::Stub(_) => continue,
+            },
+            type_ref => type_ref.clone(),
+        };
+        match type_ref {
+            TypeRef::Struct(_) | TypeRef::Alias(_) => stats.types += 1,
+            TypeRef::Enum(_) => {
+                stats.types += 1;
+                stats.enums += 1;
+            }
+            _ => (),
+        }
+    }
+
     let mut cm = ctx.cm;
     let m = ctx.m;
     cm.insert_crate(m)?;
 
-    Ok((cm, ctx.mapping))
+    Ok((cm, ctx.mapping, stats))
+}
+
+#[cfg(test)]
+fn generate_code<S: Spec>(config: &ApiConfig, spec: &S) -> anyhow::Result<TokenStream> {
+    let (codemodel, _) = checked_codemodel(config, spec)?;
+    write_code(&codemodel)
 }
 
-fn generate_code<S: Spec>(spec: &S) -> anyhow::Result<TokenStream> {
-    let (codemodel, _) = build_codemodel(spec)?;
+/// Builds the code model for `spec`, after checking it for errors we
+/// cannot generate code for
+fn checked_codemodel<S: Spec>(
+    config: &ApiConfig,
+    spec: &S,
+) -> anyhow::Result<(Codemodel, GenerationStats)> {
+    // unresolvable references would make type building panic, so we
+    // report them all upfront
+    let errors = spec.validate_references();
+    if !errors.is_empty() {
+        let messages: Vec<String> = errors.iter().map(|e| e.to_string()).collect();
+        return Err(anyhow!("invalid OpenAPI spec: {}", messages.join("; ")));
+    }
+
+    let diagnostics = lint::lint_spec_size(spec, config.max_operations, config.max_schemas);
+    if config.fail_on_limit && !diagnostics.is_empty() {
+        let messages: Vec<String> = diagnostics.iter().map(|d| d.to_string()).collect();
+        return Err(anyhow!("OpenAPI spec too large: {}", messages.join("; ")));
+    }
+    for diagnostic in diagnostics {
+        log::warn!("{diagnostic}");
+    }
+
+    let (codemodel, _, stats) = build_codemodel_with_stats(config, spec)?;
+    Ok((codemodel, stats))
+}
 
-    let ts = codewriter::write_to_token_stream(&codemodel, "crate")?;
+fn write_code(codemodel: &Codemodel) -> anyhow::Result<TokenStream> {
+    let ts = codewriter::write_to_token_stream(codemodel, "crate")?;
 
     log::trace!("token stream: \n{}", fmt_code(ts.clone()).unwrap());
     Ok(ts)
@@ -157,13 +1132,42 @@ fn generate_code<S: Spec>(spec: &S) -> anyhow::Result<TokenStream> {
 /** Maps OpenAPI type names to actual Codemodel [TypeRef]s instances */
 struct TypeMapping<S: Spec> {
     schema_mapping: HashMap<RefOr<S::Schema>, TypeRef>,
+    /// types of the schemas in `#/components/schemas` that merely reference
+    /// another schema, by schema name. Their URIs resolve to the referenced
+    /// schema, so we can only recognize them by name.
+    alias_mapping: HashMap<String, TypeRef>,
+    /// types of the headers in `#/components/headers`, by header name
+    header_mapping: HashMap<String, TypeRef>,
+    /// Rust type names of the schemas in `#/components/schemas`, by schema
+    /// name. References are still resolved by the schema name.
+    schema_names: HashMap<String, String>,
+    /// the direction in which the schemas reachable from the operations are
+    /// used, see [usage::schema_usage]
+    usage: HashMap<RefOr<S::Schema>, usage::Usage>,
 }
 
 impl<S: Spec> TypeMapping<S> {
     fn new() -> Self {
         Self {
             schema_mapping: HashMap::new(),
+            alias_mapping: HashMap::new(),
+            header_mapping: HashMap::new(),
+            schema_names: HashMap::new(),
+            usage: HashMap::new(),
+        }
+    }
+}
+
+impl<S: Spec> TypeMapping<S> {
+    /// The type mapped to the schema. For references to aliasing schemas,
+    /// this is the alias, not the aliased type.
+    fn find(&self, schema: &RefOr<S::Schema>) -> Option<TypeRef> {
+        if let RefOr::Object(s) = schema
+            && let Some(alias) = s.name().and_then(|name| self.alias_mapping.get(name))
+        {
+            return Some(alias.clone());
         }
+        self.schema_mapping.get(schema).cloned()
     }
 }
 
@@ -171,19 +1175,112 @@ impl<S: Spec> std::fmt::Debug for TypeMapping<S> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("TypeMapping")
             .field("schema_mapping", &self.schema_mapping)
+            .field("alias_mapping", &self.alias_mapping)
+            .field("header_mapping", &self.header_mapping)
+            .field("schema_names", &self.schema_names)
+            .field("usage", &self.usage)
             .finish()
     }
 }
 
+/// The module documentation listing the API's contact, license and terms
+/// of service (those present) in a markdown table, so that a published
+/// client crate documents where the API comes from
+fn info_doc<S: Spec>(spec: &S) -> Option<String> {
+    let mut rows = Vec::new();
+    if let Some(contact) = spec.contact() {
+        let parts = [
+            contact.name.as_deref().map(escape_markdown),
+            contact
+                .email
+                .as_deref()
+                .map(|email| format!("<{}>", escape_markdown_url(email))),
+            contact
+                .url
+                .as_deref()
+                .map(|url| format!("<{}>", escape_markdown_url(url))),
+        ];
+        let parts = parts.into_iter().flatten().collect::<Vec<_>>();
+        if !parts.is_empty() {
+            rows.push(("Contact", parts.join(" ")));
+        }
+    }
+    if let Some(license) = spec.license() {
+        let name = escape_markdown(&license.name);
+        let license = match license.url {
+            Some(url) => format!("[{name}]({})", escape_markdown_url(&url)),
+            None => name,
+        };
+        rows.push(("License", license));
+    }
+    if let Some(url) = spec.terms_of_service() {
+        let url = escape_markdown_url(&url);
+        rows.push(("Terms of service", format!("<{url}>")));
+    }
+    if rows.is_empty() {
+        return None;
+    }
+
+    let mut doc = "| | |\n|---|---|".to_string();
+    for (field, value) in rows {
+        doc.push_str(&format!("\n| {field} | {value} |"));
+    }
+    Some(doc)
+}
+
+/// Escapes characters with an inline meaning in markdown, so that text from
+/// the spec shows up literally in a table cell of the documentation
+fn escape_markdown(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if "\\`*_[]<>|~".contains(c) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Percent-encodes characters that would end a markdown link or autolink
+/// (or a table cell) in a URL or email address
+fn escape_markdown_url(url: &str) -> String {
+    let mut escaped = String::with_capacity(url.len());
+    for c in url.chars() {
+        match c {
+            ' ' => escaped.push_str("%20"),
+            '(' => escaped.push_str("%28"),
+            ')' => escaped.push_str("%29"),
+            '<' => escaped.push_str("%3C"),
+            '>' => escaped.push_str("%3E"),
+            '|' => escaped.push_str("%7C"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
 fn populate_types<S: Spec>(ctx: &mut Context<S>, spec: &S) -> anyhow::Result<()> {
+    // the API maintainer's contact and terms go into the module documentation
+    if let Some(doc) = info_doc(spec) {
+        ctx.m.insert_attr_with_input("doc", quote!(= #doc))?;
+    }
+
     // in order to properly deal with cyclic data structures, we create
     // type stubs for all named schemata. This way, while constructing
     // a type from a schema, we can refer to another type that we
     // didn't construct yet.
     for (name, schema) in spec.schemata_iter() {
-        let rust_name = translate::schema_to_rust_typename(&name);
+        // distinct schema names may map to the same Rust name, like
+        // `user.v1.Profile` and `UserV1Profile`
+        let rust_name = ctx
+            .names
+            .uncollide(&ctx.m, ctx.config.naming().schema_to_type_name(&name));
         let type_ref = ctx.m.insert_type_stub(&rust_name)?;
-        ctx.mapping.schema_mapping.insert(schema, type_ref);
+        ctx.mapping.schema_names.insert(name.clone(), rust_name);
+        match schema {
+            RefOr::Reference(_) => ctx.mapping.alias_mapping.insert(name, type_ref),
+            RefOr::Object(_) => ctx.mapping.schema_mapping.insert(schema, type_ref),
+        };
     }
 
     log::trace!(
@@ -194,19 +1291,42 @@ fn populate_types<S: Spec>(ctx: &mut Context<S>, spec: &S) -> anyhow::Result<()>
     // we now construct all types properly. When inserting them into
     // the module, stubs are replaced by proper types.
     for (name, ro_schema) in spec.schemata_iter() {
-        log::debug!("creating type for schema '{name}");
+        log::debug!("creating type for schema '{name}'");
+        let rust_name = ctx.mapping.schema_names[&name].clone();
         match &ro_schema {
             RefOr::Reference(_) => {
-                let alias_name = translate::schema_to_rust_typename(&name);
                 let target = ctx
                     .mapping
-                    .schema_mapping
-                    .get(&ro_schema.resolve())
+                    .find(&ro_schema.resolve())
                     .expect("type not found for schema");
-                ctx.m.insert_type_alias(&alias_name, target.clone())?;
+                let type_ref = ctx.m.insert_type_alias(&rust_name, target)?;
+                ctx.mapping.alias_mapping.insert(name, type_ref);
             }
             RefOr::Object(schema) => {
-                let type_ref = parse_schema(ctx, schema, Some(name.clone()))?;
+                let type_ref = match wrapped_reference::<S>(schema) {
+                    // wrappers keep the name, documenting the wrapped type
+                    // the way they describe it
+                    Some(target) => {
+                        let target = ctx
+                            .mapping
+                            .find(&target.resolve())
+                            .expect("type not found for schema");
+                        match schema.description() {
+                            Some(description) => {
+                                let doc = description
+                                    .trim()
+                                    .lines()
+                                    .map(|line| format!(" {line}"))
+                                    .collect::<Vec<_>>()
+                                    .join("\n");
+                                ctx.m
+                                    .insert_documented_type_alias(&rust_name, target, &doc)?
+                            }
+                            None => ctx.m.insert_type_alias(&rust_name, target)?,
+                        }
+                    }
+                    None => parse_schema(ctx, schema, Some(rust_name))?,
+                };
                 ctx.mapping.schema_mapping.insert(ro_schema, type_ref);
             }
         }
@@ -214,31 +1334,1935 @@ fn populate_types<S: Spec>(ctx: &mut Context<S>, spec: &S) -> anyhow::Result<()>
 
     log::trace!("types from schemas section constructed: {:?}", ctx.mapping);
 
-    let client_struct = StructBuilder::new("ClientImpl")
+    // headers in the components section may be shared by several responses,
+    // so we create their types once here
+    for (name, ro_header) in spec.components().iter().flat_map(|c| c.headers()) {
+        log::debug!("creating type for header '{name}'");
+        if let Some(schema) = ro_header.resolve_fully().schema() {
+            let candidate_name = translate::header_to_rust_typename(&name);
+            let type_ref = type_ref_of(ctx, &schema, &candidate_name)?;
+            ctx.mapping.header_mapping.insert(name, type_ref);
+        }
+    }
+
+    let security_middleware = build_security_middleware(ctx, spec)?;
+
+    let mut client_struct = StructBuilder::new("ClientImpl")
+        .visibility(ctx.config.visibility)
         .attr_with_input("derive", quote::quote!((Debug)))?
-        .build()?;
-    //let client_struct = ctx.m.insert_struct(client_struct)?;
+        .field("base_url", ctx.cm.type_string())?
+        .field_with_input("timeout", quote!(Option<::std::time::Duration>))?;
+    if let Some(security_middleware) = security_middleware {
+        let auth_type = ctx.cm.optional(&security_middleware);
+        client_struct = client_struct.field("auth", auth_type)?;
+    }
+    let client_struct = client_struct.build()?;
+    let client_struct = ctx.m.insert_struct(client_struct)?;
+    let client_display = build_client_display(ctx, client_struct.clone())?;
+    ctx.m.insert_implementation(client_display)?;
+    let client_url_for = build_client_url_for(ctx, client_struct.clone())?;
+    ctx.m.insert_implementation(client_url_for)?;
+
+    let duplicate_paths = duplicate_path_templates(&ctx.config, spec)?;
+    let operations = spec
+        .paths()
+        .filter(|(path, _)| !duplicate_paths.contains(path))
+        .flat_map(|(path, path_item)| {
+            path_item
+                .operations_iter()
+                .map(|(method, _)| (path.clone(), method))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    let operation_ids = if ctx.config.operation_id_names {
+        unique_operation_ids(spec)
+    } else {
+        HashMap::new()
+    };
+    let mut operation_names = derive_operation_names(
+        &operations,
+        &operation_ids,
+        ctx.config.legacy_path_names,
+        ctx.config.non_ascii_names,
+        ctx.config.naming(),
+    )?;
 
-    let mut client_trait = TraitBuilder::new("Client");
+    let mut client_trait = TraitBuilder::new("Client").visibility(ctx.config.visibility);
+    let mut operation_infos = Vec::new();
+    let mut operation_routes = Vec::new();
+    let mut response_decoders = Vec::new();
+    let mut example_tests = Vec::new();
+    let mut operation_types = Vec::new();
+    let mut router_routes = Vec::new();
+    let security_schemes = spec
+        .components()
+        .iter()
+        .flat_map(|c| c.security_schemes())
+        .collect::<HashMap<_, _>>();
+    let global_security = spec.security();
+    let mut security_appliers = Vec::new();
     //    let mut client_impl = ImplementationBuilder::new_trait(client_struct, client_trait);
     for (path, path_item) in spec.paths() {
+        if duplicate_paths.contains(&path) {
+            continue;
+        }
         for (method, path_op) in path_item.operations_iter() {
             log::debug!("creating method for {method} {path}");
+            let names = operation_names
+                .remove(&(path.clone(), method.clone()))
+                .expect("names derived for all operations");
+            operation_infos.push(operation_info_of::<S>(&names, &path, &method, &path_op));
+            operation_routes.push((names.fn_name.clone(), method.clone(), path.clone()));
+            let undeclared_params =
+                check_path_params::<S>(&ctx.config, &path, &path_item, &path_op)?;
             client_trait = parse_path_into_impl_fn(
                 ctx,
                 client_trait,
+                &names,
                 &path,
+                &method,
                 &path_item,
-                method.clone(),
                 &path_op,
+                &undeclared_params,
+                &mut response_decoders,
+                &mut example_tests,
+                &mut operation_types,
+                &mut router_routes,
             )?;
+            if ctx.m.contains_item("SecurityMiddleware") {
+                let requirements = path_op
+                    .security()
+                    .unwrap_or_else(|| global_security.clone());
+                security_appliers.push(build_operation_security(
+                    ctx,
+                    &names,
+                    &security_schemes,
+                    &requirements,
+                )?);
+            }
+        }
+    }
+    if !security_appliers.is_empty() {
+        let mut security_impl = ImplementationBuilder::new_inherent(client_struct.clone());
+        for applier in security_appliers {
+            security_impl = security_impl.function(applier);
+        }
+        ctx.m.insert_implementation(security_impl.build())?;
+    }
+    if ctx.config.response_decoders {
+        build_response_decoder_helpers(ctx)?;
+        let mut decoders_impl = ImplementationBuilder::new_inherent(client_struct.clone());
+        for decoder in response_decoders {
+            decoders_impl = decoders_impl.function(decoder);
         }
+        ctx.m.insert_implementation(decoders_impl.build())?;
+    } else if ctx.config.emit_tracing {
+        log::warn!("'emit_tracing' has no effect without 'response_decoders'");
     }
     //    ctx.m.insert_implementation(client_impl.build())?;
-    ctx.m.insert_trait(client_trait.build()?)?;
-
-    Ok(())
-}
+    let client_trait = ctx.m.insert_trait(client_trait.build()?)?;
+    let discrepancies =
+        operation_function_discrepancies(spec, &client_trait, &operation_routes, &duplicate_paths);
+    if ctx.config.strict_operations && !discrepancies.is_empty() {
+        return Err(anyhow!(
+            "operations without exactly one client function: {}",
+            discrepancies.join("; ")
+        ));
+    }
+    ctx.stats.operations = operation_routes.len();
+    ctx.stats.skipped += discrepancies.len();
+    for discrepancy in discrepancies {
+        log::error!("{discrepancy}");
+    }
+    // only the first of paths differing in parameter names is generated
+    ctx.stats.skipped += spec
+        .paths()
+        .filter(|(path, _)| duplicate_paths.contains(path))
+        .map(|(_, path_item)| path_item.operations_iter().count())
+        .sum::<usize>();
+
+    build_operations_listing(ctx, operation_infos)?;
+    build_operation_lookup(ctx, &operation_routes)?;
+    if ctx.config.link_helpers {
+        build_link_helpers(ctx, spec, &operation_types, &operation_routes)?;
+    }
+    if ctx.config.emit_exists_helpers {
+        build_exists_helpers(
+            ctx,
+            spec,
+            &client_trait,
+            &operation_types,
+            &operation_routes,
+        )?;
+    }
+    build_operation_type_aliases(ctx, operation_types)?;
+    build_server_url(ctx, spec)?;
+    if ctx.config.server_framework == ServerFramework::Axum {
+        build_axum_router(ctx, router_routes)?;
+    }
+    if ctx.config.generate_test_macro {
+        build_test_client_macro(ctx)?;
+    }
+    if ctx.config.roundtrip_tests {
+        build_roundtrip_tests(ctx, spec)?;
+    }
+    if ctx.config.json_value_conversions {
+        build_decode_with_path(ctx)?;
+    }
+    build_callbacks(ctx, spec)?;
+    if ctx.config.env_constructor {
+        build_env_constructor(ctx, spec)?;
+    }
+    if !example_tests.is_empty() {
+        build_examples_module(ctx, example_tests)?;
+    }
+
+    Ok(())
+}
+
+/// Creates the types of the request and response bodies of the operations'
+/// callbacks, which are often inline schemas found nowhere else. With
+/// [ApiConfig::callback_parsers], also creates the `callbacks` module with
+/// a `parse_{callback}()` function per callback with a JSON request body.
+fn build_callbacks<S: Spec>(ctx: &mut Context<S>, spec: &S) -> anyhow::Result<()> {
+    let mut parsers = Vec::new();
+    let mut fn_names = HashSet::new();
+    for (_, path_item) in spec.paths() {
+        for (_, path_op) in path_item.operations_iter() {
+            for (callback_name, callback_items) in path_op.callbacks() {
+                let type_name = ctx.config.naming().schema_to_type_name(&callback_name);
+                let mut payloads = Vec::new();
+                for (expression, callback_item) in callback_items {
+                    for (method, callback_op) in callback_item.operations_iter() {
+                        log::debug!(
+                            "creating types for callback '{callback_name}' ({method} {expression})"
+                        );
+                        if let Some(request_body) = callback_op.request_body() {
+                            let content = request_body.resolve_fully().content();
+                            let json = content.iter().find(|(key, _)| is_json_media_type(key));
+                            match json {
+                                Some((key, media_type)) => {
+                                    let payload_type =
+                                        map_media_type::<S>(ctx, key, media_type, || {
+                                            type_name.clone()
+                                        });
+                                    payloads.push((payload_type, method, expression.clone()));
+                                }
+                                None => {
+                                    map_content(ctx, &content, || type_name.clone())?;
+                                }
+                            }
+                        }
+                        for (_, response) in callback_op.responses() {
+                            let content = response.resolve_fully().content();
+                            map_content(ctx, &content, || format!("{type_name}Response"))?;
+                        }
+                    }
+                }
+
+                let [(payload_type, method, expression)] = payloads.as_slice() else {
+                    if payloads.len() > 1 {
+                        log::warn!(
+                            "not generating a parser for callback '{callback_name}' with several requests"
+                        );
+                        ctx.stats.skipped += 1;
+                    }
+                    continue;
+                };
+                let Some(fn_name) = translate::operation_id_to_rust_fn_name(
+                    &format!("parse_{callback_name}"),
+                    ctx.config.non_ascii_names,
+                    &fn_names,
+                ) else {
+                    continue;
+                };
+                fn_names.insert(fn_name.clone());
+                let fn_ident = format_ident!("{fn_name}");
+                let payload_ts = codewriter::syn_type_name_of(payload_type)?;
+                let doc = format!(
+                    " Deserializes the body of a `{callback_name}` callback request (`{method} {expression}`)"
+                );
+                parsers.push(quote!(
+                    #[doc = #doc]
+                    pub fn #fn_ident(
+                        body: &[u8],
+                    ) -> ::std::result::Result<#payload_ts, DecodeError> {
+                        decode_with_path(body)
+                    }
+                ));
+            }
+        }
+    }
+    if !ctx.config.callback_parsers || parsers.is_empty() {
+        return Ok(());
+    }
+
+    build_decode_with_path(ctx)?;
+    let visibility = ctx.config.visibility;
+    ctx.m.insert_raw_item(
+        "callbacks",
+        quote!(
+            /// Parsers for the bodies of the callback requests the server sends
+            #visibility mod callbacks {
+                use super::*;
+
+                #(#parsers)*
+            }
+        ),
+    )?;
+    Ok(())
+}
+
+/// Creates the `DecodeError` type and the `decode_with_path()` function,
+/// which deserializes JSON like `serde_json::from_slice()` does, but
+/// reports the path to the value that could not be deserialized (like
+/// `category.id`). This helps to find where a response deviates from
+/// the spec.
+fn build_decode_with_path<S: Spec>(ctx: &mut Context<S>) -> anyhow::Result<()> {
+    if ctx.m.contains_item("DecodeError") {
+        return Ok(());
+    }
+    let visibility = ctx.config.visibility;
+    ctx.m.insert_raw_item(
+        "DecodeError",
+        quote!(
+            #[derive(::std::fmt::Debug)]
+            #visibility struct DecodeError {
+                /// path to the value that failed to deserialize, `.` if
+                /// the error is not specific to a value
+                pub path: ::std::string::String,
+                pub source: ::serde_json::Error,
+            }
+
+            impl ::std::fmt::Display for DecodeError {
+                fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                    ::std::write!(f, "{}: {}", self.path, self.source)
+                }
+            }
+
+            impl ::std::error::Error for DecodeError {
+                fn source(&self) -> ::std::option::Option<&(dyn ::std::error::Error + 'static)> {
+                    ::std::option::Option::Some(&self.source)
+                }
+            }
+        ),
+    )?;
+    ctx.m.insert_raw_item(
+        "decode_with_path",
+        quote!(
+            #visibility fn decode_with_path<T: ::serde::de::DeserializeOwned>(
+                bytes: &[u8],
+            ) -> ::std::result::Result<T, DecodeError> {
+                let deserializer = &mut ::serde_json::Deserializer::from_slice(bytes);
+                ::serde_path_to_error::deserialize(deserializer).map_err(|e| DecodeError {
+                    path: e.path().to_string(),
+                    source: e.into_inner(),
+                })
+            }
+        ),
+    )?;
+    Ok(())
+}
+
+/// Creates `TryFrom<serde_json::Value>` and `From<T> for serde_json::Value`
+/// implementations for the struct generated for a schema
+fn build_json_value_conversions<S: Spec>(
+    ctx: &mut Context<S>,
+    struct_name: &str,
+) -> anyhow::Result<()> {
+    let struct_ident = format_ident!("{struct_name}");
+    ctx.m.insert_raw_item(
+        &format!("impl TryFrom<serde_json::Value> for {struct_name}"),
+        quote!(
+            impl ::std::convert::TryFrom<::serde_json::Value> for #struct_ident {
+                type Error = ::serde_json::Error;
+
+                fn try_from(value: ::serde_json::Value) -> ::std::result::Result<Self, Self::Error> {
+                    ::serde_json::from_value(value)
+                }
+            }
+        ),
+    )?;
+    ctx.m.insert_raw_item(
+        &format!("impl From<{struct_name}> for serde_json::Value"),
+        quote!(
+            impl ::std::convert::From<#struct_ident> for ::serde_json::Value {
+                fn from(value: #struct_ident) -> Self {
+                    // generated structs have string keys only, so they
+                    // always serialize to JSON
+                    ::serde_json::to_value(value).expect("struct serializes to JSON")
+                }
+            }
+        ),
+    )?;
+    Ok(())
+}
+
+/// Creates `ClientImpl::from_env()` and the `EnvConfigError` it fails with
+/// (see [ApiConfig::env_constructor]). The base URL is required. Of the
+/// credentials, `{PREFIX}_API_KEY` is used with the first API key scheme of
+/// the spec, `{PREFIX}_BEARER_TOKEN` is used otherwise; one of them is
+/// required if the spec has schemes for them.
+fn build_env_constructor<S: Spec>(ctx: &mut Context<S>, spec: &S) -> anyhow::Result<()> {
+    let prefix = env_prefix_of(&ctx.config);
+    let base_url_var = format!("{prefix}_BASE_URL");
+
+    let mut api_key_scheme = None;
+    let mut bearer = false;
+    for (_, scheme) in spec.components().iter().flat_map(|c| c.security_schemes()) {
+        match scheme {
+            types::SecurityScheme::ApiKey { name, location } => {
+                api_key_scheme.get_or_insert((name, location));
+            }
+            types::SecurityScheme::Http { scheme } if scheme.eq_ignore_ascii_case("bearer") => {
+                bearer = true
+            }
+            types::SecurityScheme::OAuth2 | types::SecurityScheme::OpenIdConnect => bearer = true,
+            types::SecurityScheme::Http { .. } => (),
+        }
+    }
+
+    let mut credential_vars = Vec::new();
+    let mut credentials = Vec::new();
+    if let Some((name, location)) = api_key_scheme {
+        let var = format!("{prefix}_API_KEY");
+        let location = match location {
+            types::ApiKeyLocation::Query => quote!(ApiKeyLocation::Query),
+            types::ApiKeyLocation::Header => quote!(ApiKeyLocation::Header),
+            types::ApiKeyLocation::Cookie => quote!(ApiKeyLocation::Cookie),
+        };
+        credentials.push(quote!(
+            .or_else(|| var(#var).map(|key| SecurityMiddleware::ApiKey {
+                name: ::std::string::ToString::to_string(#name),
+                key,
+                location: #location,
+            }))
+        ));
+        credential_vars.push(var);
+    }
+    if bearer {
+        let var = format!("{prefix}_BEARER_TOKEN");
+        credentials.push(quote!(.or_else(|| var(#var).map(SecurityMiddleware::BearerToken))));
+        credential_vars.push(var);
+    }
+    let auth = ctx
+        .m
+        .contains_item("SecurityMiddleware")
+        .then(|| quote!(auth,));
+    let auth_init = if auth.is_some() {
+        quote!(
+            let auth = ::std::option::Option::None #(#credentials)*;
+            let missing_credentials = if auth.is_none() {
+                ::std::vec![#(#credential_vars),*]
+            } else {
+                ::std::vec::Vec::new()
+            };
+        )
+    } else {
+        quote!(
+            let missing_credentials = ::std::vec::Vec::new();
+        )
+    };
+    let doc = if credential_vars.is_empty() {
+        format!(" Creates a client from the environment variable `{base_url_var}`")
+    } else {
+        let credential_vars = credential_vars
+            .iter()
+            .map(|var| format!("`{var}`"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        format!(
+            " Creates a client from the environment variable `{base_url_var}` \
+             and the first one set of {credential_vars}"
+        )
+    };
+
+    let visibility = ctx.config.visibility;
+    ctx.m.insert_raw_item(
+        "EnvConfigError",
+        quote!(
+            /// The environment variables `ClientImpl::from_env()` is missing
+            #[derive(::std::fmt::Debug, ::core::cmp::PartialEq, ::core::cmp::Eq)]
+            #visibility struct EnvConfigError {
+                /// the required variables that are not set
+                pub missing: ::std::vec::Vec<&'static str>,
+                /// the variables providing credentials, of which none is set
+                /// although one is required
+                pub missing_credentials: ::std::vec::Vec<&'static str>,
+            }
+
+            impl ::std::fmt::Display for EnvConfigError {
+                fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                    let mut problems = ::std::vec::Vec::new();
+                    if !self.missing.is_empty() {
+                        problems.push(::std::format!(
+                            "missing environment variables: {}",
+                            self.missing.join(", ")
+                        ));
+                    }
+                    if !self.missing_credentials.is_empty() {
+                        problems.push(::std::format!(
+                            "missing credentials, set one of: {}",
+                            self.missing_credentials.join(", ")
+                        ));
+                    }
+                    f.write_str(&problems.join("; "))
+                }
+            }
+
+            impl ::std::error::Error for EnvConfigError {}
+        ),
+    )?;
+    ctx.m.insert_raw_item(
+        "impl ClientImpl { from_env }",
+        quote!(
+            impl ClientImpl {
+                #[doc = #doc]
+                #visibility fn from_env() -> ::std::result::Result<Self, EnvConfigError> {
+                    // unset and empty variables count as missing
+                    fn var(name: &str) -> ::std::option::Option<::std::string::String> {
+                        ::std::env::var(name).ok().filter(|value| !value.is_empty())
+                    }
+                    let mut missing = ::std::vec::Vec::new();
+                    let base_url = var(#base_url_var);
+                    if base_url.is_none() {
+                        missing.push(#base_url_var);
+                    }
+                    #auth_init
+                    match base_url {
+                        ::std::option::Option::Some(base_url)
+                            if missing_credentials.is_empty() =>
+                        {
+                            ::std::result::Result::Ok(ClientImpl {
+                                base_url,
+                                timeout: ::std::option::Option::None,
+                                #auth
+                            })
+                        }
+                        _ => ::std::result::Result::Err(EnvConfigError {
+                            missing,
+                            missing_credentials,
+                        }),
+                    }
+                }
+            }
+        ),
+    )?;
+    Ok(())
+}
+
+/// Creates the `SecurityMiddleware` enum holding the credentials for the
+/// security schemes in the spec, and its `apply_security()` function adding
+/// them to an `http` request. The enum has a variant per kind of scheme:
+/// `BearerToken` for `http` bearer, OAuth 2 and OpenID Connect schemes (as
+/// they all pass access tokens), `BasicAuth` for `http` basic schemes and
+/// `ApiKey` for API keys. Unless [ApiConfig::debug_secrets] is set, its
+/// `Debug` impl prints `***` for the credentials, so that they don't leak
+/// into logs, e.g. of a `ClientImpl`. Yields the enum, or `None` if the
+/// spec has no security schemes we support.
+fn build_security_middleware<S: Spec>(
+    ctx: &mut Context<S>,
+    spec: &S,
+) -> anyhow::Result<Option<TypeRef>> {
+    let mut bearer = false;
+    let mut basic = false;
+    let mut api_key = false;
+    for (name, scheme) in spec.components().iter().flat_map(|c| c.security_schemes()) {
+        match scheme {
+            types::SecurityScheme::Http { scheme } if scheme.eq_ignore_ascii_case("bearer") => {
+                bearer = true
+            }
+            types::SecurityScheme::Http { scheme } if scheme.eq_ignore_ascii_case("basic") => {
+                basic = true
+            }
+            types::SecurityScheme::Http { scheme } => {
+                log::warn!("security scheme '{name}' uses unsupported HTTP scheme '{scheme}'");
+                ctx.stats.skipped += 1;
+            }
+            types::SecurityScheme::OAuth2 | types::SecurityScheme::OpenIdConnect => bearer = true,
+            types::SecurityScheme::ApiKey { .. } => api_key = true,
+        }
+    }
+    if !(bearer || basic || api_key) {
+        return Ok(None);
+    }
+
+    let visibility = ctx.config.visibility;
+    let derive = quote!((
+        ::std::fmt::Debug,
+        ::std::clone::Clone,
+        ::core::cmp::PartialEq,
+        ::core::cmp::Eq
+    ));
+    let middleware_derive = match ctx.config.debug_secrets {
+        true => derive.clone(),
+        false => quote!((::std::clone::Clone, ::core::cmp::PartialEq, ::core::cmp::Eq)),
+    };
+    let mut e = EnumBuilder::new("SecurityMiddleware")
+        .visibility(visibility)
+        .attr_with_input("derive", middleware_derive)?;
+    let mut match_arms = Vec::new();
+    let mut debug_arms = Vec::new();
+    if bearer {
+        e = e.tuple_variant("BearerToken", vec![ctx.cm.type_string()])?;
+        match_arms.push(quote!(
+            SecurityMiddleware::BearerToken(token) => {
+                rb.header(::http::header::AUTHORIZATION, ::std::format!("Bearer {token}"))
+            }
+        ));
+        debug_arms.push(quote!(
+            SecurityMiddleware::BearerToken(_) => f.debug_tuple("BearerToken").field(&"***").finish(),
+        ));
+    }
+    if api_key {
+        let location = EnumBuilder::new("ApiKeyLocation")
+            .visibility(visibility)
+            .attr_with_input("derive", derive)?
+            .unit_variant("Query")?
+            .unit_variant("Header")?
+            .unit_variant("Cookie")?
+            .build()?;
+        let location = ctx.m.insert_enum(location)?;
+        let string = ctx.cm.type_string();
+        e = e.struct_variant("ApiKey", |b| {
+            b.field("name", string.clone())?
+                .field("key", string)?
+                .field("location", location)
+        })?;
+        match_arms.push(quote!(
+            SecurityMiddleware::ApiKey { name, key, location } => match location {
+                ApiKeyLocation::Query => {
+                    let uri = rb.uri_ref().map(|uri| uri.to_string()).unwrap_or_default();
+                    let separator = if uri.contains('?') { '&' } else { '?' };
+                    let query = ::url::form_urlencoded::Serializer::new(::std::string::String::new())
+                        .append_pair(name, key)
+                        .finish();
+                    rb.uri(::std::format!("{uri}{separator}{query}"))
+                }
+                ApiKeyLocation::Header => rb.header(name.as_str(), key.as_str()),
+                ApiKeyLocation::Cookie => {
+                    rb.header(::http::header::COOKIE, ::std::format!("{name}={key}"))
+                }
+            }
+        ));
+        debug_arms.push(quote!(
+            SecurityMiddleware::ApiKey { name, location, .. } => f
+                .debug_struct("ApiKey")
+                .field("name", name)
+                .field("key", &"***")
+                .field("location", location)
+                .finish(),
+        ));
+    }
+    if basic {
+        let string = ctx.cm.type_string();
+        e = e.struct_variant("BasicAuth", |b| {
+            b.field("username", string.clone())?
+                .field("password", string)
+        })?;
+        match_arms.push(quote!(
+            SecurityMiddleware::BasicAuth { username, password } => {
+                let credentials = base64_encode(::std::format!("{username}:{password}").as_bytes());
+                rb.header(::http::header::AUTHORIZATION, ::std::format!("Basic {credentials}"))
+            }
+        ));
+        debug_arms.push(quote!(
+            SecurityMiddleware::BasicAuth { username, .. } => f
+                .debug_struct("BasicAuth")
+                .field("username", username)
+                .field("password", &"***")
+                .finish(),
+        ));
+        ctx.m.insert_raw_item(
+            "base64_encode",
+            quote!(
+                fn base64_encode(bytes: &[u8]) -> ::std::string::String {
+                    const ALPHABET: &[u8; 64] =
+                        b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+                    let mut encoded =
+                        ::std::string::String::with_capacity(bytes.len().div_ceil(3) * 4);
+                    for chunk in bytes.chunks(3) {
+                        let b = [
+                            chunk[0],
+                            chunk.get(1).copied().unwrap_or(0),
+                            chunk.get(2).copied().unwrap_or(0),
+                        ];
+                        let n = (b[0] as u32) << 16 | (b[1] as u32) << 8 | b[2] as u32;
+                        // a chunk of n bytes yields n + 1 characters, padded to 4
+                        for i in 0..4 {
+                            if i <= chunk.len() {
+                                encoded.push(ALPHABET[(n >> (18 - 6 * i) & 0x3f) as usize] as char);
+                            } else {
+                                encoded.push('=');
+                            }
+                        }
+                    }
+                    encoded
+                }
+            ),
+        )?;
+    }
+    let security_middleware = ctx.m.insert_enum(e.build()?)?;
+    if !ctx.config.debug_secrets {
+        let fmt_fn = FunctionBuilder::new("fmt".to_string(), ctx.cm.type_fmt_result())
+            .param("self".to_string(), ctx.cm.type_ref_self())
+            .param("f".to_string(), ctx.cm.type_ref_mut_fmt_formatter())
+            .body(quote!(
+                match self {
+                    #(#debug_arms)*
+                }
+            ))
+            .build();
+        ctx.m.insert_implementation(
+            ImplementationBuilder::new_trait(ctx.cm.trait_debug()?, security_middleware.clone())
+                .function(fmt_fn)
+                .build(),
+        )?;
+    }
+
+    let request_builder = ctx.cm.type_external("::http::request::Builder");
+    let auth_type = TypeRef::Reference {
+        referenced_type: Box::new(security_middleware.clone()),
+        mutable: false,
+        lifetime: None,
+    };
+    let apply_security =
+        FunctionBuilder::new("apply_security".to_string(), request_builder.clone())
+            .visibility(visibility)
+            .param("rb".to_string(), request_builder)
+            .param("auth".to_string(), auth_type)
+            .body(quote!(
+                match auth {
+                    #(#match_arms)*
+                }
+            ))
+            .build();
+    ctx.m.insert_implementation(
+        ImplementationBuilder::new_inherent(security_middleware.clone())
+            .function(apply_security)
+            .build(),
+    )?;
+    Ok(Some(security_middleware))
+}
+
+/// Builds the `apply_{operation}_security()` function adding the client's
+/// credentials to a request of an operation with the given alternative
+/// security `requirements`. The first requirement satisfied by the
+/// credentials is applied. Empty requirements (like `security: []` or
+/// `security: [{}]`) make authentication optional, so without a satisfied
+/// requirement the request is left unchanged. Operations requiring no
+/// authentication at all never change the request.
+fn build_operation_security<S: Spec>(
+    ctx: &mut Context<S>,
+    names: &OperationNames,
+    security_schemes: &HashMap<String, types::SecurityScheme>,
+    requirements: &[types::SecurityRequirement],
+) -> anyhow::Result<Function> {
+    let conditions = requirements
+        .iter()
+        .filter(|requirement| !requirement.is_empty())
+        .map(|requirement| {
+            // a requirement's schemes must all be satisfied, but the client
+            // holds a single credential only
+            let scheme_conditions = requirement
+                .keys()
+                .map(|scheme_name| security_scheme_condition(security_schemes, scheme_name, names));
+            quote!(#(#scheme_conditions)&&*)
+        })
+        .collect::<Vec<_>>();
+
+    let request_builder = ctx.cm.type_external("::http::request::Builder");
+    let body = match conditions.is_empty() {
+        true => quote!(rb),
+        false => quote!(
+            let ::std::option::Option::Some(auth) = &self.auth else {
+                return rb;
+            };
+            #(
+                if #conditions {
+                    return SecurityMiddleware::apply_security(rb, auth);
+                }
+            )*
+            rb
+        ),
+    };
+    let applier = FunctionBuilder::new(
+        format!("apply_{}_security", names.fn_name),
+        request_builder.clone(),
+    )
+    .visibility(ctx.config.visibility)
+    .param("self".to_string(), ctx.cm.type_ref_self())
+    .param("rb".to_string(), request_builder)
+    .body(body)
+    .build();
+    Ok(applier)
+}
+
+/// Writes the condition under which the credential `auth` satisfies the
+/// security scheme `scheme_name`
+fn security_scheme_condition(
+    security_schemes: &HashMap<String, types::SecurityScheme>,
+    scheme_name: &str,
+    names: &OperationNames,
+) -> TokenStream {
+    match security_schemes.get(scheme_name) {
+        Some(types::SecurityScheme::Http { scheme }) if scheme.eq_ignore_ascii_case("bearer") => {
+            quote!(::std::matches!(auth, SecurityMiddleware::BearerToken(_)))
+        }
+        Some(types::SecurityScheme::OAuth2 | types::SecurityScheme::OpenIdConnect) => {
+            quote!(::std::matches!(auth, SecurityMiddleware::BearerToken(_)))
+        }
+        Some(types::SecurityScheme::Http { scheme }) if scheme.eq_ignore_ascii_case("basic") => {
+            quote!(::std::matches!(auth, SecurityMiddleware::BasicAuth { .. }))
+        }
+        Some(types::SecurityScheme::ApiKey { name, location }) => {
+            let location = format_ident!("{location:?}");
+            quote!(::std::matches!(
+                auth,
+                SecurityMiddleware::ApiKey {
+                    name,
+                    location: ApiKeyLocation::#location,
+                    ..
+                } if name == #name
+            ))
+        }
+        Some(types::SecurityScheme::Http { .. }) => quote!(false),
+        None => {
+            log::warn!(
+                "operation '{}' requires undefined security scheme '{scheme_name}'",
+                names.fn_name
+            );
+            quote!(false)
+        }
+    }
+}
+
+/// Creates the `std::fmt::Display` implementation for `ClientImpl`, printing
+/// its configuration
+fn build_client_display<S: Spec>(
+    ctx: &Context<S>,
+    client_struct: TypeRef,
+) -> anyhow::Result<Implementation> {
+    let fmt_fn = FunctionBuilder::new("fmt".to_string(), ctx.cm.type_fmt_result())
+        .param("self".to_string(), ctx.cm.type_ref_self())
+        .param("f".to_string(), ctx.cm.type_ref_mut_fmt_formatter())
+        .body(quote!(::std::write!(
+            f,
+            "ClientImpl {{ base_url: {}, timeout: {:?} }}",
+            self.base_url,
+            self.timeout
+        )))
+        .build();
+    let display = ctx.cm.trait_display()?;
+    Ok(ImplementationBuilder::new_trait(display, client_struct)
+        .function(fmt_fn)
+        .build())
+}
+
+/// Creates the `ClientImpl::url_for()` function resolving an operation's
+/// path against the client's base URL. Paths in the spec start with `/`,
+/// but are relative to the server URL, whose base path (like `/v1`) must
+/// be kept, which `Url::join()` only does for base paths ending in `/`.
+/// The generated code then requires the `url` crate.
+fn build_client_url_for<S: Spec>(
+    ctx: &Context<S>,
+    client_struct: TypeRef,
+) -> anyhow::Result<Implementation> {
+    log::info!("the generated ClientImpl::url_for() requires the 'url' crate");
+    let url_result = ctx
+        .cm
+        .type_external("::std::result::Result<::url::Url, ::url::ParseError>");
+    let path_type = TypeRef::Reference {
+        referenced_type: Box::new(ctx.cm.type_str()),
+        mutable: false,
+        lifetime: None,
+    };
+    let url_for_fn = FunctionBuilder::new("url_for".to_string(), url_result)
+        .visibility(ctx.config.visibility)
+        .param("self".to_string(), ctx.cm.type_ref_self())
+        .param("path".to_string(), path_type)
+        .body(quote!(
+            let mut base_url = ::url::Url::parse(&self.base_url)?;
+            if !base_url.path().ends_with('/') {
+                let base_path = ::std::format!("{}/", base_url.path());
+                base_url.set_path(&base_path);
+            }
+            base_url.join(path.trim_start_matches('/'))
+        ))
+        .build();
+    Ok(ImplementationBuilder::new_inherent(client_struct)
+        .function(url_for_fn)
+        .build())
+}
+
+/// Compares the parameters in the path template with the path parameters
+/// declared by the operation and its path item. Yields the template
+/// parameters that are not declared, as their placeholders could not be
+/// filled in when building request URLs otherwise (see
+/// [ApiConfig::strict_path_params]). Declared path parameters missing in
+/// the template are warned about, as they would be ignored.
+fn check_path_params<S: Spec>(
+    config: &ApiConfig,
+    path: &str,
+    path_item: &S::PathItem,
+    path_op: &S::Operation,
+) -> anyhow::Result<Vec<String>> {
+    let path_params = path_item
+        .parameters()
+        .chain(path_op.parameters())
+        .map(|param| param.resolve_fully())
+        .filter(|param| param.in_() == ParameterLocation::Path)
+        .map(|param| param.name().to_string())
+        .collect::<HashSet<_>>();
+    let template_params = url_template_variables(path)
+        .into_iter()
+        .filter(|name| !name.is_empty())
+        .collect::<Vec<_>>();
+
+    let mut unused = path_params
+        .iter()
+        .filter(|name| !template_params.contains(name))
+        .collect::<Vec<_>>();
+    unused.sort();
+    for name in unused {
+        log::warn!("path parameter '{name}' does not appear in path template '{path}'");
+    }
+
+    let undeclared = template_params
+        .into_iter()
+        .filter(|name| !path_params.contains(name))
+        .collect::<Vec<_>>();
+    if !undeclared.is_empty() && config.strict_path_params {
+        return Err(anyhow!(
+            "parameters {undeclared:?} in path template '{path}' are not declared as path parameters"
+        ));
+    }
+    for name in &undeclared {
+        log::warn!(
+            "parameter '{name}' in path template '{path}' is not declared as path parameter, adding it as String parameter"
+        );
+    }
+
+    // with all template parameters accounted for, only malformed templates
+    // are left to be reported
+    let is_declared =
+        |name: &str| path_params.contains(name) || undeclared.iter().any(|u| u == name);
+    if let Err(messages) = translate::validate_path_template(path, is_declared) {
+        for message in messages {
+            log::warn!("{message}");
+        }
+    }
+    Ok(undeclared)
+}
+
+/// Finds the paths of the spec whose templates differ from an earlier
+/// path's only in the names of their parameters (see
+/// [translate::path_template_shape]). These match the same requests, so
+/// only the earlier path is generated, and the others are returned (see
+/// [ApiConfig::strict_duplicate_paths]).
+fn duplicate_path_templates<S: Spec>(
+    config: &ApiConfig,
+    spec: &S,
+) -> anyhow::Result<HashSet<String>> {
+    let mut paths_by_shape: HashMap<String, String> = HashMap::new();
+    let mut duplicates = HashSet::new();
+    for (path, _) in spec.paths() {
+        let shape = translate::path_template_shape(&path);
+        match paths_by_shape.get(&shape) {
+            Some(first) if config.strict_duplicate_paths => {
+                return Err(anyhow!(
+                    "paths '{first}' and '{path}' differ only in the names of their parameters"
+                ));
+            }
+            Some(first) => {
+                log::warn!(
+                    "paths '{first}' and '{path}' differ only in the names of their parameters, generating '{first}' only"
+                );
+                duplicates.insert(path);
+            }
+            None => {
+                paths_by_shape.insert(shape, path);
+            }
+        }
+    }
+    Ok(duplicates)
+}
+
+/// Yields the `OperationInfo` struct expression describing the given operation
+fn operation_info_of<S: Spec>(
+    names: &OperationNames,
+    path: &str,
+    method: &http::Method,
+    path_op: &S::Operation,
+) -> TokenStream {
+    let name = &names.fn_name;
+    let method = method.as_str();
+    let summary = path_op.summary().unwrap_or_default();
+    // operations may have several tags, we list the first one only
+    let tag = path_op.tags().next().unwrap_or_default();
+    quote!(OperationInfo {
+        name: #name,
+        method: #method,
+        path: #path,
+        summary: #summary,
+        tag: #tag,
+    })
+}
+
+/// Creates the `OperationInfo` struct and the `operations()` function
+/// listing all operations of the API, so they can be iterated at runtime
+fn build_operations_listing<S: Spec>(
+    ctx: &mut Context<S>,
+    operation_infos: Vec<TokenStream>,
+) -> anyhow::Result<()> {
+    let static_str = quote!(&'static str);
+    let visibility = ctx.config.visibility;
+    let mut builder = StructBuilder::new("OperationInfo")
+        .visibility(visibility)
+        .attr_with_input("derive", quote!((Debug, Clone, Copy, PartialEq, Eq)))?;
+    for field_name in ["name", "method", "path", "summary", "tag"] {
+        builder = builder.field_with_input(field_name, static_str.clone())?;
+    }
+    ctx.m.insert_struct(builder.build()?)?;
+
+    ctx.m.insert_raw_item(
+        "operations",
+        quote!(
+            #visibility fn operations() -> &'static [OperationInfo] {
+                &[#(#operation_infos),*]
+            }
+        ),
+    )?;
+    Ok(())
+}
+
+/// Creates a `{Operation}Links` trait for each operation whose success
+/// response declares `links` (see [ApiConfig::link_helpers]), implemented
+/// for the response's type. Links that cannot be followed with what the
+/// response holds are skipped with a warning.
+fn build_link_helpers<S: Spec>(
+    ctx: &mut Context<S>,
+    spec: &S,
+    operation_types: &[OperationTypes],
+    operation_routes: &[(String, http::Method, String)],
+) -> anyhow::Result<()> {
+    let types_of = |path: &str, method: &http::Method| {
+        operation_types_of(operation_types, operation_routes, path, method)
+    };
+    // the operations links may point to, by their operationId
+    let mut targets = HashMap::new();
+    for (path, path_item) in spec.paths() {
+        for (method, path_op) in path_item.operations_iter() {
+            if let (Some(operation_id), Some(types)) =
+                (path_op.operation_id(), types_of(&path, &method))
+            {
+                targets.insert(operation_id.to_string(), types);
+            }
+        }
+    }
+
+    let visibility = ctx.config.visibility;
+    let mut has_helpers = false;
+    for (path, path_item) in spec.paths() {
+        for (method, path_op) in path_item.operations_iter() {
+            let Some(source) = types_of(&path, &method) else {
+                continue;
+            };
+            let (success_responses, _) = partition_responses::<S>(&path_op);
+            let mut links = success_responses
+                .iter()
+                .flat_map(|(_, response)| response.resolve().resolve_fully().links())
+                .collect::<Vec<_>>();
+            if links.is_empty() {
+                continue;
+            }
+            if success_responses.len() > 1 {
+                log::warn!(
+                    "not generating link helpers for {method} {path}, which has several success responses"
+                );
+                ctx.stats.skipped += 1;
+                continue;
+            }
+            if !is_serializable_struct(&source.response) {
+                log::warn!(
+                    "not generating link helpers for {method} {path}, whose response is not an object"
+                );
+                ctx.stats.skipped += 1;
+                continue;
+            }
+            links.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+            let response_ts = codewriter::syn_type_name_of(&source.response)?;
+            let mut fn_names = HashSet::new();
+            let mut signatures = Vec::new();
+            let mut bodies = Vec::new();
+            for (link_name, link) in links {
+                let Some((operation_id, target)) = link
+                    .operation_id
+                    .as_ref()
+                    .and_then(|id| Some((id, *targets.get(id)?)))
+                else {
+                    log::warn!(
+                        "not generating a helper for link '{link_name}' of {method} {path}, only links to an existing operationId are supported"
+                    );
+                    ctx.stats.skipped += 1;
+                    continue;
+                };
+                let pointers = match link_pointers(&link, target) {
+                    Ok(pointers) => pointers,
+                    Err(reason) => {
+                        log::warn!(
+                            "not generating a helper for link '{link_name}' of {method} {path}: {reason}"
+                        );
+                        ctx.stats.skipped += 1;
+                        continue;
+                    }
+                };
+                let Some(fn_name) = translate::operation_id_to_rust_fn_name(
+                    &format!("follow_{link_name}"),
+                    ctx.config.non_ascii_names,
+                    &fn_names,
+                ) else {
+                    continue;
+                };
+                fn_names.insert(fn_name.clone());
+                let fn_ident = format_ident!("{fn_name}");
+                let target_fn_ident = format_ident!("{}", target.names.fn_name);
+                let ok_ts = codewriter::syn_type_name_of(&target.response)?;
+                let error_ts = codewriter::syn_type_name_of(&target.error)?;
+                let doc = format!(
+                    " Calls `{operation_id}` with the parameters taken from this response (link `{link_name}`)"
+                );
+                let signature = quote!(
+                    fn #fn_ident(&self, client: &impl Client) -> ::std::result::Result<#ok_ts, #error_ts>
+                );
+                signatures.push(quote!(
+                    #[doc = #doc]
+                    #signature;
+                ));
+                bodies.push(quote!(
+                    #signature {
+                        let body = ::serde_json::to_value(self)
+                            .map_err(|e| <#error_ts>::OtherError(::std::boxed::Box::new(e)))?;
+                        client.#target_fn_ident(
+                            #(link_value(&body, #pointers).map_err(<#error_ts>::OtherError)?),*
+                        )
+                    }
+                ));
+            }
+            if signatures.is_empty() {
+                continue;
+            }
+
+            let trait_name = source.names.type_name.clone() + "Links";
+            if ctx.m.contains_item(&trait_name) {
+                log::warn!("not generating link helpers '{trait_name}', name is taken");
+                continue;
+            }
+            let trait_ident = format_ident!("{trait_name}");
+            let doc = format!(" Follows the links of the response of `{method} {path}`");
+            ctx.m.insert_raw_item(
+                &trait_name,
+                quote!(
+                    #[doc = #doc]
+                    #visibility trait #trait_ident {
+                        #(#signatures)*
+                    }
+
+                    impl #trait_ident for #response_ts {
+                        #(#bodies)*
+                    }
+                ),
+            )?;
+            has_helpers = true;
+        }
+    }
+
+    if has_helpers {
+        ctx.m.insert_raw_item(
+            "link_value",
+            quote!(
+                /// Takes the value at a JSON pointer out of a response body,
+                /// to pass it to a linked operation
+                fn link_value<T: ::serde::de::DeserializeOwned>(
+                    body: &::serde_json::Value,
+                    pointer: &str,
+                ) -> ::std::result::Result<T, ::std::boxed::Box<dyn ::std::error::Error>>
+                {
+                    let value = body
+                        .pointer(pointer)
+                        .ok_or_else(|| format!("no value at '{pointer}' in the response body"))?;
+                    Ok(T::deserialize(value)?)
+                }
+            ),
+        )?;
+    }
+    Ok(())
+}
+
+/// The types of the operation at `method` and `path`, if it got a client
+/// function
+fn operation_types_of<'a>(
+    operation_types: &'a [OperationTypes],
+    operation_routes: &[(String, http::Method, String)],
+    path: &str,
+    method: &http::Method,
+) -> Option<&'a OperationTypes> {
+    let (fn_name, ..) = operation_routes
+        .iter()
+        .find(|(_, m, p)| p == path && m == method)?;
+    operation_types
+        .iter()
+        .find(|types| &types.names.fn_name == fn_name)
+}
+
+/// Creates the `ClientExists` trait with the existence checks of
+/// [ApiConfig::emit_exists_helpers], unless no operation qualifies
+fn build_exists_helpers<S: Spec>(
+    ctx: &mut Context<S>,
+    spec: &S,
+    client_trait: &Trait,
+    operation_types: &[OperationTypes],
+    operation_routes: &[(String, http::Method, String)],
+) -> anyhow::Result<()> {
+    let types_of = |path: &str, method: &http::Method| {
+        operation_types_of(operation_types, operation_routes, path, method)
+    };
+    let not_found = ctx
+        .config
+        .naming()
+        .status_to_variant_name(StatusSpec::ClientError(404));
+    let not_found_ident = format_ident!("{not_found}");
+    let mut taken = client_trait
+        .associated_functions
+        .iter()
+        .map(|function| function.name().to_string())
+        .collect::<HashSet<_>>();
+
+    let mut helpers = Vec::new();
+    for (path, path_item) in spec.paths() {
+        let operations = path_item.operations_iter().collect::<Vec<_>>();
+        let checking_operation = |method: http::Method| {
+            let (_, path_op) = operations.iter().find(|(m, _)| *m == method)?;
+            let types = types_of(&path, &method)?;
+            if !is_existence_check::<S>(types, path_op) {
+                log::debug!("{method} {path} does not tell reliably whether a resource exists");
+                return None;
+            }
+            Some((method, types))
+        };
+        let Some((_, get_types)) = checking_operation(http::Method::GET) else {
+            continue;
+        };
+        // HEAD responses carry no body, so they are cheaper to check with
+        let (method, types) =
+            checking_operation(http::Method::HEAD).unwrap_or((http::Method::GET, get_types));
+        let Some(function) = client_trait
+            .associated_functions
+            .iter()
+            .find(|function| function.name() == types.names.fn_name)
+        else {
+            continue;
+        };
+        let Some(param) = function
+            .function_params_iter()
+            .find(|param| param.name != "self")
+        else {
+            continue;
+        };
+
+        let get_fn_name = &get_types.names.fn_name;
+        let fn_name = match get_fn_name.strip_suffix("_get") {
+            Some(base) => format!("{base}_exists"),
+            None => format!("{get_fn_name}_exists"),
+        };
+        if !taken.insert(fn_name.clone()) {
+            log::warn!("not generating existence check '{fn_name}' for {path}, name is taken");
+            continue;
+        }
+        let fn_ident = format_ident!("{fn_name}");
+        let call_ident = format_ident!("{}", types.names.fn_name);
+        let param_ident = format_ident!("{}", param.name);
+        let param_ts = codewriter::syn_type_name_of(&param.type_)?;
+        let error_ts = codewriter::syn_type_name_of(&types.error)?;
+        let doc = format!(
+            " Whether the resource at `{path}` exists, by `{method} {path}`: `true` on success, `false` on status `404`"
+        );
+        helpers.push(quote!(
+            #[doc = #doc]
+            fn #fn_ident(&self, #param_ident: #param_ts) -> ::std::result::Result<bool, #error_ts> {
+                match self.#call_ident(#param_ident) {
+                    ::std::result::Result::Ok(_) => ::std::result::Result::Ok(true),
+                    ::std::result::Result::Err(#error_ts::#not_found_ident(..)) => {
+                        ::std::result::Result::Ok(false)
+                    }
+                    ::std::result::Result::Err(e) => ::std::result::Result::Err(e),
+                }
+            }
+        ));
+    }
+    if helpers.is_empty() {
+        return Ok(());
+    }
+
+    let visibility = ctx.config.visibility;
+    ctx.m.insert_raw_item(
+        "ClientExists",
+        quote!(
+            /// Checks whether resources exist, by calling the operations
+            /// reading them. Implemented for all clients.
+            #visibility trait ClientExists: Client {
+                #(#helpers)*
+            }
+
+            impl<T: Client + ?::std::marker::Sized> ClientExists for T {}
+        ),
+    )?;
+    Ok(())
+}
+
+/// Whether an operation tells reliably if the resource at its path exists:
+/// it takes a single path parameter only, has a single success response
+/// and declares a `404` response. The responses of other operations are
+/// ambiguous, like of those responding with `404` for other reasons, too.
+fn is_existence_check<S: Spec>(types: &OperationTypes, path_op: &S::Operation) -> bool {
+    let single_path_param = matches!(
+        types.params.as_deref(),
+        Some([(_, Some(ParameterLocation::Path))])
+    );
+    let (success_responses, _) = partition_responses::<S>(path_op);
+    let declares_not_found = path_op
+        .responses()
+        .any(|(status_spec, _)| status_spec == StatusSpec::ClientError(404));
+    single_path_param && success_responses.len() == 1 && declares_not_found
+}
+
+/// The JSON pointers into the response body a link takes the linked
+/// operation's parameters from, in the order of the client function's
+/// parameters. Fails if the function has parameters the link cannot pass.
+fn link_pointers(link: &Link, target: &OperationTypes) -> Result<Vec<String>, String> {
+    let Some(params) = &target.params else {
+        return Err("the linked operation's parameters are bundled into a struct".to_string());
+    };
+    let mut pointers = Vec::new();
+    for (spec_name, location) in params {
+        let Some(location) = location else {
+            return Err("the linked operation takes a request body".to_string());
+        };
+        // names may be qualified with the location, like `path.id`
+        let value = link.parameters.iter().find_map(|(name, value)| {
+            let (qualifier, name) = match name.split_once('.') {
+                Some((qualifier, name))
+                    if ["path", "query", "header", "cookie"].contains(&qualifier) =>
+                {
+                    (Some(qualifier), name)
+                }
+                _ => (None, name.as_str()),
+            };
+            let matches = name == spec_name
+                && qualifier
+                    .is_none_or(|q| q == translate::parameter_location_to_suffix(*location));
+            matches.then_some(value)
+        });
+        let Some(value) = value else {
+            return Err(format!("parameter '{spec_name}' is not passed"));
+        };
+        match value
+            .as_str()
+            .and_then(|expression| expression.strip_prefix("$response.body#"))
+        {
+            Some(pointer) => pointers.push(pointer.to_string()),
+            None => {
+                return Err(format!(
+                    "'{value}' is not supported, only '$response.body#/...' expressions are"
+                ));
+            }
+        }
+    }
+    Ok(pointers)
+}
+
+/// Whether the type is a struct (de)serialized by serde, like those
+/// generated for object schemas
+fn is_serializable_struct(type_ref: &TypeRef) -> bool {
+    match type_ref {
+        TypeRef::Indirection(indirection) => match &*indirection.borrow() {
+            Indirection::Resolved(type_ref) => is_serializable_struct(type_ref),
+            Indirection::Stub(_) => false,
+        },
+        TypeRef::Alias(alias) => is_serializable_struct(alias.target()),
+        TypeRef::Struct(s) => s.attr_iter().any(|attr| {
+            attr.path().as_str() == "derive" && attr.input().to_string().contains("Serialize")
+        }),
+        _ => false,
+    }
+}
+
+/// Creates the aliases for the types of each operation in the form
+/// configured by [ApiConfig::operation_type_aliases]. Aliases whose name is
+/// taken by another item are skipped with a warning.
+fn build_operation_type_aliases<S: Spec>(
+    ctx: &mut Context<S>,
+    operation_types: Vec<OperationTypes>,
+) -> anyhow::Result<()> {
+    if ctx.config.operation_type_aliases == OperationTypeAliases::None {
+        return Ok(());
+    }
+    let visibility = ctx.config.visibility;
+    for types in operation_types {
+        let aliases = [
+            ("Request", &types.request),
+            ("Response", &types.response),
+            ("Error", &types.error),
+        ];
+        match ctx.config.operation_type_aliases {
+            OperationTypeAliases::None => unreachable!("returned early"),
+            OperationTypeAliases::Modules => {
+                let module_name = &types.names.fn_name;
+                if ctx.m.contains_item(module_name) {
+                    log::warn!(
+                        "not generating module '{module_name}' for the operation's types, name is taken"
+                    );
+                    continue;
+                }
+                let mut alias_decls = Vec::new();
+                for (alias_name, target) in aliases {
+                    let alias_ident = format_ident!("{alias_name}");
+                    let target = codewriter::syn_type_name_of(target)?;
+                    alias_decls.push(quote!(pub type #alias_ident = #target;));
+                }
+                let module_ident = format_ident!("{module_name}");
+                ctx.m.insert_raw_item(
+                    module_name,
+                    quote!(
+                        #visibility mod #module_ident {
+                            use super::*;
+                            #(#alias_decls)*
+                        }
+                    ),
+                )?;
+            }
+            OperationTypeAliases::Prefixed => {
+                for (suffix, target) in aliases {
+                    let alias_name = types.names.type_name.clone() + suffix;
+                    if target.name() == alias_name {
+                        continue;
+                    }
+                    if ctx.m.contains_item(&alias_name) {
+                        log::warn!(
+                            "not generating alias '{alias_name}' for '{}', name is taken",
+                            target.name()
+                        );
+                        continue;
+                    }
+                    ctx.m.insert_type_alias_with_visibility(
+                        &alias_name,
+                        visibility,
+                        target.clone(),
+                    )?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Compares the operations declared in `spec` with the functions of the
+/// generated `Client` trait, so that operations cannot get lost silently,
+/// be it to a method we do not support or to a bug in the generator.
+/// Yields a description of each operation without a function of its own,
+/// and of each function shared by several operations. The operations of
+/// `duplicate_paths` are left out on purpose, and reported already.
+fn operation_function_discrepancies<S: Spec>(
+    spec: &S,
+    client_trait: &Trait,
+    operation_routes: &[(String, http::Method, String)],
+    duplicate_paths: &HashSet<String>,
+) -> Vec<String> {
+    let mut discrepancies: Vec<String> = spec
+        .unsupported_operations()
+        .into_iter()
+        .map(|(path, method)| {
+            format!(
+                "{} {path} has no client function, as method '{method}' is not supported",
+                method.to_uppercase()
+            )
+        })
+        .collect();
+
+    let routed: HashSet<(&str, &http::Method)> = operation_routes
+        .iter()
+        .map(|(_, method, path)| (path.as_str(), method))
+        .collect();
+    for (path, path_item) in spec.paths() {
+        if duplicate_paths.contains(&path) {
+            continue;
+        }
+        for (method, _) in path_item.operations_iter() {
+            if !routed.contains(&(path.as_str(), &method)) {
+                discrepancies.push(format!("{method} {path} has no client function"));
+            }
+        }
+    }
+
+    let mut fn_counts: HashMap<String, usize> = HashMap::new();
+    for function in client_trait.function_iter() {
+        *fn_counts.entry(function.name().to_string()).or_default() += 1;
+    }
+    let mut operations_by_fn: Vec<(&str, Vec<String>)> = Vec::new();
+    for (fn_name, method, path) in operation_routes {
+        let operation = format!("{method} {path}");
+        match operations_by_fn
+            .iter_mut()
+            .find(|(name, _)| name == fn_name)
+        {
+            Some((_, operations)) => operations.push(operation),
+            None => operations_by_fn.push((fn_name, vec![operation])),
+        }
+    }
+    for (fn_name, operations) in operations_by_fn {
+        let fn_count = fn_counts.get(fn_name).copied().unwrap_or_default();
+        if fn_count == 0 {
+            discrepancies.push(format!(
+                "{} has no client function, '{fn_name}' is missing from the trait",
+                operations.join(", ")
+            ));
+        } else if operations.len() > 1 || fn_count > 1 {
+            discrepancies.push(format!(
+                "{} share the client function '{fn_name}', declared {fn_count} time(s)",
+                operations.join(", ")
+            ));
+        }
+    }
+    discrepancies
+}
+
+/// Creates the `lookup_operation()` function, which finds the name of the
+/// function generated for a method and path template, and its inverse
+/// `operation_of()`. Path templates must match the spec's exactly, concrete
+/// paths like `/pet/42` are not matched against the templates.
+fn build_operation_lookup<S: Spec>(
+    ctx: &mut Context<S>,
+    operation_routes: &[(String, http::Method, String)],
+) -> anyhow::Result<()> {
+    let visibility = ctx.config.visibility;
+    let lookup_arms = operation_routes.iter().map(|(fn_name, method, path)| {
+        let method = method.as_str();
+        quote!((#method, #path) => ::std::option::Option::Some(#fn_name),)
+    });
+    let operation_of_arms = operation_routes.iter().map(|(fn_name, method, path)| {
+        let method = codewriter::write_http_method(method);
+        quote!(#fn_name => ::std::option::Option::Some((#method, #path)),)
+    });
+
+    ctx.m.insert_raw_item(
+        "lookup_operation",
+        quote!(
+            #visibility fn lookup_operation(
+                method: &::http::Method,
+                path_template: &str,
+            ) -> ::std::option::Option<&'static str> {
+                match (method.as_str(), path_template) {
+                    #(#lookup_arms)*
+                    _ => ::std::option::Option::None,
+                }
+            }
+        ),
+    )?;
+    ctx.m.insert_raw_item(
+        "operation_of",
+        quote!(
+            #visibility fn operation_of(
+                function_name: &str,
+            ) -> ::std::option::Option<(::http::Method, &'static str)> {
+                match function_name {
+                    #(#operation_of_arms)*
+                    _ => ::std::option::Option::None,
+                }
+            }
+        ),
+    )?;
+    Ok(())
+}
+
+/// Creates the `server_url()` function yielding the URL of the first server
+/// in the spec. Each variable in the server's URL template becomes a
+/// parameter: variables restricted by `enum` take a generated
+/// `Server{Variable}` enum, the others take a `&str`. As these may make
+/// the URL invalid, the function yields a `Result` of a `url::Url`, so the
+/// generated code then requires the `url` crate.
+fn build_server_url<S: Spec>(ctx: &mut Context<S>, spec: &S) -> anyhow::Result<()> {
+    let Some(url_template) = spec.server_url() else {
+        return Ok(());
+    };
+    // relative URLs are resolved against the location of the spec, which
+    // we do not know here
+    if !url_template.contains("://") {
+        log::debug!("not generating server_url() for relative server URL '{url_template}'");
+        return Ok(());
+    }
+    log::info!("the generated server_url() requires the 'url' crate");
+
+    let visibility = ctx.config.visibility;
+    let variables = spec.server_variables();
+    let mut params = Vec::new();
+    let mut substitutions = Vec::new();
+    for name in url_template_variables(&url_template) {
+        let variable = variables.get(&name).ok_or(anyhow!(
+            "server URL '{url_template}' refers to undefined variable '{name}'"
+        ))?;
+        let param_ident = format_ident!("{}", translate::parameter_to_rust_fn_param(&name));
+        let placeholder = format!("{{{name}}}");
+        if variable.enum_.is_empty() {
+            params.push(quote!(#param_ident: &str));
+            substitutions.push(quote!(.replace(#placeholder, #param_ident)));
+            continue;
+        }
+
+        let enum_name = "Server".to_string() + &ctx.config.naming().schema_to_type_name(&name);
+        let enum_ident = format_ident!("{enum_name}");
+        let mut e = EnumBuilder::new(&enum_name)
+            .visibility(visibility)
+            .attr_with_input(
+                "derive",
+                quote!((
+                    ::std::fmt::Debug,
+                    ::std::clone::Clone,
+                    ::std::marker::Copy,
+                    ::core::cmp::PartialEq,
+                    ::core::cmp::Eq
+                )),
+            )?;
+        let mut match_arms = Vec::new();
+        let values = variable
+            .enum_
+            .iter()
+            .map(String::as_str)
+            .collect::<Vec<_>>();
+        let variant_names = translate::enum_values_to_rust_variant_names(&values);
+        for (value, variant_name) in values.iter().zip(variant_names) {
+            e = e.unit_variant(&variant_name)?;
+            let variant_ident = format_ident!("{variant_name}");
+            match_arms.push(quote!(#enum_ident::#variant_ident => #value));
+        }
+        ctx.m.insert_enum(e.build()?)?;
+        params.push(quote!(#param_ident: #enum_ident));
+        substitutions.push(quote!(.replace(#placeholder, match #param_ident { #(#match_arms),* })));
+    }
+
+    ctx.m.insert_raw_item(
+        "server_url",
+        quote!(
+            #visibility fn server_url(#(#params),*) -> ::std::result::Result<::url::Url, ::url::ParseError> {
+                let url = #url_template.to_string() #(#substitutions)*;
+                ::url::Url::parse(&url)
+            }
+        ),
+    )?;
+    Ok(())
+}
+
+/// Creates the `make_test_client!` macro, which expands to a `ClientImpl`
+/// for the given base URL. As `macro_rules!` paths are resolved at the call
+/// site, `ClientImpl` must be in scope where the macro is invoked.
+fn build_test_client_macro<S: Spec>(ctx: &mut Context<S>) -> anyhow::Result<()> {
+    let auth = ctx
+        .m
+        .contains_item("SecurityMiddleware")
+        .then(|| quote!(auth: ::std::option::Option::None,));
+    ctx.m.insert_raw_item(
+        "make_test_client",
+        quote!(
+            #[cfg(test)]
+            macro_rules! make_test_client {
+                ($base_url:literal) => {
+                    ClientImpl {
+                        base_url: ::std::string::ToString::to_string($base_url),
+                        timeout: ::std::option::Option::None,
+                        #auth
+                    }
+                };
+            }
+            // macros are not items, so they must be re-exported to be
+            // reachable by path
+            #[cfg(test)]
+            pub(crate) use make_test_client;
+        ),
+    )?;
+    Ok(())
+}
+
+/// The prefix of the environment variables the generated code reads, see
+/// [ApiConfig::env_prefix]
+fn env_prefix_of(config: &ApiConfig) -> String {
+    let prefix = config
+        .env_prefix
+        .clone()
+        .unwrap_or_else(|| module_name_of(config));
+    translate::schema_to_rust_constname(&prefix)
+}
+
+/// Creates the `examples` test module of [ApiConfig::emit_examples], whose
+/// tests are ignored, as they call the API at the base URL given by the
+/// environment
+fn build_examples_module<S: Spec>(
+    ctx: &mut Context<S>,
+    tests: Vec<TokenStream>,
+) -> anyhow::Result<()> {
+    let base_url_var = format!("{}_BASE_URL", env_prefix_of(&ctx.config));
+    let ignore_reason = format!("calls the API at {base_url_var}");
+    let missing_var = format!("{base_url_var} is not set");
+    let auth = ctx
+        .m
+        .contains_item("SecurityMiddleware")
+        .then(|| quote!(auth: ::std::option::Option::None,));
+    ctx.m.insert_raw_item(
+        "examples",
+        quote!(
+            #[cfg(test)]
+            mod examples {
+                fn client() -> super::ClientImpl {
+                    let base_url = ::std::env::var(#base_url_var).expect(#missing_var);
+                    super::ClientImpl {
+                        base_url,
+                        timeout: ::std::option::Option::None,
+                        #auth
+                    }
+                }
+
+                #(
+                    #[ignore = #ignore_reason]
+                    #tests
+                )*
+            }
+        ),
+    )?;
+    Ok(())
+}
+
+/// Creates the `generated_roundtrip_tests` module (see
+/// [ApiConfig::roundtrip_tests]), with a test per generated type deriving
+/// serde's traits and `PartialEq`, serializing a value to JSON and back.
+/// The value is deserialized from the schema's example, or else is the
+/// type's `Default`, or else is deserialized from a minimal JSON value
+/// built from the schema. Types none of these apply to are reported with
+/// the reason, and listed in the module's doc comment.
+fn build_roundtrip_tests<S: Spec>(ctx: &mut Context<S>, spec: &S) -> anyhow::Result<()> {
+    let mut examples = HashMap::new();
+    let mut minimal_values = HashMap::new();
+    for (name, ro_schema) in spec.schemata_iter() {
+        let RefOr::Object(schema) = ro_schema else {
+            continue;
+        };
+        let rust_name = ctx.mapping.schema_names[&name].clone();
+        if let Some(example) = schema.example() {
+            examples.insert(rust_name.clone(), example.dump());
+        }
+        minimal_values.insert(rust_name, minimal_json_value(&ctx.config, &schema, 0));
+    }
+
+    let mut tests = Vec::new();
+    let mut skipped = Vec::new();
+    for type_ref in ctx.m.type_iter() {
+        let type_ref = match type_ref {
+            TypeRef::Indirection(indirection) => match &*indirection.borrow() {
+                Indirection::Resolved(type_ref) => type_ref.clone(),
+                Indirection::Stub(_) => continue,
+            },
+            type_ref => type_ref.clone(),
+        };
+        let (name, attrs, fields) = match &type_ref {
+            TypeRef::Struct(s) => (s.name(), s.attr_iter().collect(), s.field_iter().collect()),
+            TypeRef::Enum(e) => (e.name(), e.attr_iter().collect::<Vec<_>>(), Vec::new()),
+            _ => continue,
+        };
+        let derives = attrs
+            .iter()
+            .filter(|attr| attr.path().as_str() == "derive")
+            .map(|attr| attr.input().to_string())
+            .collect::<String>();
+        let roundtrips = ["Serialize", "Deserialize", "PartialEq"]
+            .iter()
+            .all(|derive| derives.contains(derive));
+        if !roundtrips {
+            continue;
+        }
+
+        let one_way = fields.iter().flat_map(|f| f.attr_iter()).any(|attr| {
+            let input = attr.input().to_string();
+            input.contains("skip_serializing") || input.contains("skip_deserializing")
+        });
+        let value = if one_way {
+            Err("has fields serialized in one direction only".to_string())
+        } else if let Some(example) = examples.get(name.as_ref()) {
+            Ok(quote!(::serde_json::from_str(#example).expect("example deserializes")))
+        } else if ctx.m.contains_item(&format!("impl Default for {name}")) {
+            Ok(quote!(::std::default::Default::default()))
+        } else {
+            match minimal_values.remove(name.as_ref()) {
+                Some(Ok(value)) => {
+                    let value = value.dump();
+                    Ok(quote!(::serde_json::from_str(#value).expect("minimal value deserializes")))
+                }
+                Some(Err(reason)) => Err(reason),
+                None => Err("has no example, Default or schema to build a value from".to_string()),
+            }
+        };
+        let type_ident = format_ident!("{name}");
+        match value {
+            Ok(value) => {
+                let test_ident = format_ident!("roundtrip_{name}");
+                tests.push(quote!(
+                    #[test]
+                    fn #test_ident() {
+                        let value: #type_ident = #value;
+                        assert_roundtrip(value);
+                    }
+                ));
+            }
+            Err(reason) => {
+                log::warn!("not generating a round-trip test for {name}: it {reason}");
+                skipped.push(format!(" - `{name}` {reason}"));
+            }
+        }
+    }
+
+    let mut doc = vec![" Round-trip tests of the generated types".to_string()];
+    if !skipped.is_empty() {
+        doc.push(String::new());
+        doc.push(" Types without a round-trip test:".to_string());
+        doc.extend(skipped);
+    }
+    ctx.m.insert_raw_item(
+        "generated_roundtrip_tests",
+        quote!(
+            #(#[doc = #doc])*
+            #[cfg(test)]
+            mod generated_roundtrip_tests {
+                use super::*;
+
+                fn assert_roundtrip<T>(value: T)
+                where
+                    T: ::serde::Serialize + ::serde::de::DeserializeOwned + ::core::cmp::PartialEq,
+                {
+                    let json = ::serde_json::to_string(&value).expect("value serializes");
+                    let roundtripped: T = ::serde_json::from_str(&json).expect("JSON deserializes");
+                    assert!(value == roundtripped, "value does not round-trip via {json}");
+                }
+
+                #(#tests)*
+            }
+        ),
+    )?;
+    Ok(())
+}
+
+/// A minimal JSON value of the schema, for deserializing a value of the
+/// type generated for it: objects hold their required properties only,
+/// arrays are empty, and scalars are their zero value (or the first of the
+/// values allowed by `enum`, or the `default`). Yields the reason if no
+/// such value can be built with confidence.
+fn minimal_json_value(
+    config: &ApiConfig,
+    schema: &impl Schema,
+    depth: usize,
+) -> Result<json::JsonValue, String> {
+    // required properties referring back to the schema cannot end
+    if depth > 16 {
+        return Err("is nested too deeply".to_string());
+    }
+    if let Some(value) = schema.default_value() {
+        return Ok(value);
+    }
+    if let Some(value) = schema.const_value() {
+        return Ok(value);
+    }
+    if let Some(value) = schema.enum_().and_then(|values| values.into_iter().next()) {
+        return Ok(value);
+    }
+    if schema.extension_value("x-rust-type").is_some() {
+        return Err("has a type set by 'x-rust-type'".to_string());
+    }
+    if schema.all_of().is_some() || schema.any_of().is_some() || schema.one_of().is_some() {
+        return Err("is composed of other schemas".to_string());
+    }
+    let value = match schema.type_().unwrap_or_default().as_slice() {
+        [types::Type::Boolean] => json::JsonValue::Boolean(false),
+        [types::Type::Number] => 0.into(),
+        [types::Type::Array] => json::JsonValue::new_array(),
+        [types::Type::Null] => json::JsonValue::Null,
+        [types::Type::String] => match schema.format() {
+            Some(types::Format::Int64 | types::Format::UInt64) if config.stringified_integers => {
+                "0".into()
+            }
+            Some(types::Format::Byte | types::Format::Binary) => {
+                return Err("has binary content".to_string());
+            }
+            _ if config.typed_formats && is_decimal(schema) => "0".into(),
+            _ => "x".repeat(schema.min_length().unwrap_or_default()).into(),
+        },
+        _ => match type_kind_of(schema).map_err(|e| format!("has no type: {e}"))? {
+            TypeKind::Struct => {
+                let required = schema.required().unwrap_or_default();
+                // properties are ordered by name, as the schema's are not
+                let mut properties = schema.properties().into_iter().collect::<Vec<_>>();
+                properties.sort_by(|(lhs, _), (rhs, _)| lhs.cmp(rhs));
+                let mut object = json::JsonValue::new_object();
+                for (name, property) in properties {
+                    if !required.contains(&name.as_str()) {
+                        continue;
+                    }
+                    let property = match property.resolve() {
+                        RefOr::Object(property) => property,
+                        RefOr::Reference(_) => return Err(format!("has an unresolved '{name}'")),
+                    };
+                    object[name.as_str()] = minimal_json_value(config, &property, depth + 1)?;
+                }
+                object
+            }
+            TypeKind::HashMap(_) if schema.min_properties().is_some_and(|min| min > 0) => {
+                return Err("requires properties of its own".to_string());
+            }
+            TypeKind::HashMap(_) => json::JsonValue::new_object(),
+            TypeKind::Const(value) => value,
+            _ => json::JsonValue::Null,
+        },
+    };
+    Ok(value)
+}
+
+/// Yields the names of the `{variable}`s in a URL template, in the order of
+/// their first occurrence
+fn url_template_variables(url_template: &str) -> Vec<String> {
+    let mut names = Vec::new();
+    for segment in url_template.split('{').skip(1) {
+        let name = segment.split('}').next().unwrap_or_default().to_string();
+        if !names.contains(&name) {
+            names.push(name);
+        }
+    }
+    names
+}
+
+/// The schema a schema wraps in a single element `allOf` only to annotate
+/// it, like with a `description` or `nullable`. This common idiom stands
+/// for the referenced schema, so no type of its own is generated for it.
+/// Yields `None` for all other schemas.
+fn wrapped_reference<S: Spec>(schema: &S::Schema) -> Option<RefOr<S::Schema>> {
+    let [element] = <[_; 1]>::try_from(schema.all_of()?).ok()?;
+    let RefOr::Reference(_) = &element else {
+        return None;
+    };
+    let annotations_only = schema.type_().is_none()
+        && schema.properties().is_empty()
+        && schema.required().is_none_or(|required| required.is_empty())
+        && schema.enum_().is_none()
+        && schema.const_value().is_none()
+        && schema.any_of().is_none()
+        && schema.one_of().is_none()
+        && schema.extension_value("x-rust-type").is_none();
+    annotations_only.then_some(element)
+}
 
 /** The rust type we're converting a JSON schema item into */
 #[derive(Debug)]
@@ -249,11 +3273,20 @@ enum TypeKind {
     String,
     Json,
     HashMap(Box<TypeKind>),
+    /// a schema allowing a single scalar value via the 'const' keyword
+    Const(json::JsonValue),
 }
 
 fn type_kind_of(schema: &impl Schema) -> anyhow::Result<TypeKind> {
     let kind: TypeKind;
 
+    // we generate Rust constants for scalar const values only
+    if let Some(value) = schema.const_value()
+        && (value.is_string() || value.is_number() || value.is_boolean())
+    {
+        return Ok(TypeKind::Const(value));
+    }
+
     if let Some(types) = schema.type_() {
         if types.len() != 1 {
             kind = TypeKind::Json;
@@ -266,13 +3299,14 @@ fn type_kind_of(schema: &impl Schema) -> anyhow::Result<TypeKind> {
                     } else {
                         // without defined properties, we check the 'additionalProperties' and
                         // 'patternProperties' - if we have those, we'll create a HashMap
-                        if let BooleanOrSchema::Boolean(true) = schema.addtional_properties() {
-                            kind = TypeKind::HashMap(Box::new(TypeKind::Json))
-                        } else {
+                        if let BooleanOrSchema::Boolean(false) = schema.addtional_properties()
+                            && schema.pattern_properties().is_empty()
+                        {
                             // empty struct - because there are no properties
                             kind = TypeKind::Struct
+                        } else {
+                            kind = TypeKind::HashMap(Box::new(TypeKind::Json))
                         }
-                        // FIXME: we're ignoring patternProperties for now...
                     }
                 }
                 types::Type::String => {
@@ -301,16 +3335,36 @@ fn parse_schema<S: Spec>(
     name: Option<String>,
 ) -> anyhow::Result<TypeRef> {
     let kind = type_kind_of(schema)?;
+    log::trace!("parsing schema {name:?} as {kind:?}");
 
     match &kind {
         TypeKind::Struct => {
             let struct_name = name.as_ref().unwrap();
-            let mut b = StructBuilder::new(struct_name);
+            // secrets must not end up in logs, so structs holding any get
+            // a Debug impl redacting them instead of the derived one
+            let secret_properties: HashSet<String> = match ctx.config.debug_secrets {
+                true => HashSet::new(),
+                false => schema
+                    .properties()
+                    .into_iter()
+                    .filter(|(_, schema)| {
+                        schema
+                            .resolve()
+                            .as_object()
+                            .is_some_and(|s| is_secret_property(&s))
+                    })
+                    .map(|(name, _)| name)
+                    .collect(),
+            };
+            let derive_debug = secret_properties
+                .is_empty()
+                .then(|| quote!(::std::fmt::Debug,));
+            let mut b = StructBuilder::new(struct_name).visibility(ctx.config.visibility);
             b = b
                 .attr_with_input(
                     "derive",
                     quote::quote!((
-                        ::std::fmt::Debug,
+                        #derive_debug
                         ::serde::Serialize,
                         ::serde::Deserialize,
                         ::core::cmp::PartialEq
@@ -323,25 +3377,214 @@ fn parse_schema<S: Spec>(
                 .iter()
                 .map(|e| *e)
                 .collect();
-            for (name, schema) in schema.properties() {
-                let rust_name = translate::property_to_rust_fieldname(&name);
+            // properties become snake case fields, which serde renames back
+            // with a single `rename_all` if their names follow a common
+            // convention like camel case, and individually otherwise
+            let properties = schema.properties();
+            let mut property_names = properties.keys().collect::<Vec<_>>();
+            property_names.sort();
+            let mut field_names = HashMap::new();
+            for property_name in property_names {
+                let taken = field_names.values().cloned().collect::<HashSet<_>>();
+                let field_name = ctx
+                    .config
+                    .naming()
+                    .property_to_field_name(property_name, &taken);
+                field_names.insert(property_name.clone(), field_name);
+            }
+            let renames = field_names
+                .iter()
+                .map(|(property_name, field_name)| (field_name.as_str(), property_name.as_str()))
+                .collect::<Vec<_>>();
+            let rename_case = translate::container_rename_case(&renames);
+            if let Some(case) = rename_case {
+                b = b.serde_rename_all(case);
+            }
+            // schemas not used by any operation may be used in both directions
+            let usage = ctx
+                .mapping
+                .usage
+                .get(&RefOr::Object(schema.clone()))
+                .copied()
+                .unwrap_or(usage::Usage::Both);
+            let mut field_defaults = Vec::new();
+            let mut debug_fields = Vec::new();
+            for (name, schema) in properties {
+                let rust_name = field_names[&name].clone();
+                debug_fields.push((rust_name.clone(), secret_properties.contains(&name)));
+                let renamed = match rename_case {
+                    Some(case) => case.apply(&rust_name),
+                    None => rust_name.clone(),
+                };
                 let schema = schema.resolve();
+                // the struct is named by the naming policy already, so the
+                // combined name is only made a type name
                 let candidate_name =
                     translate::schema_to_rust_typename((struct_name.to_string() + &name).as_str());
-                let property_type_ref = type_ref_of(ctx, &schema, &candidate_name)?;
-                let actual_type_ref;
-                // if property is required, use the type directly, otherwise wrap it in option
-                if required.contains(name.as_str()) {
-                    actual_type_ref = property_type_ref;
-                } else {
-                    actual_type_ref = ctx
-                        .cm
-                        .type_instance(&ctx.cm.type_option(), &[property_type_ref]);
+                // properties sent in one direction only are absent in the
+                // other, so they are optional regardless of 'required' if
+                // the struct is used in the other direction
+                let read_only = schema.as_object().is_some_and(|s| s.read_only());
+                let write_only = schema.as_object().is_some_and(|s| s.write_only());
+                let is_required = required.contains(name.as_str())
+                    && !(read_only && usage.in_request() || write_only && usage.in_response());
+                let rust_type = match schema.as_object() {
+                    Some(schema) => rust_type_override(&schema)?,
+                    None => None,
+                };
+                // required single value enums and string constants (like
+                // discriminators) always carry the value, so a marker type
+                // can serialize it
+                let single_value = schema.as_object().and_then(|s| single_string_value(&s));
+                let use_marker = rust_type.is_none()
+                    && single_value.is_some()
+                    && is_required
+                    && ctx.config.single_value_enums == SingleValueEnums::MarkerIfRequired;
+                let rust_type = match &single_value {
+                    Some(value) if use_marker => Some(constant_marker_type(ctx, value)?),
+                    _ => rust_type,
+                };
+                let typed_field = match rust_type {
+                    Some(_) => None,
+                    None => match fixed_size_bytes_type(ctx, &schema)? {
+                        Some(typed_field) => Some(typed_field),
+                        None => decimal_type(ctx, &schema)?,
+                    },
+                };
+                let property_type_ref = match (rust_type, &typed_field) {
+                    (Some(type_ref), _) => type_ref,
+                    (None, Some((type_ref, _))) => type_ref.clone(),
+                    (None, None) => match id_newtype(ctx, &name, &schema)? {
+                        Some(type_ref) => type_ref,
+                        None => type_ref_of(ctx, &schema, &candidate_name)?,
+                    },
+                };
+                let nullable = schema.as_object().is_some_and(|s| s.nullable());
+                // fixed size byte arrays and decimals come with their own
+                // helpers, which cannot tell absent from null values
+                let double_option = ctx.config.double_option_for_patch
+                    && !matches!(typed_field, Some((_, Some(_))));
+                let depth = option_depth(is_required, nullable, double_option);
+                let actual_type_ref = optional_type(
+                    &mut ctx.cm,
+                    &property_type_ref,
+                    is_required,
+                    nullable,
+                    double_option,
+                );
+                let mut serde_args = Vec::new();
+                match (&typed_field, depth) {
+                    (Some((_, Some(with))), 0) => {
+                        let helper = &with.plain;
+                        serde_args.push(quote!(with = #helper));
+                    }
+                    (Some((_, Some(with))), _) => {
+                        let helper = &with.option;
+                        serde_args.push(quote!(default));
+                        serde_args.push(quote!(with = #helper));
+                    }
+                    (_, 2) => {
+                        let helper = build_double_option_helper(ctx)?;
+                        serde_args.push(quote!(default));
+                        serde_args.push(quote!(
+                            skip_serializing_if = "::std::option::Option::is_none"
+                        ));
+                        serde_args.push(quote!(with = #helper));
+                    }
+                    _ => (),
+                }
+                if renamed != name {
+                    serde_args.push(quote!(rename = #name));
+                }
+                // the client must not send read-only properties, and won't
+                // receive write-only ones
+                if read_only && usage.in_request() {
+                    serde_args.push(quote!(skip_serializing));
+                }
+                if write_only && usage.in_response() {
+                    serde_args.push(quote!(skip_deserializing));
+                }
+                let mut attrs = Vec::new();
+                if let Some(value) = single_value.as_ref().filter(|_| !use_marker) {
+                    let doc = format!(" Always `{value:?}`");
+                    attrs.push(("doc", quote!(= #doc)));
+                } else if single_value.is_none()
+                    && let Some(value) = schema.as_object().and_then(|s| s.const_value())
+                {
+                    // other constants keep the type of their value
+                    log::warn!(
+                        "the value of property '{name}' of '{struct_name}' is not checked to be the constant {}",
+                        value.dump()
+                    );
+                    let doc = format!(" Always `{}`", value.dump());
+                    attrs.push(("doc", quote!(= #doc)));
                 }
-                b = b.field(&rust_name, actual_type_ref)?;
+                if !serde_args.is_empty() {
+                    attrs.push(("serde", quote!((#(#serde_args),*))));
+                }
+                b = b.field_with_attrs(&rust_name, actual_type_ref, attrs)?;
+                // markers have a single value, so there is nothing to default
+                let default = match use_marker {
+                    true => None,
+                    false => schema.as_object().and_then(|s| s.default_value()),
+                };
+                field_defaults.push(FieldDefault {
+                    name: rust_name,
+                    type_ref: property_type_ref,
+                    option_depth: depth,
+                    default,
+                });
             }
             let s = b.build()?;
-            Ok(ctx.m.insert_struct(s)?)
+            let type_ref = ctx.m.insert_struct(s)?;
+            if !secret_properties.is_empty() {
+                let debug_impl =
+                    build_redacted_debug(ctx, struct_name, type_ref.clone(), &debug_fields)?;
+                ctx.m.insert_implementation(debug_impl)?;
+            }
+            if field_defaults.iter().any(|f| f.default.is_some()) {
+                build_default_impl(ctx, struct_name, &field_defaults)?;
+            }
+            if ctx.config.json_value_conversions {
+                build_json_value_conversions(ctx, struct_name)?;
+            }
+            Ok(type_ref)
+        }
+        TypeKind::Const(value) => {
+            let name = name
+                .as_ref()
+                .ok_or(anyhow!("cannot generate a constant for unnamed schema"))?;
+            let (type_ref, const_type, const_value) = if let Some(s) = value.as_str() {
+                (ctx.cm.type_string(), quote!(&str), quote!(#s))
+            } else if let Some(b) = value.as_bool() {
+                (ctx.cm.type_bool(), quote!(bool), quote!(#b))
+            } else if let Some(i) = value.as_i64() {
+                (ctx.cm.type_i64(), quote!(i64), quote!(#i))
+            } else if let Some(f) = value.as_f64() {
+                (ctx.cm.type_f64(), quote!(f64), quote!(#f))
+            } else {
+                return Err(anyhow!("unsupported const value {value}"));
+            };
+            let const_name = translate::schema_to_rust_constname(name);
+            ctx.m
+                .insert_const(&const_name, ctx.config.visibility, const_type, const_value)?;
+            // the schema's name, given by the naming policy, still refers to
+            // a type, the type of the value
+            Ok(ctx.m.insert_type_alias(name, type_ref)?)
+        }
+        TypeKind::HashMap(_) => {
+            let name = name
+                .as_ref()
+                .ok_or(anyhow!("cannot generate a map for unnamed schema"))?;
+            let value_type = map_value_type(ctx, schema, &format!("{name}Value"))?;
+            let map_type = ctx
+                .cm
+                .type_instance(&ctx.cm.type_hashmap(), &[ctx.cm.type_string(), value_type]);
+            // the naming policy named the map already
+            if schema.min_properties().is_some() || schema.max_properties().is_some() {
+                return map_size_type(ctx, schema, name, &map_type);
+            }
+            Ok(ctx.m.insert_type_alias(name, map_type)?)
         }
         /*TypeKind::String => {
             let string_type = cm.type_string(&self);
@@ -357,20 +3600,420 @@ fn parse_schema<S: Spec>(
     }
 }
 
+/// The value type of a map generated for an object schema without
+/// properties. Values are typed if 'additionalProperties' and
+/// 'patternProperties' allow a single schema only, otherwise they're kept
+/// as JSON values. Note that property names are not checked against the
+/// patterns.
+fn map_value_type<S: Spec>(
+    ctx: &mut Context<S>,
+    schema: &S::Schema,
+    candidate_name: &str,
+) -> anyhow::Result<TypeRef> {
+    let mut value_schemas: Vec<RefOr<S::Schema>> = schema
+        .pattern_properties()
+        .into_values()
+        .map(|s| s.resolve())
+        .collect();
+    if let BooleanOrSchema::Schema(s) = schema.addtional_properties() {
+        value_schemas.push(RefOr::Object(s));
+    }
+    value_schemas.dedup();
+    match value_schemas.as_slice() {
+        [value_schema] => type_ref_of(ctx, value_schema, candidate_name),
+        _ => Ok(ctx.cm.type_json_value()),
+    }
+}
+
+/// How many `Option`s wrap the type of a property or parameter. Values
+/// that are not required or nullable need one, and those that are both
+/// need a second if absent and null values are to be told apart (see
+/// [ApiConfig::double_option_for_patch]).
+fn option_depth(required: bool, nullable: bool, double_option: bool) -> usize {
+    match (required, nullable) {
+        (true, false) => 0,
+        (true, true) | (false, false) => 1,
+        (false, true) if double_option => 2,
+        (false, true) => 1,
+    }
+}
+
+/// Wraps the type of a property or parameter in as many `Option`s as
+/// [option_depth] demands. `type_ref` must be the type the value's schema
+/// maps to, which is never an `Option` itself.
+fn optional_type(
+    cm: &mut Codemodel,
+    type_ref: &TypeRef,
+    required: bool,
+    nullable: bool,
+    double_option: bool,
+) -> TypeRef {
+    let mut type_ref = type_ref.clone();
+    for _ in 0..option_depth(required, nullable, double_option) {
+        type_ref = cm.optional(&type_ref);
+    }
+    type_ref
+}
+
+/// Creates the `double_option` module (de)serializing `Option<Option<T>>`
+/// fields, so that absent values become `None` and `null` values
+/// `Some(None)`. Serde maps both to `None` otherwise. Yields the name of
+/// the module.
+fn build_double_option_helper<S: Spec>(ctx: &mut Context<S>) -> anyhow::Result<String> {
+    let helper = "double_option";
+    if !ctx.m.raw_item_iter().any(|item| item.name() == helper) {
+        let ts = quote! {
+            mod double_option {
+                pub fn serialize<S: ::serde::Serializer, T: ::serde::Serialize>(value: &::std::option::Option<::std::option::Option<T>>, serializer: S) -> ::std::result::Result<S::Ok, S::Error> {
+                    match value {
+                        ::std::option::Option::Some(value) => ::serde::Serialize::serialize(value, serializer),
+                        ::std::option::Option::None => serializer.serialize_none(),
+                    }
+                }
+
+                pub fn deserialize<'de, D: ::serde::Deserializer<'de>, T: ::serde::Deserialize<'de>>(deserializer: D) -> ::std::result::Result<::std::option::Option<::std::option::Option<T>>, D::Error> {
+                    // only called for present values, absent ones take the default
+                    <::std::option::Option<T> as ::serde::Deserialize>::deserialize(deserializer).map(::std::option::Option::Some)
+                }
+            }
+        };
+        ctx.m.insert_raw_item(helper, ts)?;
+    }
+    Ok(helper.to_string())
+}
+
+/// A struct field along with the `default` value of its property schema
+struct FieldDefault {
+    name: String,
+    /// the type of the property, not wrapped in `Option`
+    type_ref: TypeRef,
+    /// how many `Option`s wrap the field's type, see [option_depth]
+    option_depth: usize,
+    default: Option<json::JsonValue>,
+}
+
+/// Creates `impl Default` for a struct whose properties have `default`
+/// values. Fields without a default are `None` if optional, or take
+/// their type's default. If a field's value cannot be expressed, we
+/// don't generate the impl at all.
+fn build_default_impl<S: Spec>(
+    ctx: &mut Context<S>,
+    struct_name: &str,
+    fields: &[FieldDefault],
+) -> anyhow::Result<()> {
+    let mut field_values = Vec::new();
+    for field in fields {
+        // defaults the field's type cannot hold are ignored, so the field
+        // is treated as if it had none
+        let default = field.default.as_ref().filter(|default| {
+            let fits = integer_default_fits(&field.type_ref, default);
+            if !fits {
+                log::warn!(
+                    "ignoring default {default} of field {} of {struct_name}: it does not fit type {}",
+                    field.name,
+                    field.type_ref.name()
+                );
+            }
+            fits
+        });
+        let value = match (default, field.option_depth) {
+            (Some(default), depth) => default_value_tokens(&field.type_ref, default).map(|value| {
+                (0..depth).fold(
+                    value,
+                    |value, _| quote!(::std::option::Option::Some(#value)),
+                )
+            }),
+            (None, 1..) => Some(quote!(::std::option::Option::None)),
+            (None, 0) => match &field.type_ref {
+                TypeRef::Builtin(_) => Some(quote!(::std::default::Default::default())),
+                t if t.name() == "String" || t.name().starts_with("Vec<") => {
+                    Some(quote!(::std::default::Default::default()))
+                }
+                _ => None,
+            },
+        };
+        let Some(value) = value else {
+            log::debug!(
+                "not generating Default for {struct_name}: no default value for field {}",
+                field.name
+            );
+            return Ok(());
+        };
+        let field_ident = format_ident!("{}", field.name);
+        field_values.push(quote!(#field_ident: #value));
+    }
+
+    let struct_ident = format_ident!("{struct_name}");
+    ctx.m.insert_raw_item(
+        &format!("impl Default for {struct_name}"),
+        quote!(
+            impl ::std::default::Default for #struct_ident {
+                fn default() -> Self {
+                    Self { #(#field_values),* }
+                }
+            }
+        ),
+    )?;
+    Ok(())
+}
+
+/// Whether a property holds a secret that must not show in `Debug` output:
+/// strings of format `password`, and write-only strings, which the client
+/// sends but never gets back, like new passwords or API keys.
+fn is_secret_property(schema: &impl Schema) -> bool {
+    schema.format() == Some(types::Format::Password)
+        || schema.write_only() && schema.type_() == Some(vec![types::Type::String])
+}
+
+/// Creates the `Debug` impl of a struct holding secrets, which prints `***`
+/// for the secret fields (given as pairs of field name and whether it is
+/// secret) and the values of all others, like a derived impl would.
+fn build_redacted_debug<S: Spec>(
+    ctx: &Context<S>,
+    struct_name: &str,
+    struct_ref: TypeRef,
+    fields: &[(String, bool)],
+) -> anyhow::Result<Implementation> {
+    let field_values = fields.iter().map(|(field_name, secret)| {
+        let field_ident = format_ident!("{field_name}");
+        match secret {
+            true => quote!(.field(#field_name, &"***")),
+            false => quote!(.field(#field_name, &self.#field_ident)),
+        }
+    });
+    let fmt_fn = FunctionBuilder::new("fmt".to_string(), ctx.cm.type_fmt_result())
+        .param("self".to_string(), ctx.cm.type_ref_self())
+        .param("f".to_string(), ctx.cm.type_ref_mut_fmt_formatter())
+        .body(quote!(f.debug_struct(#struct_name) #(#field_values)* .finish()))
+        .build();
+    let debug = ctx.cm.trait_debug()?;
+    Ok(ImplementationBuilder::new_trait(debug, struct_ref)
+        .function(fmt_fn)
+        .build())
+}
+
+/// The rust expression for a `default` value of a property of the given
+/// type, if the value fits the type.
+fn default_value_tokens(type_ref: &TypeRef, value: &json::JsonValue) -> Option<TokenStream> {
+    let type_name = type_ref.name();
+    match type_name.as_ref() {
+        "String" => value
+            .as_str()
+            .map(|s| quote!(::std::string::String::from(#s))),
+        "bool" => value.as_bool().map(|b| quote!(#b)),
+        "f32" | "f64" => value
+            .as_f64()
+            .map(proc_macro2::Literal::f64_unsuffixed)
+            .map(|f| quote!(#f)),
+        "u8" | "u16" | "u32" | "u64" => value
+            .as_u64()
+            .map(proc_macro2::Literal::u64_unsuffixed)
+            .map(|i| quote!(#i)),
+        "i8" | "i16" | "i32" | "i64" => value
+            .as_i64()
+            .map(proc_macro2::Literal::i64_unsuffixed)
+            .map(|i| quote!(#i)),
+        t if t.starts_with("Vec<") && value.is_array() && value.is_empty() => {
+            Some(quote!(::std::vec::Vec::new()))
+        }
+        _ => None,
+    }
+}
+
+/// Whether a numeric `default` value fits the property's type if that is
+/// an integer type. Values of other types always fit.
+fn integer_default_fits(type_ref: &TypeRef, value: &json::JsonValue) -> bool {
+    if !value.is_number() {
+        return true;
+    }
+    match type_ref.name().as_ref() {
+        "u8" => value.as_u8().is_some(),
+        "u16" => value.as_u16().is_some(),
+        "u32" => value.as_u32().is_some(),
+        "u64" => value.as_u64().is_some(),
+        "i8" => value.as_i8().is_some(),
+        "i16" => value.as_i16().is_some(),
+        "i32" => value.as_i32().is_some(),
+        "i64" => value.as_i64().is_some(),
+        _ => true,
+    }
+}
+
+/// Names derived from an operation's path and method: the name of the
+/// generated client function and the prefix of the types generated for it
+#[derive(Debug, Clone, PartialEq)]
+struct OperationNames {
+    fn_name: String,
+    type_name: String,
+    /// the name of the client function's `_to` variant (see
+    /// [has_binary_success_response]), unique among all client functions
+    to_fn_name: String,
+    /// the name of the client function's `_events` variant (see
+    /// [ApiConfig::sse_events]), unique among all client functions
+    events_fn_name: String,
+}
+
+/// The types of an operation's request body (unit if it has none), success
+/// and error responses, which [ApiConfig::operation_type_aliases] name
+struct OperationTypes {
+    names: OperationNames,
+    request: TypeRef,
+    response: TypeRef,
+    error: TypeRef,
+    /// the spec names and locations (`None` for the body) of the client
+    /// function's parameters in order, `None` if they are bundled into a
+    /// params struct
+    params: Option<Vec<(String, Option<ParameterLocation>)>>,
+}
+
+/// The `operationId`s of the operations declaring one, by path and method.
+/// `operationId`s must be unique, but some specs reuse them nonetheless
+/// (see [lint::lint_duplicate_operation_ids]). Each repetition gets the
+/// operation's method appended, so `getUser` becomes `getUserGet`.
+fn unique_operation_ids<S: Spec>(spec: &S) -> HashMap<(String, http::Method), String> {
+    let mut used = HashSet::new();
+    let mut operation_ids = HashMap::new();
+    for (path, path_item) in spec.paths() {
+        for (method, path_op) in path_item.operations_iter() {
+            let Some(operation_id) = path_op.operation_id() else {
+                continue;
+            };
+            let mut unique_id = operation_id.to_string();
+            if used.contains(&unique_id) {
+                let (l, r) = method.as_str().split_at(1);
+                unique_id = format!("{operation_id}{}{}", l.to_uppercase(), r.to_lowercase());
+            }
+            used.insert(unique_id.clone());
+            operation_ids.insert((path.clone(), method), unique_id);
+        }
+    }
+    operation_ids
+}
+
+/// Derives unique [OperationNames] for all given operations, from their
+/// `operationId` if given in `operation_ids`, and from path and method
+/// otherwise. Paths that are identical after normalization (like `/pets`
+/// and `/pets/`) would yield the same names. In that case, the operations
+/// on normalized paths keep their names, while the others get a numeric
+/// suffix.
+fn derive_operation_names(
+    operations: &[(String, http::Method)],
+    operation_ids: &HashMap<(String, http::Method), String>,
+    legacy_path_names: bool,
+    non_ascii: NonAsciiHandling,
+    naming: &dyn NamingPolicy,
+) -> anyhow::Result<HashMap<(String, http::Method), OperationNames>> {
+    let mut paths_by_normalized: HashMap<String, Vec<&str>> = HashMap::new();
+    for (path, _) in operations {
+        let paths = paths_by_normalized
+            .entry(translate::normalize_path(path))
+            .or_default();
+        if !paths.contains(&path.as_str()) {
+            paths.push(path);
+        }
+    }
+    for (normalized, paths) in &paths_by_normalized {
+        if paths.len() > 1 {
+            log::warn!("paths {paths:?} are identical after normalization to '{normalized}'");
+        }
+    }
+
+    let (normalized, unnormalized): (Vec<_>, Vec<_>) = operations
+        .iter()
+        .partition(|(path, _)| translate::normalize_path(path) == *path);
+
+    let mut fn_names = HashSet::new();
+    let mut type_names = HashSet::new();
+    let mut operation_names = HashMap::new();
+    for (path, method) in normalized.into_iter().chain(unnormalized) {
+        let operation_id_fn_name = operation_ids
+            .get(&(path.clone(), method.clone()))
+            .and_then(|id| translate::operation_id_to_rust_fn_name(id, non_ascii, &fn_names));
+        let (fn_name, type_name) = match operation_id_fn_name {
+            Some(fn_name) => {
+                let type_name = translate::fn_name_to_rust_typename(&fn_name);
+                (fn_name, type_name)
+            }
+            None if legacy_path_names => (
+                translate::legacy_path_method_to_rust_fn_name(method, path)?,
+                translate::legacy_path_method_to_rust_type_name(method.clone(), path),
+            ),
+            None => (
+                naming.operation_to_fn_name(method, path)?,
+                translate::path_method_to_rust_type_name(method.clone(), path),
+            ),
+        };
+
+        let mut names = OperationNames {
+            fn_name: fn_name.clone(),
+            type_name: type_name.clone(),
+            to_fn_name: String::new(),
+            events_fn_name: String::new(),
+        };
+        let mut n = 0;
+        while fn_names.contains(&names.fn_name) || type_names.contains(&names.type_name) {
+            n += 1;
+            names.fn_name = format!("{fn_name}{n}");
+            names.type_name = format!("{type_name}{n}");
+        }
+        fn_names.insert(names.fn_name.clone());
+        type_names.insert(names.type_name.clone());
+        operation_names.insert((path.clone(), method.clone()), names);
+    }
+    // the variants are named once all client functions are, as they must
+    // not take the name of another operation's function, like `pets_to`
+    for names in operation_names.values_mut() {
+        names.to_fn_name = variant_fn_name(&fn_names, &names.fn_name, "_to");
+        names.events_fn_name = variant_fn_name(&fn_names, &names.fn_name, "_events");
+    }
+    Ok(operation_names)
+}
+
+/// The name of a variant of the client function `fn_name`, which is the
+/// function's name with `suffix`, numbered if a client function of that
+/// name exists already. Variants with different suffixes cannot collide.
+fn variant_fn_name(fn_names: &HashSet<String>, fn_name: &str, suffix: &str) -> String {
+    let mut name = format!("{fn_name}{suffix}");
+    let mut n = 0;
+    while fn_names.contains(&name) {
+        n += 1;
+        name = format!("{fn_name}{suffix}{n}");
+    }
+    name
+}
+
+#[allow(clippy::too_many_arguments)]
 fn parse_path_into_impl_fn<S: Spec, B: FunctionListBuilder>(
     ctx: &mut Context<S>,
     impl_builder: B,
-    path_name: &str,
+    names: &OperationNames,
+    path: &str,
+    method: &http::Method,
     path_item: &S::PathItem,
-    method: http::Method,
     path_op: &S::Operation,
+    undeclared_path_params: &[String],
+    response_decoders: &mut Vec<Function>,
+    example_tests: &mut Vec<TokenStream>,
+    operation_types: &mut Vec<OperationTypes>,
+    router_routes: &mut Vec<TokenStream>,
 ) -> anyhow::Result<B> {
-    let candidate_name = translate::path_method_to_rust_fn_name(&method, path_name)?;
-
-    let fn_name = candidate_name; // FIXME: handle collisions
-
-    let return_type = parse_into_fn_result(ctx, path_name, path_item, method.clone(), path_op)?;
-    let mut function = FunctionBuilder::new(fn_name, return_type)
+    let success_type = build_response_type(ctx, names, path_op, true)?;
+    let error_type = build_response_type(ctx, names, path_op, false)?;
+    let return_type = ctx.cm.type_instance(
+        &ctx.cm.type_result(),
+        &[success_type.clone(), error_type.clone()],
+    );
+    if ctx.config.response_decoders {
+        response_decoders.push(build_response_decoder(
+            ctx,
+            names,
+            path,
+            method,
+            path_op,
+            return_type.clone(),
+        )?);
+    }
+    let function = FunctionBuilder::new(names.fn_name.clone(), return_type)
         .param("self".to_string(), ctx.cm.type_ref_self());
 
     // Parameters in path_op can override those in path_item, so
@@ -387,83 +4030,524 @@ fn parse_path_into_impl_fn<S: Spec, B: FunctionListBuilder>(
         })
         .collect::<Vec<_>>();
 
-    fn param_type_name_fn<S: Spec>(param: &S::Parameter) -> String {
-        param.name().to_owned()
-    }
+    // types generated for parameters are named after operation and
+    // parameter, as operations often share parameter names like `filter`
+    let param_type_name_fn = |param: &S::Parameter| {
+        names.type_name.clone() + &translate::parameter_to_rust_typename(param.name())
+    };
+
+    // we collect all inputs of the operation first, so we can decide
+    // whether they become function parameters or fields of a params struct
+    let mut inputs = Vec::new();
 
     for param in outer_params {
-        function = append_param(ctx, function, &param, param_type_name_fn::<S>)?;
+        append_param(ctx, &mut inputs, &param, param_type_name_fn)?;
     }
 
     for param in path_op.parameters() {
-        function = append_param(
-            ctx,
-            function,
-            &param.resolve_fully(),
-            param_type_name_fn::<S>,
-        )?;
+        append_param(ctx, &mut inputs, &param.resolve_fully(), param_type_name_fn)?;
+    }
+
+    // template parameters the spec forgot to declare are taken as strings
+    for name in undeclared_path_params {
+        let derived_name =
+            derive_function_param_name(name, Some(ParameterLocation::Path), &mut inputs);
+        inputs.push(OperationInput {
+            spec_name: name.clone(),
+            name: derived_name,
+            location: Some(ParameterLocation::Path),
+            type_ref: ctx.cm.type_string(),
+            required: true,
+            nullable: false,
+            string_valued: true,
+            example: None,
+        });
     }
 
     // add request body as function parameter if defined
+    let mut request_type = ctx.cm.type_unit();
+    let mut request_body_doc = None;
     if let Some(request_body) = path_op.request_body() {
+        let request_body = request_body.resolve_fully();
+        if request_body.required() && request_body.content().is_empty() {
+            log::warn!(
+                "request body of {method} {path} is required, but declares no content to send"
+            );
+        }
+        request_body_doc = Some(request_body_doc_of::<S>(&request_body));
         // closure to build name from {operationFragment}Content pattern
         // - called if needed.
-        let op_fragment_content_fn =
-            || translate::path_method_to_rust_type_name(method.clone(), path_name) + "Content";
-        let type_ref = map_content(
-            ctx,
-            &request_body.resolve_fully().content(),
-            op_fragment_content_fn,
+        let op_fragment_content_fn = || names.type_name.clone() + "Content";
+        // we cannot send a range, so we pick the media type to send
+        let (content, replacements) = request_body_content::<S>(&request_body);
+        for (range, concrete) in replacements {
+            log::warn!(
+                "request body of {method} {path} declares media type range '{range}', sending '{concrete}'"
+            );
+        }
+        let type_ref = map_content(ctx, &content, op_fragment_content_fn)?;
+        request_type = type_ref.clone();
+        let name = derive_function_param_name("body", None, &mut inputs);
+        inputs.push(OperationInput {
+            name,
+            spec_name: "body".to_string(),
+            location: None,
+            type_ref,
+            required: request_body.required(),
+            nullable: false,
+            string_valued: false,
+            example: None,
+        });
+    }
+    let writer_name = derive_function_param_name("writer", None, &mut inputs);
+    let parameters_doc = parameters_doc_of(&inputs);
+
+    let bundle_inputs = ctx
+        .config
+        .params_struct_threshold
+        .is_some_and(|threshold| inputs.len() > threshold);
+    if ctx.config.emit_examples && !bundle_inputs {
+        match build_example_test(names, &inputs)? {
+            Some(test) => example_tests.push(test),
+            None => log::debug!("not generating an example test for {}", names.fn_name),
+        }
+    }
+    let input_params = inputs
+        .iter()
+        .map(|input| (input.spec_name.clone(), input.location))
+        .collect();
+    operation_types.push(OperationTypes {
+        names: names.clone(),
+        request: request_type,
+        response: success_type.clone(),
+        error: error_type.clone(),
+        params: (!bundle_inputs).then_some(input_params),
+    });
+    if ctx.config.server_framework == ServerFramework::Axum {
+        let route = build_axum_route(ctx, names, path, method, path_op, &inputs, bundle_inputs);
+        if route.is_none() {
+            ctx.stats.skipped += 1;
+        }
+        router_routes.extend(route);
+    }
+    let params = if bundle_inputs {
+        let params_type = build_params_struct(ctx, names, inputs)?;
+        vec![("params".to_string(), params_type)]
+    } else {
+        inputs
+            .into_iter()
+            .map(|input| (input.name, input.type_ref))
+            .collect::<Vec<_>>()
+    };
+    let function_doc = [parameters_doc, request_body_doc]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>();
+    let function_doc = (!function_doc.is_empty()).then(|| function_doc.join("\n\n"));
+    // all variants of the operation take the same inputs, and document them
+    let with_doc = |function: FunctionBuilder| match &function_doc {
+        Some(doc) => function.attr_with_input("doc", quote!(= #doc)),
+        None => Ok(function),
+    };
+    let mut function = with_doc(function)?;
+    for (name, type_ref) in &params {
+        function = function.param(name.clone(), type_ref.clone());
+    }
+    let mut impl_builder = impl_builder.function(function.build());
+
+    // binary downloads get an additional variant copying the response body
+    // into a writer instead of buffering it, returning the number of bytes.
+    // By default, it writes the buffered body, implementations of the trait
+    // override it to stream the body.
+    if has_binary_success_response::<S>(path_op) {
+        let return_type = ctx.cm.type_instance(
+            &ctx.cm.type_result(),
+            &[ctx.cm.type_u64(), error_type.clone()],
+        );
+        let fn_ident = format_ident!("{}", names.fn_name);
+        let param_idents = params
+            .iter()
+            .map(|(name, _)| format_ident!("{name}"))
+            .collect::<Vec<_>>();
+        let writer_ident = format_ident!("{writer_name}");
+        let error_ident = format_ident!("{}", error_type.name());
+        let body = quote!(
+            let bytes = self.#fn_ident(#(#param_idents),*)?;
+            ::std::io::Write::write_all(#writer_ident, &bytes)
+                .map_err(|e| #error_ident::OtherError(::std::boxed::Box::new(e)))?;
+            ::std::result::Result::Ok(bytes.len() as u64)
+        );
+        // the writer's type is the generic parameter
+        let writer_type = TypeRef::Reference {
+            referenced_type: Box::new(StructBuilder::new("W").build()?.into()),
+            mutable: true,
+            lifetime: None,
+        };
+        let mut streaming_function = with_doc(
+            FunctionBuilder::new(names.to_fn_name.clone(), return_type)
+                .generic_param("W".to_string(), quote!(::std::io::Write))
+                .param("self".to_string(), ctx.cm.type_ref_self()),
         )?;
-        let body_param_name = derive_function_param_name("body", &function);
-        function = function.param(body_param_name, type_ref);
+        for (name, type_ref) in params.iter().cloned() {
+            streaming_function = streaming_function.param(name, type_ref);
+        }
+        streaming_function = streaming_function
+            .param(writer_name, writer_type)
+            .body(body);
+        impl_builder = impl_builder.function(streaming_function.build());
+    }
+
+    // event streams get an additional variant returning the parsed events
+    if ctx.config.sse_events && has_event_stream_success_response::<S>(path_op) {
+        let sse_event = build_sse_event(ctx)?;
+        let events_type = ctx.cm.type_external(&format!(
+            "impl ::std::iter::Iterator<Item = Result<{}, ::std::io::Error>>",
+            sse_event.name()
+        ));
+        let return_type = ctx
+            .cm
+            .type_instance(&ctx.cm.type_result(), &[events_type, error_type]);
+        let mut events_function = with_doc(
+            FunctionBuilder::new(names.events_fn_name.clone(), return_type)
+                .param("self".to_string(), ctx.cm.type_ref_self()),
+        )?;
+        for (name, type_ref) in params {
+            events_function = events_function.param(name, type_ref);
+        }
+        impl_builder = impl_builder.function(events_function.build());
+    }
+
+    Ok(impl_builder)
+}
+
+/// The `# Parameters` section of an operation's documentation, listing the
+/// parameters with their locations and examples. `None` unless a parameter
+/// has an example, as the section would only repeat the signature.
+fn parameters_doc_of(inputs: &[OperationInput]) -> Option<String> {
+    if inputs.iter().all(|input| input.example.is_none()) {
+        return None;
+    }
+    let mut doc = " # Parameters\n".to_string();
+    // the body is not a parameter
+    for input in inputs {
+        let Some(location) = input.location else {
+            continue;
+        };
+        let location = translate::parameter_location_to_suffix(location);
+        doc.push_str(&format!("\n - `{}` ({location})", input.name));
+        if let Some(example) = &input.example {
+            doc.push_str(&format!(", example: `{}`", example.dump()));
+        }
+    }
+    Some(doc)
+}
+
+/// Creates the test of [ApiConfig::emit_examples] calling an operation's
+/// client function with the examples of its parameters. `None` if the
+/// operation takes a body, or if a parameter has no example or one of a
+/// type we cannot write a literal for.
+fn build_example_test(
+    names: &OperationNames,
+    inputs: &[OperationInput],
+) -> anyhow::Result<Option<TokenStream>> {
+    let mut args = Vec::new();
+    for input in inputs {
+        if input.location.is_none() {
+            return Ok(None);
+        }
+        let type_name = codewriter::syn_type_name_of(&input.type_ref)?.to_string();
+        let Some(arg) = input
+            .example
+            .as_ref()
+            .and_then(|example| example_literal(&type_name, example))
+        else {
+            return Ok(None);
+        };
+        args.push(arg);
+    }
+    let fn_name = &names.fn_name;
+    let fn_ident = format_ident!("{fn_name}");
+    let doc = format!(" Calls `{fn_name}` with the examples of its parameters");
+    let failure = format!("{fn_name} failed");
+    Ok(Some(quote!(
+        #[doc = #doc]
+        #[test]
+        fn #fn_ident() {
+            let client = client();
+            let result = super::Client::#fn_ident(&client, #(#args),*);
+            ::std::assert!(result.is_ok(), "{}", #failure);
+        }
+    )))
+}
+
+/// A literal of the builtin type `type_name` holding an example value
+fn example_literal(type_name: &str, example: &json::JsonValue) -> Option<TokenStream> {
+    use proc_macro2::Literal;
+    let literal = match type_name {
+        "String" if example.is_string() || example.is_number() || example.is_boolean() => {
+            let value = example
+                .as_str()
+                .map(str::to_string)
+                .unwrap_or_else(|| example.dump());
+            return Some(quote!(::std::string::ToString::to_string(#value)));
+        }
+        "bool" => {
+            let value = example.as_bool()?;
+            return Some(quote!(#value));
+        }
+        "i8" => Literal::i8_suffixed(example.as_i8()?),
+        "i16" => Literal::i16_suffixed(example.as_i16()?),
+        "i32" => Literal::i32_suffixed(example.as_i32()?),
+        "i64" => Literal::i64_suffixed(example.as_i64()?),
+        "u8" => Literal::u8_suffixed(example.as_u8()?),
+        "u16" => Literal::u16_suffixed(example.as_u16()?),
+        "u32" => Literal::u32_suffixed(example.as_u32()?),
+        "u64" => Literal::u64_suffixed(example.as_u64()?),
+        "f32" => Literal::f32_suffixed(example.as_f32()?),
+        "f64" => Literal::f64_suffixed(example.as_f64()?),
+        _ => return None,
+    };
+    Some(quote!(#literal))
+}
+
+/// The `# Request body` section of an operation's documentation, with the
+/// body's description and the media types it may be sent as
+fn request_body_doc_of<S: Spec>(request_body: &S::RequestBody) -> String {
+    let mut doc = " # Request body\n".to_string();
+    // lines get a leading space, like those of `///` comments
+    if let Some(description) = request_body.description() {
+        doc.push('\n');
+        for line in description.trim().lines() {
+            doc.push_str(&format!(" {line}\n"));
+        }
+    }
+    let mut media_types = request_body.content().into_keys().collect::<Vec<_>>();
+    media_types.sort();
+    if !media_types.is_empty() {
+        let media_types = media_types
+            .iter()
+            .map(|media_type| format!("`{media_type}`"))
+            .collect::<Vec<_>>();
+        doc.push_str(&format!("\n Media types: {}\n", media_types.join(", ")));
+    }
+    match request_body.required() {
+        true => doc.push_str("\n The body is required."),
+        false => doc.push_str("\n The body is optional."),
+    }
+    doc
+}
+
+/// Returns `true` if the operation has a single success response, whose
+/// content is binary
+fn has_binary_success_response<S: Spec>(path_op: &S::Operation) -> bool {
+    let (success_responses, _) = partition_responses::<S>(path_op);
+    let [(_, response)] = success_responses.as_slice() else {
+        return false;
+    };
+    let content = response.resolve().resolve_fully().content();
+    content.len() == 1
+        && content.iter().all(|(media_type_key, media_type)| {
+            is_binary_media_type::<S>(media_type_key, media_type)
+        })
+}
+
+/// Returns `true` if the operation has a single success response, whose
+/// content is an event stream
+fn has_event_stream_success_response<S: Spec>(path_op: &S::Operation) -> bool {
+    let (success_responses, _) = partition_responses::<S>(path_op);
+    let [(_, response)] = success_responses.as_slice() else {
+        return false;
+    };
+    let content = response.resolve().resolve_fully().content();
+    content.len() == 1 && content.keys().all(|key| is_event_stream_media_type(key))
+}
+
+/// Whether the media type is JSON, like `application/json` or
+/// `application/problem+json`
+fn is_json_media_type(media_type_key: &str) -> bool {
+    let essence = media_type_key
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase();
+    essence == "application/json" || essence.ends_with("+json")
+}
+
+/// Server-sent events are declared by the `text/event-stream` media type
+fn is_event_stream_media_type(media_type_key: &str) -> bool {
+    media_type_key
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .eq_ignore_ascii_case("text/event-stream")
+}
+
+/// Binary content is declared either by its media type (like
+/// `application/octet-stream` or `image/png`), or by a schema with format
+/// `binary`
+fn is_binary_media_type<S: Spec>(media_type_key: &str, media_type: &S::MediaType) -> bool {
+    let essence = media_type_key
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase();
+    if essence == "application/octet-stream" || essence.starts_with("image/") {
+        return true;
+    }
+    media_type
+        .schema()
+        .is_some_and(|schema| schema.resolve_fully().format() == Some(types::Format::Binary))
+}
+
+/// The media type a request body declared for a media type range (like
+/// `*/*` or `application/*`) is sent as: `application/json` if its schema
+/// is object-like (an object, an array or a composition of schemas), and
+/// `application/octet-stream` otherwise, like for binary or string
+/// content. Yields `None` for media types that are no range.
+fn concrete_request_media_type<S: Spec>(
+    media_type_key: &str,
+    media_type: &S::MediaType,
+) -> Option<&'static str> {
+    if !media_type_key.contains('*') {
+        return None;
+    }
+    let object_like = media_type.schema().is_some_and(|schema| {
+        let schema = schema.resolve_fully();
+        let object_type = matches!(
+            schema.type_().as_deref(),
+            Some([types::Type::Object | types::Type::Array])
+        );
+        let composed =
+            schema.all_of().is_some() || schema.any_of().is_some() || schema.one_of().is_some();
+        schema.format() != Some(types::Format::Binary)
+            && (object_type || composed || !schema.properties().is_empty())
+    });
+    match object_like {
+        true => Some("application/json"),
+        false => Some("application/octet-stream"),
     }
+}
+
+/// The content of a request body by media type
+type Content<S> = IndexMap<String, <S as Spec>::MediaType>;
+
+/// The content of a request body, with media type ranges replaced by the
+/// media type sent for them (see [concrete_request_media_type]), and the
+/// replacements made. Ranges replaced by a media type the body declares
+/// as well are dropped.
+fn request_body_content<S: Spec>(
+    request_body: &S::RequestBody,
+) -> (Content<S>, Vec<(String, String)>) {
+    let (ranges, mut content): (Content<S>, Content<S>) = request_body
+        .content()
+        .into_iter()
+        .partition(|(key, _)| key.contains('*'));
+    let mut ranges = ranges.into_iter().collect::<Vec<_>>();
+    ranges.sort_by(|(lhs, _), (rhs, _)| lhs.cmp(rhs));
+    let mut replacements = Vec::new();
+    for (range, media_type) in ranges {
+        let concrete = concrete_request_media_type::<S>(&range, &media_type)
+            .expect("media type ranges have a concrete media type");
+        content.entry(concrete.to_string()).or_insert(media_type);
+        replacements.push((range, concrete.to_string()));
+    }
+    (content, replacements)
+}
 
-    Ok(impl_builder.function(function.build()))
+/// An input of an operation (a parameter or the request body),
+/// already mapped to a rust name and type
+struct OperationInput {
+    name: String,
+    /// the parameter's name in the spec, `body` for the request body
+    spec_name: String,
+    /// where the input is passed, if it is a parameter
+    location: Option<ParameterLocation>,
+    type_ref: TypeRef,
+    required: bool,
+    nullable: bool,
+    /// whether the parameter's schema is of type `string`, so values
+    /// passed as text are taken as they are rather than as JSON
+    string_valued: bool,
+    /// the parameter's example, documented with the client function
+    example: Option<json::JsonValue>,
 }
 
-fn parse_into_fn_result<S: Spec>(
+/// Builds the `{Operation}Params` struct bundling all inputs of an
+/// operation. Inputs that are not required or nullable are wrapped in
+/// `Option`.
+fn build_params_struct<S: Spec>(
     ctx: &mut Context<S>,
-    path_name: &str,
-    path_item: &S::PathItem,
-    method: http::Method,
-    path_op: &S::Operation,
+    names: &OperationNames,
+    inputs: Vec<OperationInput>,
 ) -> anyhow::Result<TypeRef> {
-    let success_type = build_response_type(ctx, path_name, method.clone(), path_op, true)?;
-    let error_type = build_response_type(ctx, path_name, method, path_op, false)?;
+    let struct_name = names.type_name.clone() + "Params";
+    let mut b = StructBuilder::new(&struct_name)
+        .visibility(ctx.config.visibility)
+        .attr_with_input("derive", quote::quote!((::std::fmt::Debug)))?;
+    for input in inputs {
+        // parameters have no way to tell absent from null values
+        let field_type = optional_type(
+            &mut ctx.cm,
+            &input.type_ref,
+            input.required,
+            input.nullable,
+            false,
+        );
+        b = b.field(&input.name, field_type)?;
+    }
+    Ok(ctx.m.insert_struct(b.build()?)?)
+}
 
-    Ok(TypeRef::GenericInstance {
-        generic_type: Box::new(ctx.cm.type_result()),
-        type_parameter: vec![success_type, error_type], // FIXME: need to assign proper result type params
-    })
+/// Responses of an operation, along with their status
+type StatusResponses<S> = Vec<(StatusSpec, RefOr<<S as Spec>::Response>)>;
+
+/// The responses of an operation, split into success and error responses.
+/// The `default` response covers all statuses not declared otherwise, so
+/// it is an error response, unless the operation declares no success
+/// responses at all - then `default` is the success response.
+fn partition_responses<S: Spec>(
+    path_op: &S::Operation,
+) -> (StatusResponses<S>, StatusResponses<S>) {
+    let has_success = path_op.responses().any(|(s, _)| is_success(s));
+    path_op
+        .responses()
+        .partition(|(status_spec, _)| match status_spec {
+            StatusSpec::Default => !has_success,
+            s => is_success(*s),
+        })
+}
+
+/// Whether an operation returns the raw response on success, as it declares
+/// no responses at all (see [ApiConfig::missing_responses])
+fn returns_raw_response<S: Spec>(ctx: &Context<S>, path_op: &S::Operation) -> bool {
+    ctx.config.missing_responses == MissingResponses::RawResponse
+        && path_op.responses().next().is_none()
 }
 
+/// Builds the type of an operation's success responses
+/// (`build_for_success`), or of its error responses:
+/// * without success responses, the success type is `()` (or the raw
+///   response, see [returns_raw_response])
+/// * a single success response maps to the type of its content
+/// * several success responses map to a `{Operation}Success` enum
+/// * error responses always map to a `{Operation}Error` enum, which
+///   has an `UnknownResponse` and an `OtherError` variant in addition
+///   to a variant per error response, even if there are none.
 fn build_response_type<S: Spec>(
     ctx: &mut Context<S>,
-    path_name: &str,
-    method: http::Method,
+    names: &OperationNames,
     path_op: &S::Operation,
     build_for_success: bool,
 ) -> anyhow::Result<TypeRef> {
-    fn is_success(status_spec: StatusSpec) -> bool {
-        match status_spec {
-            types::StatusSpec::Informational(_)
-            | types::StatusSpec::Informational1XX
-            | types::StatusSpec::Success(_)
-            | types::StatusSpec::Success2XX
-            | types::StatusSpec::Redirection(_)
-            | types::StatusSpec::Redirection3XX => true,
-            _ => false,
-        }
-    }
-
-    let responses = path_op
-        .responses()
-        .filter(|(status_spec, _)| match status_spec {
-            types::StatusSpec::Default => true,
-            s => !build_for_success ^ is_success(s.clone()),
-        })
-        .collect::<Vec<_>>();
+    let (success_responses, error_responses) = partition_responses::<S>(path_op);
+    let responses = if build_for_success {
+        success_responses
+    } else {
+        error_responses
+    };
 
     let resonses_name_suffix: &str = if build_for_success {
         "Success"
@@ -472,55 +4556,1065 @@ fn build_response_type<S: Spec>(
     };
 
     let type_ref = match (build_for_success, responses.len()) {
+        (true, 0) if returns_raw_response(ctx, path_op) => {
+            let body_type = ctx
+                .cm
+                .type_instance(&ctx.cm.type_vec(), &[ctx.cm.type_u8()]);
+            ctx.cm
+                .type_instance(&ctx.cm.type_external("::http::Response"), &[body_type])
+        }
         (true, 0) => ctx.cm.type_unit(),
         (true, 1) => {
             let single_response = responses.get(0).unwrap();
             let status_spec = single_response.0.clone();
             let content = single_response.1.resolve().resolve_fully().content();
-            map_content(ctx, &content, || {
-                content_enum_name(&method, path_name, &status_spec)
-            })?
+            let content_name = content_enum_name(ctx.config.naming(), names, &status_spec);
+            map_content(ctx, &content, || content_name.clone())?
         }
         _ => {
-            let enum_name = translate::path_method_to_rust_type_name(method.clone(), path_name)
-                + resonses_name_suffix;
-            let mut e = EnumBuilder::new(&enum_name);
+            let enum_name = names.type_name.clone() + resonses_name_suffix;
+            let mut e = EnumBuilder::new(&enum_name).visibility(ctx.config.visibility);
 
             for (status_spec, response) in responses {
                 let status_spec = &status_spec;
                 let content = response.resolve_fully().content();
-                let variant_name = translate::status_spec_to_rust_type_name(status_spec.clone());
-                let variant_type = map_content(ctx, &content, || {
-                    content_enum_name(&method, path_name, &status_spec)
-                })?;
-                e = e.tuple_variant(&variant_name, vec![variant_type])?;
+                let variant_name = ctx.config.naming().status_to_variant_name(*status_spec);
+                let range_body_suffix = match status_spec {
+                    StatusSpec::ClientError4XX => Some("ClientError4XXBody"),
+                    StatusSpec::ServerError5XX => Some("ServerError5XXBody"),
+                    _ => None,
+                };
+                match range_body_suffix {
+                    // without a body, the actual status code is the only
+                    // information a range response carries
+                    Some(_) if content.is_empty() => {
+                        e = e.tuple_variant_with_input(
+                            &variant_name,
+                            vec![quote!(::http::StatusCode)],
+                        )?;
+                    }
+                    Some(suffix) => {
+                        let variant_type =
+                            map_content(ctx, &content, || names.type_name.clone() + suffix)?;
+                        e = e.tuple_variant(&variant_name, vec![variant_type])?;
+                    }
+                    None if *status_spec == TOO_MANY_REQUESTS => {
+                        let content_name =
+                            content_enum_name(ctx.config.naming(), names, status_spec);
+                        let body = map_content(ctx, &content, || content_name.clone())?;
+                        let duration = ctx.cm.type_external("::std::time::Duration");
+                        let retry_after = ctx.cm.optional(&duration);
+                        let response = response.resolve_fully();
+                        let rate_limit_fields = rate_limit_header_fields::<S>(&response)
+                            .into_iter()
+                            .map(|(field_name, header_name)| {
+                                let field_type =
+                                    rate_limit_field_type(ctx, &response, &header_name);
+                                (field_name, ctx.cm.optional(&field_type))
+                            })
+                            .collect::<Vec<_>>();
+                        e = e.struct_variant(&variant_name, |b| {
+                            let mut b = b.field("body", body)?.field("retry_after", retry_after)?;
+                            for (field_name, field_type) in rate_limit_fields {
+                                b = b.field(&field_name, field_type)?;
+                            }
+                            Ok(b)
+                        })?;
+                    }
+                    None => {
+                        let content_name =
+                            content_enum_name(ctx.config.naming(), names, status_spec);
+                        let variant_type = map_content(ctx, &content, || content_name.clone())?;
+                        e = e.tuple_variant(&variant_name, vec![variant_type])?;
+                    }
+                }
+            }
+
+            if !build_for_success {
+                // responses with undeclared statuses still need to be
+                // reported, so these are present even if no error
+                // responses are declared
+                e = e.tuple_variant_with_input(
+                    "UnknownResponse",
+                    vec![quote!(::http::Response<::std::vec::Vec<u8>>)],
+                )?;
+                e = e.tuple_variant_with_input(
+                    "OtherError",
+                    vec![quote!(::std::boxed::Box<dyn ::std::error::Error>)],
+                )?
+            }
+
+            ctx.m.insert_enum(e.build()?)?
+        }
+    };
+    Ok(type_ref)
+}
+
+/// The status of responses whose error variant carries the rate limit
+/// the server imposes in addition to the response's body
+const TOO_MANY_REQUESTS: StatusSpec = StatusSpec::ClientError(429);
+
+/// The rate limit headers declared for a response, as pairs of the error
+/// variant's field name and the (lowercase) header name, ordered by field
+/// name. Both the `X-RateLimit-*` headers and their standardized
+/// `RateLimit-*` counterparts are recognized.
+fn rate_limit_header_fields<S: Spec>(response: &S::Response) -> Vec<(String, String)> {
+    let mut fields = response
+        .headers()
+        .into_keys()
+        .filter_map(|header_name| {
+            let header_name = header_name.to_ascii_lowercase();
+            let kind = header_name
+                .strip_prefix("x-ratelimit-")
+                .or_else(|| header_name.strip_prefix("ratelimit-"))?;
+            matches!(kind, "limit" | "remaining" | "reset")
+                .then(|| (format!("rate_limit_{kind}"), header_name.clone()))
+        })
+        .collect::<Vec<_>>();
+    fields.sort();
+    fields.dedup_by(|a, b| a.0 == b.0);
+    fields
+}
+
+/// The type of the rate limit field for a header of `response`: the type
+/// shared by all responses referencing the same header in the components
+/// section, `u64` for headers declared inline
+fn rate_limit_field_type<S: Spec>(
+    ctx: &Context<S>,
+    response: &S::Response,
+    header_name: &str,
+) -> TypeRef {
+    response
+        .headers()
+        .into_iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(header_name))
+        .and_then(|(_, ro_header)| match ro_header {
+            RefOr::Reference(r) => r
+                .uri()
+                .strip_prefix("#/components/headers/")
+                .and_then(|name| ctx.mapping.header_mapping.get(name))
+                .cloned(),
+            RefOr::Object(_) => None,
+        })
+        .unwrap_or_else(|| ctx.cm.type_u64())
+}
+
+/// Creates the private helpers reading the rate limit of a response with
+/// [TOO_MANY_REQUESTS] status, used by [build_response_decoder]
+fn build_rate_limit_helpers<S: Spec>(ctx: &mut Context<S>) -> anyhow::Result<()> {
+    if ctx.m.raw_item_iter().any(|i| i.name() == "retry_after") {
+        return Ok(());
+    }
+    ctx.m.insert_raw_item(
+        "retry_after",
+        quote!(
+            /// The delay requested by a response's `Retry-After` header,
+            /// given either in seconds or as an HTTP date. Dates in the
+            /// past yield a zero delay.
+            fn retry_after(
+                response: &::http::Response<::std::vec::Vec<u8>>,
+            ) -> ::std::option::Option<::std::time::Duration> {
+                let value = response
+                    .headers()
+                    .get(::http::header::RETRY_AFTER)?
+                    .to_str()
+                    .ok()?
+                    .trim();
+                if let Ok(seconds) = value.parse::<u64>() {
+                    return Some(::std::time::Duration::from_secs(seconds));
+                }
+                let date = parse_http_date(value)?;
+                Some(
+                    date.duration_since(::std::time::SystemTime::now())
+                        .unwrap_or_default(),
+                )
+            }
+        ),
+    )?;
+    ctx.m.insert_raw_item(
+        "parse_http_date",
+        quote!(
+            /// Parses an HTTP date in its preferred format, like
+            /// `Sun, 06 Nov 1994 08:49:37 GMT` (see RFC 9110, section 5.6.7)
+            fn parse_http_date(value: &str) -> ::std::option::Option<::std::time::SystemTime> {
+                const MONTHS: [&str; 12] = [
+                    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov",
+                    "Dec",
+                ];
+                let (_weekday, rest) = value.split_once(", ")?;
+                let parts = rest.split(' ').collect::<::std::vec::Vec<_>>();
+                let [day, month, year, time, "GMT"] = parts[..] else {
+                    return None;
+                };
+                let day = day.parse::<i64>().ok()?;
+                let month = MONTHS.iter().position(|m| *m == month)? as i64 + 1;
+                let year = year.parse::<i64>().ok()?;
+                let mut time = time.split(':').map(|t| t.parse::<i64>().ok());
+                let (hours, minutes, seconds) = (time.next()??, time.next()??, time.next()??);
+
+                // days since the epoch of the proleptic Gregorian calendar
+                // date, see http://howardhinnant.github.io/date_algorithms.html
+                let y = if month <= 2 { year - 1 } else { year };
+                let era = y.div_euclid(400);
+                let yoe = y - era * 400;
+                let doy = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+                let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+                let days = era * 146097 + doe - 719468;
+
+                let secs = days * 86400 + hours * 3600 + minutes * 60 + seconds;
+                let secs = u64::try_from(secs).ok()?;
+                Some(::std::time::UNIX_EPOCH + ::std::time::Duration::from_secs(secs))
+            }
+        ),
+    )?;
+    ctx.m.insert_raw_item(
+        "rate_limit_header",
+        quote!(
+            /// The value of a rate limit header like `X-RateLimit-Remaining`,
+            /// deserialized from JSON, or the string it is if it is no JSON
+            fn rate_limit_header<T: ::serde::de::DeserializeOwned>(
+                response: &::http::Response<::std::vec::Vec<u8>>,
+                name: &str,
+            ) -> ::std::option::Option<T> {
+                let value = response.headers().get(name)?.to_str().ok()?.trim();
+                ::serde_json::from_str(value)
+                    .or_else(|_| {
+                        ::serde_json::from_value(::serde_json::Value::String(value.to_string()))
+                    })
+                    .ok()
+            }
+        ),
+    )?;
+    Ok(())
+}
+
+/// Creates the private helpers used by the functions generated by
+/// [build_response_decoder], and `decode_with_path()`, which reports
+/// where JSON bodies deviate from the spec
+fn build_response_decoder_helpers<S: Spec>(ctx: &mut Context<S>) -> anyhow::Result<()> {
+    build_decode_with_path(ctx)?;
+    ctx.m.insert_raw_item(
+        "response_media_type",
+        quote!(
+            /// The media type of a response without parameters, like
+            /// `application/json` for `application/json; charset=utf-8`
+            fn response_media_type(
+                response: &::http::Response<::std::vec::Vec<u8>>,
+            ) -> ::std::string::String {
+                response
+                    .headers()
+                    .get(::http::header::CONTENT_TYPE)
+                    .and_then(|value| value.to_str().ok())
+                    .and_then(|value| value.split(';').next())
+                    .map(|essence| essence.trim().to_ascii_lowercase())
+                    .unwrap_or_default()
+            }
+        ),
+    )?;
+    ctx.m.insert_raw_item(
+        "media_type_matches",
+        quote!(
+            /// Whether a media type is covered by a media type range like
+            /// `image/*`, as used as keys in an OpenAPI content map
+            fn media_type_matches(media_type: &str, range: &str) -> bool {
+                match range.split_once('/') {
+                    Some(("*", "*")) => true,
+                    Some((type_, "*")) => media_type
+                        .split_once('/')
+                        .is_some_and(|(t, _)| t.eq_ignore_ascii_case(type_)),
+                    _ => media_type.eq_ignore_ascii_case(range),
+                }
+            }
+        ),
+    )?;
+    Ok(())
+}
+
+/// Builds the `decode_{operation}_response()` function mapping a response
+/// to the operation's `return_type` (see [ApiConfig::response_decoders]).
+/// Its status code selects the response declared for it, preferring exact
+/// codes over ranges over `default`. Responses with undeclared statuses or
+/// media types become `UnknownResponse`s, bodies failing to deserialize
+/// `OtherError`s.
+fn build_response_decoder<S: Spec>(
+    ctx: &mut Context<S>,
+    names: &OperationNames,
+    path: &str,
+    method: &http::Method,
+    path_op: &S::Operation,
+    return_type: TypeRef,
+) -> anyhow::Result<Function> {
+    let (success_responses, error_responses) = partition_responses::<S>(path_op);
+    let single_success = success_responses.len() == 1;
+    let success_enum = format_ident!("{}Success", names.type_name);
+    let error_enum = format_ident!("{}Error", names.type_name);
+
+    let mut arms = Vec::new();
+    let mut error_variants = Vec::new();
+    let responses = success_responses
+        .into_iter()
+        .map(|r| (r, true))
+        .chain(error_responses.into_iter().map(|r| (r, false)));
+    for ((status_spec, response), success) in responses {
+        let content = response.resolve_fully().content();
+        let variant = format_ident!(
+            "{}",
+            ctx.config.naming().status_to_variant_name(status_spec)
+        );
+        if !success {
+            error_variants.push(variant.clone());
+        }
+        let rate_limit = if !success && status_spec == TOO_MANY_REQUESTS {
+            build_rate_limit_helpers(ctx)?;
+            let fields = rate_limit_header_fields::<S>(&response.resolve_fully())
+                .into_iter()
+                .map(|(field_name, header_name)| {
+                    let field_name = format_ident!("{field_name}");
+                    quote!(#field_name: rate_limit_header(&response, #header_name),)
+                });
+            Some(quote!(retry_after: retry_after(&response), #(#fields)*))
+        } else {
+            None
+        };
+        let wrap = |value: TokenStream| match (success, single_success, &rate_limit) {
+            (true, true, _) => quote!(Ok(#value)),
+            (true, false, _) => quote!(Ok(#success_enum::#variant(#value))),
+            (false, _, Some(rate_limit)) => quote!(Err(#error_enum::#variant {
+                body: #value,
+                #rate_limit
+            })),
+            (false, _, None) => quote!(Err(#error_enum::#variant(#value))),
+        };
+        // mirrors the variants created by build_response_type()
+        let range_body_suffix = match status_spec {
+            StatusSpec::ClientError4XX => Some("ClientError4XXBody"),
+            StatusSpec::ServerError5XX => Some("ServerError5XXBody"),
+            _ => None,
+        };
+        let body = match range_body_suffix {
+            Some(_) if content.is_empty() => wrap(quote!(response.status())),
+            _ => {
+                let content_name = match range_body_suffix {
+                    Some(suffix) => names.type_name.clone() + suffix,
+                    None => content_enum_name(ctx.config.naming(), names, &status_spec),
+                };
+                let decoded = decode_content_ts::<S>(ctx, &content, &content_name);
+                let wrapped = wrap(quote!(value));
+                quote!({
+                    let decoded = #decoded;
+                    match decoded {
+                        Some(Ok(value)) => #wrapped,
+                        Some(Err(e)) => Err(#error_enum::OtherError(e)),
+                        None => Err(#error_enum::UnknownResponse(response)),
+                    }
+                })
+            }
+        };
+        arms.push((status_spec, body));
+    }
+
+    // match arms are tried in order, so more specific ones go first
+    arms.sort_by_key(|(status_spec, _)| match status_spec {
+        StatusSpec::Default => 2,
+        StatusSpec::Informational1XX
+        | StatusSpec::Success2XX
+        | StatusSpec::Redirection3XX
+        | StatusSpec::ClientError4XX
+        | StatusSpec::ServerError5XX => 1,
+        _ => 0,
+    });
+    let has_default = arms
+        .iter()
+        .any(|(status_spec, _)| *status_spec == StatusSpec::Default);
+    let mut arms = arms
+        .into_iter()
+        .map(|(status_spec, body)| {
+            let pattern = match status_spec {
+                StatusSpec::Default => quote!(_),
+                StatusSpec::Informational1XX => quote!(100..=199),
+                StatusSpec::Success2XX => quote!(200..=299),
+                StatusSpec::Redirection3XX => quote!(300..=399),
+                StatusSpec::ClientError4XX => quote!(400..=499),
+                StatusSpec::ServerError5XX => quote!(500..=599),
+                StatusSpec::Informational(code)
+                | StatusSpec::Success(code)
+                | StatusSpec::Redirection(code)
+                | StatusSpec::ClientError(code)
+                | StatusSpec::ServerError(code) => {
+                    let code = proc_macro2::Literal::u16_unsuffixed(code);
+                    quote!(#code)
+                }
+            };
+            quote!(#pattern => #body,)
+        })
+        .collect::<Vec<_>>();
+    if returns_raw_response(ctx, path_op) {
+        arms.push(quote!(_ => Ok(response),));
+    } else if !has_default {
+        arms.push(quote!(_ => Err(#error_enum::UnknownResponse(response)),));
+    }
+
+    let body_type = ctx
+        .cm
+        .type_instance(&ctx.cm.type_vec(), &[ctx.cm.type_u8()]);
+    let response_type = ctx
+        .cm
+        .type_instance(&ctx.cm.type_external("::http::Response"), &[body_type]);
+    let mut body = quote!(
+        match response.status().as_u16() {
+            #(#arms)*
+        }
+    );
+    if ctx.config.emit_tracing {
+        error_variants.push(format_ident!("UnknownResponse"));
+        error_variants.push(format_ident!("OtherError"));
+        body = traced_decoder_body(ctx, names, path, method, &error_variants, body);
+    }
+    let decoder = FunctionBuilder::new(format!("decode_{}_response", names.fn_name), return_type)
+        .visibility(ctx.config.visibility)
+        .param("response".to_string(), response_type)
+        .body(body)
+        .build();
+    Ok(decoder)
+}
+
+/// Wraps the body of an operation's response decoder into a `tracing`
+/// span for the operation (see [ApiConfig::emit_tracing]), recording the
+/// status of the response and the variant of the `{Operation}Error` enum
+/// it decodes to, if any
+fn traced_decoder_body<S: Spec>(
+    ctx: &Context<S>,
+    names: &OperationNames,
+    path: &str,
+    method: &http::Method,
+    error_variants: &[Ident],
+    body: TokenStream,
+) -> TokenStream {
+    let cfg = ctx
+        .config
+        .tracing_feature
+        .as_ref()
+        .map(|feature| quote!(#[cfg(feature = #feature)]));
+    let operation = &names.fn_name;
+    let method = method.as_str();
+    let error_enum = format_ident!("{}Error", names.type_name);
+    let error_kinds = error_variants.iter().map(|variant| variant.to_string());
+    quote!(
+        #cfg
+        let span = ::tracing::span!(
+            target: "cogenitor::client",
+            ::tracing::Level::INFO,
+            "operation",
+            operation = #operation,
+            method = #method,
+            path = #path
+        );
+        #cfg
+        let _entered = span.enter();
+        #cfg
+        ::tracing::event!(
+            target: "cogenitor::client",
+            ::tracing::Level::DEBUG,
+            status = response.status().as_u16(),
+            "response received"
+        );
+        let result = #body;
+        #cfg
+        if let Err(e) = &result {
+            let kind = match e {
+                #(#error_enum::#error_variants { .. } => #error_kinds,)*
+            };
+            ::tracing::event!(
+                target: "cogenitor::client",
+                ::tracing::Level::WARN,
+                error = kind,
+                "operation failed"
+            );
+        }
+        result
+    )
+}
+
+/// Writes an expression decoding the body of `response` according to
+/// `content`, yielding `None` if the response's media type is not declared,
+/// and `Some` result of deserialization otherwise. Mirrors the type mapping
+/// of [map_content], whose enums are named by `content_name`.
+fn decode_content_ts<S: Spec>(
+    ctx: &Context<S>,
+    content: &IndexMap<String, S::MediaType>,
+    content_name: &str,
+) -> TokenStream {
+    let single = match content.len() {
+        0 => return quote!(Some(Ok(()))),
+        1 => content.iter().next(),
+        _ => find_preferred_media_type(&ctx.config, content),
+    };
+    if let Some((media_type_key, media_type)) = single {
+        let decode = decode_media_type_ts::<S>(media_type_key, media_type);
+        return quote!(Some(#decode));
+    }
+
+    // ranges are matched after exact media types
+    let mut media_types = content.iter().collect::<Vec<_>>();
+    media_types.sort_by_key(|(key, _)| (key.contains('*'), key.to_string()));
+    let content_enum = format_ident!("{content_name}");
+    let collapsed = ctx.collapsed_media_types.contains_key(content_name);
+    let arms = media_types.into_iter().map(|(media_type_key, media_type)| {
+        let variant = format_ident!(
+            "{}",
+            ctx.config
+                .naming()
+                .media_type_to_variant_name(media_type_key)
+        );
+        let decode = decode_media_type_ts::<S>(media_type_key, media_type);
+        let decoded = if collapsed {
+            quote!(Some(#decode))
+        } else {
+            quote!(Some(#decode.map(#content_enum::#variant)))
+        };
+        quote!(
+            m if media_type_matches(m, #media_type_key) => {
+                #decoded
+            }
+        )
+    });
+    quote!({
+        let media_type = response_media_type(&response);
+        match media_type.as_str() {
+            #(#arms)*
+            _ => None,
+        }
+    })
+}
+
+/// Writes an expression deserializing the body of `response` as the given
+/// media type into a `Result<_, Box<dyn Error>>`
+fn decode_media_type_ts<S: Spec>(media_type_key: &str, media_type: &S::MediaType) -> TokenStream {
+    if is_binary_media_type::<S>(media_type_key, media_type)
+        || is_event_stream_media_type(media_type_key)
+    {
+        return quote!(Ok::<_, ::std::boxed::Box<dyn ::std::error::Error>>(
+            response.body().clone()
+        ));
+    }
+    if is_text_media_type::<S>(media_type_key, media_type) {
+        return quote!(
+            ::std::string::String::from_utf8(response.body().clone()).map_err(|e| {
+                ::std::boxed::Box::new(e) as ::std::boxed::Box<dyn ::std::error::Error>
+            })
+        );
+    }
+    // ranges other than binary ones are taken for JSON, the only structured
+    // media type we support
+    if is_json_media_type(media_type_key) || media_type_key.contains('*') {
+        quote!(
+            decode_with_path(response.body()).map_err(
+                |e| ::std::boxed::Box::new(e) as ::std::boxed::Box<dyn ::std::error::Error>
+            )
+        )
+    } else {
+        let message = format!("decoding media type '{media_type_key}' is not supported");
+        quote!(Err(::std::boxed::Box::<dyn ::std::error::Error>::from(#message)))
+    }
+}
+
+/// Whether content of a `text/*` media type is plain text, mapping to a
+/// `String`: its schema is an inline string schema without any enum or
+/// format mapping it to another type
+fn is_text_media_type<S: Spec>(media_type_key: &str, media_type: &S::MediaType) -> bool {
+    let is_text = media_type_key
+        .split_once('/')
+        .is_some_and(|(type_, _)| type_.eq_ignore_ascii_case("text"));
+    let Some(schema) = media_type.schema().and_then(|schema| schema.as_object()) else {
+        return false;
+    };
+    let maps_to_string = match schema.format() {
+        None | Some(types::Format::Date | types::Format::DateTime | types::Format::Password) => {
+            true
+        }
+        Some(types::Format::Custom(_)) => true,
+        Some(_) => false,
+    };
+    is_text
+        && schema.type_() == Some(vec![types::Type::String])
+        && maps_to_string
+        && schema.enum_().is_none()
+        && schema.const_value().is_none()
+        && schema.extension_value("x-rust-type").is_none()
+}
+
+/// Writes the route of an operation for the `router()` generated with
+/// [ServerFramework::Axum]: its handler extracts the operation's `inputs`
+/// from the request, calls the `Client` implementation and encodes the
+/// result as response, mirroring [build_response_type]. Operations the
+/// router cannot serve are reported and yield `None`.
+#[allow(clippy::too_many_arguments)]
+fn build_axum_route<S: Spec>(
+    ctx: &Context<S>,
+    names: &OperationNames,
+    path: &str,
+    method: &http::Method,
+    path_op: &S::Operation,
+    inputs: &[OperationInput],
+    bundle_inputs: bool,
+) -> Option<TokenStream> {
+    let skip = |reason: &str| {
+        log::warn!("not routing {method} {path} in the generated router: {reason}");
+        None
+    };
+
+    const METHOD_FILTERS: [&str; 9] = [
+        "GET", "POST", "PUT", "DELETE", "PATCH", "HEAD", "OPTIONS", "TRACE", "CONNECT",
+    ];
+    if !METHOD_FILTERS.contains(&method.as_str()) {
+        return skip("axum does not route the method");
+    }
+    // axum only captures whole segments, and takes segments starting with
+    // `:` or `*` for captures of an older syntax
+    let unroutable_segment = path.split('/').any(|segment| {
+        let capture = segment
+            .strip_prefix('{')
+            .and_then(|s| s.strip_suffix('}'))
+            .is_some_and(|name| !name.contains(['{', '}', '*']));
+        segment.starts_with([':', '*']) || (segment.contains(['{', '}']) && !capture)
+    });
+    if unroutable_segment {
+        return skip("the path template has segments axum cannot capture");
+    }
+    if bundle_inputs {
+        return skip("its inputs are bundled into a params struct");
+    }
+
+    let mut extractors = Vec::new();
+    let mut extracted = Vec::new();
+    let mut args = Vec::new();
+    let mut with_path = false;
+    let mut with_query = false;
+    let mut with_headers = false;
+    let mut with_body = false;
+    for input in inputs {
+        let name = format_ident!("{}", input.name);
+        let spec_name = &input.spec_name;
+        let value = match input.location {
+            Some(ParameterLocation::Path) => {
+                with_path = true;
+                quote!(path_params.get(#spec_name).map(::std::string::String::as_str))
+            }
+            Some(ParameterLocation::Query) => {
+                with_query = true;
+                quote!(query_params.get(#spec_name).map(::std::string::String::as_str))
+            }
+            Some(ParameterLocation::Header) => {
+                with_headers = true;
+                quote!(headers.get(#spec_name).and_then(|value| value.to_str().ok()))
+            }
+            Some(ParameterLocation::Cookie) => {
+                return skip("cookie parameters are not supported");
+            }
+            None => {
+                let request_body = path_op
+                    .request_body()
+                    .expect("body input of an operation with a request body")
+                    .resolve_fully();
+                let (content, _) = request_body_content::<S>(&request_body);
+                let mut media_types = content.iter().collect::<Vec<_>>();
+                media_types.sort_by_key(|(key, _)| *key);
+                let content_name = names.type_name.clone() + "Content";
+                if ctx.collapsed_media_types.contains_key(&content_name) {
+                    // any media type will do, as all map to the same type
+                } else if let Some(single) = find_preferred_media_type(&ctx.config, &content) {
+                    media_types = vec![single];
+                } else if media_types.len() > 1 {
+                    return skip("request bodies of several media types are not supported");
+                }
+                with_body = true;
+                let decoded = if media_types.is_empty() {
+                    quote!(())
+                } else if media_types.iter().all(|(key, media_type)| {
+                    is_binary_media_type::<S>(key, media_type) || is_event_stream_media_type(key)
+                }) {
+                    quote!(body.to_vec())
+                } else if media_types.iter().all(|(key, _)| is_json_media_type(key)) {
+                    quote!(router_body(&body)?)
+                } else {
+                    return skip("only JSON and binary request bodies are supported");
+                };
+                extracted.push(quote!(let #name = #decoded;));
+                args.push(name);
+                continue;
+            }
+        };
+        let string_valued = input.string_valued;
+        extracted.push(quote!(let #name = router_param(#spec_name, #value, #string_valued)?;));
+        args.push(name);
+    }
+    // the body is the only extractor consuming the request, so goes last
+    let string_map =
+        quote!(::std::collections::HashMap<::std::string::String, ::std::string::String>);
+    if with_path {
+        extractors
+            .push(quote!(::axum::extract::Path(path_params): ::axum::extract::Path<#string_map>));
+    }
+    if with_query {
+        extractors.push(
+            quote!(::axum::extract::Query(query_params): ::axum::extract::Query<#string_map>),
+        );
+    }
+    if with_headers {
+        extractors.push(quote!(headers: ::axum::http::HeaderMap));
+    }
+    if with_body {
+        extractors.push(quote!(body: ::axum::body::Bytes));
+    }
+
+    let (success_responses, error_responses) = partition_responses::<S>(path_op);
+    let success_enum = format_ident!("{}Success", names.type_name);
+    let error_enum = format_ident!("{}Error", names.type_name);
+    let success_arm = match success_responses.as_slice() {
+        [] if returns_raw_response(ctx, path_op) => {
+            quote!(Ok(response) => response.map(::axum::body::Body::from),)
+        }
+        [] => quote!(Ok(()) => router_response(200, Ok(None)),),
+        [(status_spec, response)] => {
+            let content = response.resolve().resolve_fully().content();
+            let content_name = content_enum_name(ctx.config.naming(), names, status_spec);
+            let encoded = encode_content_ts::<S>(ctx, &content, &content_name, status_spec);
+            quote!(Ok(value) => #encoded,)
+        }
+        success_responses => {
+            let arms = success_responses.iter().map(|(status_spec, response)| {
+                let content = response.resolve_fully().content();
+                let variant = format_ident!(
+                    "{}",
+                    ctx.config.naming().status_to_variant_name(*status_spec)
+                );
+                let content_name = content_enum_name(ctx.config.naming(), names, status_spec);
+                let encoded = encode_content_ts::<S>(ctx, &content, &content_name, status_spec);
+                quote!(#success_enum::#variant(value) => #encoded,)
+            });
+            quote!(Ok(value) => match value {
+                #(#arms)*
+            },)
+        }
+    };
+    // mirrors the variants created by build_response_type()
+    let mut error_arms = Vec::new();
+    for (status_spec, response) in &error_responses {
+        let content = response.resolve_fully().content();
+        let variant = format_ident!(
+            "{}",
+            ctx.config.naming().status_to_variant_name(*status_spec)
+        );
+        let range_body_suffix = match status_spec {
+            StatusSpec::ClientError4XX => Some("ClientError4XXBody"),
+            StatusSpec::ServerError5XX => Some("ServerError5XXBody"),
+            _ => None,
+        };
+        let content_name = match range_body_suffix {
+            Some(suffix) => names.type_name.clone() + suffix,
+            None => content_enum_name(ctx.config.naming(), names, status_spec),
+        };
+        let encoded = encode_content_ts::<S>(ctx, &content, &content_name, status_spec);
+        let arm = match range_body_suffix {
+            Some(_) if content.is_empty() => quote!(
+                #error_enum::#variant(status) => router_response(status.as_u16(), Ok(None)),
+            ),
+            None if *status_spec == TOO_MANY_REQUESTS => {
+                let (fields, header_names): (Vec<_>, Vec<_>) =
+                    rate_limit_header_fields::<S>(&response.resolve_fully())
+                        .into_iter()
+                        .map(|(field_name, header_name)| {
+                            (format_ident!("{field_name}"), header_name)
+                        })
+                        .unzip();
+                quote!(
+                    #error_enum::#variant { body: value, retry_after, #(#fields),* } => {
+                        let mut response = #encoded;
+                        let headers = response.headers_mut();
+                        if let Some(retry_after) = retry_after {
+                            headers.insert(::axum::http::header::RETRY_AFTER, retry_after.as_secs().into());
+                        }
+                        #(
+                            if let Some(value) = #fields.and_then(|value| router_header_value(&value)) {
+                                headers.insert(#header_names, value);
+                            }
+                        )*
+                        response
+                    }
+                )
+            }
+            _ => quote!(#error_enum::#variant(value) => #encoded,),
+        };
+        error_arms.push(arm);
+    }
+
+    // the method is one of METHOD_FILTERS, so the conversion cannot fail
+    let method = codewriter::write_http_method(method);
+    let fn_name = format_ident!("{}", names.fn_name);
+    Some(quote!(
+        let router = router.route(#path, ::axum::routing::on(::axum::routing::MethodFilter::try_from(#method).expect("method is routable"), {
+            let api = api.clone();
+            move |#(#extractors),*| async move {
+                // the API is synchronous, so must not block the runtime
+                let handled = ::tokio::task::spawn_blocking(move || {
+                    let handle = || -> ::std::result::Result<::axum::response::Response, ::axum::response::Response> {
+                        #(#extracted)*
+                        Ok(match api.#fn_name(#(#args),*) {
+                            #success_arm
+                            Err(error) => match error {
+                                #(#error_arms)*
+                                #error_enum::UnknownResponse(response) => response.map(::axum::body::Body::from),
+                                #error_enum::OtherError(e) => router_response(500, Err(e.to_string())),
+                            },
+                        })
+                    };
+                    handle().unwrap_or_else(|response| response)
+                })
+                .await;
+                handled.unwrap_or_else(|e| router_response(500, Err(e.to_string())))
+            }
+        }));
+    ))
+}
+
+/// The status code a response declared for `status_spec` is sent with:
+/// ranges send their first code, `default` sends `200` for success and
+/// `500` for errors
+fn status_code_of(status_spec: &StatusSpec) -> u16 {
+    match status_spec {
+        StatusSpec::Informational(code)
+        | StatusSpec::Success(code)
+        | StatusSpec::Redirection(code)
+        | StatusSpec::ClientError(code)
+        | StatusSpec::ServerError(code) => *code,
+        StatusSpec::Informational1XX => 100,
+        StatusSpec::Success2XX => 200,
+        StatusSpec::Redirection3XX => 300,
+        StatusSpec::ClientError4XX => 400,
+        StatusSpec::ServerError5XX => 500,
+        StatusSpec::Default => 200,
+    }
+}
+
+/// Writes an expression encoding `value` according to `content` into the
+/// response to send for `status_spec`. Mirrors the type mapping of
+/// [map_content], whose enums are named by `content_name`.
+fn encode_content_ts<S: Spec>(
+    ctx: &Context<S>,
+    content: &IndexMap<String, S::MediaType>,
+    content_name: &str,
+    status_spec: &StatusSpec,
+) -> TokenStream {
+    let status = match status_spec {
+        StatusSpec::Default if !is_success(*status_spec) => 500,
+        status_spec => status_code_of(status_spec),
+    };
+    let mut media_types = content.iter().collect::<Vec<_>>();
+    media_types.sort_by_key(|(key, _)| (!is_json_media_type(key), key.to_string()));
+    let single = match content.len() {
+        0 => return quote!(router_response(#status, { let _ = value; Ok(None) })),
+        1 => content.iter().next(),
+        _ if ctx.collapsed_media_types.contains_key(content_name) => media_types.first().copied(),
+        _ => find_preferred_media_type(&ctx.config, content),
+    };
+    if let Some((media_type_key, media_type)) = single {
+        let encoded = encode_media_type_ts::<S>(media_type_key, media_type);
+        return quote!(router_response(#status, #encoded));
+    }
+
+    let content_enum = format_ident!("{content_name}");
+    let arms = media_types.into_iter().map(|(media_type_key, media_type)| {
+        let variant = format_ident!(
+            "{}",
+            ctx.config
+                .naming()
+                .media_type_to_variant_name(media_type_key)
+        );
+        let encoded = encode_media_type_ts::<S>(media_type_key, media_type);
+        quote!(#content_enum::#variant(value) => #encoded,)
+    });
+    quote!(router_response(#status, match value {
+        #(#arms)*
+    }))
+}
+
+/// Writes an expression serializing `value` as the given media type into
+/// a `Result` of the content type and body to send, if any
+fn encode_media_type_ts<S: Spec>(media_type_key: &str, media_type: &S::MediaType) -> TokenStream {
+    // ranges are no content type to send
+    if is_binary_media_type::<S>(media_type_key, media_type)
+        || is_event_stream_media_type(media_type_key)
+    {
+        let content_type = match media_type_key.contains('*') {
+            true => "application/octet-stream",
+            false => media_type_key,
+        };
+        return quote!(Ok(Some((#content_type, value))));
+    }
+    // like when decoding, ranges other than binary ones are taken for JSON
+    if is_json_media_type(media_type_key) || media_type_key.contains('*') {
+        let content_type = match media_type_key.contains('*') {
+            true => "application/json",
+            false => media_type_key,
+        };
+        quote!(
+            ::serde_json::to_vec(&value)
+                .map(|bytes| Some((#content_type, bytes)))
+                .map_err(|e| e.to_string())
+        )
+    } else {
+        log::warn!(
+            "encoding media type '{media_type_key}' is not supported by the generated router, responding with status 500 instead"
+        );
+        let message = format!("encoding media type '{media_type_key}' is not supported");
+        quote!({
+            let _ = value;
+            Err(#message.to_string())
+        })
+    }
+}
+
+/// Creates the `router()` function of [ServerFramework::Axum] from the
+/// `routes` built by [build_axum_route], and the helpers they use
+fn build_axum_router<S: Spec>(
+    ctx: &mut Context<S>,
+    routes: Vec<TokenStream>,
+) -> anyhow::Result<()> {
+    let visibility = ctx.config.visibility;
+    ctx.m.insert_raw_item(
+        "router",
+        quote!(
+            /// An `axum::Router` serving the API's operations by passing
+            /// requests on to `api`
+            #visibility fn router<T>(api: T) -> ::axum::Router
+            where
+                T: Client + ::std::clone::Clone + ::std::marker::Send + ::std::marker::Sync + 'static,
+            {
+                let router = ::axum::Router::new();
+                #(#routes)*
+                router
+            }
+        ),
+    )?;
+    ctx.m.insert_raw_item(
+        "router_response",
+        quote!(
+            /// The response with the given status and encoded content, or
+            /// an internal server error if encoding failed
+            fn router_response(
+                status: u16,
+                encoded: ::std::result::Result<
+                    ::std::option::Option<(&str, ::std::vec::Vec<u8>)>,
+                    ::std::string::String,
+                >,
+            ) -> ::axum::response::Response {
+                use ::axum::response::IntoResponse;
+                let status = ::axum::http::StatusCode::from_u16(status)
+                    .unwrap_or(::axum::http::StatusCode::INTERNAL_SERVER_ERROR);
+                match encoded {
+                    Ok(None) => status.into_response(),
+                    Ok(Some((content_type, bytes))) => (
+                        status,
+                        [(::axum::http::header::CONTENT_TYPE, content_type.to_string())],
+                        bytes,
+                    )
+                        .into_response(),
+                    Err(message) => {
+                        (::axum::http::StatusCode::INTERNAL_SERVER_ERROR, message).into_response()
+                    }
+                }
             }
-
-            if !build_for_success {
-                e = e.tuple_variant_with_input(
-                    "UnknownResponse",
-                    vec![quote!(::http::Response<::std::vec::Vec<u8>>)],
-                )?;
-                e = e.tuple_variant_with_input(
-                    "OtherError",
-                    vec![quote!(::std::boxed::Box<dyn ::std::error::Error>)],
-                )?
+        ),
+    )?;
+    ctx.m.insert_raw_item(
+        "router_param",
+        quote!(
+            /// Deserializes a parameter's value from the string it is if
+            /// the parameter is `string_valued`, and otherwise from JSON, or
+            /// the string if it is no JSON. Absent parameters are `null`.
+            fn router_param<T: ::serde::de::DeserializeOwned>(
+                name: &str,
+                value: ::std::option::Option<&str>,
+                string_valued: bool,
+            ) -> ::std::result::Result<T, ::axum::response::Response> {
+                let parsed = match value {
+                    None => ::serde_json::from_value(::serde_json::Value::Null),
+                    Some(value) if string_valued => {
+                        ::serde_json::from_value(::serde_json::Value::String(value.to_string()))
+                    }
+                    Some(value) => ::serde_json::from_str(value).or_else(|_| {
+                        ::serde_json::from_value(::serde_json::Value::String(value.to_string()))
+                    }),
+                };
+                parsed.map_err(|e| {
+                    let message = format!("invalid parameter '{name}': {e}");
+                    router_response(400, Ok(Some(("text/plain", message.into_bytes()))))
+                })
+            }
+        ),
+    )?;
+    ctx.m.insert_raw_item(
+        "router_body",
+        quote!(
+            /// Deserializes a JSON request body, taking an empty one for
+            /// `null`
+            fn router_body<T: ::serde::de::DeserializeOwned>(
+                body: &[u8],
+            ) -> ::std::result::Result<T, ::axum::response::Response> {
+                let parsed = match body.is_empty() {
+                    true => ::serde_json::from_value(::serde_json::Value::Null),
+                    false => ::serde_json::from_slice(body),
+                };
+                parsed.map_err(|e| {
+                    let message = format!("invalid request body: {e}");
+                    router_response(400, Ok(Some(("text/plain", message.into_bytes()))))
+                })
             }
+        ),
+    )?;
+    ctx.m.insert_raw_item(
+        "router_header_value",
+        quote!(
+            /// The header value for `value`, like a rate limit field of an
+            /// error variant: the string it is if it serializes to a string,
+            /// and its JSON otherwise
+            fn router_header_value<T: ::serde::Serialize>(
+                value: &T,
+            ) -> ::std::option::Option<::axum::http::HeaderValue> {
+                let value = match ::serde_json::to_value(value).ok()? {
+                    ::serde_json::Value::String(value) => value,
+                    value => value.to_string(),
+                };
+                ::axum::http::HeaderValue::try_from(value).ok()
+            }
+        ),
+    )?;
+    Ok(())
+}
 
-            ctx.m.insert_enum(e.build()?)?
-        }
-    };
-    Ok(type_ref)
+fn is_success(status_spec: StatusSpec) -> bool {
+    matches!(
+        status_spec,
+        types::StatusSpec::Informational(_)
+            | types::StatusSpec::Informational1XX
+            | types::StatusSpec::Success(_)
+            | types::StatusSpec::Success2XX
+            | types::StatusSpec::Redirection(_)
+            | types::StatusSpec::Redirection3XX
+    )
 }
 
-fn content_enum_name(method: &http::Method, path_name: &str, status_spec: &StatusSpec) -> String {
-    let prefix = translate::path_method_to_rust_type_name(method.clone(), path_name);
-    prefix + translate::status_spec_to_rust_type_name(status_spec.clone()).as_str()
+fn content_enum_name(
+    naming: &dyn NamingPolicy,
+    names: &OperationNames,
+    status_spec: &StatusSpec,
+) -> String {
+    names.type_name.clone() + naming.status_to_variant_name(*status_spec).as_str()
 }
 
 fn map_content<S: Spec>(
     ctx: &mut Context<S>,
-    content: &HashMap<String, S::MediaType>,
+    content: &IndexMap<String, S::MediaType>,
     content_name_fn: impl Fn() -> String,
 ) -> anyhow::Result<TypeRef> {
     let mapped_type;
@@ -530,30 +5624,117 @@ fn map_content<S: Spec>(
             let (media_type_key, media_type) = content.iter().next().unwrap();
             mapped_type = map_media_type::<S>(ctx, media_type_key, media_type, content_name_fn);
         }
-        _ => {
-            mapped_type = map_enum_from_content::<S>(ctx, content, content_name_fn)?;
-        }
+        _ => match preferred_media_type(&ctx.config, content) {
+            Some((media_type_key, media_type)) => {
+                mapped_type = map_media_type::<S>(ctx, media_type_key, media_type, content_name_fn);
+            }
+            None => {
+                mapped_type = map_enum_from_content::<S>(ctx, content, content_name_fn)?;
+            }
+        },
     };
     Ok(mapped_type)
 }
 
+/// The media type to generate code for from content offering several, if
+/// [MediaTypeStrategy::PreferSingle] is configured: the first of the
+/// [ApiConfig::preferred_media_types] offered. Media type parameters (like
+/// `charset`) are ignored when matching.
+fn preferred_media_type<'a, M>(
+    config: &ApiConfig,
+    content: &'a IndexMap<String, M>,
+) -> Option<(&'a String, &'a M)> {
+    if config.media_type_strategy != MediaTypeStrategy::PreferSingle {
+        return None;
+    }
+    let mut offered = content.keys().collect::<Vec<_>>();
+    offered.sort();
+    let preferred = find_preferred_media_type(config, content);
+    match preferred {
+        Some((key, _)) => {
+            for dropped in offered.iter().filter(|k| **k != key) {
+                log::debug!("dropping media type '{dropped}' in favor of '{key}'");
+            }
+        }
+        None => log::warn!(
+            "none of the preferred media types {:?} is offered by content with media types {offered:?}, generating an enum for all of them",
+            config.preferred_media_types
+        ),
+    }
+    preferred
+}
+
+/// Like [preferred_media_type], but without reporting the media types
+/// dropped
+fn find_preferred_media_type<'a, M>(
+    config: &ApiConfig,
+    content: &'a IndexMap<String, M>,
+) -> Option<(&'a String, &'a M)> {
+    if config.media_type_strategy != MediaTypeStrategy::PreferSingle {
+        return None;
+    }
+    offered_preferred_media_type(config, content)
+}
+
+/// The first of the [ApiConfig::preferred_media_types] offered by the
+/// content, whatever the [MediaTypeStrategy]
+fn offered_preferred_media_type<'a, M>(
+    config: &ApiConfig,
+    content: &'a IndexMap<String, M>,
+) -> Option<(&'a String, &'a M)> {
+    config.preferred_media_types.iter().find_map(|preferred| {
+        content.iter().find(|(key, _)| {
+            let essence = key.split(';').next().unwrap_or_default().trim();
+            essence.eq_ignore_ascii_case(preferred)
+        })
+    })
+}
+
 fn map_enum_from_content<S: Spec>(
     ctx: &mut Context<S>,
-    content: &HashMap<String, S::MediaType>,
+    content: &IndexMap<String, S::MediaType>,
     content_name_fn: impl Fn() -> String,
 ) -> anyhow::Result<TypeRef> {
     // TODO: disambiguate!
     let enum_name = content_name_fn();
-    let mut e = EnumBuilder::new(&enum_name);
 
-    for (media_type_key, media_type) in content.iter() {
-        let variant_name = translate::media_type_range_to_rust_type_name(media_type_key);
+    // variants are ordered by media type, as the content map's order is not
+    // stable
+    let mut content = content.iter().collect::<Vec<_>>();
+    content.sort_by_key(|(media_type_key, _)| *media_type_key);
+    let mut variants = Vec::new();
+    for (media_type_key, media_type) in content {
+        let variant_name = ctx
+            .config
+            .naming()
+            .media_type_to_variant_name(media_type_key);
         let content_variant_name_fn = || enum_name.clone() + variant_name.as_str();
         let variant_type =
             map_media_type::<S>(ctx, media_type_key, media_type, content_variant_name_fn);
-        e = e.tuple_variant(&variant_name, vec![variant_type])?;
+        variants.push((media_type_key, variant_name, variant_type));
     }
 
+    // several media types of the same type make the enum pure noise
+    let collapse = ctx.config.media_type_strategy == MediaTypeStrategy::CollapseIdentical
+        && !variants.is_empty()
+        && variants
+            .windows(2)
+            .all(|pair| pair[0].2.name() == pair[1].2.name());
+    if collapse {
+        let media_types = variants
+            .iter()
+            .map(|(media_type_key, _, _)| media_type_key.to_string())
+            .collect::<Vec<_>>();
+        log::debug!("collapsing media types {media_types:?} of '{enum_name}' to a single type");
+        ctx.collapsed_media_types.insert(enum_name, media_types);
+        let (_, _, type_ref) = variants.swap_remove(0);
+        return Ok(type_ref);
+    }
+
+    let mut e = EnumBuilder::new(&enum_name).visibility(ctx.config.visibility);
+    for (_, variant_name, variant_type) in variants {
+        e = e.tuple_variant(&variant_name, vec![variant_type])?;
+    }
     let e = e.build()?;
     Ok(ctx.m.insert_enum(e)?)
 }
@@ -564,6 +5745,26 @@ fn map_media_type<S: Spec>(
     media_type: &S::MediaType,
     schema_name_fn: impl Fn() -> String,
 ) -> TypeRef {
+    // event streams are raw bytes, unless the events are parsed by the
+    // operation's `_events()` variant
+    if is_event_stream_media_type(media_type_key) {
+        if !ctx.config.sse_events {
+            log::warn!(
+                "server-sent events are not parsed unless enabled by the 'sse_events' option, mapping media type '{media_type_key}' to Vec<u8>"
+            );
+        }
+        return ctx
+            .cm
+            .type_instance(&ctx.cm.type_vec(), &[ctx.cm.type_u8()]);
+    }
+
+    // binary content is passed on as is, regardless of its schema
+    if is_binary_media_type::<S>(media_type_key, media_type) {
+        return ctx
+            .cm
+            .type_instance(&ctx.cm.type_vec(), &[ctx.cm.type_u8()]);
+    }
+
     match media_type.schema() {
         Some(schema) => {
             let schema = schema.resolve();
@@ -577,34 +5778,95 @@ fn map_media_type<S: Spec>(
     }
 }
 
-fn derive_function_param_name(name_candidate: &str, function: &FunctionBuilder) -> String {
-    let existing_names = function.param_names();
+impl translate::ContainsPredicate for Module {
+    fn contains_str(&self, item: &str) -> bool {
+        self.contains_item(item)
+    }
+}
+
+/// The name of the type generated for an inline schema: its `title`, made
+/// unique within the module, or the candidate name derived from the
+/// schema's location if there is no title (or titles are ignored, see
+/// [ApiConfig::ignore_schema_titles]).
+fn inline_schema_name<S: Spec>(
+    ctx: &mut Context<S>,
+    schema: &S::Schema,
+    candidate_name: &str,
+) -> String {
+    match schema.title() {
+        Some(title) if !ctx.config.ignore_schema_titles => ctx
+            .names
+            .uncollide(&ctx.m, ctx.config.naming().schema_to_type_name(title)),
+        _ => candidate_name.to_string(),
+    }
+}
+
+/// Derives the function parameter name of an input, unique among the
+/// `inputs` so far. Parameters of the same name passed in different
+/// locations are told apart by the location: a path and a query parameter
+/// `id` become `id_path` and `id_query`, renaming the input taken first.
+/// Other collisions are resolved by a numeric suffix.
+fn derive_function_param_name(
+    name_candidate: &str,
+    location: Option<ParameterLocation>,
+    inputs: &mut [OperationInput],
+) -> String {
+    let mut mapped_name = translate::parameter_to_rust_fn_param(name_candidate);
+    if let Some(location) = location {
+        let with_suffix = |name: &str, location: ParameterLocation| {
+            let suffix = translate::parameter_location_to_suffix(location);
+            format!("{name}_{suffix}")
+        };
+        let mut split_by_location = false;
+        for input in inputs.iter_mut() {
+            match input.location {
+                Some(other) if other != location && input.name == mapped_name => {
+                    input.name = with_suffix(&mapped_name, other);
+                    split_by_location = true;
+                }
+                Some(other)
+                    if other != location && input.name == with_suffix(&mapped_name, other) =>
+                {
+                    split_by_location = true;
+                }
+                _ => {}
+            }
+        }
+        if split_by_location {
+            mapped_name = with_suffix(&mapped_name, location);
+        }
+    }
+
+    // 'self' is always taken by the receiver of the generated function
+    let existing_names = std::iter::once("self")
+        .chain(inputs.iter().map(|input| input.name.as_str()))
+        .collect::<Vec<&str>>();
 
-    let mapped_name = translate::parameter_to_rust_fn_param(name_candidate);
     translate::uncollide(&existing_names, mapped_name)
 }
 
-/// Append a an OAS operation parameter as rust function parameter
-/// to the given FunctionBuilder, while respecting Rust
+/// Append a an OAS operation parameter as operation input
+/// to the given list, while respecting Rust
 /// conventions and naming uniqueness constraints. This may lead
 /// to different names than those in the spec.
 fn append_param<S: Spec>(
     ctx: &mut Context<S>,
-    function: FunctionBuilder,
+    inputs: &mut Vec<OperationInput>,
     param: &S::Parameter,
     param_type_name_fn: impl Fn(&S::Parameter) -> String,
-) -> anyhow::Result<FunctionBuilder> {
-    let mapped_name = derive_function_param_name(param.name(), &function);
+) -> anyhow::Result<()> {
+    let mapped_name = derive_function_param_name(param.name(), Some(param.in_()), inputs);
 
     // TODO: params are incredibly complex in OAS. Currently we ignore most
     // of this complexity, however, it may severely impact the way parameters
     // are serialized. See the sections in the spec, starting from here:
     // https://spec.openapis.org/oas/v3.0.4.html#x4-7-12-2-2-fixed-fields-for-use-with-schema
-    let candidate_param_type_name = param_type_name_fn(param);
+    let candidate_param_type_name = ctx.names.uncollide(&ctx.m, param_type_name_fn(param));
     let mapped_type;
     if let Some(schema) = param.schema() {
         mapped_type = type_ref_of(ctx, &schema, &candidate_param_type_name)?;
     } else if let Some(content) = param.content() {
+        let content = param_content_media_type(&ctx.config, param.name(), content)?;
         mapped_type = map_content(ctx, &content, || candidate_param_type_name.clone())?;
     } else {
         return Err(anyhow!(
@@ -614,7 +5876,728 @@ fn append_param<S: Spec>(
     }
 
     // finally add parameter
-    Ok(function.param(mapped_name, mapped_type))
+    inputs.push(OperationInput {
+        name: mapped_name,
+        spec_name: param.name().to_string(),
+        location: Some(param.in_()),
+        type_ref: mapped_type,
+        required: param.required(),
+        nullable: param
+            .schema()
+            .is_some_and(|s| s.as_object().is_some_and(|s| s.nullable())),
+        string_valued: param.schema().is_some_and(|s| {
+            s.resolve_fully()
+                .type_()
+                .is_some_and(|type_| type_.contains(&types::Type::String))
+        }),
+        example: param.example(),
+    });
+    Ok(())
+}
+
+/// The builtin type of a `number` or `integer` schema. Integers of a format
+/// we do not recognize map to `i64`, as a float would round large values
+/// like IDs.
+fn number_type<S: Spec>(ctx: &Context<S>, schema: &S::Schema) -> TypeRef {
+    match schema.format() {
+        Some(types::Format::Int8) => ctx.cm.type_i8(),
+        Some(types::Format::Int16) => ctx.cm.type_i16(),
+        Some(types::Format::Int32) => ctx.cm.type_i32(),
+        Some(types::Format::Int64) => ctx.cm.type_i64(),
+        Some(types::Format::UInt8) => ctx.cm.type_u8(),
+        Some(types::Format::UInt16) => ctx.cm.type_u16(),
+        Some(types::Format::UInt32) => ctx.cm.type_u32(),
+        Some(types::Format::UInt64) => ctx.cm.type_u64(),
+        Some(types::Format::Float) => ctx.cm.type_f32(),
+        // decimal properties are mapped before (see decimal_type)
+        Some(types::Format::Decimal) if ctx.config.typed_formats => {
+            log::warn!(
+                "mapping decimal number to f64, which may lose precision, as only properties map to rust_decimal::Decimal"
+            );
+            ctx.cm.type_f64()
+        }
+        Some(types::Format::Decimal) => {
+            log::warn!(
+                "mapping decimal number to f64, which may lose precision; enable 'typed_formats' to map it to rust_decimal::Decimal"
+            );
+            ctx.cm.type_f64()
+        }
+        Some(types::Format::Custom(format)) if schema.is_integer() => {
+            log::warn!("unrecognized integer format '{format}', mapping to i64");
+            ctx.cm.type_i64()
+        }
+        Some(types::Format::Custom(format)) => {
+            log::warn!("unrecognized number format '{format}', mapping to f64");
+            ctx.cm.type_f64()
+        }
+        _ => ctx.cm.type_f64(),
+    }
+}
+
+/// Reduces the `content` of a parameter to a single media type, as a
+/// parameter value is serialized in one media type only (a JSON string for
+/// `application/json`). Picks the first of the
+/// [ApiConfig::preferred_media_types] offered, regardless of the
+/// [MediaTypeStrategy], then a JSON media type, then the first media type
+/// in alphabetical order. Fails instead with
+/// [ApiConfig::strict_param_content].
+fn param_content_media_type<M>(
+    config: &ApiConfig,
+    param_name: &str,
+    mut content: IndexMap<String, M>,
+) -> anyhow::Result<IndexMap<String, M>> {
+    if content.len() <= 1 {
+        return Ok(content);
+    }
+    let mut offered = content.keys().cloned().collect::<Vec<_>>();
+    offered.sort();
+    if config.strict_param_content {
+        return Err(anyhow!(
+            "parameter '{param_name}' offers several media types {offered:?}, but a parameter is serialized in one only"
+        ));
+    }
+    let picked = offered_preferred_media_type(config, &content)
+        .map(|(key, _)| key.clone())
+        .or_else(|| offered.iter().find(|key| is_json_media_type(key)).cloned())
+        .unwrap_or_else(|| offered[0].clone());
+    let ignored = offered
+        .iter()
+        .filter(|key| **key != picked)
+        .collect::<Vec<_>>();
+    log::warn!(
+        "parameter '{param_name}' is serialized as '{picked}', ignoring media types {ignored:?}"
+    );
+    let media_type = content
+        .shift_remove(&picked)
+        .expect("picked from the offered media types");
+    Ok(IndexMap::from([(picked, media_type)]))
+}
+
+/// The type given by a property's `x-rust-type` extension (like
+/// `uuid::Uuid`), which is used as is instead of the type derived from the
+/// schema. The crate embedding the generated code must provide it, with
+/// serde support.
+fn rust_type_override(schema: &impl Schema) -> anyhow::Result<Option<TypeRef>> {
+    let Some(value) = schema.extension_value("x-rust-type") else {
+        return Ok(None);
+    };
+    let rust_type = value
+        .as_str()
+        .ok_or(anyhow!("x-rust-type must be a string, found {value}"))?;
+    if let Err(e) = syn::parse_str::<syn::Type>(rust_type) {
+        return Err(anyhow!("x-rust-type '{rust_type}' is not a Rust type: {e}"));
+    }
+    Ok(Some(TypeRef::External(rust_type.into())))
+}
+
+/// The modules to (de)serialize a field with, by `#[serde(with = ...)]`,
+/// if the field has the type they are for (`plain`) or an `Option` of it
+struct SerdeWith {
+    plain: String,
+    option: String,
+}
+
+/// The type of a property holding a `byte` or `binary` string of fixed
+/// length, see [ApiConfig::fixed_size_bytes_limit]. Arrays come with the
+/// generated module to (de)serialize them with, as serde only supports
+/// arrays of up to 32 elements and does not check their length, and
+/// `byte` strings are base64 encoded.
+/// Yields `None` for other (and referenced) schemas.
+fn fixed_size_bytes_type<S: Spec>(
+    ctx: &mut Context<S>,
+    schema: &RefOr<S::Schema>,
+) -> anyhow::Result<Option<(TypeRef, Option<SerdeWith>)>> {
+    let Some(limit) = ctx.config.fixed_size_bytes_limit else {
+        return Ok(None);
+    };
+    let Some(schema) = schema.as_object() else {
+        return Ok(None);
+    };
+    let base64 = match schema.format() {
+        Some(types::Format::Byte) => true,
+        Some(types::Format::Binary) => false,
+        _ => return Ok(None),
+    };
+    let len = match (schema.min_length(), schema.max_length()) {
+        (Some(min), Some(max)) if min == max => min,
+        _ => return Ok(None),
+    };
+    // base64 encodes 3 bytes in 4 characters, and 1 or 2 remaining bytes
+    // in 2 or 3 characters; no number of bytes takes 1 remaining character
+    let len = match (base64, len % 4) {
+        (false, _) => len,
+        (true, 1) => {
+            log::warn!("{len} characters are no base64 encoding without padding");
+            return Ok(None);
+        }
+        (true, rest) => len / 4 * 3 + rest.saturating_sub(1),
+    };
+    if base64 {
+        insert_base64_helper(ctx)?;
+    }
+    if len > limit {
+        let vec_u8 = ctx
+            .cm
+            .type_instance(&ctx.cm.type_vec(), &[ctx.cm.type_u8()]);
+        let with = base64.then(|| SerdeWith {
+            plain: "base64_bytes".to_string(),
+            option: "base64_bytes::option".to_string(),
+        });
+        return Ok(Some((vec_u8, with)));
+    }
+
+    let helper = if base64 {
+        format!("fixed_base64_bytes_{len}")
+    } else {
+        format!("fixed_bytes_{len}")
+    };
+    if !ctx.m.raw_item_iter().any(|item| item.name() == helper) {
+        let ident = Ident::new(&helper, proc_macro2::Span::call_site());
+        let expected = format!("{len} bytes");
+        let len = proc_macro2::Literal::usize_unsuffixed(len);
+        // the helpers only differ in how they (de)serialize byte slices
+        let (serialize, deserialize, serialize_option, deserialize_option) = if base64 {
+            (
+                quote!(super::base64_bytes::serialize(&bytes[..], serializer)),
+                quote!(super::base64_bytes::deserialize(deserializer)?),
+                quote!(super::super::base64_bytes::option::serialize(
+                    bytes, serializer
+                )),
+                quote!(super::super::base64_bytes::option::deserialize(
+                    deserializer
+                )?),
+            )
+        } else {
+            (
+                quote!(::serde::Serialize::serialize(&bytes[..], serializer)),
+                quote!(<::std::vec::Vec<u8> as ::serde::Deserialize>::deserialize(deserializer)?),
+                quote!(match bytes {
+                    ::std::option::Option::Some(bytes) => serializer.serialize_some(&bytes[..]),
+                    ::std::option::Option::None => serializer.serialize_none(),
+                }),
+                quote!(<::std::option::Option<::std::vec::Vec<u8>> as ::serde::Deserialize>::deserialize(deserializer)?),
+            )
+        };
+        let ts = quote! {
+            mod #ident {
+                pub fn serialize<S: ::serde::Serializer>(bytes: &[u8; #len], serializer: S) -> ::std::result::Result<S::Ok, S::Error> {
+                    #serialize
+                }
+
+                pub fn deserialize<'de, D: ::serde::Deserializer<'de>>(deserializer: D) -> ::std::result::Result<[u8; #len], D::Error> {
+                    let bytes = #deserialize;
+                    let len = bytes.len();
+                    bytes
+                        .try_into()
+                        .map_err(|_| ::serde::de::Error::invalid_length(len, &#expected))
+                }
+
+                pub mod option {
+                    pub fn serialize<S: ::serde::Serializer>(bytes: &::std::option::Option<[u8; #len]>, serializer: S) -> ::std::result::Result<S::Ok, S::Error> {
+                        #serialize_option
+                    }
+
+                    pub fn deserialize<'de, D: ::serde::Deserializer<'de>>(deserializer: D) -> ::std::result::Result<::std::option::Option<[u8; #len]>, D::Error> {
+                        let bytes = #deserialize_option;
+                        bytes
+                            .map(|bytes| {
+                                let len = bytes.len();
+                                bytes
+                                    .try_into()
+                                    .map_err(|_| ::serde::de::Error::invalid_length(len, &#expected))
+                            })
+                            .transpose()
+                    }
+                }
+            }
+        };
+        ctx.m.insert_raw_item(&helper, ts)?;
+    }
+    let array = ctx.cm.type_array(&ctx.cm.type_u8(), len);
+    let with = SerdeWith {
+        option: format!("{helper}::option"),
+        plain: helper,
+    };
+    Ok(Some((array, Some(with))))
+}
+
+/// Inserts the `base64_bytes` module (de)serializing byte slices as
+/// base64 strings without padding, as used for `byte` strings of fixed
+/// length. Padded strings are accepted as well.
+fn insert_base64_helper<S: Spec>(ctx: &mut Context<S>) -> anyhow::Result<()> {
+    if ctx
+        .m
+        .raw_item_iter()
+        .any(|item| item.name() == "base64_bytes")
+    {
+        return Ok(());
+    }
+    let ts = quote! {
+        mod base64_bytes {
+            const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+            pub fn encode(bytes: &[u8]) -> ::std::string::String {
+                let mut encoded = ::std::string::String::with_capacity((bytes.len() * 4).div_ceil(3));
+                for chunk in bytes.chunks(3) {
+                    let n = chunk.iter().fold(0u32, |n, b| n << 8 | *b as u32) << (8 * (3 - chunk.len()));
+                    for i in 0..=chunk.len() {
+                        encoded.push(ALPHABET[(n >> (18 - 6 * i) & 63) as usize] as char);
+                    }
+                }
+                encoded
+            }
+
+            pub fn decode(encoded: &str) -> ::std::result::Result<::std::vec::Vec<u8>, ::std::string::String> {
+                let encoded = encoded.trim_end_matches('=');
+                if encoded.len() % 4 == 1 {
+                    return ::std::result::Result::Err(::std::format!("invalid base64 length {}", encoded.len()));
+                }
+                let mut bytes = ::std::vec::Vec::with_capacity(encoded.len() * 3 / 4);
+                let (mut n, mut bits) = (0u32, 0);
+                for c in encoded.bytes() {
+                    let Some(value) = ALPHABET.iter().position(|a| *a == c) else {
+                        return ::std::result::Result::Err(::std::format!("invalid base64 character '{}'", c as char));
+                    };
+                    n = (n << 6 | value as u32) & 0xffff;
+                    bits += 6;
+                    if bits >= 8 {
+                        bits -= 8;
+                        bytes.push((n >> bits) as u8);
+                    }
+                }
+                ::std::result::Result::Ok(bytes)
+            }
+
+            pub fn serialize<S: ::serde::Serializer>(bytes: &[u8], serializer: S) -> ::std::result::Result<S::Ok, S::Error> {
+                serializer.serialize_str(&encode(bytes))
+            }
+
+            pub fn deserialize<'de, D: ::serde::Deserializer<'de>>(deserializer: D) -> ::std::result::Result<::std::vec::Vec<u8>, D::Error> {
+                let encoded = <::std::string::String as ::serde::Deserialize>::deserialize(deserializer)?;
+                decode(&encoded).map_err(::serde::de::Error::custom)
+            }
+
+            pub mod option {
+                pub fn serialize<S: ::serde::Serializer>(bytes: &::std::option::Option<impl ::std::convert::AsRef<[u8]>>, serializer: S) -> ::std::result::Result<S::Ok, S::Error> {
+                    match bytes {
+                        ::std::option::Option::Some(bytes) => serializer.serialize_some(&super::encode(bytes.as_ref())),
+                        ::std::option::Option::None => serializer.serialize_none(),
+                    }
+                }
+
+                pub fn deserialize<'de, D: ::serde::Deserializer<'de>>(deserializer: D) -> ::std::result::Result<::std::option::Option<::std::vec::Vec<u8>>, D::Error> {
+                    let encoded = <::std::option::Option<::std::string::String> as ::serde::Deserialize>::deserialize(deserializer)?;
+                    encoded
+                        .map(|encoded| super::decode(&encoded).map_err(::serde::de::Error::custom))
+                        .transpose()
+                }
+            }
+        }
+    };
+    Ok(ctx.m.insert_raw_item("base64_bytes", ts)?)
+}
+
+/// Whether the schema holds a decimal, by its `format` or, for formats
+/// taken by other tools, its `x-format`
+fn is_decimal(schema: &impl Schema) -> bool {
+    schema.format() == Some(types::Format::Decimal)
+        || schema
+            .extension_value("x-format")
+            .is_some_and(|format| format.as_str() == Some("decimal"))
+}
+
+/// The type of a property holding a decimal string or number, see
+/// [ApiConfig::typed_formats], which comes with `rust_decimal`'s modules
+/// to (de)serialize it as the schema's type. Yields `None` for other (and
+/// referenced) schemas.
+fn decimal_type<S: Spec>(
+    ctx: &Context<S>,
+    schema: &RefOr<S::Schema>,
+) -> anyhow::Result<Option<(TypeRef, Option<SerdeWith>)>> {
+    if !ctx.config.typed_formats {
+        return Ok(None);
+    }
+    let Some(schema) = schema.as_object() else {
+        return Ok(None);
+    };
+    if !is_decimal(&schema) {
+        return Ok(None);
+    }
+    let representation = match schema.type_().unwrap_or_default().as_slice() {
+        [types::Type::String] => "str",
+        [types::Type::Number] => "float",
+        _ => return Ok(None),
+    };
+    let decimal = ctx.cm.type_external("::rust_decimal::Decimal");
+    let with = SerdeWith {
+        plain: format!("::rust_decimal::serde::{representation}"),
+        option: format!("::rust_decimal::serde::{representation}_option"),
+    };
+    Ok(Some((decimal, Some(with))))
+}
+
+/// Yields the newtype wrapping `int_type` that (de)serializes from/to a
+/// JSON string. The newtype is generated once per module, as a raw item
+/// along with its serde impls, on first use.
+/// Creates the `SseEvent` struct, and the `SseEvents` iterator parsing
+/// them from a server-sent event stream, unless already present. Comments
+/// and `retry` fields are skipped, as are events without data.
+fn build_sse_event<S: Spec>(ctx: &mut Context<S>) -> anyhow::Result<TypeRef> {
+    let type_name = "SseEvent";
+    if !ctx.m.raw_item_iter().any(|item| item.name() == type_name) {
+        let visibility = ctx.config.visibility;
+        let ts = quote! {
+            /// An event received from a server-sent event stream
+            #[derive(
+                ::std::fmt::Debug,
+                ::std::clone::Clone,
+                ::core::cmp::PartialEq,
+                ::core::cmp::Eq
+            )]
+            #visibility struct SseEvent {
+                /// the event type, if not the default `message`
+                pub event: ::std::option::Option<::std::string::String>,
+                /// the event's data lines, joined by `\n`
+                pub data: ::std::string::String,
+                pub id: ::std::option::Option<::std::string::String>,
+            }
+
+            /// Parses [SseEvent]s from the lines of an event stream
+            #visibility struct SseEvents<R> {
+                reader: R,
+            }
+
+            impl<R: ::std::io::BufRead> SseEvents<R> {
+                #visibility fn new(reader: R) -> Self {
+                    SseEvents { reader }
+                }
+            }
+
+            impl<R: ::std::io::BufRead> ::std::iter::Iterator for SseEvents<R> {
+                type Item = ::std::result::Result<SseEvent, ::std::io::Error>;
+
+                fn next(&mut self) -> ::std::option::Option<Self::Item> {
+                    let mut event = None;
+                    let mut data: ::std::option::Option<::std::string::String> = None;
+                    let mut id = None;
+                    let mut buf = ::std::string::String::new();
+                    loop {
+                        buf.clear();
+                        match self.reader.read_line(&mut buf) {
+                            // an event not terminated by an empty line is discarded
+                            Ok(0) => return None,
+                            Ok(_) => {}
+                            Err(e) => return Some(Err(e)),
+                        }
+                        let line = buf.trim_end_matches(['\n', '\r']);
+                        if line.is_empty() {
+                            match data.take() {
+                                Some(data) => return Some(Ok(SseEvent { event, data, id })),
+                                None => {
+                                    event = None;
+                                    id = None;
+                                    continue;
+                                }
+                            }
+                        }
+                        if line.starts_with(':') {
+                            continue;
+                        }
+                        let (field, value) = match line.split_once(':') {
+                            Some((field, value)) => (field, value.strip_prefix(' ').unwrap_or(value)),
+                            None => (line, ""),
+                        };
+                        match field {
+                            "event" => event = Some(value.to_string()),
+                            "data" => match &mut data {
+                                Some(data) => {
+                                    data.push('\n');
+                                    data.push_str(value);
+                                }
+                                None => data = Some(value.to_string()),
+                            },
+                            "id" if !value.contains('\0') => id = Some(value.to_string()),
+                            _ => {}
+                        }
+                    }
+                }
+            }
+        };
+        ctx.m.insert_raw_item(type_name, ts)?;
+    }
+    Ok(StructBuilder::new(type_name).build()?.into())
+}
+
+/// The newtype for an ID property (see [ApiConfig::generate_id_newtypes]),
+/// named after the property. Yields `None` for other properties, and for
+/// ID properties whose newtype name is taken by another type.
+fn id_newtype<S: Spec>(
+    ctx: &mut Context<S>,
+    property_name: &str,
+    schema: &RefOr<S::Schema>,
+) -> anyhow::Result<Option<TypeRef>> {
+    if !ctx.config.generate_id_newtypes {
+        return Ok(None);
+    }
+    let is_id_name = ["Id", "Identifier"]
+        .iter()
+        .any(|suffix| property_name.len() > suffix.len() && property_name.ends_with(suffix));
+    let Some(schema) = schema.as_object() else {
+        return Ok(None);
+    };
+    let is_int64 = schema.type_() == Some(vec![types::Type::Number])
+        && schema.format() == Some(types::Format::Int64);
+    if !(is_id_name && is_int64) {
+        return Ok(None);
+    }
+
+    let type_name = ctx.config.naming().schema_to_type_name(property_name);
+    if !ctx.m.raw_item_iter().any(|item| item.name() == type_name) {
+        if ctx.m.contains_item(&type_name) {
+            log::warn!(
+                "not generating ID newtype for property '{property_name}', as type '{type_name}' exists already"
+            );
+            return Ok(None);
+        }
+        let ident = Ident::new(&type_name, proc_macro2::Span::call_site());
+        let visibility = ctx.config.visibility;
+        let ts = quote! {
+            #[derive(
+                ::std::fmt::Debug,
+                ::std::clone::Clone,
+                ::std::marker::Copy,
+                ::core::cmp::PartialEq,
+                ::core::cmp::Eq,
+                ::core::cmp::PartialOrd,
+                ::core::cmp::Ord,
+                ::std::hash::Hash,
+                ::serde::Serialize,
+                ::serde::Deserialize
+            )]
+            #[serde(transparent)]
+            #visibility struct #ident(pub i64);
+
+            impl ::std::convert::From<i64> for #ident {
+                fn from(id: i64) -> Self {
+                    #ident(id)
+                }
+            }
+
+            impl ::std::convert::From<#ident> for i64 {
+                fn from(id: #ident) -> Self {
+                    id.0
+                }
+            }
+
+            impl ::std::fmt::Display for #ident {
+                fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                    ::std::fmt::Display::fmt(&self.0, f)
+                }
+            }
+        };
+        ctx.m.insert_raw_item(&type_name, ts)?;
+    }
+    // being a raw item, the newtype is referred to by name only
+    Ok(Some(StructBuilder::new(&type_name).build()?.into()))
+}
+
+/// The type of a map schema declaring `minProperties` or `maxProperties`.
+/// This is a newtype checking the map size on conversion and
+/// deserialization (see [ApiConfig::validation_newtypes]), or a type alias
+/// documenting the bounds.
+fn map_size_type<S: Spec>(
+    ctx: &mut Context<S>,
+    schema: &S::Schema,
+    type_name: &str,
+    map_type: &TypeRef,
+) -> anyhow::Result<TypeRef> {
+    let min = schema.min_properties();
+    let max = schema.max_properties();
+    let bounds = match (min, max) {
+        (Some(min), Some(max)) => format!("at least {min} and at most {max} entries"),
+        (Some(min), None) => format!("at least {min} entries"),
+        (None, Some(max)) => format!("at most {max} entries"),
+        (None, None) => unreachable!("map without size bounds"),
+    };
+    let ident = Ident::new(type_name, proc_macro2::Span::call_site());
+    let map_ts = codewriter::syn_type_name_of(map_type)?;
+    let visibility = ctx.config.visibility;
+
+    let ts = if ctx.config.validation_newtypes {
+        let doc = format!(" A map holding {bounds}, checked on conversion and deserialization");
+        let min_check = min.map(|min| {
+            quote! {
+                if map.len() < #min {
+                    return ::std::result::Result::Err(::std::format!(
+                        "expected at least {} entries, got {}", #min, map.len()
+                    ));
+                }
+            }
+        });
+        let max_check = max.map(|max| {
+            quote! {
+                if map.len() > #max {
+                    return ::std::result::Result::Err(::std::format!(
+                        "expected at most {} entries, got {}", #max, map.len()
+                    ));
+                }
+            }
+        });
+        let map_type_name = map_ts.to_string();
+        quote! {
+            #[doc = #doc]
+            #[derive(
+                ::std::fmt::Debug,
+                ::std::clone::Clone,
+                ::core::cmp::PartialEq,
+                ::serde::Serialize,
+                ::serde::Deserialize
+            )]
+            #[serde(try_from = #map_type_name, into = #map_type_name)]
+            #visibility struct #ident(#map_ts);
+
+            impl ::std::convert::AsRef<#map_ts> for #ident {
+                fn as_ref(&self) -> &#map_ts {
+                    &self.0
+                }
+            }
+
+            impl ::std::convert::TryFrom<#map_ts> for #ident {
+                type Error = ::std::string::String;
+
+                fn try_from(map: #map_ts) -> ::std::result::Result<Self, Self::Error> {
+                    #min_check
+                    #max_check
+                    ::std::result::Result::Ok(#ident(map))
+                }
+            }
+
+            impl ::std::convert::From<#ident> for #map_ts {
+                fn from(map: #ident) -> Self {
+                    map.0
+                }
+            }
+        }
+    } else {
+        let doc = format!(" A map holding {bounds}, which is not checked");
+        quote! {
+            #[doc = #doc]
+            #visibility type #ident = #map_ts;
+        }
+    };
+    let placeholder = StructBuilder::new(type_name).build()?.into();
+    Ok(ctx.m.insert_raw_type(placeholder, ts)?)
+}
+
+fn stringified_integer_type<S: Spec>(
+    ctx: &mut Context<S>,
+    type_name: &str,
+    int_type: TokenStream,
+) -> anyhow::Result<TypeRef> {
+    if !ctx.m.raw_item_iter().any(|item| item.name() == type_name) {
+        let ident = Ident::new(type_name, proc_macro2::Span::call_site());
+        let visibility = ctx.config.visibility;
+        let ts = quote! {
+            #[derive(
+                ::std::fmt::Debug,
+                ::std::clone::Clone,
+                ::std::marker::Copy,
+                ::core::cmp::PartialEq,
+                ::core::cmp::Eq,
+                ::std::hash::Hash
+            )]
+            #visibility struct #ident(pub #int_type);
+
+            impl ::serde::Serialize for #ident {
+                fn serialize<S: ::serde::Serializer>(&self, serializer: S) -> ::std::result::Result<S::Ok, S::Error> {
+                    serializer.collect_str(&self.0)
+                }
+            }
+
+            impl<'de> ::serde::Deserialize<'de> for #ident {
+                fn deserialize<D: ::serde::Deserializer<'de>>(deserializer: D) -> ::std::result::Result<Self, D::Error> {
+                    let s = <::std::string::String as ::serde::Deserialize>::deserialize(deserializer)?;
+                    s.parse().map(#ident).map_err(::serde::de::Error::custom)
+                }
+            }
+        };
+        ctx.m.insert_raw_item(type_name, ts)?;
+    }
+    // being a raw item, the newtype is referred to by name only
+    Ok(StructBuilder::new(type_name).build()?.into())
+}
+
+/// The value of a string schema allowing a single string, via `enum` or
+/// `const`
+fn single_string_value(schema: &impl Schema) -> Option<String> {
+    if let Some(value) = schema.const_value() {
+        let is_string = schema
+            .type_()
+            .is_none_or(|types| types == [types::Type::String]);
+        return value.as_str().filter(|_| is_string).map(str::to_string);
+    }
+    if schema.type_()? != [types::Type::String] {
+        return None;
+    }
+    match schema.enum_()?.as_slice() {
+        [value] => value.as_str().map(str::to_string),
+        _ => None,
+    }
+}
+
+/// Creates the zero-sized marker type for a single value enum (see
+/// [SingleValueEnums::MarkerIfRequired]), named after the value (like
+/// `DogMarker` for `"dog"`). Properties with the same value share their
+/// marker.
+fn constant_marker_type<S: Spec>(ctx: &mut Context<S>, value: &str) -> anyhow::Result<TypeRef> {
+    if let Some(type_name) = ctx.constant_markers.get(value) {
+        return Ok(StructBuilder::new(type_name).build()?.into());
+    }
+    let candidate_name = ctx.config.naming().schema_to_type_name(value) + "Marker";
+    let type_name = ctx.names.uncollide(&ctx.m, candidate_name);
+    let ident = Ident::new(&type_name, proc_macro2::Span::call_site());
+    let visibility = ctx.config.visibility;
+    let ts = quote! {
+        #[derive(
+            ::std::fmt::Debug,
+            ::std::default::Default,
+            ::std::clone::Clone,
+            ::std::marker::Copy,
+            ::core::cmp::PartialEq,
+            ::core::cmp::Eq,
+            ::std::hash::Hash
+        )]
+        #visibility struct #ident;
+
+        impl #ident {
+            #visibility const VALUE: &'static str = #value;
+        }
+
+        impl ::serde::Serialize for #ident {
+            fn serialize<S: ::serde::Serializer>(&self, serializer: S) -> ::std::result::Result<S::Ok, S::Error> {
+                serializer.serialize_str(Self::VALUE)
+            }
+        }
+
+        impl<'de> ::serde::Deserialize<'de> for #ident {
+            fn deserialize<D: ::serde::Deserializer<'de>>(deserializer: D) -> ::std::result::Result<Self, D::Error> {
+                let s = <::std::string::String as ::serde::Deserialize>::deserialize(deserializer)?;
+                if s == Self::VALUE {
+                    ::std::result::Result::Ok(#ident)
+                } else {
+                    ::std::result::Result::Err(::serde::de::Error::invalid_value(
+                        ::serde::de::Unexpected::Str(&s),
+                        &Self::VALUE,
+                    ))
+                }
+            }
+        }
+    };
+    ctx.m.insert_raw_item(&type_name, ts)?;
+    ctx.constant_markers
+        .insert(value.to_string(), type_name.clone());
+    // being a raw item, the marker is referred to by name only
+    Ok(StructBuilder::new(&type_name).build()?.into())
 }
 
 fn type_ref_of<S: Spec>(
@@ -622,9 +6605,16 @@ fn type_ref_of<S: Spec>(
     schema: &RefOr<S::Schema>,
     candidate_name: &str,
 ) -> anyhow::Result<TypeRef> {
-    if let Some(type_ref) = ctx.mapping.schema_mapping.get(schema) {
+    if let Some(type_ref) = ctx.mapping.find(schema) {
         // mapped type found for RefOr
-        return Ok(type_ref.clone());
+        return Ok(type_ref);
+    }
+    // the annotations of a wrapper apply where it is used, like
+    // `nullable` wrapping the type in an `Option`
+    if let RefOr::Object(object) = schema
+        && let Some(target) = wrapped_reference::<S>(object)
+    {
+        return type_ref_of(ctx, &target.resolve(), candidate_name);
     }
 
     // If we get there, there are only two options (assuming that we
@@ -653,7 +6643,8 @@ fn type_ref_of<S: Spec>(
                         types::Type::Null => Ok(ctx.cm.type_unit()),
                         types::Type::Boolean => Ok(ctx.cm.type_bool()),
                         types::Type::Object => {
-                            parse_schema(ctx, schema, Some(candidate_name.to_string()))
+                            let name = inline_schema_name(ctx, schema, candidate_name);
+                            parse_schema(ctx, schema, Some(name))
                         }
                         types::Type::Array => {
                             // check for violations against rules for 'items' in
@@ -670,20 +6661,17 @@ fn type_ref_of<S: Spec>(
                                 type_ref_of(ctx, &item_schema, &candidate_item_name).unwrap();
                             Ok(ctx.cm.type_instance(&ctx.cm.type_vec(), &vec![item_type]))
                         }
-                        types::Type::Number => match schema.format() {
-                            Some(format) => Ok(match format {
-                                types::Format::Int32 => ctx.cm.type_i32(),
-                                types::Format::Int64 => ctx.cm.type_i64(),
-                                types::Format::Float => ctx.cm.type_f32(),
-                                types::Format::Double => ctx.cm.type_f64(),
-                                _ => ctx.cm.type_f64(),
-                            }),
-                            None => Ok(ctx.cm.type_f64()),
-                        },
-                        types::Type::String => {
+                        types::Type::Number => Ok(number_type(ctx, schema)),
+                        types::Type::String => match schema.format() {
+                            Some(types::Format::Int64) if ctx.config.stringified_integers => {
+                                stringified_integer_type(ctx, "Int64String", quote!(i64))
+                            }
+                            Some(types::Format::UInt64) if ctx.config.stringified_integers => {
+                                stringified_integer_type(ctx, "UInt64String", quote!(u64))
+                            }
                             // FIXME: we need to implement enums here!
-                            Ok(ctx.cm.type_string())
-                        }
+                            _ => Ok(ctx.cm.type_string()),
+                        },
                     }
                 }
             }
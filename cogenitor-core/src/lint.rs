@@ -0,0 +1,577 @@
+//! Checks on an OAS document for constructs we cannot generate code for
+//! properly, like names that may conflict with Rust once they are turned
+//! into identifiers of generated code.
+
+use std::collections::HashSet;
+use std::fmt::Display;
+
+use crate::NamingPolicy;
+use crate::translate;
+use crate::types::{Operation, Parameter, PathItem, Schema, Spec};
+
+// Names of primitive types, which would shadow or be shadowed by generated items
+const PRIMITIVE_TYPE_NAMES: &[&str] = &[
+    "bool", "char", "str", "u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32", "i64",
+    "i128", "isize", "f32", "f64",
+];
+
+// Names of items we always generate
+const GENERATED_ITEM_NAMES: &[&str] = &["Client", "ClientImpl", "OperationInfo"];
+
+/// The kind of conflict a name in the OAS document has with Rust
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NamingConflict {
+    /// a strict or reserved keyword, like `type` or `yield`
+    Keyword,
+    /// the `Self` type
+    SelfType,
+    /// a primitive type name, like `bool` or `u8`
+    PrimitiveType,
+    /// a name of an item that is always generated, like `Client`
+    GeneratedItem,
+}
+
+/// A warning about a name in the OAS document that conflicts with Rust
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NamingDiagnostic {
+    /// location of the named item in the OAS document, e.g. `#/components/schemas`
+    pub location: String,
+    /// the name as it appears in the OAS document
+    pub name: String,
+    pub conflict: NamingConflict,
+}
+
+impl Display for NamingDiagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let reason = match self.conflict {
+            NamingConflict::Keyword => "is a Rust keyword",
+            NamingConflict::SelfType => "is the Rust 'Self' type",
+            NamingConflict::PrimitiveType => "is a Rust primitive type name",
+            NamingConflict::GeneratedItem => "collides with a generated item",
+        };
+        write!(f, "name '{}' at {} {reason}", self.name, self.location)
+    }
+}
+
+fn conflict_of(name: &str) -> Option<NamingConflict> {
+    if name == "Self" {
+        Some(NamingConflict::SelfType)
+    } else if translate::is_keyword(name) {
+        Some(NamingConflict::Keyword)
+    } else if PRIMITIVE_TYPE_NAMES.contains(&name) {
+        Some(NamingConflict::PrimitiveType)
+    } else {
+        None
+    }
+}
+
+/// Checks schema names, property names, parameter names and operation IDs
+/// of the given spec for names that conflict with Rust. Schema names are
+/// checked as `naming` turns them into type names.
+pub fn lint_oas_names<S: Spec>(spec: &S, naming: &dyn NamingPolicy) -> Vec<NamingDiagnostic> {
+    let mut diagnostics = Vec::new();
+    let mut check = |location: &str, name: &str| {
+        if let Some(conflict) = conflict_of(name) {
+            diagnostics.push(NamingDiagnostic {
+                location: location.to_string(),
+                name: name.to_string(),
+                conflict,
+            });
+        }
+    };
+
+    for (name, schema) in spec.schemata_iter() {
+        check("#/components/schemas", &name);
+
+        let schema = schema.resolve_fully();
+        let location = format!("#/components/schemas/{name}/properties");
+        for property_name in schema.properties().keys() {
+            check(&location, property_name);
+        }
+    }
+
+    for (path, path_item) in spec.paths() {
+        let location = format!("#/paths/{path}/parameters");
+        for param in path_item.parameters() {
+            check(&location, param.resolve_fully().name());
+        }
+
+        for (method, operation) in path_item.operations_iter() {
+            let method = method.as_str().to_lowercase();
+            if let Some(operation_id) = operation.operation_id() {
+                check(
+                    &format!("#/paths/{path}/{method}/operationId"),
+                    operation_id,
+                );
+            }
+            let location = format!("#/paths/{path}/{method}/parameters");
+            for param in operation.parameters() {
+                check(&location, param.resolve_fully().name());
+            }
+        }
+    }
+
+    // schemas are turned into types, which must not collide with
+    // the items we generate for the client
+    for (name, _) in spec.schemata_iter() {
+        let type_name = naming.schema_to_type_name(&name);
+        if GENERATED_ITEM_NAMES.contains(&type_name.as_str()) {
+            diagnostics.push(NamingDiagnostic {
+                location: "#/components/schemas".to_string(),
+                name,
+                conflict: NamingConflict::GeneratedItem,
+            });
+        }
+    }
+
+    diagnostics
+}
+
+// JSON Schema dialects we generate code for. The OAS 3.1 base dialect
+// extends JSON Schema 2020-12.
+const SUPPORTED_SCHEMA_DIALECTS: &[&str] = &[
+    "https://json-schema.org/draft/2020-12/schema",
+    "https://spec.openapis.org/oas/3.1/dialect/base",
+];
+
+/// A warning about a schema declaring a JSON Schema dialect via `$schema`
+/// that we don't support, so generated code may not match its semantics
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchemaDialectDiagnostic {
+    /// name of the schema in `#/components/schemas`
+    pub schema_name: String,
+    pub dialect: String,
+}
+
+impl Display for SchemaDialectDiagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "schema '{}' uses unsupported JSON Schema dialect '{}'",
+            self.schema_name, self.dialect
+        )
+    }
+}
+
+fn is_supported_schema_dialect(dialect: &str) -> bool {
+    // dialect URIs may or may not carry a trailing '#'
+    let dialect = dialect.trim_end_matches('#');
+    SUPPORTED_SCHEMA_DIALECTS.contains(&dialect)
+}
+
+/// Checks the schemas of the given spec for unsupported JSON Schema dialects
+pub fn lint_schema_dialects<S: Spec>(spec: &S) -> Vec<SchemaDialectDiagnostic> {
+    spec.schemata_iter()
+        .filter_map(|(name, schema)| {
+            let schema = schema.resolve_fully();
+            let dialect = schema.schema_dialect()?;
+            if is_supported_schema_dialect(dialect) {
+                None
+            } else {
+                Some(SchemaDialectDiagnostic {
+                    schema_name: name,
+                    dialect: dialect.to_string(),
+                })
+            }
+        })
+        .collect()
+}
+
+/// A warning about an operation declaring no responses, although the spec
+/// requires at least one
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MissingResponsesDiagnostic {
+    pub path: String,
+    pub method: http::Method,
+}
+
+impl Display for MissingResponsesDiagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "operation {} {} declares no responses",
+            self.method, self.path
+        )
+    }
+}
+
+/// Checks the operations of the given spec for missing or empty
+/// `responses`
+pub fn lint_missing_responses<S: Spec>(spec: &S) -> Vec<MissingResponsesDiagnostic> {
+    spec.paths()
+        .flat_map(|(path, path_item)| {
+            path_item
+                .operations_iter()
+                .filter(|(_, operation)| operation.responses().next().is_none())
+                .map(|(method, _)| MissingResponsesDiagnostic {
+                    path: path.clone(),
+                    method,
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+/// A warning about a path item given as a `$ref` that cannot be resolved,
+/// whose operations are skipped
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PathItemRefDiagnostic {
+    pub path: String,
+    pub reference: String,
+}
+
+impl Display for PathItemRefDiagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "skipping the operations of path '{}', as its path item reference '{}' cannot be resolved",
+            self.path, self.reference
+        )
+    }
+}
+
+/// Checks the paths of the given spec for path item references that
+/// cannot be resolved
+pub fn lint_path_item_refs<S: Spec>(spec: &S) -> Vec<PathItemRefDiagnostic> {
+    spec.unresolved_path_item_refs()
+        .into_iter()
+        .map(|(path, reference)| PathItemRefDiagnostic { path, reference })
+        .collect()
+}
+
+/// A warning about an operation reusing the `operationId` of another
+/// operation, although `operationId`s must be unique
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DuplicateOperationIdDiagnostic {
+    pub operation_id: String,
+    pub path: String,
+    pub method: http::Method,
+}
+
+impl Display for DuplicateOperationIdDiagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "operationId '{}' of operation {} {} is used by another operation already",
+            self.operation_id, self.method, self.path
+        )
+    }
+}
+
+/// Checks the operations of the given spec for `operationId`s used by
+/// several operations. All but the first of them are reported.
+pub fn lint_duplicate_operation_ids<S: Spec>(spec: &S) -> Vec<DuplicateOperationIdDiagnostic> {
+    let mut used = HashSet::new();
+    let mut diagnostics = Vec::new();
+    for (path, path_item) in spec.paths() {
+        for (method, operation) in path_item.operations_iter() {
+            if let Some(operation_id) = operation.operation_id()
+                && !used.insert(operation_id.to_string())
+            {
+                diagnostics.push(DuplicateOperationIdDiagnostic {
+                    operation_id: operation_id.to_string(),
+                    path: path.clone(),
+                    method,
+                });
+            }
+        }
+    }
+    diagnostics
+}
+
+/// What a [SpecSizeDiagnostic] counted
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpecSizeItem {
+    Operations,
+    Schemas,
+}
+
+/// A warning about a spec too large to generate code for in reasonable time
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpecSizeDiagnostic {
+    pub item: SpecSizeItem,
+    pub count: usize,
+    pub limit: usize,
+}
+
+impl Display for SpecSizeDiagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let item = match self.item {
+            SpecSizeItem::Operations => "operations",
+            SpecSizeItem::Schemas => "schemas",
+        };
+        write!(
+            f,
+            "spec has {} {item}, exceeding the limit of {}",
+            self.count, self.limit
+        )
+    }
+}
+
+/// Checks the number of operations and schemas of the given spec against
+/// the given limits (see [crate::ApiConfig::max_operations] and
+/// [crate::ApiConfig::max_schemas])
+pub fn lint_spec_size<S: Spec>(
+    spec: &S,
+    max_operations: Option<usize>,
+    max_schemas: Option<usize>,
+) -> Vec<SpecSizeDiagnostic> {
+    let mut diagnostics = Vec::new();
+    if let Some(limit) = max_operations {
+        let count = spec
+            .paths()
+            .map(|(_, path_item)| path_item.operations_iter().count())
+            .sum();
+        if count > limit {
+            diagnostics.push(SpecSizeDiagnostic {
+                item: SpecSizeItem::Operations,
+                count,
+                limit,
+            });
+        }
+    }
+    if let Some(limit) = max_schemas {
+        let count = spec.schemata_iter().count();
+        if count > limit {
+            diagnostics.push(SpecSizeDiagnostic {
+                item: SpecSizeItem::Schemas,
+                count,
+                limit,
+            });
+        }
+    }
+    diagnostics
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+    use crate::adapters::oas30::OAS30Spec;
+
+    #[test]
+    fn test_lint_oas_names() {
+        let oas = r"
+openapi: 3.0.0
+info:
+    title: Conflicting names
+    version: v1
+paths:
+    /things/{self}:
+        parameters:
+            -   {name: self, in: path, required: true, schema: {type: string}}
+        get:
+            operationId: match
+            parameters:
+                -   {name: u8, in: query, schema: {type: string}}
+            responses: {}
+components:
+    schemas:
+        type:
+            type: object
+            properties:
+                Self:
+                    type: string
+                name:
+                    type: string
+        Client:
+            type: object
+";
+        let spec = OAS30Spec::from_str(oas).unwrap();
+        let diagnostics = lint_oas_names(&spec, &crate::DefaultNamingPolicy);
+        let found = |name: &str, conflict: NamingConflict| {
+            diagnostics
+                .iter()
+                .any(|d| d.name == name && d.conflict == conflict)
+        };
+
+        assert!(found("type", NamingConflict::Keyword));
+        assert!(found("Self", NamingConflict::SelfType));
+        assert!(found("self", NamingConflict::Keyword));
+        assert!(found("match", NamingConflict::Keyword));
+        assert!(found("u8", NamingConflict::PrimitiveType));
+        assert!(found("Client", NamingConflict::GeneratedItem));
+        assert!(!diagnostics.iter().any(|d| d.name == "name"));
+        assert_eq!(6, diagnostics.len(), "diagnostics: {diagnostics:?}");
+
+        assert_eq!(
+            "name 'type' at #/components/schemas is a Rust keyword",
+            diagnostics
+                .iter()
+                .find(|d| d.name == "type")
+                .unwrap()
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn test_schema_dialects() {
+        assert!(is_supported_schema_dialect(
+            "https://json-schema.org/draft/2020-12/schema"
+        ));
+        assert!(is_supported_schema_dialect(
+            "https://spec.openapis.org/oas/3.1/dialect/base#"
+        ));
+        assert!(!is_supported_schema_dialect(
+            "http://json-schema.org/draft-04/schema"
+        ));
+
+        let diagnostic = SchemaDialectDiagnostic {
+            schema_name: "Pet".to_string(),
+            dialect: "http://json-schema.org/draft-04/schema".to_string(),
+        };
+        assert_eq!(
+            "schema 'Pet' uses unsupported JSON Schema dialect 'http://json-schema.org/draft-04/schema'",
+            diagnostic.to_string()
+        );
+
+        // OAS 3.0 has no '$schema' keyword, so it never yields diagnostics
+        let oas = r"
+openapi: 3.0.0
+info:
+    title: Dialects
+    version: v1
+paths: {}
+components:
+    schemas:
+        Pet:
+            type: object
+";
+        let spec = OAS30Spec::from_str(oas).unwrap();
+        assert!(lint_schema_dialects(&spec).is_empty());
+    }
+
+    #[test]
+    fn test_lint_spec_size() {
+        let oas = r"
+openapi: 3.0.0
+info:
+    title: Sizable API
+    version: v1
+paths:
+    /pets:
+        get:
+            responses: {}
+        post:
+            responses: {}
+    /owners:
+        get:
+            responses: {}
+components:
+    schemas:
+        Pet:
+            type: object
+        Owner:
+            type: object
+";
+        let spec = OAS30Spec::from_str(oas).unwrap();
+        assert!(lint_spec_size(&spec, None, None).is_empty());
+        assert!(lint_spec_size(&spec, Some(3), Some(2)).is_empty());
+
+        let diagnostics = lint_spec_size(&spec, Some(2), Some(1));
+        assert_eq!(
+            vec![
+                "spec has 3 operations, exceeding the limit of 2",
+                "spec has 2 schemas, exceeding the limit of 1"
+            ],
+            diagnostics
+                .iter()
+                .map(|d| d.to_string())
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_lint_missing_responses() {
+        let oas = r"
+openapi: 3.0.0
+info:
+    title: Missing responses
+    version: v1
+paths:
+    /things:
+        get: {}
+        post:
+            responses: {}
+        delete:
+            responses:
+                '204':
+                    description: deleted
+";
+        let spec = OAS30Spec::from_str(oas).unwrap();
+        let mut diagnostics = lint_missing_responses(&spec)
+            .iter()
+            .map(|d| d.to_string())
+            .collect::<Vec<_>>();
+        diagnostics.sort();
+        assert_eq!(
+            vec![
+                "operation GET /things declares no responses",
+                "operation POST /things declares no responses"
+            ],
+            diagnostics
+        );
+    }
+
+    #[test]
+    fn test_lint_duplicate_operation_ids() {
+        let oas = r"
+openapi: 3.0.0
+info:
+    title: Duplicate operationIds
+    version: v1
+paths:
+    /users/{id}:
+        get:
+            operationId: getUser
+        delete:
+            operationId: deleteUser
+    /admins/{id}:
+        get:
+            operationId: getUser
+";
+        let spec = OAS30Spec::from_str(oas).unwrap();
+        let diagnostics = lint_duplicate_operation_ids(&spec)
+            .iter()
+            .map(|d| d.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                "operationId 'getUser' of operation GET /admins/{id} is used by another operation already"
+            ],
+            diagnostics
+        );
+    }
+
+    #[test]
+    fn test_lint_path_item_refs() {
+        let oas = r"
+openapi: 3.0.0
+info:
+    title: Path item references
+    version: v1
+paths:
+    /things:
+        get:
+            responses:
+                '200':
+                    description: the things
+    /stuff:
+        $ref: '#/paths/~1things'
+    /external:
+        $ref: 'other.yaml#/paths/~1external'
+";
+        let spec = OAS30Spec::from_str(oas).unwrap();
+        let diagnostics = lint_path_item_refs(&spec)
+            .iter()
+            .map(|d| d.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                "skipping the operations of path '/external', as its path item reference 'other.yaml#/paths/~1external' cannot be resolved"
+            ],
+            diagnostics
+        );
+    }
+}
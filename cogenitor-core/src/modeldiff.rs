@@ -0,0 +1,341 @@
+//! Comparison of two code models, e.g. generated from an old and a new
+//! version of a spec, to see how the generated API changes before
+//! regenerating a client.
+//!
+//! Items are matched by name, and types are compared by their rendered
+//! names, so the models need not share any types.
+
+use std::fmt::Display;
+
+use crate::codemodel::{
+    Codemodel, EnumVariantData, Field, Indirection, Module, NamedItem, TypeRef,
+    TypeRefOrTokenStream, function::Function,
+};
+
+/// The kind of a generated item
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ItemKind {
+    Struct,
+    Enum,
+    TypeAlias,
+    Trait,
+    /// an item generated as a token stream, like a newtype
+    RawItem,
+}
+
+impl Display for ItemKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            ItemKind::Struct => "struct",
+            ItemKind::Enum => "enum",
+            ItemKind::TypeAlias => "type alias",
+            ItemKind::Trait => "trait",
+            ItemKind::RawItem => "item",
+        })
+    }
+}
+
+/// A change of a struct field, an enum variant, a trait function or the
+/// target of a type alias. Members are described by their rendered
+/// signature, like `Option<String>` for a field or
+/// `fn pets_get(&self) -> Result<Pets,PetsGetError>` for a function.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MemberDiff {
+    Added {
+        name: String,
+        signature: String,
+    },
+    Removed {
+        name: String,
+        signature: String,
+    },
+    Changed {
+        name: String,
+        old: String,
+        new: String,
+    },
+}
+
+/// A change of an item in a module. Items whose kind changed, like a
+/// struct becoming an enum, are reported as removed and added.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ItemDiff {
+    Added {
+        kind: ItemKind,
+        name: String,
+    },
+    Removed {
+        kind: ItemKind,
+        name: String,
+    },
+    /// `members` is empty for raw items, whose tokens are compared only
+    Changed {
+        kind: ItemKind,
+        name: String,
+        members: Vec<MemberDiff>,
+    },
+}
+
+/// The changes between two code models. Items of nested
+/// modules are named by their path, like `models::Pet`. The [Display]
+/// implementation renders a markdown summary.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ModelDiff {
+    pub items: Vec<ItemDiff>,
+}
+
+impl ModelDiff {
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+}
+
+impl Display for ModelDiff {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.items.is_empty() {
+            return writeln!(f, "No changes.");
+        }
+        for item in &self.items {
+            match item {
+                ItemDiff::Added { kind, name } => writeln!(f, "- added {kind} `{name}`")?,
+                ItemDiff::Removed { kind, name } => writeln!(f, "- removed {kind} `{name}`")?,
+                ItemDiff::Changed {
+                    kind,
+                    name,
+                    members,
+                } => {
+                    writeln!(f, "- changed {kind} `{name}`")?;
+                    for member in members {
+                        match member {
+                            MemberDiff::Added { name, signature } => {
+                                writeln!(f, "  - added `{name}`: `{signature}`")?
+                            }
+                            MemberDiff::Removed { name, signature } => {
+                                writeln!(f, "  - removed `{name}`: `{signature}`")?
+                            }
+                            MemberDiff::Changed { name, old, new } => {
+                                writeln!(f, "  - changed `{name}`: `{old}` to `{new}`")?
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// An item of a module, reduced to what we compare
+struct ItemSummary {
+    kind: ItemKind,
+    name: String,
+    /// members by name with their rendered signatures
+    members: Vec<(String, String)>,
+}
+
+/// Compares the crates of two code models by item name
+pub fn diff(old: &Codemodel, new: &Codemodel) -> ModelDiff {
+    let old_items = crate_items(old);
+    let new_items = crate_items(new);
+
+    let mut items = Vec::new();
+    for old_item in &old_items {
+        match new_items.iter().find(|i| i.name == old_item.name) {
+            Some(new_item) if new_item.kind == old_item.kind => {
+                if let Some(item_diff) = diff_item(old_item, new_item) {
+                    items.push(item_diff);
+                }
+            }
+            Some(new_item) => {
+                items.push(ItemDiff::Removed {
+                    kind: old_item.kind,
+                    name: old_item.name.clone(),
+                });
+                items.push(ItemDiff::Added {
+                    kind: new_item.kind,
+                    name: new_item.name.clone(),
+                });
+            }
+            None => items.push(ItemDiff::Removed {
+                kind: old_item.kind,
+                name: old_item.name.clone(),
+            }),
+        }
+    }
+    for new_item in &new_items {
+        if !old_items.iter().any(|i| i.name == new_item.name) {
+            items.push(ItemDiff::Added {
+                kind: new_item.kind,
+                name: new_item.name.clone(),
+            });
+        }
+    }
+    ModelDiff { items }
+}
+
+fn diff_item(old: &ItemSummary, new: &ItemSummary) -> Option<ItemDiff> {
+    // raw items have their tokens as their only, unnamed member
+    if old.kind == ItemKind::RawItem {
+        return (old.members != new.members).then(|| ItemDiff::Changed {
+            kind: new.kind,
+            name: new.name.clone(),
+            members: Vec::new(),
+        });
+    }
+
+    let mut members = Vec::new();
+    for (name, old_signature) in &old.members {
+        match new.members.iter().find(|(n, _)| n == name) {
+            Some((_, new_signature)) if new_signature != old_signature => {
+                members.push(MemberDiff::Changed {
+                    name: name.clone(),
+                    old: old_signature.clone(),
+                    new: new_signature.clone(),
+                })
+            }
+            Some(_) => {}
+            None => members.push(MemberDiff::Removed {
+                name: name.clone(),
+                signature: old_signature.clone(),
+            }),
+        }
+    }
+    for (name, signature) in &new.members {
+        if !old.members.iter().any(|(n, _)| n == name) {
+            members.push(MemberDiff::Added {
+                name: name.clone(),
+                signature: signature.clone(),
+            });
+        }
+    }
+    (!members.is_empty()).then(|| ItemDiff::Changed {
+        kind: new.kind,
+        name: new.name.clone(),
+        members,
+    })
+}
+
+fn crate_items(cm: &Codemodel) -> Vec<ItemSummary> {
+    let mut items = Vec::new();
+    if let Some(crate_) = cm.find_crate("crate") {
+        module_items(&crate_, "", &mut items);
+    }
+    items
+}
+
+fn module_items(m: &Module, prefix: &str, items: &mut Vec<ItemSummary>) {
+    for type_ref in m.type_iter() {
+        if let Some(item) = type_summary(type_ref, prefix) {
+            items.push(item);
+        }
+    }
+    for trait_ref in m.trait_iter() {
+        items.push(ItemSummary {
+            kind: ItemKind::Trait,
+            name: format!("{prefix}{}", trait_ref.name()),
+            members: trait_ref
+                .function_iter()
+                .map(|function| (function.name().to_string(), function_signature(function)))
+                .collect(),
+        });
+    }
+    for raw_item in m.raw_item_iter() {
+        items.push(ItemSummary {
+            kind: ItemKind::RawItem,
+            name: format!("{prefix}{}", raw_item.name()),
+            members: vec![(String::new(), raw_item.token_stream().to_string())],
+        });
+    }
+    for module in m.mod_iter() {
+        module_items(module, &format!("{prefix}{}::", module.name()), items);
+    }
+}
+
+fn type_summary(type_ref: &TypeRef, prefix: &str) -> Option<ItemSummary> {
+    let name = format!("{prefix}{}", type_ref.name());
+    let (kind, members) = match type_ref {
+        TypeRef::Indirection(i) => {
+            return match &*i.borrow() {
+                Indirection::Resolved(type_ref) => type_summary(type_ref, prefix),
+                Indirection::Stub(_) => None,
+            };
+        }
+        TypeRef::Struct(s) => (ItemKind::Struct, field_members(s.field_iter())),
+        TypeRef::Enum(e) => (
+            ItemKind::Enum,
+            e.variant_iter()
+                .map(|variant| {
+                    let signature = match variant.data() {
+                        EnumVariantData::Unit => String::new(),
+                        EnumVariantData::Tuple(types) => {
+                            let types: Vec<String> = types.iter().map(rendered_type).collect();
+                            format!("({})", types.join(", "))
+                        }
+                        EnumVariantData::Struct(fields) => {
+                            let fields: Vec<String> = field_members(fields.iter())
+                                .into_iter()
+                                .map(|(name, type_)| format!("{name}: {type_}"))
+                                .collect();
+                            format!("{{ {} }}", fields.join(", "))
+                        }
+                    };
+                    (variant.name().to_string(), signature)
+                })
+                .collect(),
+        ),
+        TypeRef::Alias(alias) => (
+            ItemKind::TypeAlias,
+            vec![("target".to_string(), alias.target().name().to_string())],
+        ),
+        _ => return None,
+    };
+    Some(ItemSummary {
+        kind,
+        name,
+        members,
+    })
+}
+
+fn field_members<'a>(fields: impl Iterator<Item = &'a Field>) -> Vec<(String, String)> {
+    fields
+        .map(|field| (field.name().to_string(), rendered_type(field.type_())))
+        .collect()
+}
+
+fn rendered_type(type_: &TypeRefOrTokenStream) -> String {
+    match type_ {
+        TypeRefOrTokenStream::TypeRef(type_ref) => type_ref.name().to_string(),
+        TypeRefOrTokenStream::TokenStream(ts) => ts.to_string(),
+    }
+}
+
+fn function_signature(function: &Function) -> String {
+    let generic_params: Vec<String> = function
+        .lifetime_params_iter()
+        .map(|lifetime| format!("'{lifetime}"))
+        .chain(
+            function
+                .generic_params_iter()
+                .map(|param| format!("{}: {}", param.name, param.bounds)),
+        )
+        .collect();
+    let generic_params = if generic_params.is_empty() {
+        String::new()
+    } else {
+        format!("<{}>", generic_params.join(", "))
+    };
+    let params: Vec<String> = function
+        .function_params_iter()
+        .map(|param| match param.name.as_str() {
+            // receivers like `self: &Self` read better as `&self`
+            "self" => param.type_.name().replace("Self", "self"),
+            _ => format!("{}: {}", param.name, param.type_.name()),
+        })
+        .collect();
+    format!(
+        "fn {}{generic_params}({}) -> {}",
+        function.name(),
+        params.join(", "),
+        function.return_type().name()
+    )
+}
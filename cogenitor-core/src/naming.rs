@@ -0,0 +1,92 @@
+//! Naming of the generated items. Teams with house conventions (like
+//! stripping a vendor prefix from every schema name) implement
+//! [NamingPolicy] and set it in [crate::ApiConfig::naming_policy]; all
+//! other names stay as the generator derives them.
+
+use std::any::Any;
+use std::collections::HashSet;
+use std::fmt::Debug;
+
+use crate::translate;
+use crate::types::StatusSpec;
+
+/// Derives the names of generated items from names in the spec. Each
+/// method defaults to the generator's own naming, so a policy only
+/// overrides what it wants to change. Names must be valid Rust
+/// identifiers; they are made unique after the policy is applied.
+///
+/// Policies are cloned and compared along with the configuration, so they
+/// implement `Clone` and `PartialEq` (see [NamingPolicyClone]).
+pub trait NamingPolicy: NamingPolicyClone + Debug + Send + Sync {
+    /// The type name for a schema named `schema_name` in the spec. This
+    /// also names types derived from other names in the spec, like
+    /// schema titles, callbacks and server variables.
+    fn schema_to_type_name(&self, schema_name: &str) -> String {
+        translate::schema_to_rust_typename(schema_name)
+    }
+
+    /// The field name for a property, which must not be contained in
+    /// `taken`, the names of the fields named before
+    fn property_to_field_name(&self, property_name: &str, taken: &HashSet<String>) -> String {
+        translate::property_to_rust_fieldname(property_name, taken)
+    }
+
+    /// The function name for the operation at `method` and `path`. It is
+    /// not asked for operations named after their `operationId` (see
+    /// [crate::ApiConfig::operation_id_names]) or for legacy names (see
+    /// [crate::ApiConfig::legacy_path_names]).
+    fn operation_to_fn_name(&self, method: &http::Method, path: &str) -> anyhow::Result<String> {
+        translate::path_method_to_rust_fn_name(method, path)
+    }
+
+    /// The enum variant name for a media type (or range) of a content map
+    fn media_type_to_variant_name(&self, media_type: &str) -> String {
+        translate::media_type_range_to_rust_type_name(media_type)
+    }
+
+    /// The enum variant name for a response status
+    fn status_to_variant_name(&self, status_spec: StatusSpec) -> String {
+        translate::status_spec_to_rust_type_name(status_spec)
+    }
+}
+
+/// Clones and compares a [NamingPolicy] behind a trait object.
+/// Implemented for all policies implementing `Clone` and `PartialEq`.
+pub trait NamingPolicyClone {
+    fn clone_box(&self) -> Box<dyn NamingPolicy>;
+    fn as_any(&self) -> &dyn Any;
+    fn eq_box(&self, other: &dyn NamingPolicy) -> bool;
+}
+
+impl<T: NamingPolicy + Clone + PartialEq + 'static> NamingPolicyClone for T {
+    fn clone_box(&self) -> Box<dyn NamingPolicy> {
+        Box::new(self.clone())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn eq_box(&self, other: &dyn NamingPolicy) -> bool {
+        other.as_any().downcast_ref::<T>() == Some(self)
+    }
+}
+
+impl Clone for Box<dyn NamingPolicy> {
+    fn clone(&self) -> Self {
+        self.clone_box()
+    }
+}
+
+/// Policies are equal if they are of the same type and equal values
+impl PartialEq for dyn NamingPolicy {
+    fn eq(&self, other: &Self) -> bool {
+        self.eq_box(other)
+    }
+}
+
+/// The generator's own naming
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct DefaultNamingPolicy;
+
+impl NamingPolicy for DefaultNamingPolicy {}
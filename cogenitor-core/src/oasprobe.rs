@@ -31,7 +31,9 @@ pub(super) fn probe_yaml_oas_version(input: impl Read) -> Result<OASMajorVersion
         if line_count >= MAX_PROBE_LINES {
             break;
         }
-        if let Some(captures) = regex.captures(&line) {
+        // lines() leaves the carriage return of CRLF line endings
+        let line = line.strip_suffix('\r').unwrap_or(&line);
+        if let Some(captures) = regex.captures(line) {
             let major_minor_version = captures.get(2).unwrap().as_str();
             let full_version = captures.get(1).unwrap().as_str();
             let v = match major_minor_version {
@@ -69,6 +71,14 @@ mod tests {
         assert_eq!(v, OASMajorVersion::OAS30);
     }
 
+    #[test]
+    pub fn test_match_crlf() {
+        let input = b"openapi: 3.0.3\r\ninfo:\r\n".as_ref();
+
+        let v = probe_yaml_oas_version(input).unwrap();
+        assert_eq!(v, OASMajorVersion::OAS30);
+    }
+
     #[test]
     pub fn test_binary() {
         let input = [0u8, 0x0au8, 0xffu8, 0xe0].as_ref();
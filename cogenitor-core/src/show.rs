@@ -0,0 +1,302 @@
+//! Shows what is generated for a single operation of a spec: its client
+//! functions, how its parameters map to function parameters, and the types
+//! it takes and returns. This spares reading through the whole generated
+//! code when exploring a large spec.
+
+use std::fmt::Display;
+use std::str::FromStr;
+
+use anyhow::anyhow;
+
+use crate::codemodel::{Indirection, NamedItem, TypeRef, TypeRefOrTokenStream};
+use crate::types::{Operation, Parameter, ParameterLocation, PathItem, RequestBody, Spec};
+use crate::{ApiConfig, SpecVisitor, codewriter, translate};
+
+/// Selects an operation of a spec, either by method and path template
+/// (like `POST /orders`) or by its `operationId`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OperationSelector {
+    Route(http::Method, String),
+    OperationId(String),
+}
+
+impl FromStr for OperationSelector {
+    type Err = String;
+
+    /// Parses a method and path template separated by whitespace, like
+    /// `POST /orders`. The method is case insensitive.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (method, path) = s.trim().split_once(char::is_whitespace).ok_or(format!(
+            "invalid operation '{s}', expected a method and a path like 'POST /orders'"
+        ))?;
+        let method = http::Method::from_str(&method.to_ascii_uppercase())
+            .map_err(|_| format!("invalid method '{method}' in operation '{s}'"))?;
+        Ok(OperationSelector::Route(method, path.trim().to_string()))
+    }
+}
+
+impl Display for OperationSelector {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OperationSelector::Route(method, path) => write!(f, "{method} {path}"),
+            OperationSelector::OperationId(operation_id) => {
+                write!(f, "operationId '{operation_id}'")
+            }
+        }
+    }
+}
+
+/// Renders what is generated for the operation selected from the spec of
+/// `config`: the functions of the `Client` trait, a table mapping the
+/// operation's parameters and request body to function parameters, and
+/// the declarations of the types involved, formatted.
+pub fn show_operation(config: &ApiConfig, selector: &OperationSelector) -> anyhow::Result<String> {
+    crate::visit_config_spec(config, ShowOperation { config, selector })
+}
+
+struct ShowOperation<'a> {
+    config: &'a ApiConfig,
+    selector: &'a OperationSelector,
+}
+
+impl SpecVisitor for ShowOperation<'_> {
+    type Output = String;
+
+    fn visit<S: Spec>(self, spec: &S) -> anyhow::Result<String> {
+        show_operation_of(self.config, spec, self.selector)
+    }
+}
+
+pub(crate) fn show_operation_of<S: Spec>(
+    config: &ApiConfig,
+    spec: &S,
+    selector: &OperationSelector,
+) -> anyhow::Result<String> {
+    let mut selected = None;
+    for (path, path_item) in spec.paths() {
+        let operation = path_item
+            .operations_iter()
+            .find(|(method, path_op)| match selector {
+                OperationSelector::Route(m, p) => m == method && *p == path,
+                OperationSelector::OperationId(id) => path_op.operation_id() == Some(id.as_str()),
+            });
+        if let Some((method, path_op)) = operation {
+            selected = Some((path, method, path_item, path_op));
+            break;
+        }
+    }
+    let (path, method, path_item, path_op) =
+        selected.ok_or(anyhow!("no operation {selector} in the spec"))?;
+
+    // the function is named like in the generated code
+    let operations = spec
+        .paths()
+        .flat_map(|(path, path_item)| {
+            path_item
+                .operations_iter()
+                .map(|(method, _)| (path.clone(), method))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    let operation_ids = if config.operation_id_names {
+        crate::unique_operation_ids(spec)
+    } else {
+        Default::default()
+    };
+    let mut operation_names = crate::derive_operation_names(
+        &operations,
+        &operation_ids,
+        config.legacy_path_names,
+        config.non_ascii_names,
+        config.naming(),
+    )?;
+    let fn_name = operation_names
+        .remove(&(path.clone(), method.clone()))
+        .expect("names derived for all operations")
+        .fn_name;
+
+    // there is no way to generate a single operation, so we pick it from
+    // the code generated for the whole spec
+    let (codemodel, _) = crate::checked_codemodel(config, spec)?;
+    let crate_ = codemodel
+        .find_crate("crate")
+        .ok_or(anyhow!("crate not found"))?;
+    let client_trait = crate_
+        .trait_iter()
+        .find(|t| t.name() == "Client")
+        .ok_or(anyhow!("no Client trait generated"))?;
+    let function = client_trait
+        .function_iter()
+        .find(|f| f.name() == fn_name)
+        .ok_or(anyhow!("no client function generated for {method} {path}"))?;
+    let variants = [
+        fn_name.clone(),
+        fn_name.clone() + "_to",
+        fn_name.clone() + "_events",
+    ];
+    let signatures = codewriter::write_trait_functions(client_trait, |f| {
+        variants.iter().any(|variant| f.name() == variant.as_str())
+    })?;
+
+    // the inputs of the operation, in the order they become parameters
+    let mut spec_inputs = Vec::new();
+    let op_params = path_op
+        .parameters()
+        .map(|param| param.resolve_fully())
+        .collect::<Vec<_>>();
+    let outer_params = path_item
+        .parameters()
+        .map(|param| param.resolve_fully())
+        .filter(|param| {
+            !op_params
+                .iter()
+                .any(|p| p.name() == param.name() && p.in_() == param.in_())
+        })
+        .collect::<Vec<_>>();
+    for param in outer_params.iter().chain(&op_params) {
+        let location = translate::parameter_location_to_suffix(param.in_());
+        spec_inputs.push((param.name().to_string(), location));
+    }
+    for name in crate::url_template_variables(&path) {
+        let declared = outer_params
+            .iter()
+            .chain(&op_params)
+            .any(|p| p.in_() == ParameterLocation::Path && p.name() == name);
+        if !declared {
+            spec_inputs.push((name, "path (undeclared)"));
+        }
+    }
+    if let Some(request_body) = path_op.request_body() {
+        let required = request_body.resolve_fully().required();
+        let location = if required { "body" } else { "body (optional)" };
+        spec_inputs.push(("requestBody".to_string(), location));
+    }
+
+    let mut rust_params = function
+        .function_params_iter()
+        .filter(|param| param.name != "self")
+        .map(|param| (param.name.clone(), param.type_.name().to_string()))
+        .collect::<Vec<_>>();
+    // inputs bundled into a params struct are fields of it
+    if let [(name, type_name)] = rust_params.as_slice()
+        && spec_inputs.len() > 1
+        && let Some(fields) = struct_fields(&crate_, type_name)
+    {
+        rust_params = fields
+            .into_iter()
+            .map(|(field, field_type)| (format!("{name}.{field}"), field_type))
+            .collect();
+    }
+
+    let mut table = "| input | in | Rust parameter | type |\n|---|---|---|---|".to_string();
+    for ((input, location), (param, type_name)) in spec_inputs.iter().zip(&rust_params) {
+        table.push_str(&format!(
+            "\n| {input} | {location} | `{param}` | `{type_name}` |"
+        ));
+    }
+
+    let mut type_names = Vec::new();
+    for param in function.function_params_iter() {
+        codewriter::collect_idents(codewriter::syn_type_name_of(&param.type_)?, &mut type_names);
+    }
+    codewriter::collect_idents(
+        codewriter::syn_type_name_of(function.return_type())?,
+        &mut type_names,
+    );
+    let types = codewriter::write_type_decls_of(&codemodel, "crate", &type_names)?;
+
+    let signatures = codewriter::fmt_code(signatures)?;
+    let types = codewriter::fmt_code(types)?;
+    Ok(format!(
+        "{method} {path}\n\n{}\n\n{table}\n\n{}\n",
+        signatures.trim_end(),
+        types.trim_end()
+    ))
+}
+
+/// The names and types of the fields of the struct `name` of `module`
+fn struct_fields(module: &crate::codemodel::Module, name: &str) -> Option<Vec<(String, String)>> {
+    let type_ref = module.type_or_raw_item_iter().find_map(|item| match item {
+        crate::codemodel::TypeOrRawItem::Type(t) if t.name() == name => Some(t.clone()),
+        _ => None,
+    })?;
+    let type_ref = match type_ref {
+        TypeRef::Indirection(indirection) => match &*indirection.borrow() {
+            Indirection::Resolved(type_ref) => type_ref.clone(),
+            Indirection::Stub(_) => return None,
+        },
+        type_ref => type_ref,
+    };
+    let TypeRef::Struct(s) = type_ref else {
+        return None;
+    };
+    let fields = s
+        .field_iter()
+        .map(|field| {
+            let type_name = match field.type_() {
+                TypeRefOrTokenStream::TypeRef(type_ref) => type_ref.name().to_string(),
+                TypeRefOrTokenStream::TokenStream(ts) => ts.to_string(),
+            };
+            (field.name().to_string(), type_name)
+        })
+        .collect();
+    Some(fields)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_show_petstore_operation() {
+        let config = ApiConfig::new_from_path("../test-data/petstore.yaml".to_string());
+        let selector = OperationSelector::from_str("get /pet/{petId}").unwrap();
+        let shown = show_operation(&config, &selector).unwrap();
+        assert!(shown.starts_with("GET /pet/{petId}\n"), "{shown}");
+        assert!(
+            shown.contains(
+                "fn pet_pet_id_get(self: &Self, petId: i64) -> Result<PetPetIdGetOk200, PetPetIdGetError>;"
+            ),
+            "{shown}"
+        );
+        assert!(
+            shown.contains("| petId | path | `petId` | `i64` |"),
+            "{shown}"
+        );
+        // the types returned are declared, as are those they refer to
+        for declaration in [
+            "pub enum PetPetIdGetOk200",
+            "pub enum PetPetIdGetError",
+            "pub struct Pet ",
+            "pub struct Category",
+        ] {
+            assert!(shown.contains(declaration), "{shown}");
+        }
+        // but not the types of other operations
+        assert!(!shown.contains("pub struct Order"), "{shown}");
+
+        let selector = OperationSelector::OperationId("addPet".to_string());
+        let shown = show_operation(&config, &selector).unwrap();
+        assert!(shown.starts_with("POST /pet\n"), "{shown}");
+        assert!(shown.contains("| requestBody | body | `body` |"), "{shown}");
+
+        let selector = OperationSelector::from_str("DELETE /nowhere").unwrap();
+        let error = show_operation(&config, &selector).unwrap_err();
+        assert_eq!(
+            "no operation DELETE /nowhere in the spec",
+            error.to_string()
+        );
+    }
+
+    #[test]
+    fn test_parse_operation_selector() {
+        assert_eq!(
+            Ok(OperationSelector::Route(
+                http::Method::POST,
+                "/orders".to_string()
+            )),
+            OperationSelector::from_str(" post  /orders ")
+        );
+        assert!(OperationSelector::from_str("/orders").is_err());
+    }
+}
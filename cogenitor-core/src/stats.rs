@@ -0,0 +1,67 @@
+//! Statistics about a generation run, for logging and for the summary at
+//! the top of the files [crate::generate_file] writes, which gives
+//! reviewers of the generated code an idea of its size.
+
+use std::fmt::Display;
+use std::time::Duration;
+
+/// What a generation run produced and skipped, see
+/// [crate::generate_mod_with_stats]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GenerationStats {
+    /// The operations a client function was generated for
+    pub operations: usize,
+    /// The types generated: structs, enums and type aliases
+    pub types: usize,
+    /// The enums among the [GenerationStats::types]
+    pub enums: usize,
+    /// The lines of the formatted code. Only known where the code is
+    /// formatted, like in [crate::generate_file].
+    pub lines: Option<usize>,
+    /// The unsupported features skipped with a warning, like operations
+    /// without a client function or links that cannot be followed
+    pub skipped: usize,
+    /// How long generation took, from reading the spec to writing the code
+    pub duration: Duration,
+}
+
+impl GenerationStats {
+    /// The summary of a file generated from the spec at `source`, as `//`
+    /// comment lines. The duration is left out, so that generating the
+    /// same file twice yields the same bytes.
+    pub(crate) fn header_comment(&self, source: &str) -> String {
+        let mut comment = format!("// Generated by cogenitor from {source}. Do not edit.\n//\n");
+        comment.push_str(&format!("// operations: {}\n", self.operations));
+        comment.push_str(&format!(
+            "// types: {} ({} enums)\n",
+            self.types, self.enums
+        ));
+        if let Some(lines) = self.lines {
+            comment.push_str(&format!("// lines: {lines}\n"));
+        }
+        comment.push_str(&format!(
+            "// unsupported features skipped: {}\n",
+            self.skipped
+        ));
+        comment
+    }
+}
+
+impl Display for GenerationStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} operations, {} types ({} enums)",
+            self.operations, self.types, self.enums
+        )?;
+        if let Some(lines) = self.lines {
+            write!(f, ", {lines} lines")?;
+        }
+        write!(
+            f,
+            ", {} unsupported features skipped, generated in {} ms",
+            self.skipped,
+            self.duration.as_millis()
+        )
+    }
+}
@@ -4,7 +4,7 @@ use types::Components;
 
 #[cfg(test)]
 use crate::codemodel::function::Function;
-use crate::codemodel::{NamedItem, Scope};
+use crate::codemodel::{NamedItem, Scope, TypeRefOrTokenStream};
 
 use super::*;
 
@@ -13,7 +13,8 @@ static PETSTORE_YAML: &[u8] = include_bytes!("../../test-data/petstore.yaml");
 #[test]
 fn test_oas_petstore() {
     let reader = Cursor::new(PETSTORE_YAML);
-    super::generate_from_reader(reader).expect("reading petstore.yaml failed");
+    super::generate_from_reader(&ApiConfig::default(), reader)
+        .expect("reading petstore.yaml failed");
 }
 
 #[test]
@@ -49,7 +50,7 @@ fn test_simple_pet() -> anyhow::Result<()> {
 
     let spec = adapters::oas30::OAS30Spec::from_str(oas)?;
     assert_eq!(1, spec.schemata_iter().count());
-    let (cm, mapping) = super::build_codemodel(&spec)?;
+    let (cm, mapping) = super::build_codemodel(&ApiConfig::default(), &spec)?;
     let pet = spec
         .components()
         .unwrap()
@@ -68,6 +69,49 @@ fn test_simple_pet() -> anyhow::Result<()> {
     Ok(())
 }
 
+/// The spec as saved by Windows editors: with a byte order mark and CRLF
+/// line endings
+fn windows_saved(spec: &[u8]) -> Vec<u8> {
+    let mut saved = b"\xEF\xBB\xBF".to_vec();
+    for line in spec.split_inclusive(|b| *b == b'\n') {
+        match line.strip_suffix(b"\n") {
+            Some(line) => {
+                saved.extend_from_slice(line);
+                saved.extend_from_slice(b"\r\n");
+            }
+            None => saved.extend_from_slice(line),
+        }
+    }
+    saved
+}
+
+#[test]
+fn test_bom_crlf_petstore() -> anyhow::Result<()> {
+    let plain = super::generate_from_reader(&ApiConfig::default(), PETSTORE_YAML)?;
+    let windows = windows_saved(PETSTORE_YAML);
+    assert!(windows.windows(2).any(|w| w == b"\r\n"));
+    let generated = super::generate_from_reader(&ApiConfig::default(), windows.as_slice())?;
+    assert_tokenstreams_eq::assert_tokenstreams_eq!(&plain, &generated);
+    Ok(())
+}
+
+#[test]
+fn test_bom_crlf_error_location() {
+    let oas = b"openapi: 3.0.0
+info:
+    title: invalid spec
+    version: v1
+paths:
+    /pets:
+        get: [
+";
+    let plain = super::generate_from_reader(&ApiConfig::default(), oas.as_slice()).unwrap_err();
+    let windows = windows_saved(oas);
+    let error = super::generate_from_reader(&ApiConfig::default(), windows.as_slice()).unwrap_err();
+    assert!(plain.to_string().contains("line 8"), "{plain}");
+    assert_eq!(plain.to_string(), error.to_string());
+}
+
 #[test]
 fn test_simple_fn() -> anyhow::Result<()> {
     let oas = r"
@@ -84,7 +128,7 @@ paths:
 
     let spec = adapters::oas30::OAS30Spec::from_str(oas)?;
     assert_eq!(1, spec.paths().count());
-    let (cm, _mapping) = super::build_codemodel(&spec)?;
+    let (cm, _mapping) = super::build_codemodel(&ApiConfig::default(), &spec)?;
     let crate_ = cm.find_crate("crate").unwrap();
     assert!(
         crate_.trait_iter().any(|t| t.name() == "Client"),
@@ -134,12 +178,12 @@ paths:
 
     let spec = adapters::oas30::OAS30Spec::from_str(oas)?;
     assert_eq!(1, spec.paths().count());
-    let (cm, _mapping) = super::build_codemodel(&spec)?;
+    let (cm, _mapping) = super::build_codemodel(&ApiConfig::default(), &spec)?;
     let crate_ = cm.find_crate("crate").unwrap();
     assert!(crate_.trait_iter().any(|t| t.name() == "Client"));
     let trait_ = crate_.trait_iter().next().unwrap();
     let the_answer_get_fn =
-        unwrap_function("pet_findbystatus_get", trait_.associated_functions.iter());
+        unwrap_function("pet_find_by_status_get", trait_.associated_functions.iter());
 
     assert_eq!(
         2,
@@ -150,6 +194,577 @@ paths:
     Ok(())
 }
 
+#[test]
+fn test_content_param() -> anyhow::Result<()> {
+    let oas = r"
+openapi: 3.0.0
+info:
+    title: test for a parameter encoded as media type content
+    version: v1
+paths:
+    /pets:
+        get:
+            parameters:
+                -   name: filter
+                    in: query
+                    required: true
+                    content:
+                        application/json:
+                            schema:
+                                type: object
+                                properties:
+                                    color:
+                                        type: string
+            responses: {}";
+
+    let spec = adapters::oas30::OAS30Spec::from_str(oas)?;
+    let (cm, _mapping) = super::build_codemodel(&ApiConfig::default(), &spec)?;
+    let crate_ = cm.find_crate("crate").unwrap();
+    let trait_ = crate_.trait_iter().next().unwrap();
+    let pets_get_fn = unwrap_function("pets_get", trait_.associated_functions.iter());
+
+    let params = pets_get_fn
+        .function_params_iter()
+        .map(|p| (p.name.as_str(), p.type_.name().to_string()))
+        .collect::<Vec<_>>();
+    assert_eq!(2, params.len(), "function decl object: {pets_get_fn:?}");
+    let (param_name, param_type_name) = &params[1];
+    assert_eq!("filter", *param_name);
+
+    // the schema in the 'application/json' content becomes the parameter type
+    match crate_.find_type(param_type_name) {
+        Some(TypeRef::Struct(s)) => assert!(s.field_iter().any(|f| f.name() == "color")),
+        t => panic!("struct expected for parameter type {param_type_name}, found {t:?}"),
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_no_content_error_type() -> anyhow::Result<()> {
+    let oas = r"
+openapi: 3.0.0
+info:
+    title: test for an operation without error responses
+    version: v1
+paths:
+    /nothing:
+        get:
+            responses:
+                '204':
+                    description: get no response here";
+
+    let spec = adapters::oas30::OAS30Spec::from_str(oas)?;
+    let (cm, _mapping) = super::build_codemodel(&ApiConfig::default(), &spec)?;
+    let crate_ = cm.find_crate("crate").unwrap();
+    // undeclared statuses are still reported via the synthetic variants
+    let Some(TypeRef::Enum(error_enum)) = crate_.find_type("NothingGetError") else {
+        panic!("error enum expected in crate with content: {crate_:?}")
+    };
+    let variant_names = error_enum
+        .variant_iter()
+        .map(|v| v.name().to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(vec!["UnknownResponse", "OtherError"], variant_names);
+    let trait_ = crate_.trait_iter().next().unwrap();
+    let nothing_get_fn = unwrap_function("nothing_get", trait_.associated_functions.iter());
+    assert_eq!(
+        "Result<(),NothingGetError>",
+        nothing_get_fn.return_type().name()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_response_partitioning() -> anyhow::Result<()> {
+    let string_content = "
+                    content:
+                        text/plain:
+                            schema:
+                                type: string";
+    let success = format!(
+        "
+                '200':
+                    description: success{string_content}"
+    );
+    let errors = "
+                '400':
+                    description: client error
+                '500':
+                    description: server error";
+    let default = format!(
+        "
+                default:
+                    description: anything else{string_content}"
+    );
+
+    // (responses, success type, error enum variants)
+    let cases = [
+        ("{}".to_string(), "()", vec![]),
+        (
+            errors.to_string(),
+            "()",
+            vec!["BadRequest400", "InternalServerError500"],
+        ),
+        (success.clone(), "String", vec![]),
+        (
+            success.clone() + errors,
+            "String",
+            vec!["BadRequest400", "InternalServerError500"],
+        ),
+        // 'default' is an error response if there are success responses...
+        (success.clone() + &default, "String", vec!["Default"]),
+        (
+            default.clone() + errors,
+            "String",
+            vec!["BadRequest400", "InternalServerError500"],
+        ),
+        // ...and the success response otherwise
+        (default.clone(), "String", vec![]),
+    ];
+    for (responses, success_type, error_variants) in cases {
+        let oas = format!(
+            "
+openapi: 3.0.0
+info:
+    title: test for partitioning responses into success and error responses
+    version: v1
+paths:
+    /op:
+        get:
+            responses: {responses}"
+        );
+        let spec = adapters::oas30::OAS30Spec::from_str(&oas)?;
+        let (cm, _mapping) = super::build_codemodel(&ApiConfig::default(), &spec)?;
+        let crate_ = cm.find_crate("crate").unwrap();
+
+        let trait_ = crate_.trait_iter().next().unwrap();
+        let op_get_fn = unwrap_function("op_get", trait_.associated_functions.iter());
+        assert_eq!(
+            format!("Result<{success_type},OpGetError>"),
+            op_get_fn.return_type().name(),
+            "for responses {responses}"
+        );
+
+        let Some(TypeRef::Enum(error_enum)) = crate_.find_type("OpGetError") else {
+            panic!("error enum expected for responses {responses}")
+        };
+        let mut variant_names = error_enum
+            .variant_iter()
+            .map(|v| v.name().to_string())
+            .collect::<Vec<_>>();
+        variant_names.sort();
+        let mut expected_variants = error_variants;
+        expected_variants.extend(["UnknownResponse", "OtherError"]);
+        expected_variants.sort();
+        assert_eq!(
+            expected_variants, variant_names,
+            "for responses {responses}"
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_status_range_error_variants() -> anyhow::Result<()> {
+    let oas = r"
+openapi: 3.0.0
+info:
+    title: test for responses for status ranges
+    version: v1
+paths:
+    /pets:
+        get:
+            responses:
+                '200':
+                    description: the pets
+                '4XX':
+                    description: Client error
+                    content:
+                        application/json:
+                            schema:
+                                $ref: '#/components/schemas/Error'
+                '5XX':
+                    description: Server error
+        post:
+            responses:
+                '201':
+                    description: pet created
+                '4XX':
+                    description: Client error
+                    content:
+                        application/json:
+                            schema:
+                                type: object
+                                properties:
+                                    reason:
+                                        type: string
+components:
+    schemas:
+        Error:
+            type: object
+            properties:
+                message:
+                    type: string
+";
+
+    let spec = adapters::oas30::OAS30Spec::from_str(oas)?;
+    let (cm, _mapping) = super::build_codemodel(&ApiConfig::default(), &spec)?;
+    let code = codewriter::write_to_token_stream(&cm, "crate")?.to_string();
+
+    // a range with a body carries the body
+    assert!(code.contains("Status4XX (Error)"), "{code}");
+    // a range without body carries the actual status code
+    assert!(code.contains("Status5XX (:: http :: StatusCode)"), "{code}");
+    // inlined bodies of ranges get a named type
+    assert!(
+        code.contains("Status4XX (PetsPostClientError4XXBody)"),
+        "{code}"
+    );
+    let crate_ = cm.find_crate("crate").unwrap();
+    assert!(matches!(
+        crate_.find_type("PetsPostClientError4XXBody"),
+        Some(TypeRef::Struct(_))
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_shared_header_type() -> anyhow::Result<()> {
+    let oas = r"
+openapi: 3.0.0
+info:
+    title: test for response headers shared between operations
+    version: v1
+paths:
+    /foo:
+        get:
+            responses:
+                '200':
+                    description: a foo
+                    headers:
+                        X-RateLimit:
+                            $ref: '#/components/headers/X-RateLimit'
+    /bar:
+        get:
+            responses:
+                '200':
+                    description: a bar
+                    headers:
+                        X-RateLimit:
+                            $ref: '#/components/headers/X-RateLimit'
+components:
+    headers:
+        X-RateLimit:
+            schema:
+                type: object
+                properties:
+                    remaining:
+                        type: integer
+                    reset:
+                        type: integer
+        X-Request-Id:
+            schema:
+                type: string";
+
+    let spec = adapters::oas30::OAS30Spec::from_str(oas)?;
+    let (cm, mapping) = super::build_codemodel(&ApiConfig::default(), &spec)?;
+    let crate_ = cm.find_crate("crate").unwrap();
+
+    // one shared type for the object header
+    let rate_limit = crate_.find_type("XRateLimit").unwrap();
+    match &rate_limit {
+        TypeRef::Struct(s) => assert_eq!(2, s.field_iter().count()),
+        _ => panic!("struct expected, found {rate_limit:?}"),
+    }
+    assert_eq!(
+        1,
+        crate_
+            .type_iter()
+            .filter(|t| t.name() == "XRateLimit")
+            .count()
+    );
+    assert_eq!(Some(&rate_limit), mapping.header_mapping.get("X-RateLimit"));
+
+    // the string header maps to a builtin type, no type is generated for it
+    assert!(crate_.find_type("XRequestId").is_none());
+    assert_eq!(
+        "String",
+        mapping.header_mapping.get("X-Request-Id").unwrap().name()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_shared_rate_limit_header_type() -> anyhow::Result<()> {
+    let oas = r"
+openapi: 3.0.0
+info:
+    title: test for rate limit headers shared between operations
+    version: v1
+paths:
+    /foo:
+        get:
+            responses:
+                '200':
+                    description: a foo
+                '429':
+                    description: too many requests
+                    headers:
+                        X-RateLimit-Limit:
+                            $ref: '#/components/headers/X-RateLimit-Limit'
+                        X-RateLimit-Remaining:
+                            schema:
+                                type: integer
+    /bar:
+        get:
+            responses:
+                '200':
+                    description: a bar
+                '429':
+                    description: too many requests
+                    headers:
+                        X-RateLimit-Limit:
+                            $ref: '#/components/headers/X-RateLimit-Limit'
+components:
+    headers:
+        X-RateLimit-Limit:
+            schema:
+                type: object
+                properties:
+                    requests:
+                        type: integer
+                    window:
+                        type: string";
+
+    let spec = adapters::oas30::OAS30Spec::from_str(oas)?;
+    let (cm, mapping) = super::build_codemodel(&ApiConfig::default(), &spec)?;
+    let crate_ = cm.find_crate("crate").unwrap();
+    assert_eq!(
+        1,
+        crate_
+            .type_iter()
+            .filter(|t| t.name() == "XRateLimitLimit")
+            .count()
+    );
+    assert!(mapping.header_mapping.contains_key("X-RateLimit-Limit"));
+
+    // both error variants use the shared type, inline headers stay numeric
+    let code = codewriter::write_to_token_stream(&cm, "crate")?.to_string();
+    let limit_field = "rate_limit_limit : Option < XRateLimitLimit >";
+    assert_eq!(2, code.matches(limit_field).count(), "{code}");
+    assert!(
+        code.contains("rate_limit_remaining : Option < u64 >"),
+        "{code}"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_trailing_slash_path() -> anyhow::Result<()> {
+    let oas = r"
+openapi: 3.0.0
+info:
+    title: test for paths that only differ in a trailing slash
+    version: v1
+paths:
+    /pets/:
+        get:
+            responses:
+                '200':
+                    description: all pets
+                    content:
+                        application/json:
+                            schema:
+                                type: string
+                '404':
+                    description: not found
+    /pets:
+        get:
+            responses:
+                '200':
+                    description: all pets
+                    content:
+                        application/json:
+                            schema:
+                                type: string
+                '404':
+                    description: not found";
+
+    let spec = adapters::oas30::OAS30Spec::from_str(oas)?;
+    let (cm, _mapping) = super::build_codemodel(&ApiConfig::default(), &spec)?;
+    let crate_ = cm.find_crate("crate").unwrap();
+    let trait_ = crate_.trait_iter().next().unwrap();
+
+    // '/pets' keeps its names even though '/pets/' comes first
+    let function_names = trait_
+        .associated_functions
+        .iter()
+        .map(|f| f.name().to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(vec!["pets_get1", "pets_get"], function_names);
+    assert!(crate_.find_type("PetsGetError").is_some());
+    assert!(crate_.find_type("PetsGet1Error").is_some());
+
+    Ok(())
+}
+
+#[test]
+fn test_derive_operation_names() -> anyhow::Result<()> {
+    let operations = vec![
+        ("/pets/".to_string(), http::Method::GET),
+        ("/pets".to_string(), http::Method::GET),
+        ("/pets".to_string(), http::Method::PUT),
+        ("/foo-bar".to_string(), http::Method::GET),
+        ("/foo_bar".to_string(), http::Method::GET),
+    ];
+    let names = super::derive_operation_names(
+        &operations,
+        &HashMap::new(),
+        false,
+        super::NonAsciiHandling::Strip,
+        &super::DefaultNamingPolicy,
+    )?;
+    let name_of = |path: &str, method: http::Method| {
+        let names = names.get(&(path.to_string(), method)).unwrap();
+        (names.fn_name.as_str(), names.type_name.as_str())
+    };
+
+    assert_eq!(("pets_get", "PetsGet"), name_of("/pets", http::Method::GET));
+    assert_eq!(
+        ("pets_get1", "PetsGet1"),
+        name_of("/pets/", http::Method::GET)
+    );
+    assert_eq!(("pets_put", "PetsPut"), name_of("/pets", http::Method::PUT));
+    assert_eq!(
+        ("foo_bar_get", "FooBarGet"),
+        name_of("/foo-bar", http::Method::GET)
+    );
+    assert_eq!(
+        ("foo_bar_get1", "FooBarGet1"),
+        name_of("/foo_bar", http::Method::GET)
+    );
+
+    let operations = vec![("/pet/findByStatus".to_string(), http::Method::GET)];
+    let key = (operations[0].0.clone(), http::Method::GET);
+    let names = super::derive_operation_names(
+        &operations,
+        &HashMap::new(),
+        false,
+        super::NonAsciiHandling::Strip,
+        &super::DefaultNamingPolicy,
+    )?;
+    assert_eq!("pet_find_by_status_get", names[&key].fn_name);
+    let names = super::derive_operation_names(
+        &operations,
+        &HashMap::new(),
+        true,
+        super::NonAsciiHandling::Strip,
+        &super::DefaultNamingPolicy,
+    )?;
+    assert_eq!("pet_findbystatus_get", names[&key].fn_name);
+    assert_eq!("PetFindByStatusGet", names[&key].type_name);
+
+    Ok(())
+}
+
+static MANY_PARAMS_YAML: &str = r"
+openapi: 3.0.0
+info:
+    title: test for an operation with many parameters
+    version: v1
+paths:
+    /items/{id}:
+        put:
+            parameters:
+                -   {name: id, in: path, required: true, schema: {type: string}}
+                -   {name: p1, in: query, required: true, schema: {type: string}}
+                -   {name: p2, in: query, schema: {type: string}}
+                -   {name: p3, in: query, schema: {type: string}}
+                -   {name: p4, in: query, schema: {type: string}}
+                -   {name: p5, in: query, schema: {type: string}}
+                -   {name: p6, in: query, schema: {type: string}}
+                -   {name: p7, in: query, schema: {type: string}}
+                -   {name: p8, in: header, schema: {type: string}}
+                -   {name: p9, in: header, schema: {type: string}}
+            requestBody:
+                required: true
+                content:
+                    application/json:
+                        schema:
+                            type: string
+            responses:
+                '204':
+                    description: no content";
+
+#[test]
+fn test_many_params_without_struct() -> anyhow::Result<()> {
+    let spec = adapters::oas30::OAS30Spec::from_str(MANY_PARAMS_YAML)?;
+    let (cm, _mapping) = super::build_codemodel(&ApiConfig::default(), &spec)?;
+    let crate_ = cm.find_crate("crate").unwrap();
+    assert!(crate_.find_type("ItemsIdPutParams").is_none());
+    let trait_ = crate_.trait_iter().next().unwrap();
+    let put_fn = unwrap_function("items_id_put", trait_.associated_functions.iter());
+
+    // self, ten parameters and the body
+    assert_eq!(12, put_fn.function_params_iter().count());
+
+    Ok(())
+}
+
+#[test]
+fn test_many_params_with_struct() -> anyhow::Result<()> {
+    let config = ApiConfig {
+        params_struct_threshold: Some(8),
+        ..Default::default()
+    };
+    let spec = adapters::oas30::OAS30Spec::from_str(MANY_PARAMS_YAML)?;
+    let (cm, _mapping) = super::build_codemodel(&config, &spec)?;
+    let crate_ = cm.find_crate("crate").unwrap();
+    let trait_ = crate_.trait_iter().next().unwrap();
+    let put_fn = unwrap_function("items_id_put", trait_.associated_functions.iter());
+
+    let params = put_fn
+        .function_params_iter()
+        .map(|p| (p.name.as_str(), p.type_.name().to_string()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        vec![
+            ("self", "&Self".to_string()),
+            ("params", "ItemsIdPutParams".to_string())
+        ],
+        params
+    );
+
+    let params_struct = crate_.find_type("ItemsIdPutParams").unwrap();
+    match &params_struct {
+        TypeRef::Struct(s) => {
+            let fields = s
+                .field_iter()
+                .map(|f| match f.type_() {
+                    TypeRefOrTokenStream::TypeRef(t) => {
+                        (f.name().to_string(), t.name().to_string())
+                    }
+                    TypeRefOrTokenStream::TokenStream(ts) => (f.name().to_string(), ts.to_string()),
+                })
+                .collect::<Vec<_>>();
+            assert_eq!(11, fields.len());
+            assert!(fields.contains(&("id".to_string(), "String".to_string())));
+            assert!(fields.contains(&("p1".to_string(), "String".to_string())));
+            assert!(fields.contains(&("p2".to_string(), "Option<String>".to_string())));
+            assert!(fields.contains(&("body".to_string(), "String".to_string())));
+        }
+        _ => panic!("struct expected, found {params_struct:?}"),
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 fn unwrap_function<'a, 'b>(
     name: &'a str,
@@ -160,3 +775,3238 @@ fn unwrap_function<'a, 'b>(
         None => panic!("Implemenation does not match pattern"),
     }
 }
+
+#[test]
+fn test_generate_batch() -> anyhow::Result<()> {
+    let test_data = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("../test-data");
+    let temp_dir = std::env::temp_dir();
+    let empty_path = temp_dir.join("cogenitor_test_generate_batch_empty.yaml");
+    std::fs::write(
+        &empty_path,
+        r"
+openapi: 3.0.0
+info:
+    title: Empty API
+    version: v1
+paths: {}
+",
+    )?;
+    let orders_path = temp_dir.join("cogenitor_test_generate_batch_orders.yaml");
+    std::fs::write(
+        &orders_path,
+        r"
+openapi: 3.0.0
+info:
+    title: Orders API
+    version: v1
+paths:
+    /orders/{orderId}:
+        get:
+            parameters:
+                - name: orderId
+                  in: path
+                  required: true
+                  schema:
+                      type: integer
+            responses:
+                '200':
+                    description: the order
+                    content:
+                        application/json:
+                            schema:
+                                $ref: '#/components/schemas/Order'
+components:
+    schemas:
+        Order:
+            type: object
+            properties:
+                id:
+                    type: integer
+",
+    )?;
+
+    let config_of = |path: std::path::PathBuf, module_name: &str| ApiConfig {
+        path: Some(path.to_string_lossy().into_owned()),
+        module_name: Some(module_name.to_string()),
+        ..Default::default()
+    };
+    let configs = [
+        config_of(test_data.join("petstore.yaml"), "petstore"),
+        config_of(orders_path, "orders"),
+        config_of(empty_path, "empty"),
+    ];
+
+    let sequential = super::generate_batch(&configs)?;
+
+    let to_strings = |generated: Vec<(String, TokenStream)>| {
+        generated
+            .into_iter()
+            .map(|(name, ts)| (name, ts.to_string()))
+            .collect::<Vec<_>>()
+    };
+    let sequential = to_strings(sequential);
+    assert_eq!(
+        vec!["petstore", "orders", "empty"],
+        sequential.iter().map(|(name, _)| name).collect::<Vec<_>>()
+    );
+    #[cfg(feature = "parallel")]
+    {
+        let parallel = super::generate_batch_parallel(&configs)?;
+        assert_eq!(sequential, to_strings(parallel));
+    }
+    Ok(())
+}
+
+static SPLIT_SPEC_YAML: &str = r"
+openapi: 3.0.0
+info:
+    title: a spec split into several files
+    version: v1
+paths:
+    /pets/{petId}:
+        get:
+            parameters:
+                - name: petId
+                  in: path
+                  required: true
+                  schema:
+                      type: integer
+            responses:
+                '200':
+                    description: the pet
+                    content:
+                        application/json:
+                            schema:
+                                $ref: '#/components/schemas/Pet'
+components:
+    schemas:
+        Pet:
+            type: object
+            properties:
+                name:
+                    type: string
+";
+
+static SPLIT_SPEC_ORDERS_YAML: &str = r"
+openapi: 3.0.3
+info:
+    title: the orders of the split spec
+    version: v1
+paths:
+    /orders/{orderId}:
+        get:
+            parameters:
+                - name: orderId
+                  in: path
+                  required: true
+                  schema:
+                      type: integer
+            responses:
+                '200':
+                    description: the order
+                    content:
+                        application/json:
+                            schema:
+                                $ref: '#/components/schemas/Order'
+components:
+    schemas:
+        Order:
+            type: object
+            properties:
+                pet:
+                    $ref: '#/components/schemas/Pet'
+    parameters:
+        Limit:
+            name: limit
+            in: query
+            schema:
+                type: integer
+";
+
+static SPLIT_SPEC_STORE_YAML: &str = r"
+openapi: 3.0.0
+info:
+    title: the store of the split spec
+    version: v1
+paths:
+    /store:
+        get:
+            parameters:
+                - $ref: '#/components/parameters/Limit'
+            responses:
+                '200':
+                    description: the store's inventory
+                    content:
+                        application/json:
+                            schema:
+                                type: array
+                                items:
+                                    $ref: '#/components/schemas/Order'
+";
+
+#[test]
+fn test_additional_paths() -> anyhow::Result<()> {
+    let temp_dir = std::env::temp_dir();
+    let write = |name: &str, yaml: &str| -> anyhow::Result<String> {
+        let path = temp_dir.join(name);
+        std::fs::write(&path, yaml)?;
+        Ok(path.to_string_lossy().into_owned())
+    };
+    let path = write("cogenitor_test_additional_paths.yaml", SPLIT_SPEC_YAML)?;
+    let orders_path = write(
+        "cogenitor_test_additional_paths_orders.yaml",
+        SPLIT_SPEC_ORDERS_YAML,
+    )?;
+    let store_path = write(
+        "cogenitor_test_additional_paths_store.yaml",
+        SPLIT_SPEC_STORE_YAML,
+    )?;
+    let config = ApiConfig {
+        path: Some(path.clone()),
+        additional_paths: vec![orders_path.clone(), store_path.clone()],
+        ..Default::default()
+    };
+    let code = super::generate_string(&config)?;
+
+    assert!(code.contains("fn pets_pet_id_get"), "{code}");
+    assert!(code.contains("fn orders_order_id_get"), "{code}");
+    assert!(code.contains("fn store_get"), "{code}");
+    assert!(code.contains("struct Order"), "{code}");
+
+    // operations of additional paths can be shown, too
+    let selector = super::OperationSelector::Route(http::Method::GET, "/store".to_string());
+    let shown = super::show_operation(&config, &selector)?;
+    assert!(shown.starts_with("GET /store\n"), "{shown}");
+
+    // loading the files in parallel keeps their order, so the merged spec
+    // is that of loading them one after the other
+    let paths = [&path, &orders_path, &store_path];
+    let mut spec = super::load_spec_document(&path)?;
+    for path in &paths[1..] {
+        super::merge_spec_document(&mut spec, super::load_spec_document(path)?)?;
+    }
+    let merged = super::merged_spec(&path, &config.additional_paths)?;
+    assert_eq!(serde_yaml::to_string(&spec)?.into_bytes(), merged);
+
+    // names declared twice are an error
+    let config = ApiConfig {
+        path: Some(path.clone()),
+        additional_paths: vec![orders_path.clone(), orders_path],
+        ..Default::default()
+    };
+    let error = super::generate_string(&config).unwrap_err().to_string();
+    assert!(error.contains("paths//orders/{orderId}"), "{error}");
+
+    // so are specs of another OpenAPI version
+    let v31_path = write(
+        "cogenitor_test_additional_paths_v31.yaml",
+        &SPLIT_SPEC_STORE_YAML.replace("3.0.0", "3.1.0"),
+    )?;
+    let config = ApiConfig {
+        path: Some(path),
+        additional_paths: vec![v31_path],
+        ..Default::default()
+    };
+    super::generate_string(&config).unwrap_err();
+
+    // and additional paths without a path
+    let config = ApiConfig {
+        url: Some("https://example.com/openapi.yaml".to_string()),
+        additional_paths: vec![store_path],
+        ..Default::default()
+    };
+    super::generate_string(&config).unwrap_err();
+    Ok(())
+}
+
+#[test]
+fn test_generate_deterministic() -> anyhow::Result<()> {
+    // each HashMap iterates in its own order, so generating twice in the
+    // same process reveals code depending on it
+    let test_data = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("../test-data");
+    for entry in std::fs::read_dir(test_data)? {
+        let path = entry?.path();
+        // the conformance specs contain constructs we don't support yet
+        let name = path.file_name().unwrap_or_default().to_string_lossy();
+        if !name.ends_with(".yaml") || name.starts_with("conformance-") {
+            continue;
+        }
+        let mut config = ApiConfig::new_from_path(path.to_string_lossy().into_owned());
+        config.response_decoders = true;
+        config.server_framework = ServerFramework::Axum;
+        config.link_helpers = true;
+        config.callback_parsers = true;
+        config.emit_examples = true;
+        let Ok(expected) = super::generate_string(&config) else {
+            continue;
+        };
+        for _ in 0..4 {
+            assert_eq!(expected, super::generate_string(&config)?, "{path:?}");
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn test_generation_stats() -> anyhow::Result<()> {
+    let spec_path =
+        std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("../test-data/petstore.yaml");
+    let config = ApiConfig::new_from_path(spec_path.to_string_lossy().into_owned());
+    let (_, stats) = super::generate_mod_with_stats(&config)?;
+    assert_eq!(19, stats.operations);
+    assert_eq!(46, stats.types);
+    assert_eq!(37, stats.enums);
+    assert_eq!(0, stats.skipped);
+    // lines are only counted when the code is formatted
+    assert_eq!(None, stats.lines);
+
+    // generated files start with the stats
+    let output_path = std::env::temp_dir().join("cogenitor_test_generation_stats.rs");
+    super::generate_file(&config, &output_path)?;
+    let code = std::fs::read_to_string(&output_path)?;
+    let header = code
+        .lines()
+        .take_while(|line| line.starts_with("//"))
+        .collect::<Vec<_>>();
+    let lines = code.lines().count() - header.len();
+    assert!(header[0].contains("petstore.yaml"), "{header:?}");
+    assert!(header.contains(&"// operations: 19"), "{header:?}");
+    assert!(
+        header.contains(&format!("// types: {} ({} enums)", stats.types, stats.enums).as_str()),
+        "{header:?}"
+    );
+    assert!(
+        header.contains(&format!("// lines: {lines}").as_str()),
+        "{header:?}"
+    );
+    assert!(
+        header.contains(&"// unsupported features skipped: 0"),
+        "{header:?}"
+    );
+    // generating again yields the same bytes
+    super::generate_file(&config, &output_path)?;
+    assert_eq!(code, std::fs::read_to_string(&output_path)?);
+
+    std::fs::remove_file(&output_path)?;
+    Ok(())
+}
+
+#[test]
+fn test_generate_on_threads() -> anyhow::Result<()> {
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<ApiConfig>();
+
+    let test_data = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("../test-data");
+    let configs = ["petstore.yaml", "response-decoders.yaml"]
+        .map(|file| ApiConfig::new_from_path(test_data.join(file).to_string_lossy().into_owned()));
+
+    // both threads generate at the same time
+    let barrier = std::sync::Arc::new(std::sync::Barrier::new(configs.len()));
+    let handles = configs.clone().map(|config| {
+        let barrier = barrier.clone();
+        std::thread::spawn(move || {
+            barrier.wait();
+            super::generate_string(&config)
+        })
+    });
+    let mut generated = Vec::new();
+    for handle in handles {
+        let code = handle.join().expect("generation panicked")?;
+        generated.push(
+            code.parse::<TokenStream>()
+                .map_err(|e| anyhow!(e.to_string()))?,
+        );
+    }
+
+    for (config, ts) in configs.iter().zip(generated) {
+        let expected = super::generate_token_stream(config)?.to_string();
+        assert_eq!(expected, ts.to_string());
+    }
+    Ok(())
+}
+
+static VISIBILITY_YAML: &str = r"
+openapi: 3.0.0
+info:
+    title: Visibility
+    version: v1
+paths: {}
+components:
+    schemas:
+        Pet:
+            type: object
+            required:
+                - name
+            properties:
+                name:
+                    type: string
+";
+
+#[test]
+fn test_visibility() -> anyhow::Result<()> {
+    use assert_tokenstreams_eq::assert_tokenstreams_eq;
+
+    let spec = adapters::oas30::OAS30Spec::from_str(VISIBILITY_YAML)?;
+    for (visibility, vis) in [
+        (Visibility::Public, quote!(pub)),
+        (Visibility::Crate, quote!(pub(crate))),
+        (Visibility::Private, quote!()),
+    ] {
+        let config = ApiConfig {
+            visibility,
+            ..Default::default()
+        };
+        let ts = super::generate_code(&config, &spec)?;
+        let ts_reference = quote!(
+            #vis trait Client {}
+            #[derive(
+                ::std::fmt::Debug,
+                ::serde::Serialize,
+                ::serde::Deserialize,
+                ::core::cmp::PartialEq
+            )]
+            #vis struct Pet {
+                pub name: String
+            }
+            #[derive(Debug)]
+            #vis struct ClientImpl {
+                pub base_url: String,
+                pub timeout: Option<::std::time::Duration>
+            }
+            #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+            #vis struct OperationInfo {
+                pub name: &'static str,
+                pub method: &'static str,
+                pub path: &'static str,
+                pub summary: &'static str,
+                pub tag: &'static str
+            }
+            #vis fn operations() -> &'static [OperationInfo] {
+                &[]
+            }
+            #vis fn lookup_operation(
+                method: &::http::Method,
+                path_template: &str,
+            ) -> ::std::option::Option<&'static str> {
+                match (method.as_str(), path_template) {
+                    _ => ::std::option::Option::None,
+                }
+            }
+            #vis fn operation_of(
+                function_name: &str,
+            ) -> ::std::option::Option<(::http::Method, &'static str)> {
+                match function_name {
+                    _ => ::std::option::Option::None,
+                }
+            }
+            // trait implementations never carry a visibility
+            impl ::std::fmt::Display for ClientImpl {
+                fn fmt(self: &Self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                    ::std::write!(
+                        f,
+                        "ClientImpl {{ base_url: {}, timeout: {:?} }}",
+                        self.base_url,
+                        self.timeout
+                    )
+                }
+            }
+            impl ClientImpl {
+                #vis fn url_for(
+                    self: &Self,
+                    path: &str
+                ) -> ::std::result::Result<::url::Url, ::url::ParseError> {
+                    let mut base_url = ::url::Url::parse(&self.base_url)?;
+                    if !base_url.path().ends_with('/') {
+                        let base_path = ::std::format!("{}/", base_url.path());
+                        base_url.set_path(&base_path);
+                    }
+                    base_url.join(path.trim_start_matches('/'))
+                }
+            }
+        );
+        assert_tokenstreams_eq!(&ts, &ts_reference);
+    }
+    Ok(())
+}
+
+static STRINGIFIED_INTEGERS_YAML: &str = r"
+openapi: 3.0.0
+info:
+    title: test for integers stringified in JSON
+    version: v1
+paths: {}
+components:
+    schemas:
+        Account:
+            type: object
+            required: [id, owner_id, balance]
+            properties:
+                id:
+                    type: string
+                    format: int64
+                owner_id:
+                    type: string
+                    format: int64
+                balance:
+                    type: string
+                    format: uint64
+";
+
+#[test]
+fn test_stringified_integers() -> anyhow::Result<()> {
+    let spec = adapters::oas30::OAS30Spec::from_str(STRINGIFIED_INTEGERS_YAML)?;
+
+    for (stringified_integers, int64_type, uint64_type) in [
+        (false, "String", "String"),
+        (true, "Int64String", "UInt64String"),
+    ] {
+        let config = ApiConfig {
+            stringified_integers,
+            ..Default::default()
+        };
+        let (cm, _mapping) = super::build_codemodel(&config, &spec)?;
+        let crate_ = cm.find_crate("crate").unwrap();
+
+        let account = crate_.find_type("Account").unwrap();
+        let TypeRef::Struct(account) = account else {
+            panic!("struct expected, found {account:?}")
+        };
+        let fields = account
+            .field_iter()
+            .map(|f| match f.type_() {
+                TypeRefOrTokenStream::TypeRef(t) => (f.name().to_string(), t.name().to_string()),
+                TypeRefOrTokenStream::TokenStream(ts) => (f.name().to_string(), ts.to_string()),
+            })
+            .collect::<HashMap<_, _>>();
+        assert_eq!(int64_type, fields["id"]);
+        assert_eq!(int64_type, fields["owner_id"]);
+        assert_eq!(uint64_type, fields["balance"]);
+
+        // newtypes are shared by all their uses in the module
+        let mut raw_item_names = crate_
+            .raw_item_iter()
+            .map(|item| item.name().to_string())
+            .filter(|name| name.ends_with("String"))
+            .collect::<Vec<_>>();
+        raw_item_names.sort();
+        if stringified_integers {
+            assert_eq!(vec!["Int64String", "UInt64String"], raw_item_names);
+        } else {
+            assert!(raw_item_names.is_empty());
+        }
+    }
+    Ok(())
+}
+
+static MAPS_YAML: &str = r"
+openapi: 3.0.0
+info:
+    title: test for objects mapped to HashMaps
+    version: v1
+paths: {}
+components:
+    schemas:
+        Counters:
+            type: object
+            additionalProperties:
+                type: integer
+                format: int32
+        Pets:
+            type: object
+            additionalProperties:
+                $ref: '#/components/schemas/Pet'
+        Pet:
+            type: object
+            properties:
+                name:
+                    type: string
+        Anything:
+            type: object
+        Nothing:
+            type: object
+            additionalProperties: false
+";
+
+#[test]
+fn test_additional_properties_map() -> anyhow::Result<()> {
+    let spec = adapters::oas30::OAS30Spec::from_str(MAPS_YAML)?;
+    let (cm, _mapping) = super::build_codemodel(&ApiConfig::default(), &spec)?;
+    let crate_ = cm.find_crate("crate").unwrap();
+
+    for (alias_name, target_name) in [
+        ("Counters", "::std::collections::HashMap<String, i32>"),
+        ("Pets", "::std::collections::HashMap<String, Pet>"),
+        (
+            "Anything",
+            "::std::collections::HashMap<String, ::serde_json::Value>",
+        ),
+    ] {
+        let Some(TypeRef::Alias(alias)) = crate_.find_type(alias_name) else {
+            panic!("alias {alias_name} expected")
+        };
+        let target: syn::Type = syn::parse_str(&alias.target().name())?;
+        let expected: syn::Type = syn::parse_str(target_name)?;
+        assert_eq!(
+            quote::quote!(#expected).to_string(),
+            quote::quote!(#target).to_string()
+        );
+    }
+
+    // no properties may be added, so this remains an (empty) struct
+    assert!(matches!(
+        crate_.find_type("Nothing"),
+        Some(TypeRef::Struct(_))
+    ));
+    Ok(())
+}
+
+#[test]
+fn test_contact_doc() -> anyhow::Result<()> {
+    let temp_dir = std::env::temp_dir();
+    let spec_path = temp_dir.join("cogenitor_test_contact_doc.yaml");
+    std::fs::write(
+        &spec_path,
+        r"
+openapi: 3.0.0
+info:
+    title: API with contact
+    version: v1
+    termsOfService: https://example.com/terms
+    contact:
+        name: API_Team
+        email: api@example.com
+        url: https://example.com/(team)
+    license:
+        name: Apache 2.0
+        url: https://www.apache.org/licenses/LICENSE-2.0.html
+paths: {}
+",
+    )?;
+    let output_path = temp_dir.join("cogenitor_test_contact_doc.rs");
+    let config = ApiConfig::new_from_path(spec_path.to_string_lossy().into_owned());
+    super::generate_file(&config, &output_path)?;
+    let code = std::fs::read_to_string(&output_path)?;
+
+    // contact, license and terms document the generated module
+    let doc = "| | |\n|---|---|\n\
+        | Contact | API\\_Team <api@example.com> <https://example.com/%28team%29> |\n\
+        | License | [Apache 2.0](https://www.apache.org/licenses/LICENSE-2.0.html) |\n\
+        | Terms of service | <https://example.com/terms> |";
+    let mod_pos = code.find("pub mod generated_api {").unwrap();
+    let doc_pos = code
+        .find(&format!("#![doc = {doc:?}]"))
+        .unwrap_or_else(|| panic!("info doc not found in generated code:\n{code}"));
+    assert!(mod_pos < doc_pos);
+
+    // without contact, license and terms, there is no module documentation
+    let spec = adapters::oas30::OAS30Spec::from_str(
+        r"
+openapi: 3.0.0
+info:
+    title: API without info
+    version: v1
+paths: {}
+",
+    )?;
+    let ts = super::generate_code(&ApiConfig::default(), &spec)?;
+    assert!(!ts.to_string().contains("doc"));
+
+    Ok(())
+}
+
+#[test]
+fn test_request_body_doc() -> anyhow::Result<()> {
+    let spec = adapters::oas30::OAS30Spec::from_str(
+        r"
+openapi: 3.0.0
+info:
+    title: API with a described request body
+    version: v1
+paths:
+    /pets:
+        post:
+            requestBody:
+                description: |-
+                    The pet to add.
+                    Its id is assigned by the server.
+                required: true
+                content:
+                    application/xml:
+                        schema:
+                            type: string
+                    application/json:
+                        schema:
+                            type: string
+            responses:
+                '201':
+                    description: the pet was added
+",
+    )?;
+    let (cm, _mapping) = super::build_codemodel(&ApiConfig::default(), &spec)?;
+    let crate_ = cm.find_crate("crate").unwrap();
+    let trait_ = crate_.trait_iter().next().unwrap();
+    let docs = trait_.associated_functions[0]
+        .attr_iter()
+        .map(|attr| attr.input().to_string())
+        .collect::<Vec<_>>();
+    let expected = " # Request body\n\n The pet to add.\n Its id is assigned by the server.\n\n \
+        Media types: `application/json`, `application/xml`\n\n The body is required.";
+    assert_eq!(vec![quote!(= #expected).to_string()], docs);
+    Ok(())
+}
+
+static BINARY_DOWNLOAD_YAML: &str = r"
+openapi: 3.0.0
+info:
+    title: test for downloading binary content
+    version: v1
+paths:
+    /export:
+        get:
+            parameters:
+                -   name: since
+                    in: query
+                    schema:
+                        type: string
+            responses:
+                '200':
+                    description: the export
+                    content:
+                        application/octet-stream: {}
+                '404':
+                    description: nothing to export
+                    content:
+                        application/json:
+                            schema:
+                                type: object
+                                properties:
+                                    message:
+                                        type: string
+    /avatar:
+        get:
+            responses:
+                '200':
+                    description: the avatar
+                    content:
+                        image/png:
+                            schema:
+                                type: string
+                                format: binary
+    /report:
+        get:
+            responses:
+                '200':
+                    description: the report
+                    content:
+                        text/csv:
+                            schema:
+                                type: string
+                                format: binary
+    /status:
+        get:
+            responses:
+                '200':
+                    description: the status
+                    content:
+                        application/json:
+                            schema:
+                                type: string
+";
+
+#[test]
+fn test_binary_download_to_writer() -> anyhow::Result<()> {
+    let spec = adapters::oas30::OAS30Spec::from_str(BINARY_DOWNLOAD_YAML)?;
+    let (cm, _mapping) = super::build_codemodel(&ApiConfig::default(), &spec)?;
+    let crate_ = cm.find_crate("crate").unwrap();
+    let trait_ = crate_.trait_iter().next().unwrap();
+    let fn_names = trait_
+        .function_iter()
+        .map(|f| f.name().to_string())
+        .collect::<HashSet<_>>();
+    let expected_fn_names = [
+        "export_get",
+        "export_get_to",
+        "avatar_get",
+        "avatar_get_to",
+        "report_get",
+        "report_get_to",
+        "status_get",
+    ]
+    .into_iter()
+    .map(String::from)
+    .collect::<HashSet<_>>();
+    assert_eq!(expected_fn_names, fn_names);
+
+    // the buffering variant remains, returning the body as bytes
+    let export_get = unwrap_function("export_get", trait_.function_iter());
+    assert_eq!(
+        "Result<Vec<u8>,ExportGetError>",
+        export_get.return_type().name()
+    );
+
+    // the streaming variant takes the same parameters, plus the writer
+    let export_get_to = unwrap_function("export_get_to", trait_.function_iter());
+    let params = export_get_to
+        .function_params_iter()
+        .map(|p| (p.name.as_str(), p.type_.name().to_string()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        vec![
+            ("self", "&Self".to_string()),
+            ("since", "String".to_string()),
+            ("writer", "&mut W".to_string())
+        ],
+        params
+    );
+    let generic_params = export_get_to
+        .generic_params_iter()
+        .map(|p| (p.name.as_str(), p.bounds.to_string()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        vec![("W", quote!(::std::io::Write).to_string())],
+        generic_params
+    );
+    assert_eq!(
+        "Result<u64,ExportGetError>",
+        export_get_to.return_type().name()
+    );
+
+    // without error responses, the error enum has the synthetic variants only
+    let avatar_get_to = unwrap_function("avatar_get_to", trait_.function_iter());
+    assert_eq!(
+        "Result<u64,AvatarGetError>",
+        avatar_get_to.return_type().name()
+    );
+
+    Ok(())
+}
+
+static SERVERS_YAML: &str = r"
+openapi: 3.0.0
+info:
+    title: test for a server URL with variables
+    version: v1
+servers:
+    -   url: https://{env}.example.com:{port}/v1
+        variables:
+            env:
+                default: production
+                enum:
+                    - production
+                    - staging
+            port:
+                default: '443'
+paths: {}
+";
+
+#[test]
+fn test_server_url() -> anyhow::Result<()> {
+    use assert_tokenstreams_eq::assert_tokenstreams_eq;
+
+    let spec = adapters::oas30::OAS30Spec::from_str(SERVERS_YAML)?;
+    let (cm, _mapping) = super::build_codemodel(&ApiConfig::default(), &spec)?;
+    let crate_ = cm.find_crate("crate").unwrap();
+
+    let server_env = crate_.find_type("ServerEnv").unwrap();
+    let TypeRef::Enum(server_env) = server_env else {
+        panic!("enum expected, found {server_env:?}")
+    };
+    let variant_names = server_env
+        .variant_iter()
+        .map(|v| v.name().to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(vec!["Production", "Staging"], variant_names);
+
+    // only variables restricted by 'enum' get an enum
+    assert!(crate_.find_type("ServerPort").is_none());
+
+    let server_url = crate_
+        .raw_item_iter()
+        .find(|item| item.name() == "server_url")
+        .unwrap();
+    assert_tokenstreams_eq!(
+        server_url.token_stream(),
+        &quote!(
+            pub fn server_url(
+                env: ServerEnv,
+                port: &str,
+            ) -> ::std::result::Result<::url::Url, ::url::ParseError> {
+                let url = "https://{env}.example.com:{port}/v1"
+                    .to_string()
+                    .replace(
+                        "{env}",
+                        match env {
+                            ServerEnv::Production => "production",
+                            ServerEnv::Staging => "staging",
+                        },
+                    )
+                    .replace("{port}", port);
+                ::url::Url::parse(&url)
+            }
+        )
+    );
+
+    // relative server URLs yield no server_url()
+    let spec = adapters::oas30::OAS30Spec::from_str(
+        r"
+openapi: 3.0.0
+info:
+    title: test for a relative server URL
+    version: v1
+servers:
+    -   url: /api/v3
+paths: {}
+",
+    )?;
+    let (cm, _mapping) = super::build_codemodel(&ApiConfig::default(), &spec)?;
+    let crate_ = cm.find_crate("crate").unwrap();
+    assert!(
+        crate_
+            .raw_item_iter()
+            .all(|item| item.name() != "server_url")
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_unresolved_reference_error() {
+    let oas = r"
+openapi: 3.0.0
+info:
+    title: test for a reference that does not resolve
+    version: v1
+paths: {}
+components:
+    schemas:
+        Pet:
+            type: object
+            properties:
+                owner:
+                    $ref: '#/components/schemas/NonExistent'
+";
+    let err = super::generate_from_str::<adapters::oas30::OAS30Spec>(oas).unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("'#/components/schemas/NonExistent'"),
+        "unexpected error: {err}"
+    );
+}
+
+static ALIAS_YAML: &str = r"
+openapi: 3.0.0
+info:
+    title: test for schemas aliasing other schemas
+    version: v1
+paths: {}
+components:
+    schemas:
+        Pet:
+            type: object
+            properties:
+                name:
+                    type: string
+        NewPet:
+            $ref: '#/components/schemas/Pet'
+        Shop:
+            type: object
+            required: [new_pet, pets]
+            properties:
+                new_pet:
+                    $ref: '#/components/schemas/NewPet'
+                pets:
+                    type: array
+                    items:
+                        $ref: '#/components/schemas/NewPet'
+";
+
+#[test]
+fn test_alias_use_sites() -> anyhow::Result<()> {
+    let spec = adapters::oas30::OAS30Spec::from_str(ALIAS_YAML)?;
+    let (cm, _mapping) = super::build_codemodel(&ApiConfig::default(), &spec)?;
+    let crate_ = cm.find_crate("crate").unwrap();
+
+    let Some(TypeRef::Alias(new_pet)) = crate_.find_type("NewPet") else {
+        panic!("alias NewPet expected")
+    };
+    assert_eq!("Pet", new_pet.target().name());
+
+    let Some(TypeRef::Struct(shop)) = crate_.find_type("Shop") else {
+        panic!("struct Shop expected")
+    };
+    let fields = shop
+        .field_iter()
+        .map(|f| match f.type_() {
+            TypeRefOrTokenStream::TypeRef(t) => (f.name().to_string(), t.name().to_string()),
+            TypeRefOrTokenStream::TokenStream(ts) => (f.name().to_string(), ts.to_string()),
+        })
+        .collect::<HashMap<_, _>>();
+    assert_eq!("NewPet", fields["new_pet"]);
+    assert_eq!("Vec<NewPet>", fields["pets"]);
+
+    // no types beyond the ones in the schemas are generated
+    let mut type_names = crate_
+        .type_iter()
+        .map(|t| t.name().to_string())
+        .collect::<Vec<_>>();
+    type_names.sort();
+    assert_eq!(
+        vec!["ClientImpl", "NewPet", "OperationInfo", "Pet", "Shop"],
+        type_names
+    );
+
+    // the written code refers to the alias as well
+    let code = codewriter::write_to_token_stream(&cm, "crate")?.to_string();
+    assert!(code.contains("new_pet : NewPet"), "{code}");
+    assert!(code.contains("pets : Vec < NewPet >"), "{code}");
+    Ok(())
+}
+
+#[test]
+fn test_generate_file_check() -> anyhow::Result<()> {
+    let spec_path =
+        std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("../test-data/petstore.yaml");
+    let config = ApiConfig::new_from_path(spec_path.to_string_lossy().into_owned());
+    let output_path = std::env::temp_dir().join("cogenitor_test_generate_file_check.rs");
+    let _ = std::fs::remove_file(&output_path);
+
+    assert_eq!(
+        GenerateCheck::Missing,
+        super::generate_file_check(&config, &output_path)?
+    );
+
+    // trailing whitespace does not count as a change
+    super::generate_file(&config, &output_path)?;
+    let code = std::fs::read_to_string(&output_path)?;
+    std::fs::write(&output_path, code.replace(";\n", "; \n") + "\n\n")?;
+    assert_eq!(
+        GenerateCheck::Unchanged,
+        super::generate_file_check(&config, &output_path)?
+    );
+
+    std::fs::write(
+        &output_path,
+        code.replacen("pub mod generated_api", "pub mod stale_api", 1),
+    )?;
+    let GenerateCheck::Changed { diff_summary } =
+        super::generate_file_check(&config, &output_path)?
+    else {
+        panic!("change expected")
+    };
+    assert!(
+        diff_summary.starts_with("@@ -1,1 +1,1 @@\n"),
+        "{diff_summary}"
+    );
+    assert!(diff_summary.contains("\n-pub mod stale_api {\n+pub mod generated_api {\n"));
+
+    std::fs::remove_file(&output_path)?;
+    Ok(())
+}
+
+#[test]
+fn test_generate_file_if_changed() -> anyhow::Result<()> {
+    let spec_path = std::env::temp_dir().join("cogenitor_test_generate_file_if_changed.yaml");
+    std::fs::write(
+        &spec_path,
+        r"
+openapi: 3.0.0
+info:
+    title: API for generating files if changed
+    version: v1
+paths: {}
+components:
+    schemas:
+        Pet:
+            type: object
+            properties:
+                name:
+                    type: string
+",
+    )?;
+    let config = ApiConfig::new_from_path(spec_path.to_str().unwrap().to_string());
+    let output_path = std::env::temp_dir().join("cogenitor_test_generate_file_if_changed.rs");
+    let _ = std::fs::remove_file(&output_path);
+
+    // a missing file is written, but only once
+    assert!(super::generate_file_if_changed(&config, &output_path)?);
+    assert!(!super::generate_file_if_changed(&config, &output_path)?);
+
+    // it is written like by generate_file, so neither rewrites the other's
+    let written = std::fs::read(&output_path)?;
+    super::generate_file(&config, &output_path)?;
+    assert_eq!(written, std::fs::read(&output_path)?);
+    assert!(!super::generate_file_if_changed(&config, &output_path)?);
+
+    // any difference causes the file to be rewritten
+    std::fs::write(&output_path, "// stale\n")?;
+    assert!(super::generate_file_if_changed(&config, &output_path)?);
+    assert_eq!(
+        GenerateCheck::Unchanged,
+        super::generate_file_check(&config, &output_path)?
+    );
+
+    std::fs::remove_file(&output_path)?;
+    std::fs::remove_file(&spec_path)?;
+    Ok(())
+}
+
+static DEFAULTS_YAML: &str = r"
+openapi: 3.0.0
+info:
+    title: test for default values of properties
+    version: v1
+paths: {}
+components:
+    schemas:
+        Account:
+            type: object
+            required: [status, limit]
+            properties:
+                status:
+                    type: string
+                    default: active
+                limit:
+                    type: integer
+                    format: int32
+                nickname:
+                    type: string
+        Settings:
+            type: object
+            properties:
+                retries:
+                    type: integer
+                    format: int32
+                    default: 3
+                timeout:
+                    type: integer
+                    format: int32
+                    default: 3000000000
+                quota:
+                    type: integer
+                    format: uint64
+                    default: -1
+                account:
+                    $ref: '#/components/schemas/Account'
+        Unrepresentable:
+            type: object
+            required: [account]
+            properties:
+                account:
+                    $ref: '#/components/schemas/Account'
+                ratio:
+                    type: number
+                    default: 0.5
+";
+
+#[test]
+fn test_default_impl() -> anyhow::Result<()> {
+    let spec = adapters::oas30::OAS30Spec::from_str(DEFAULTS_YAML)?;
+    let (cm, _mapping) = super::build_codemodel(&ApiConfig::default(), &spec)?;
+    let crate_ = cm.find_crate("crate").unwrap();
+    let default_impl = |struct_name: &str| {
+        crate_
+            .raw_item_iter()
+            .find(|item| item.name() == format!("impl Default for {struct_name}"))
+            .map(|item| item.token_stream().to_string())
+    };
+
+    let account_default = default_impl("Account").expect("Default impl for Account expected");
+    for field_value in [
+        r#"status : :: std :: string :: String :: from ("active")"#,
+        "limit : :: std :: default :: Default :: default ()",
+        "nickname : :: std :: option :: Option :: None",
+    ] {
+        assert!(account_default.contains(field_value), "{account_default}");
+    }
+
+    let settings_default = default_impl("Settings").expect("Default impl for Settings expected");
+    assert!(
+        settings_default.contains("retries : :: std :: option :: Option :: Some (3)"),
+        "{settings_default}"
+    );
+    // defaults out of the type's range are ignored
+    for field_value in [
+        "timeout : :: std :: option :: Option :: None",
+        "quota : :: std :: option :: Option :: None",
+    ] {
+        assert!(settings_default.contains(field_value), "{settings_default}");
+    }
+
+    // there's no value for the required 'account' field
+    assert!(default_impl("Unrepresentable").is_none());
+    Ok(())
+}
+
+static TITLED_SCHEMA_YAML: &str = r"
+openapi: 3.0.0
+info:
+    title: test for naming inline schemas after their title
+    version: v1
+paths:
+    /pet:
+        post:
+            requestBody:
+                content:
+                    application/json:
+                        schema:
+                            title: CreatePetRequest
+                            type: object
+                            properties:
+                                name:
+                                    type: string
+            responses:
+                '201':
+                    description: pet created
+";
+
+#[test]
+fn test_schema_title_naming() -> anyhow::Result<()> {
+    let spec = adapters::oas30::OAS30Spec::from_str(TITLED_SCHEMA_YAML)?;
+
+    let (cm, _mapping) = super::build_codemodel(&ApiConfig::default(), &spec)?;
+    let crate_ = cm.find_crate("crate").unwrap();
+    assert!(matches!(
+        crate_.find_type("CreatePetRequest"),
+        Some(TypeRef::Struct(_))
+    ));
+    assert!(crate_.find_type("PetPostContent").is_none());
+
+    // the location-derived name is used if titles are ignored
+    let config = ApiConfig {
+        ignore_schema_titles: true,
+        ..Default::default()
+    };
+    let (cm, _mapping) = super::build_codemodel(&config, &spec)?;
+    let crate_ = cm.find_crate("crate").unwrap();
+    assert!(crate_.find_type("CreatePetRequest").is_none());
+    assert!(matches!(
+        crate_.find_type("PetPostContent"),
+        Some(TypeRef::Struct(_))
+    ));
+    Ok(())
+}
+
+#[test]
+fn test_generate_test_macro() -> anyhow::Result<()> {
+    let spec = adapters::oas30::OAS30Spec::from_str(SERVERS_YAML)?;
+    let find_macro = |config: &ApiConfig| -> anyhow::Result<bool> {
+        let (cm, _mapping) = super::build_codemodel(config, &spec)?;
+        let crate_ = cm.find_crate("crate").unwrap();
+        Ok(crate_
+            .raw_item_iter()
+            .any(|item| item.name() == "make_test_client"))
+    };
+
+    assert!(!find_macro(&ApiConfig::default())?);
+    let config = ApiConfig {
+        generate_test_macro: true,
+        ..Default::default()
+    };
+    assert!(find_macro(&config)?);
+    Ok(())
+}
+
+static ROUNDTRIP_YAML: &str = r#"
+openapi: 3.0.0
+info:
+  title: Round trips
+  version: 1.0.0
+paths: {}
+components:
+  schemas:
+    Widget:
+      type: object
+      required: [id, name]
+      properties:
+        id:
+          type: integer
+        name:
+          type: string
+      example:
+        id: 7
+        name: sprocket
+    Gadget:
+      type: object
+      properties:
+        size:
+          type: integer
+          default: 3
+    Part:
+      type: object
+      required: [label, count]
+      properties:
+        label:
+          type: string
+          minLength: 2
+        count:
+          type: integer
+        note:
+          type: string
+    Account:
+      type: object
+      properties:
+        id:
+          type: string
+          readOnly: true
+    Node:
+      type: object
+      required: [child]
+      properties:
+        child:
+          $ref: '#/components/schemas/Node'
+"#;
+
+#[test]
+fn test_roundtrip_tests() -> anyhow::Result<()> {
+    let spec = adapters::oas30::OAS30Spec::from_str(ROUNDTRIP_YAML)?;
+    let code = super::generate_code(&ApiConfig::default(), &spec)?.to_string();
+    assert!(!code.contains("generated_roundtrip_tests"), "{code}");
+
+    let config = ApiConfig {
+        roundtrip_tests: true,
+        ..ApiConfig::default()
+    };
+    let code = super::generate_code(&config, &spec)?.to_string();
+    // values come from the example, Default, or the required properties
+    assert!(
+        code.contains(
+            r#"fn roundtrip_Widget () { let value : Widget = :: serde_json :: from_str ("{\"id\":7,\"name\":\"sprocket\"}")"#
+        ),
+        "{code}"
+    );
+    assert!(
+        code.contains(
+            "fn roundtrip_Gadget () { let value : Gadget = :: std :: default :: Default :: default () ;"
+        ),
+        "{code}"
+    );
+    assert!(
+        code.contains(
+            r#"fn roundtrip_Part () { let value : Part = :: serde_json :: from_str ("{\"count\":0,\"label\":\"xx\"}")"#
+        ),
+        "{code}"
+    );
+    // the others are listed with the reason
+    assert!(!code.contains("roundtrip_Account"), "{code}");
+    assert!(
+        code.contains(" - `Account` has fields serialized in one direction only"),
+        "{code}"
+    );
+    assert!(!code.contains("roundtrip_Node"), "{code}");
+    assert!(code.contains(" - `Node` is nested too deeply"), "{code}");
+    Ok(())
+}
+
+const OAUTH2_YAML: &str = r#"
+openapi: 3.0.0
+info:
+  title: OAuth 2
+  version: 1.0.0
+paths: {}
+components:
+  securitySchemes:
+    oauth:
+      type: oauth2
+      flows:
+        clientCredentials:
+          tokenUrl: https://example.com/token
+          scopes: {}
+"#;
+
+#[test]
+fn test_security_middleware() -> anyhow::Result<()> {
+    // OAuth 2 access tokens are passed as bearer tokens
+    let spec = adapters::oas30::OAS30Spec::from_str(OAUTH2_YAML)?;
+    let (cm, _mapping) = super::build_codemodel(&ApiConfig::default(), &spec)?;
+    let crate_ = cm.find_crate("crate").unwrap();
+    let Some(TypeRef::Enum(security_middleware)) = crate_.find_type("SecurityMiddleware") else {
+        panic!("SecurityMiddleware enum expected");
+    };
+    let variant_names = security_middleware
+        .variant_iter()
+        .map(|v| v.name().to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(vec!["BearerToken"], variant_names);
+    assert!(crate_.find_type("ApiKeyLocation").is_none());
+
+    // no security schemes, no middleware
+    let spec = adapters::oas30::OAS30Spec::from_str(SERVERS_YAML)?;
+    let (cm, _mapping) = super::build_codemodel(&ApiConfig::default(), &spec)?;
+    let crate_ = cm.find_crate("crate").unwrap();
+    assert!(crate_.find_type("SecurityMiddleware").is_none());
+    Ok(())
+}
+
+#[test]
+fn test_prefer_single_media_type() -> anyhow::Result<()> {
+    let petstore = std::str::from_utf8(PETSTORE_YAML)?;
+    let spec = adapters::oas30::OAS30Spec::from_str(petstore)?;
+    let signature_of = |config: &ApiConfig, fn_name: &str| -> anyhow::Result<Vec<String>> {
+        let (cm, _mapping) = super::build_codemodel(config, &spec)?;
+        let crate_ = cm.find_crate("crate").unwrap();
+        let trait_ = crate_.trait_iter().next().unwrap();
+        let function = unwrap_function(fn_name, trait_.associated_functions.iter());
+        let mut signature = function
+            .function_params_iter()
+            .map(|p| format!("{}: {}", p.name, p.type_.name()))
+            .collect::<Vec<_>>();
+        signature.push(format!("-> {}", function.return_type().name()));
+        Ok(signature)
+    };
+
+    // JSON, XML and form content become enums by default...
+    let signature = signature_of(&ApiConfig::default(), "pet_put")?;
+    assert_eq!(
+        vec![
+            "self: &Self",
+            "body: PetPutContent",
+            "-> Result<PetPutOk200,PetPutError>"
+        ],
+        signature
+    );
+
+    // ...while only JSON is used if preferred
+    let config = ApiConfig {
+        preferred_media_types: vec!["application/json".to_string()],
+        media_type_strategy: MediaTypeStrategy::PreferSingle,
+        ..Default::default()
+    };
+    let signature = signature_of(&config, "pet_put")?;
+    assert_eq!(
+        vec!["self: &Self", "body: Pet", "-> Result<Pet,PetPutError>"],
+        signature
+    );
+
+    // without a match, all media types are kept
+    let config = ApiConfig {
+        preferred_media_types: vec!["text/plain".to_string()],
+        media_type_strategy: MediaTypeStrategy::PreferSingle,
+        ..Default::default()
+    };
+    assert_eq!(
+        signature_of(&ApiConfig::default(), "pet_put")?,
+        signature_of(&config, "pet_put")?
+    );
+    Ok(())
+}
+
+static UNDECLARED_PATH_PARAMS_YAML: &str = r"
+openapi: 3.0.0
+info:
+  title: Undeclared path parameters
+  version: 1.0.0
+paths:
+  /pets/{petId}/photos/{photoId}:
+    parameters:
+      - name: photoId
+        in: path
+        required: true
+        schema:
+          type: integer
+          format: int64
+    get:
+      responses:
+        '204':
+          description: no content
+  /owners/{ownerId}:
+    get:
+      parameters:
+        - name: ownerId
+          in: path
+          required: true
+          schema:
+            type: string
+        - name: petId
+          in: path
+          required: true
+          schema:
+            type: string
+      responses:
+        '204':
+          description: no content
+";
+
+#[test]
+fn test_undeclared_path_params() -> anyhow::Result<()> {
+    let spec = adapters::oas30::OAS30Spec::from_str(UNDECLARED_PATH_PARAMS_YAML)?;
+    let (cm, _mapping) = super::build_codemodel(&ApiConfig::default(), &spec)?;
+    let crate_ = cm.find_crate("crate").unwrap();
+    let trait_ = crate_.trait_iter().next().unwrap();
+    let params_of = |fn_name: &str| {
+        unwrap_function(fn_name, trait_.associated_functions.iter())
+            .function_params_iter()
+            .map(|p| format!("{}: {}", p.name, p.type_.name()))
+            .collect::<Vec<_>>()
+    };
+
+    // the undeclared template parameter is added as string
+    assert_eq!(
+        vec!["self: &Self", "photoId: i64", "petId: String"],
+        params_of("pets_pet_id_photos_photo_id_get")
+    );
+
+    // declared parameters missing in the template are kept
+    assert_eq!(
+        vec!["self: &Self", "ownerId: String", "petId: String"],
+        params_of("owners_owner_id_get")
+    );
+
+    // strict mode rejects the spec, naming path and parameter
+    let config = ApiConfig {
+        strict_path_params: true,
+        ..Default::default()
+    };
+    let message = super::build_codemodel(&config, &spec)
+        .err()
+        .expect("undeclared path parameter must fail in strict mode")
+        .to_string();
+    assert!(
+        message.contains("/pets/{petId}/photos/{photoId}"),
+        "{message}"
+    );
+    assert!(message.contains("petId"), "{message}");
+    Ok(())
+}
+
+static PARAM_CONTENT_YAML: &str = r"
+openapi: 3.0.0
+info:
+  title: Parameters with content
+  version: 1.0.0
+paths:
+  /pets:
+    get:
+      parameters:
+        - name: filter
+          in: query
+          content:
+            application/json:
+              schema:
+                type: object
+                properties:
+                  name:
+                    type: string
+            text/plain:
+              schema:
+                type: string
+            application/xml:
+              schema:
+                type: string
+      responses:
+        '204':
+          description: no content
+  /owners:
+    get:
+      parameters:
+        - name: filter
+          in: query
+          content:
+            text/plain:
+              schema:
+                type: string
+            application/json:
+              schema:
+                type: string
+      responses:
+        '204':
+          description: no content
+";
+
+#[test]
+fn test_param_content_media_types() -> anyhow::Result<()> {
+    let spec = adapters::oas30::OAS30Spec::from_str(PARAM_CONTENT_YAML)?;
+    let param_types = |config: &ApiConfig| -> anyhow::Result<Vec<String>> {
+        let (cm, _mapping) = super::build_codemodel(config, &spec)?;
+        let crate_ = cm.find_crate("crate").unwrap();
+        let trait_ = crate_.trait_iter().next().unwrap();
+        // a parameter is serialized in a single media type, so no enum of
+        // the media types is generated
+        for name in ["PetsGetFilter", "OwnersGetFilter"] {
+            let t = crate_.find_type(name);
+            assert!(!matches!(t, Some(TypeRef::Enum(_))), "{t:?}");
+        }
+        Ok(["pets_get", "owners_get"]
+            .into_iter()
+            .map(|fn_name| {
+                let function = unwrap_function(fn_name, trait_.associated_functions.iter());
+                let filter = function
+                    .function_params_iter()
+                    .find(|p| p.name == "filter")
+                    .unwrap();
+                filter.type_.name().to_string()
+            })
+            .collect())
+    };
+
+    // JSON is picked unless another offered media type is preferred
+    let types = param_types(&ApiConfig::default())?;
+    assert_eq!(vec!["PetsGetFilter", "String"], types);
+    let config = ApiConfig {
+        preferred_media_types: vec!["application/xml".to_string()],
+        ..ApiConfig::default()
+    };
+    let types = param_types(&config)?;
+    assert_eq!(vec!["String", "String"], types);
+
+    let config = ApiConfig {
+        strict_param_content: true,
+        ..ApiConfig::default()
+    };
+    let error = param_types(&config).unwrap_err();
+    assert!(error.to_string().contains("'filter'"), "{error}");
+    Ok(())
+}
+
+static QUERY_METHOD_YAML: &str = r"
+openapi: 3.0.0
+info:
+  title: Unsupported method
+  version: 1.0.0
+paths:
+  /pets:
+    x-internal: true
+    summary: Pets
+    get:
+      responses:
+        '200':
+          description: the pets
+    query:
+      responses:
+        '200':
+          description: the pets matching the query
+";
+
+#[test]
+fn test_operation_function_discrepancies() -> anyhow::Result<()> {
+    let spec = adapters::oas30::OAS30Spec::from_str(QUERY_METHOD_YAML)?;
+    assert_eq!(
+        vec![("/pets".to_string(), "query".to_string())],
+        spec.unsupported_operations()
+    );
+
+    // by default, the operation is dropped with an error logged
+    let (cm, _mapping) = super::build_codemodel(&ApiConfig::default(), &spec)?;
+    let crate_ = cm.find_crate("crate").unwrap();
+    let trait_ = crate_.trait_iter().next().unwrap();
+    let routes = vec![(
+        "pets_get".to_string(),
+        http::Method::GET,
+        "/pets".to_string(),
+    )];
+    assert_eq!(
+        vec!["QUERY /pets has no client function, as method 'query' is not supported"],
+        super::operation_function_discrepancies(&spec, &trait_, &routes, &HashSet::new())
+    );
+
+    // two operations mapped to one function are reported as well
+    let routes = vec![
+        (
+            "pets_get".to_string(),
+            http::Method::GET,
+            "/pets".to_string(),
+        ),
+        (
+            "pets_get".to_string(),
+            http::Method::GET,
+            "/pets/".to_string(),
+        ),
+    ];
+    let discrepancies =
+        super::operation_function_discrepancies(&spec, &trait_, &routes, &HashSet::new());
+    assert!(
+        discrepancies.contains(
+            &"GET /pets, GET /pets/ share the client function 'pets_get', declared 1 time(s)"
+                .to_string()
+        ),
+        "{discrepancies:?}"
+    );
+
+    let config = ApiConfig {
+        strict_operations: true,
+        ..ApiConfig::default()
+    };
+    let message = super::build_codemodel(&config, &spec)
+        .err()
+        .expect("unsupported method must fail in strict mode")
+        .to_string();
+    assert!(message.contains("QUERY /pets"), "{message}");
+    Ok(())
+}
+
+static DUPLICATE_PATHS_YAML: &str = r"
+openapi: 3.0.0
+info:
+  title: Duplicate paths
+  version: 1.0.0
+paths:
+  /users/{id}:
+    get:
+      parameters:
+        - name: id
+          in: path
+          required: true
+          schema:
+            type: string
+      responses:
+        '200':
+          description: the user
+  /users/{userId}:
+    delete:
+      parameters:
+        - name: userId
+          in: path
+          required: true
+          schema:
+            type: string
+      responses:
+        '204':
+          description: the user is gone
+  /Users/{userId}:
+    get:
+      parameters:
+        - name: userId
+          in: path
+          required: true
+          schema:
+            type: string
+      responses:
+        '200':
+          description: the user, under another path
+";
+
+#[test]
+fn test_duplicate_path_templates() -> anyhow::Result<()> {
+    let spec = adapters::oas30::OAS30Spec::from_str(DUPLICATE_PATHS_YAML)?;
+
+    // only the first of the paths differing in parameter names is generated
+    let config = ApiConfig {
+        strict_operations: true,
+        ..ApiConfig::default()
+    };
+    let (cm, _mapping) = super::build_codemodel(&config, &spec)?;
+    let crate_ = cm.find_crate("crate").unwrap();
+    let trait_ = crate_.trait_iter().next().unwrap();
+    let mut fn_names = trait_
+        .function_iter()
+        .map(|f| f.name().to_string())
+        .collect::<Vec<_>>();
+    fn_names.sort();
+    assert_eq!(vec!["users_id_get", "users_user_id_get"], fn_names);
+
+    let config = ApiConfig {
+        strict_duplicate_paths: true,
+        ..ApiConfig::default()
+    };
+    let message = super::build_codemodel(&config, &spec)
+        .err()
+        .expect("duplicate paths must fail in strict mode")
+        .to_string();
+    assert_eq!(
+        "paths '/users/{id}' and '/users/{userId}' differ only in the names of their parameters",
+        message
+    );
+    Ok(())
+}
+
+const WILDCARD_MEDIA_TYPES_YAML: &str = r#"
+openapi: 3.0.0
+info:
+  title: wildcard media types
+  version: 1.0.0
+paths:
+  /notes:
+    post:
+      requestBody:
+        required: true
+        content:
+          "*/*":
+            schema:
+              $ref: '#/components/schemas/Note'
+      responses:
+        "200":
+          description: the note stored
+          content:
+            application/*:
+              schema:
+                $ref: '#/components/schemas/Note'
+            image/*:
+              schema:
+                type: string
+                format: binary
+    put:
+      requestBody:
+        required: true
+        content:
+          application/*:
+            schema:
+              type: string
+      responses:
+        "204":
+          description: stored
+  /mixed:
+    post:
+      requestBody:
+        required: true
+        content:
+          application/json:
+            schema:
+              $ref: '#/components/schemas/Note'
+          "*/*":
+            schema:
+              type: object
+      responses:
+        "204":
+          description: stored
+components:
+  schemas:
+    Note:
+      type: object
+      properties:
+        text:
+          type: string
+"#;
+
+#[test]
+fn test_wildcard_media_types() -> anyhow::Result<()> {
+    let spec = adapters::oas30::OAS30Spec::from_str(WILDCARD_MEDIA_TYPES_YAML)?;
+    let config = ApiConfig {
+        response_decoders: true,
+        ..ApiConfig::default()
+    };
+    let (cm, _mapping) = super::build_codemodel(&config, &spec)?;
+    let crate_ = cm.find_crate("crate").unwrap();
+    let trait_ = crate_.trait_iter().next().unwrap();
+    let body_type_of = |fn_name: &str| {
+        let function = trait_
+            .function_iter()
+            .find(|f| f.name() == fn_name)
+            .unwrap();
+        function
+            .function_params_iter()
+            .find(|param| param.name == "body")
+            .map(|param| param.type_.name().to_string())
+    };
+
+    // object-like content of a range is sent as JSON, other content as
+    // bytes, so neither needs an enum of media types
+    assert_eq!(Some("Note".to_string()), body_type_of("notes_post"));
+    assert_eq!(Some("Vec<u8>".to_string()), body_type_of("notes_put"));
+    // a range resolving to a media type declared as well is dropped
+    assert_eq!(Some("Note".to_string()), body_type_of("mixed_post"));
+
+    let code = super::generate_code(&config, &spec)?.to_string();
+    // responses of a range are matched by prefix and decoded as JSON
+    assert!(
+        code.contains(
+            r#"m if media_type_matches (m , "application/*") => { Some (decode_with_path"#
+        ),
+        "{code}"
+    );
+    assert!(!code.contains("decoding media type"), "{code}");
+    Ok(())
+}
+
+const WRAPPED_REFERENCE_YAML: &str = r#"
+openapi: 3.0.0
+info:
+  title: wrapped references
+  version: 1.0.0
+paths: {}
+components:
+  schemas:
+    Pet:
+      type: object
+      properties:
+        name:
+          type: string
+    FavoritePet:
+      description: |
+        The pet liked best.
+        There is only one.
+      allOf:
+        - $ref: '#/components/schemas/Pet'
+    Owner:
+      type: object
+      required: [pet, favorite]
+      properties:
+        pet:
+          description: the pet currently owned, if any
+          nullable: true
+          allOf:
+            - $ref: '#/components/schemas/Pet'
+        favorite:
+          $ref: '#/components/schemas/FavoritePet'
+        previous:
+          readOnly: true
+          allOf:
+            - $ref: '#/components/schemas/Pet'
+"#;
+
+#[test]
+fn test_wrapped_reference() -> anyhow::Result<()> {
+    let spec = adapters::oas30::OAS30Spec::from_str(WRAPPED_REFERENCE_YAML)?;
+    let code = super::generate_code(&ApiConfig::default(), &spec)?.to_string();
+
+    // wrappers are no types of their own, but stand for the wrapped type
+    assert_eq!(1, code.matches("pub struct Pet ").count(), "{code}");
+    assert!(!code.contains("struct FavoritePet"), "{code}");
+    assert!(!code.contains("OwnerPet"), "{code}");
+    assert!(
+        code.contains(
+            r#"# [doc = " The pet liked best.\n There is only one."] type FavoritePet = Pet ;"#
+        ),
+        "{code}"
+    );
+    // nullable wrappers make the field optional
+    assert!(code.contains("pub pet : Option < Pet >"), "{code}");
+    assert!(code.contains("pub favorite : FavoritePet"), "{code}");
+    assert!(code.contains("pub previous : Option < Pet >"), "{code}");
+    Ok(())
+}
+
+#[test]
+fn test_sse_events_disabled() -> anyhow::Result<()> {
+    let spec = adapters::oas30::OAS30Spec::from_str(include_str!("../../test-data/sse.yaml"))?;
+    let (cm, _mapping) = super::build_codemodel(&ApiConfig::default(), &spec)?;
+    let crate_ = cm.find_crate("crate").unwrap();
+    let trait_ = crate_.trait_iter().next().unwrap();
+
+    // without the option, events are neither parsed nor offered as variant
+    let function_names = trait_
+        .associated_functions
+        .iter()
+        .map(|f| f.name().to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(vec!["notifications_get"], function_names);
+    assert_eq!(
+        "Result<Vec<u8>,NotificationsGetError>",
+        trait_.associated_functions[0].return_type().name()
+    );
+    assert!(!crate_.raw_item_iter().any(|item| item.name() == "SseEvent"));
+    Ok(())
+}
+
+static SAME_NAMED_PARAMS_YAML: &str = r"
+openapi: 3.0.0
+info:
+  title: Parameters with the same name in different locations
+  version: 1.0.0
+paths:
+  /pets/{id}:
+    parameters:
+      - name: id
+        in: path
+        required: true
+        schema:
+          type: integer
+          format: int64
+    get:
+      parameters:
+        - name: id
+          in: query
+          schema:
+            type: string
+        - name: Id
+          in: header
+          schema:
+            type: string
+        - name: limit
+          in: query
+          schema:
+            type: integer
+            format: int32
+      responses:
+        '204':
+          description: no content
+";
+
+#[test]
+fn test_same_named_params() -> anyhow::Result<()> {
+    let spec = adapters::oas30::OAS30Spec::from_str(SAME_NAMED_PARAMS_YAML)?;
+    let (cm, _mapping) = super::build_codemodel(&ApiConfig::default(), &spec)?;
+    let crate_ = cm.find_crate("crate").unwrap();
+    let trait_ = crate_.trait_iter().next().unwrap();
+    let params = unwrap_function("pets_id_get", trait_.associated_functions.iter())
+        .function_params_iter()
+        .map(|p| format!("{}: {}", p.name, p.type_.name()))
+        .collect::<Vec<_>>();
+
+    // parameters sharing a name are suffixed with their location
+    assert_eq!(
+        vec![
+            "self: &Self",
+            "id_path: i64",
+            "id_query: String",
+            "id_header: String",
+            "limit: i32"
+        ],
+        params
+    );
+    Ok(())
+}
+
+#[test]
+fn test_parameter_examples() -> anyhow::Result<()> {
+    let spec = adapters::oas30::OAS30Spec::from_str(include_str!(
+        "../../test-data/parameter-examples.yaml"
+    ))?;
+
+    // examples are documented with the parameters
+    let code = super::generate_code(&ApiConfig::default(), &spec)?.to_string();
+    assert!(
+        code.contains(r#"# [doc = " # Parameters\n\n - `status` (query), example: `\"available\"`\n - `limit` (query), example: `10`"] fn pets_get"#),
+        "{code}"
+    );
+    assert!(
+        code.contains(r#"- `petId` (path), example: `1`\n - `photoId` (path)"]"#),
+        "{code}"
+    );
+    // without any examples, parameters are not documented
+    assert!(
+        code.contains(r#"# [doc = " # Request body\n\n Media types: `application/json`\n\n The body is required."] fn pets_post"#),
+        "{code}"
+    );
+    assert!(!code.contains("mod examples"), "{code}");
+
+    let config = ApiConfig {
+        emit_examples: true,
+        module_name: Some("pets".to_string()),
+        ..Default::default()
+    };
+    let code = super::generate_code(&config, &spec)?.to_string();
+    assert!(
+        code.contains("# [ignore = \"calls the API at PETS_BASE_URL\"] # [doc = \" Calls `pets_pet_id_get` with the examples of its parameters\"] # [test] fn pets_pet_id_get ()"),
+        "{code}"
+    );
+    assert!(
+        code.contains("super :: Client :: pets_pet_id_get (& client , 1i64 , true)"),
+        "{code}"
+    );
+    assert!(
+        code.contains("super :: Client :: pets_get (& client , :: std :: string :: ToString :: to_string (\"available\") , 10i32)"),
+        "{code}"
+    );
+    // the body and the photo ID have no examples
+    assert!(!code.contains("fn pets_post ()"), "{code}");
+    assert!(
+        !code.contains("fn pets_pet_id_photos_photo_id_get ()"),
+        "{code}"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_fail_on_limit() -> anyhow::Result<()> {
+    let petstore = std::str::from_utf8(PETSTORE_YAML)?;
+    let spec = adapters::oas30::OAS30Spec::from_str(petstore)?;
+
+    // exceeding the limits is only warned about by default...
+    let config = ApiConfig {
+        max_operations: Some(1),
+        max_schemas: Some(1),
+        ..Default::default()
+    };
+    super::generate_code(&config, &spec)?;
+
+    // ...but fails generation on request
+    let config = ApiConfig {
+        fail_on_limit: true,
+        ..config
+    };
+    let message = super::generate_code(&config, &spec)
+        .expect_err("spec exceeding limits must fail")
+        .to_string();
+    assert!(
+        message.contains("operations, exceeding the limit of 1"),
+        "{message}"
+    );
+    assert!(
+        message.contains("schemas, exceeding the limit of 1"),
+        "{message}"
+    );
+    Ok(())
+}
+
+static RUST_TYPE_YAML: &str = r"
+openapi: 3.0.0
+info:
+  title: Rust type overrides
+  version: 1.0.0
+paths: {}
+components:
+  schemas:
+    Pet:
+      type: object
+      required:
+        - id
+      properties:
+        id:
+          type: string
+          format: uuid
+          x-rust-type: uuid::Uuid
+        born:
+          type: string
+          format: date-time
+          x-rust-type: chrono::DateTime<chrono::Utc>
+        name:
+          type: string
+";
+
+#[test]
+fn test_rust_type_override() -> anyhow::Result<()> {
+    let spec = adapters::oas30::OAS30Spec::from_str(RUST_TYPE_YAML)?;
+    let (cm, _mapping) = super::build_codemodel(&ApiConfig::default(), &spec)?;
+    let crate_ = cm.find_crate("crate").unwrap();
+    let Some(TypeRef::Struct(pet)) = crate_.find_type("Pet") else {
+        panic!("struct Pet expected in crate: {crate_:?}")
+    };
+    let fields = pet
+        .field_iter()
+        .map(|f| match f.type_() {
+            TypeRefOrTokenStream::TypeRef(t) => format!("{}: {}", f.name(), t.name()),
+            TypeRefOrTokenStream::TokenStream(ts) => format!("{}: {ts}", f.name()),
+        })
+        .collect::<Vec<_>>();
+    assert!(fields.contains(&"id: uuid::Uuid".to_string()), "{fields:?}");
+    assert!(
+        fields.contains(&"born: Option<chrono::DateTime<chrono::Utc>>".to_string()),
+        "{fields:?}"
+    );
+    assert!(
+        fields.contains(&"name: Option<String>".to_string()),
+        "{fields:?}"
+    );
+
+    // the type must be valid Rust
+    let invalid = RUST_TYPE_YAML.replace("uuid::Uuid", "'uuid::'");
+    let spec = adapters::oas30::OAS30Spec::from_str(&invalid)?;
+    let message = super::build_codemodel(&ApiConfig::default(), &spec)
+        .err()
+        .expect("invalid x-rust-type must fail")
+        .to_string();
+    assert!(message.contains("'uuid::'"), "{message}");
+    Ok(())
+}
+
+#[test]
+fn test_collapse_identical_media_types() -> anyhow::Result<()> {
+    let petstore = std::str::from_utf8(PETSTORE_YAML)?;
+    let spec = adapters::oas30::OAS30Spec::from_str(petstore)?;
+    let type_names_of = |config: &ApiConfig| -> anyhow::Result<Vec<String>> {
+        let (cm, _mapping) = super::build_codemodel(config, &spec)?;
+        let crate_ = cm.find_crate("crate").unwrap();
+        Ok(crate_.type_iter().map(|t| t.name().to_string()).collect())
+    };
+
+    let config = ApiConfig {
+        media_type_strategy: MediaTypeStrategy::CollapseIdentical,
+        ..Default::default()
+    };
+    let collapsed = type_names_of(&config)?;
+    let default = type_names_of(&ApiConfig::default())?;
+    assert!(collapsed.iter().all(|name| default.contains(name)));
+    let dropped = default
+        .into_iter()
+        .filter(|name| !collapsed.contains(name))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        vec![
+            "PetPutOk200",
+            "PetPutContent",
+            "PetPostOk200",
+            "PetPostContent",
+            "PetFindByStatusGetOk200",
+            "PetFindByTagsGetOk200",
+            "PetPetIdGetOk200",
+            "PetPetIdPostOk200",
+            "StoreOrderPostContent",
+            "StoreOrderOrderIdGetOk200",
+            "UserPostOk200",
+            "UserPostContent",
+            "UserCreateWithListPostOk200",
+            "UserLoginGetOk200",
+            "UserUsernameGetOk200",
+            "UserUsernamePutContent",
+        ],
+        dropped
+    );
+    Ok(())
+}
+
+#[test]
+fn test_generate_from_url() -> anyhow::Result<()> {
+    // a cached spec is used without downloading it
+    let url = "https://example.com/cogenitor/test_generate_from_url.yaml";
+    std::fs::write(super::spec_cache_path(url), PETSTORE_YAML)?;
+    let config = ApiConfig::new_from_url(url.to_string());
+    let ts = super::generate_token_stream(&config)?;
+    assert!(ts.to_string().contains("struct Pet"));
+    let config = ApiConfig {
+        spec_cache_ttl: Some(std::time::Duration::from_secs(3600)),
+        ..config
+    };
+    super::generate_token_stream(&config)?;
+
+    // an expired spec is downloaded again, which fails here
+    let config = ApiConfig {
+        spec_cache_ttl: Some(std::time::Duration::ZERO),
+        ..config
+    };
+    if !cfg!(feature = "url") {
+        super::generate_token_stream(&config).unwrap_err();
+    }
+
+    // a path takes precedence over the URL
+    let config = ApiConfig {
+        path: Some("/does/not/exist.yaml".to_string()),
+        ..config
+    };
+    super::generate_token_stream(&config).unwrap_err();
+
+    let config = ApiConfig::new_from_url("ftp://example.com/openapi.yaml".to_string());
+    super::generate_token_stream(&config).unwrap_err();
+    super::generate_token_stream(&ApiConfig::default()).unwrap_err();
+    Ok(())
+}
+
+#[test]
+fn test_write_spec_cache() -> anyhow::Result<()> {
+    let cache_path = super::spec_cache_path("https://example.com/cogenitor/test_write_spec_cache");
+    super::write_spec_cache(&cache_path, b"first")?;
+    super::write_spec_cache(&cache_path, b"second")?;
+    assert_eq!(b"second", std::fs::read(&cache_path)?.as_slice());
+    // no temporary files are left behind
+    let file_name = cache_path
+        .file_name()
+        .unwrap()
+        .to_string_lossy()
+        .to_string();
+    let leftovers = std::fs::read_dir(cache_path.parent().unwrap())?
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            name.starts_with(&file_name) && name != file_name
+        })
+        .count();
+    assert_eq!(0, leftovers);
+    std::fs::remove_file(cache_path)?;
+    Ok(())
+}
+
+#[cfg(feature = "url")]
+#[test]
+#[ignore = "downloads a spec from the internet"]
+fn test_download_spec() -> anyhow::Result<()> {
+    let url = "https://petstore3.swagger.io/api/v3/openapi.yaml";
+    let _ = std::fs::remove_file(super::spec_cache_path(url));
+    let ts = super::generate_token_stream(&ApiConfig::new_from_url(url.to_string()))?;
+    assert!(ts.to_string().contains("struct Pet"));
+    Ok(())
+}
+
+#[test]
+fn test_operation_security() -> anyhow::Result<()> {
+    let petstore = std::str::from_utf8(PETSTORE_YAML)?;
+    let spec = adapters::oas30::OAS30Spec::from_str(petstore)?;
+    assert!(spec.security().is_empty());
+
+    let security_of = |path: &str, method: http::Method| {
+        let (_, path_item) = spec.paths().find(|(p, _)| p == path).unwrap();
+        let (_, operation) = path_item
+            .operations_iter()
+            .find(|(m, _)| *m == method)
+            .unwrap();
+        operation.security()
+    };
+    let security = security_of("/pet/{petId}", http::Method::GET).unwrap();
+    assert_eq!(2, security.len());
+    assert_eq!(Some(&vec![]), security[0].get("api_key"));
+    assert_eq!(
+        Some(&vec!["write:pets".to_string(), "read:pets".to_string()]),
+        security[1].get("petstore_auth")
+    );
+    assert_eq!(None, security_of("/user/login", http::Method::GET));
+    Ok(())
+}
+
+#[test]
+fn test_operation_security_appliers() -> anyhow::Result<()> {
+    let spec = adapters::oas30::OAS30Spec::from_str(include_str!(
+        "../../test-data/security-override.yaml"
+    ))?;
+    let code = super::generate_code(&ApiConfig::default(), &spec)?.to_string();
+    // operations requiring no authentication don't look at the credentials
+    assert!(
+        code.contains("pub fn apply_login_post_security (self : & Self , rb : :: http :: request :: Builder) -> :: http :: request :: Builder { rb }"),
+        "{code}"
+    );
+    assert!(
+        code.contains("pub fn apply_pets_get_security (self : & Self , rb : :: http :: request :: Builder) -> :: http :: request :: Builder { let :: std :: option :: Option :: Some (auth) = & self . auth else { return rb ; } ;"),
+        "{code}"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_id_newtypes() -> anyhow::Result<()> {
+    let petstore = std::str::from_utf8(PETSTORE_YAML)?;
+    let spec = adapters::oas30::OAS30Spec::from_str(petstore)?;
+    let order_fields_of = |config: &ApiConfig| -> anyhow::Result<Vec<String>> {
+        let (cm, _mapping) = super::build_codemodel(config, &spec)?;
+        let crate_ = cm.find_crate("crate").unwrap();
+        let Some(TypeRef::Struct(order)) = crate_.find_type("Order") else {
+            panic!("struct Order expected in crate: {crate_:?}")
+        };
+        Ok(order
+            .field_iter()
+            .filter_map(|f| match f.type_() {
+                TypeRefOrTokenStream::TypeRef(t) => Some(format!("{}: {}", f.name(), t.name())),
+                TypeRefOrTokenStream::TokenStream(_) => None,
+            })
+            .collect())
+    };
+
+    let fields = order_fields_of(&ApiConfig::default())?;
+    assert!(
+        fields.contains(&"pet_id: Option<i64>".to_string()),
+        "{fields:?}"
+    );
+
+    let config = ApiConfig {
+        generate_id_newtypes: true,
+        ..Default::default()
+    };
+    let fields = order_fields_of(&config)?;
+    assert!(
+        fields.contains(&"pet_id: Option<PetId>".to_string()),
+        "{fields:?}"
+    );
+    // 'id' is not named after the thing it identifies
+    assert!(
+        fields.contains(&"id: Option<i64>".to_string()),
+        "{fields:?}"
+    );
+
+    let (cm, _mapping) = super::build_codemodel(&config, &spec)?;
+    let crate_ = cm.find_crate("crate").unwrap();
+    assert!(crate_.raw_item_iter().any(|i| i.name() == "PetId"));
+    Ok(())
+}
+
+static MISSING_RESPONSES_YAML: &str = r"
+openapi: 3.0.0
+info:
+    title: API with operations declaring no responses
+    version: v1
+paths:
+    /ping:
+        get: {}
+    /pong:
+        get:
+            responses: {}
+";
+
+#[test]
+fn test_missing_responses() -> anyhow::Result<()> {
+    let spec = adapters::oas30::OAS30Spec::from_str(MISSING_RESPONSES_YAML)?;
+    let return_type_of = |config: &ApiConfig, fn_name: &str| -> anyhow::Result<String> {
+        let (cm, _mapping) = super::build_codemodel(config, &spec)?;
+        let crate_ = cm.find_crate("crate").unwrap();
+        let trait_ = crate_.trait_iter().next().unwrap();
+        let function = unwrap_function(fn_name, trait_.associated_functions.iter());
+        Ok(function.return_type().name().to_string())
+    };
+
+    // missing and empty responses are treated alike
+    let config = ApiConfig::default();
+    assert_eq!(
+        "Result<(),PingGetError>",
+        return_type_of(&config, "ping_get")?
+    );
+    assert_eq!(
+        "Result<(),PongGetError>",
+        return_type_of(&config, "pong_get")?
+    );
+
+    let config = ApiConfig {
+        missing_responses: MissingResponses::RawResponse,
+        ..Default::default()
+    };
+    assert_eq!(
+        "Result<::http::Response<Vec<u8>>,PingGetError>",
+        return_type_of(&config, "ping_get")?
+    );
+    assert_eq!(
+        "Result<::http::Response<Vec<u8>>,PongGetError>",
+        return_type_of(&config, "pong_get")?
+    );
+    Ok(())
+}
+
+#[test]
+fn test_map_size_bounds() -> anyhow::Result<()> {
+    let spec = adapters::oas30::OAS30Spec::from_str(include_str!("../../test-data/map-size.yaml"))?;
+    let labels_of = |config: &ApiConfig| -> anyhow::Result<String> {
+        let (cm, _mapping) = super::build_codemodel(config, &spec)?;
+        let crate_ = cm.find_crate("crate").unwrap();
+        let item = crate_
+            .raw_item_iter()
+            .find(|i| i.name() == "Labels")
+            .expect("raw item Labels expected");
+        Ok(item.token_stream().to_string())
+    };
+
+    let labels = labels_of(&ApiConfig::default())?;
+    assert!(labels.contains("type Labels"), "{labels}");
+    assert!(
+        labels.contains("at least 2 and at most 5 entries, which is not checked"),
+        "{labels}"
+    );
+
+    let config = ApiConfig {
+        validation_newtypes: true,
+        ..Default::default()
+    };
+    let labels = labels_of(&config)?;
+    assert!(labels.contains("struct Labels"), "{labels}");
+    assert!(labels.contains("TryFrom"), "{labels}");
+    assert!(labels.contains("map . len () < 2usize"), "{labels}");
+    assert!(labels.contains("map . len () > 5usize"), "{labels}");
+    Ok(())
+}
+
+#[test]
+fn test_diff_codemodels() -> anyhow::Result<()> {
+    let petstore = std::str::from_utf8(PETSTORE_YAML)?;
+    // drop Category's name and add an operation
+    let modified = petstore
+        .replace(
+            "        name:\n          type: string\n          example: Dogs\n",
+            "",
+        )
+        .replace(
+            "paths:\n",
+            "paths:\n  /store/ping:\n    get:\n      responses:\n        \"200\":\n          description: pong\n",
+        );
+    assert_ne!(petstore, modified);
+    let codemodel_of = |yaml: &str| -> anyhow::Result<Codemodel> {
+        let spec = adapters::oas30::OAS30Spec::from_str(yaml)?;
+        Ok(super::build_codemodel(&ApiConfig::default(), &spec)?.0)
+    };
+    let old = codemodel_of(petstore)?;
+    let new = codemodel_of(&modified)?;
+
+    assert!(super::diff(&old, &old).is_empty());
+
+    let model_diff = super::diff(&old, &new);
+    assert!(model_diff.items.contains(&ItemDiff::Changed {
+        kind: ItemKind::Struct,
+        name: "Category".to_string(),
+        members: vec![MemberDiff::Removed {
+            name: "name".to_string(),
+            signature: "Option<String>".to_string(),
+        }],
+    }));
+    assert!(model_diff.items.contains(&ItemDiff::Added {
+        kind: ItemKind::Enum,
+        name: "StorePingGetError".to_string(),
+    }));
+    let Some(ItemDiff::Changed { members, .. }) = model_diff.items.iter().find(|i| {
+        matches!(
+            i,
+            ItemDiff::Changed {
+                kind: ItemKind::Trait,
+                ..
+            }
+        )
+    }) else {
+        panic!("changed trait expected in {model_diff}")
+    };
+    assert!(matches!(
+        members.as_slice(),
+        [MemberDiff::Added { name, .. }] if name == "store_ping_get"
+    ));
+
+    let summary = model_diff.to_string();
+    assert!(
+        summary.contains("- changed struct `Category`\n  - removed `name`: `Option<String>`\n")
+    );
+    Ok(())
+}
+
+static PATH_ITEM_REFS_YAML: &str = r"
+openapi: 3.0.0
+info:
+    title: API with referenced path items
+    version: v1
+paths:
+    /things:
+        get:
+            responses:
+                '200':
+                    description: the things
+    /stuff:
+        $ref: '#/paths/~1things'
+    /external:
+        $ref: 'other.yaml#/paths/~1external'
+";
+
+#[test]
+fn test_path_item_refs() -> anyhow::Result<()> {
+    let spec = adapters::oas30::OAS30Spec::from_str(PATH_ITEM_REFS_YAML)?;
+    let (cm, _mapping) = super::build_codemodel(&ApiConfig::default(), &spec)?;
+    let crate_ = cm.find_crate("crate").unwrap();
+    let trait_ = crate_.trait_iter().next().unwrap();
+    let mut fn_names = trait_
+        .function_iter()
+        .map(|f| f.name().to_string())
+        .collect::<Vec<_>>();
+    fn_names.sort();
+    // the operations of the external path item are skipped
+    assert_eq!(vec!["stuff_get", "things_get"], fn_names);
+    Ok(())
+}
+
+static DUPLICATE_OPERATION_IDS_YAML: &str = r"
+openapi: 3.0.0
+info:
+    title: API reusing operationIds
+    version: v1
+paths:
+    /users/{id}:
+        parameters:
+            - name: id
+              in: path
+              required: true
+              schema:
+                  type: string
+        get:
+            operationId: getUser
+            responses:
+                '200':
+                    description: the user
+        delete:
+            operationId: deleteUser
+            responses:
+                '204':
+                    description: deleted
+    /admins/{id}:
+        parameters:
+            - name: id
+              in: path
+              required: true
+              schema:
+                  type: string
+        get:
+            operationId: getUser
+            responses:
+                '200':
+                    description: the admin
+    /health:
+        get:
+            responses:
+                '200':
+                    description: healthy
+";
+
+#[test]
+fn test_operation_id_names() -> anyhow::Result<()> {
+    let spec = adapters::oas30::OAS30Spec::from_str(DUPLICATE_OPERATION_IDS_YAML)?;
+    let fn_names_of = |config: &ApiConfig| -> anyhow::Result<Vec<String>> {
+        let (cm, _mapping) = super::build_codemodel(config, &spec)?;
+        let crate_ = cm.find_crate("crate").unwrap();
+        let trait_ = crate_.trait_iter().next().unwrap();
+        Ok(trait_
+            .function_iter()
+            .map(|f| f.name().to_string())
+            .collect())
+    };
+
+    assert_eq!(
+        vec![
+            "users_id_get",
+            "users_id_delete",
+            "admins_id_get",
+            "health_get"
+        ],
+        fn_names_of(&ApiConfig::default())?
+    );
+
+    let config = ApiConfig {
+        operation_id_names: true,
+        ..Default::default()
+    };
+    // the second 'getUser' gets its method appended
+    assert_eq!(
+        vec!["get_user", "delete_user", "get_user_get", "health_get"],
+        fn_names_of(&config)?
+    );
+    let (cm, _mapping) = super::build_codemodel(&config, &spec)?;
+    let crate_ = cm.find_crate("crate").unwrap();
+    assert!(crate_.find_type("GetUserError").is_some());
+    assert!(crate_.find_type("GetUserGetError").is_some());
+    Ok(())
+}
+
+static NON_ASCII_OPERATION_IDS_YAML: &str = r"
+openapi: 3.0.0
+info:
+    title: test for operationIds with non-ASCII characters
+    version: v1
+paths:
+    /orders:
+        post:
+            operationId: commanderUnCafé
+            responses:
+                '204':
+                    description: ordered
+";
+
+#[test]
+fn test_non_ascii_names() -> anyhow::Result<()> {
+    let spec = adapters::oas30::OAS30Spec::from_str(NON_ASCII_OPERATION_IDS_YAML)?;
+    let fn_name_of = |non_ascii_names| -> anyhow::Result<String> {
+        let config = ApiConfig {
+            operation_id_names: true,
+            non_ascii_names,
+            ..Default::default()
+        };
+        let (cm, _mapping) = super::build_codemodel(&config, &spec)?;
+        let crate_ = cm.find_crate("crate").unwrap();
+        let trait_ = crate_.trait_iter().next().unwrap();
+        Ok(trait_.function_iter().next().unwrap().name().to_string())
+    };
+
+    assert_eq!("commander_un_caf", fn_name_of(NonAsciiHandling::Strip)?);
+    assert_eq!("commander_un_café", fn_name_of(NonAsciiHandling::Keep)?);
+
+    let config = ApiConfig {
+        operation_id_names: true,
+        non_ascii_names: NonAsciiHandling::Keep,
+        ..Default::default()
+    };
+    let code = super::generate_code(&config, &spec)?.to_string();
+    assert!(code.contains("CommanderUnCaféError"), "{code}");
+    Ok(())
+}
+
+#[test]
+fn test_emit_tracing() -> anyhow::Result<()> {
+    let spec = adapters::oas30::OAS30Spec::from_str(include_str!(
+        "../../test-data/response-decoders.yaml"
+    ))?;
+    let code_of = |config: &ApiConfig| -> anyhow::Result<String> {
+        let (cm, _mapping) = super::build_codemodel(config, &spec)?;
+        Ok(codewriter::write_to_token_stream(&cm, "crate")?.to_string())
+    };
+
+    let config = ApiConfig {
+        response_decoders: true,
+        ..Default::default()
+    };
+    let code = code_of(&config)?;
+    assert!(!code.contains("tracing"), "{code}");
+
+    let config = ApiConfig {
+        response_decoders: true,
+        emit_tracing: true,
+        ..Default::default()
+    };
+    let code = code_of(&config)?;
+    assert!(code.contains(r#"target : "cogenitor::client""#), "{code}");
+    assert!(code.contains(r#"operation = "pets_pet_id_get""#), "{code}");
+    assert!(code.contains(r#"path = "/pets/{petId}""#), "{code}");
+    assert!(
+        code.contains(r#"PetsPetIdGetError :: NotFound404 { .. } => "NotFound404""#),
+        "{code}"
+    );
+    assert!(!code.contains("cfg (feature"), "{code}");
+
+    // the tracing calls are compiled only if the feature is enabled
+    let config = ApiConfig {
+        response_decoders: true,
+        emit_tracing: true,
+        tracing_feature: Some("client-tracing".to_string()),
+        ..Default::default()
+    };
+    let code = code_of(&config)?;
+    assert!(
+        code.contains(r#"# [cfg (feature = "client-tracing")] let span = :: tracing :: span !"#),
+        "{code}"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_optional_type() {
+    let mut cm = Codemodel::new();
+    let i32_type = cm.type_i32();
+    // (required, nullable, double_option)
+    let cases = [
+        ((true, false, false), "i32"),
+        ((true, true, false), "Option<i32>"),
+        ((false, false, false), "Option<i32>"),
+        ((false, true, false), "Option<i32>"),
+        ((true, false, true), "i32"),
+        ((true, true, true), "Option<i32>"),
+        ((false, false, true), "Option<i32>"),
+        ((false, true, true), "Option<Option<i32>>"),
+    ];
+    for ((required, nullable, double_option), expected) in cases {
+        let type_ref = super::optional_type(&mut cm, &i32_type, required, nullable, double_option);
+        assert_eq!(
+            expected,
+            type_ref.name(),
+            "required: {required}, nullable: {nullable}, double_option: {double_option}"
+        );
+    }
+}
+
+#[test]
+fn test_single_value_enums() -> anyhow::Result<()> {
+    let spec = adapters::oas30::OAS30Spec::from_str(include_str!(
+        "../../test-data/single-value-enums.yaml"
+    ))?;
+
+    let (cm, _mapping) = super::build_codemodel(&ApiConfig::default(), &spec)?;
+    let code = codewriter::write_to_token_stream(&cm, "crate")?.to_string();
+    assert!(code.contains("pub pet_type : DogMarker"), "{code}");
+    assert!(code.contains("pub pet_type : CatMarker"), "{code}");
+    assert_eq!(1, code.matches("struct DogMarker").count(), "{code}");
+    // optional properties may be absent, so they cannot carry the value
+    assert!(
+        code.contains(r#"# [doc = " Always `\"mammal\"`"] pub kind : Option < String >"#),
+        "{code}"
+    );
+    assert!(!code.contains("MammalMarker"), "{code}");
+
+    let config = ApiConfig {
+        single_value_enums: SingleValueEnums::String,
+        ..ApiConfig::default()
+    };
+    let (cm, _mapping) = super::build_codemodel(&config, &spec)?;
+    let code = codewriter::write_to_token_stream(&cm, "crate")?.to_string();
+    assert!(
+        code.contains(r#"# [doc = " Always `\"dog\"`"] pub pet_type : String"#),
+        "{code}"
+    );
+    assert!(!code.contains("Marker"), "{code}");
+    Ok(())
+}
+
+#[test]
+fn test_read_write_only_by_usage() -> anyhow::Result<()> {
+    let oas = r"
+openapi: 3.0.0
+info:
+    title: Read-only and write-only properties by usage
+    version: v1
+paths:
+    /accounts:
+        post:
+            requestBody:
+                content:
+                    application/json:
+                        schema:
+                            $ref: '#/components/schemas/NewAccount'
+            responses:
+                '201':
+                    description: the account created
+                    content:
+                        application/json:
+                            schema:
+                                $ref: '#/components/schemas/Account'
+    /accounts/{id}:
+        put:
+            parameters:
+                -   {name: id, in: path, required: true, schema: {type: integer}}
+            requestBody:
+                content:
+                    application/json:
+                        schema:
+                            $ref: '#/components/schemas/Profile'
+            responses:
+                '200':
+                    description: the profile updated
+                    content:
+                        application/json:
+                            schema:
+                                $ref: '#/components/schemas/Profile'
+components:
+    schemas:
+        NewAccount:
+            type: object
+            required: [password]
+            properties:
+                password:
+                    type: string
+                    writeOnly: true
+        Account:
+            type: object
+            required: [id]
+            properties:
+                id:
+                    type: integer
+                    format: int64
+                    readOnly: true
+        Profile:
+            type: object
+            required: [version]
+            properties:
+                version:
+                    type: integer
+                    format: int64
+                    readOnly: true
+";
+    let spec = adapters::oas30::OAS30Spec::from_str(oas)?;
+    let (cm, _mapping) = super::build_codemodel(&ApiConfig::default(), &spec)?;
+    let code = codewriter::write_to_token_stream(&cm, "crate")?.to_string();
+
+    // sent or received only, so the properties are always present
+    assert!(code.contains("pub password : String"), "{code}");
+    assert!(code.contains("pub id : i64"), "{code}");
+    assert!(!code.contains("skip_deserializing"), "{code}");
+    // sent and received, so the property is absent in requests
+    assert!(
+        code.contains("# [serde (skip_serializing)] pub version : Option < i64 >"),
+        "{code}"
+    );
+    Ok(())
+}
+
+const RENAMES_YAML: &str = r#"
+openapi: 3.0.0
+info:
+  title: Property renames
+  version: 1.0.0
+paths: {}
+components:
+  schemas:
+    Camel:
+      type: object
+      properties:
+        petId:
+          type: string
+        createdAt:
+          type: string
+        name:
+          type: string
+    Mixed:
+      type: object
+      properties:
+        petId:
+          type: string
+        pet-name:
+          type: string
+        name:
+          type: string
+    Typed:
+      type: object
+      properties:
+        type:
+          type: string
+        name:
+          type: string
+"#;
+
+#[test]
+fn test_property_renames() -> anyhow::Result<()> {
+    let spec = adapters::oas30::OAS30Spec::from_str(RENAMES_YAML)?;
+    let (cm, _mapping) = super::build_codemodel(&ApiConfig::default(), &spec)?;
+    let code = codewriter::write_to_token_stream(&cm, "crate")?.to_string();
+    // the declaration of a struct, from its derive attribute to its closing brace
+    let struct_of = |name: &str| -> String {
+        let decl = code.find(&format!("pub struct {name} {{")).unwrap();
+        let start = code[..decl].rfind("# [derive").unwrap();
+        let end = decl + code[decl..].find('}').unwrap();
+        code[start..=end].to_string()
+    };
+
+    let code = struct_of("Camel");
+    assert!(
+        code.contains(r#"# [serde (rename_all = "camelCase")]"#),
+        "{code}"
+    );
+    assert!(code.contains("pub pet_id : Option < String >"), "{code}");
+    assert!(
+        code.contains("pub created_at : Option < String >"),
+        "{code}"
+    );
+    assert!(!code.contains("rename ="), "{code}");
+
+    let code = struct_of("Mixed");
+    assert!(!code.contains("rename_all"), "{code}");
+    assert!(
+        code.contains(r#"# [serde (rename = "petId")] pub pet_id"#),
+        "{code}"
+    );
+    assert!(
+        code.contains(r#"# [serde (rename = "pet-name")] pub pet_name"#),
+        "{code}"
+    );
+    assert!(code.contains("pub name : Option < String >"), "{code}");
+
+    // serde's camel case drops the trailing underscore of `type_`
+    let code = struct_of("Typed");
+    assert!(
+        code.contains(r#"# [serde (rename_all = "camelCase")]"#),
+        "{code}"
+    );
+    assert!(code.contains("pub type_ : Option < String >"), "{code}");
+    assert!(!code.contains("rename ="), "{code}");
+    Ok(())
+}
+
+static PANICKING_YAML: &str = r"
+openapi: 3.0.0
+info:
+    title: API with a top-level integer schema, which we cannot generate yet
+    version: v1
+paths: {}
+components:
+    schemas:
+        Count:
+            type: integer
+";
+
+#[test]
+fn test_run_corpus() -> anyhow::Result<()> {
+    let corpus_dir = std::env::temp_dir().join(format!("cogenitor-corpus-{}", std::process::id()));
+    std::fs::create_dir_all(corpus_dir.join("panicking"))?;
+    std::fs::write(corpus_dir.join("petstore.yaml"), PETSTORE_YAML)?;
+    std::fs::write(corpus_dir.join("panicking/spec.yaml"), PANICKING_YAML)?;
+    std::fs::write(corpus_dir.join("swagger.json"), r#"{"swagger": "2.0"}"#)?;
+    std::fs::write(corpus_dir.join("README.md"), "not a spec")?;
+
+    let timeout = std::time::Duration::from_secs(60);
+    let report = super::run_corpus(&corpus_dir, &ApiConfig::default(), timeout)?;
+    let outcomes = report
+        .entries
+        .iter()
+        .map(|entry| {
+            let path = entry.path.strip_prefix(&corpus_dir).unwrap();
+            (path.to_string_lossy().to_string(), entry.outcome.clone())
+        })
+        .collect::<Vec<_>>();
+    assert!(
+        matches!(
+            outcomes.as_slice(),
+            [
+                (panicking, CorpusOutcome::Panic(_)),
+                (petstore, CorpusOutcome::Success),
+                (swagger, CorpusOutcome::Error(_)),
+            ] if panicking == "panicking/spec.yaml"
+                && petstore == "petstore.yaml"
+                && swagger == "swagger.json"
+        ),
+        "{outcomes:?}"
+    );
+
+    let json = report.to_json();
+    assert_eq!(
+        serde_json::json!({"success": 1, "error": 1, "panic": 1, "timeout": 0, "skipped": 0}),
+        json["summary"]
+    );
+    assert_eq!("panic", json["specs"][0]["outcome"]);
+    let table = report.to_string();
+    assert!(
+        table.ends_with("3 specs: 1 success, 1 error, 1 panic, 0 timeout, 0 skipped"),
+        "{table}"
+    );
+
+    // nothing generates that fast
+    let report = super::run_corpus(&corpus_dir, &ApiConfig::default(), Default::default())?;
+    assert_eq!(3, report.count(&CorpusOutcome::Timeout));
+
+    // specs are skipped while too many timed out specs are generating
+    std::fs::remove_dir_all(&corpus_dir)?;
+    std::fs::create_dir_all(&corpus_dir)?;
+    for name in ["a.yaml", "b.yaml"] {
+        std::fs::write(corpus_dir.join(name), many_schemas_spec(500))?;
+    }
+    let report = super::corpus::run_corpus_with_limit(
+        &corpus_dir,
+        &ApiConfig::default(),
+        Default::default(),
+        1,
+    )?;
+    assert_eq!(1, report.count(&CorpusOutcome::Timeout));
+    assert_eq!(1, report.count(&CorpusOutcome::Skipped));
+
+    std::fs::remove_dir_all(&corpus_dir)?;
+    Ok(())
+}
+
+/// A spec with `count` object schemas, and a reference to each of them
+fn many_schemas_spec(count: usize) -> String {
+    let mut yaml = "openapi: 3.0.0\ninfo: {title: Many schemas, version: v1}\npaths: {}\ncomponents:\n  schemas:\n".to_string();
+    for i in 0..count {
+        yaml.push_str(&format!(
+            "    Schema{i}: {{type: object, properties: {{id: {{type: integer}}}}}}\n    Alias{i}: {{$ref: '#/components/schemas/Schema{i}'}}\n"
+        ));
+    }
+    yaml
+}
+
+#[test]
+fn test_ref_or_hash() -> anyhow::Result<()> {
+    use std::hash::BuildHasher;
+
+    let spec = adapters::oas30::OAS30Spec::from_str(&many_schemas_spec(100))?;
+    let schemata = spec.schemata_iter().collect::<Vec<_>>();
+
+    // objects and references hash by what they point to, not just by
+    // their variant
+    let hasher = std::hash::RandomState::new();
+    let hashes = schemata
+        .iter()
+        .map(|(_, schema)| hasher.hash_one(schema))
+        .collect::<HashSet<_>>();
+    assert_eq!(200, hashes.len());
+
+    let mapping = schemata
+        .iter()
+        .cloned()
+        .map(|(name, schema)| (schema, name))
+        .collect::<HashMap<_, _>>();
+    for (name, schema) in &schemata {
+        assert_eq!(Some(name), mapping.get(schema));
+    }
+
+    // a reference is never taken for the object it resolves to
+    let (_, alias) = schemata.iter().find(|(name, _)| name == "Alias7").unwrap();
+    let target = alias.resolve();
+    assert!(matches!(target, RefOr::Object(_)));
+    assert_ne!(alias, &target);
+    assert_eq!(Some(&"Schema7".to_string()), mapping.get(&target));
+    Ok(())
+}
+
+#[test]
+#[ignore = "benchmark, run with --ignored --nocapture"]
+fn bench_ref_or_lookup() -> anyhow::Result<()> {
+    for count in [500, 1000, 2000, 4000] {
+        let spec = adapters::oas30::OAS30Spec::from_str(&many_schemas_spec(count))?;
+        let schemata = spec.schemata_iter().collect::<Vec<_>>();
+        let mapping = schemata
+            .iter()
+            .cloned()
+            .map(|(name, schema)| (schema, name))
+            .collect::<HashMap<_, _>>();
+        let start = std::time::Instant::now();
+        for (_, schema) in &schemata {
+            assert!(mapping.contains_key(schema));
+        }
+        let elapsed = start.elapsed();
+        println!(
+            "{} entries: {elapsed:?} for all lookups, {:?} per lookup",
+            schemata.len(),
+            elapsed / schemata.len() as u32
+        );
+    }
+    Ok(())
+}
+
+/// Prefixes the types named after schemas with the prefix given
+#[derive(Debug, Clone, PartialEq)]
+struct PrefixNaming(&'static str);
+
+impl NamingPolicy for PrefixNaming {
+    fn schema_to_type_name(&self, schema_name: &str) -> String {
+        let type_name = DefaultNamingPolicy.schema_to_type_name(schema_name);
+        format!("{}{type_name}", self.0)
+    }
+}
+
+#[test]
+fn test_naming_policy() -> anyhow::Result<()> {
+    let spec = adapters::oas30::OAS30Spec::from_reader(PETSTORE_YAML)?;
+    let config = ApiConfig {
+        naming_policy: Some(Box::new(PrefixNaming("X"))),
+        ..ApiConfig::default()
+    };
+    // policies are carried along with the config
+    assert_eq!(config, config.clone());
+    assert_ne!(config, ApiConfig::default());
+    // and compared by value
+    let other_prefix_config = ApiConfig {
+        naming_policy: Some(Box::new(PrefixNaming("Y"))),
+        ..ApiConfig::default()
+    };
+    assert_ne!(config, other_prefix_config);
+    // policies of different types differ, even if they print the same
+    let default_policy_config = ApiConfig {
+        naming_policy: Some(Box::new(DefaultNamingPolicy)),
+        ..ApiConfig::default()
+    };
+    assert_ne!(config, default_policy_config);
+
+    // types are declared and referred to by the names of the policy
+    let code = super::generate_code(&config, &spec)?.to_string();
+    for expected in [
+        "pub struct XPet {",
+        "pub category : Option < XCategory >",
+        "pub tags : Option < Vec < XTag > >",
+        "body : Vec < XUser >",
+        "-> Result < XApiResponse , PetPetIdUploadImagePostError >",
+        "pub enum PetPutOk200 { ApplicationJson (XPet) , ApplicationXml (XPet) }",
+    ] {
+        assert!(code.contains(expected), "{expected} missing in {code}");
+    }
+    assert!(!code.contains("struct Pet "), "{code}");
+    assert!(!code.contains("XXPet"), "{code}");
+    Ok(())
+}
+
+static MAP_SCHEMA_YAML: &str = r"
+openapi: 3.0.0
+info:
+    title: test for schemas mapping to type aliases
+    version: v1
+paths: {}
+components:
+    schemas:
+        Counts:
+            type: object
+            additionalProperties:
+                type: integer
+                format: int32
+";
+
+/// Prefixes the types named after schemas with `t_`, which the generator
+/// would not name types
+#[derive(Debug, Clone, PartialEq)]
+struct SnakePrefixNaming;
+
+impl NamingPolicy for SnakePrefixNaming {
+    fn schema_to_type_name(&self, schema_name: &str) -> String {
+        format!("t_{}", DefaultNamingPolicy.schema_to_type_name(schema_name))
+    }
+}
+
+#[test]
+fn test_naming_policy_aliases() -> anyhow::Result<()> {
+    let spec = adapters::oas30::OAS30Spec::from_str(MAP_SCHEMA_YAML)?;
+    let config = ApiConfig {
+        naming_policy: Some(Box::new(SnakePrefixNaming)),
+        ..ApiConfig::default()
+    };
+    let code = super::generate_code(&config, &spec)?.to_string();
+    assert!(
+        code.contains("type t_Counts = :: std :: collections :: HashMap < String , i32 >"),
+        "{code}"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_link_helpers() -> anyhow::Result<()> {
+    let spec = adapters::oas30::OAS30Spec::from_str(include_str!("../../test-data/links.yaml"))?;
+
+    // links are ignored unless asked for
+    let code = super::generate_code(&ApiConfig::default(), &spec)?.to_string();
+    assert!(!code.contains("Links"), "{code}");
+
+    let config = ApiConfig {
+        link_helpers: true,
+        ..Default::default()
+    };
+    let code = super::generate_code(&config, &spec)?.to_string();
+    assert!(
+        code.contains("pub trait StoreOrderPostLinks { # [doc = \" Calls `getOrderById` with the parameters taken from this response (link `GetOrderById`)\"] fn follow_get_order_by_id (& self , client : & impl Client) -> :: std :: result :: Result < Order , StoreOrderOrderIdGetError > ; }"),
+        "{code}"
+    );
+    assert!(
+        code.contains("impl StoreOrderPostLinks for Order"),
+        "{code}"
+    );
+    assert!(
+        code.contains("client . store_order_order_id_get (link_value (& body , \"/id\")"),
+        "{code}"
+    );
+    assert!(code.contains("fn link_value"), "{code}");
+    // only values taken from the response body are supported
+    assert!(!code.contains("follow_cancel_order"), "{code}");
+    Ok(())
+}
+
+#[test]
+fn test_exists_helpers() -> anyhow::Result<()> {
+    let spec = adapters::oas30::OAS30Spec::from_str(include_str!("../../test-data/petstore.yaml"))?;
+
+    // existence checks are only generated when asked for
+    let code = super::generate_code(&ApiConfig::default(), &spec)?.to_string();
+    assert!(!code.contains("ClientExists"), "{code}");
+
+    let config = ApiConfig {
+        emit_exists_helpers: true,
+        ..Default::default()
+    };
+    let code = super::generate_code(&config, &spec)?.to_string();
+    assert!(code.contains("pub trait ClientExists : Client {"), "{code}");
+    assert!(
+        code.contains("fn pet_pet_id_exists (& self , petId : i64) -> :: std :: result :: Result < bool , PetPetIdGetError >"),
+        "{code}"
+    );
+    assert!(
+        code.contains(":: std :: result :: Result :: Err (PetPetIdGetError :: NotFound404 (..)) => { :: std :: result :: Result :: Ok (false) }"),
+        "{code}"
+    );
+    assert!(code.contains("fn user_username_exists"), "{code}");
+    assert!(
+        code.contains("impl < T : Client + ? :: std :: marker :: Sized > ClientExists for T { }"),
+        "{code}"
+    );
+    // not reading a single resource by its path
+    assert!(!code.contains("find_by_status_exists"), "{code}");
+
+    let spec =
+        adapters::oas30::OAS30Spec::from_str(include_str!("../../test-data/exists-helpers.yaml"))?;
+    let code = super::generate_code(&config, &spec)?.to_string();
+    // a declared HEAD operation is preferred
+    assert!(
+        code.contains("match self . documents_document_id_head (documentId)"),
+        "{code}"
+    );
+    assert!(code.contains("fn documents_document_id_exists"), "{code}");
+    // several success responses make the outcome ambiguous
+    assert!(!code.contains("drafts_draft_id_exists"), "{code}");
+    // more than the path parameter
+    assert!(!code.contains("documents_exists"), "{code}");
+    Ok(())
+}
+
+#[test]
+fn test_typed_formats() -> anyhow::Result<()> {
+    let spec = adapters::oas30::OAS30Spec::from_str(include_str!("../../test-data/decimals.yaml"))?;
+
+    // decimals are plain strings and floats by default
+    let code = super::generate_code(&ApiConfig::default(), &spec)?.to_string();
+    assert!(code.contains("pub amount : String"), "{code}");
+    assert!(code.contains("pub rate : f64"), "{code}");
+    assert!(!code.contains("rust_decimal"), "{code}");
+
+    let config = ApiConfig {
+        typed_formats: true,
+        ..Default::default()
+    };
+    let code = super::generate_code(&config, &spec)?.to_string();
+    assert!(
+        code.contains(r#"# [serde (with = "::rust_decimal::serde::str")] pub amount : :: rust_decimal :: Decimal"#),
+        "{code}"
+    );
+    assert!(
+        code.contains(r#"# [serde (with = "::rust_decimal::serde::float")] pub rate : :: rust_decimal :: Decimal"#),
+        "{code}"
+    );
+    // x-format is recognized for strings of other formats
+    assert!(
+        code.contains(r#"# [serde (default , with = "::rust_decimal::serde::str_option")] pub fee : Option < :: rust_decimal :: Decimal >"#),
+        "{code}"
+    );
+    assert!(
+        code.contains(r#"# [serde (default , with = "::rust_decimal::serde::float_option")] pub tax : Option < :: rust_decimal :: Decimal >"#),
+        "{code}"
+    );
+    Ok(())
+}
@@ -1,6 +1,8 @@
 use std::collections::{HashMap, HashSet};
 
-use crate::types::StatusSpec;
+use crate::NonAsciiHandling;
+use crate::codemodel::SerdeRenameCase;
+use crate::types::{ParameterLocation, StatusSpec};
 
 // Array of strict keywords (currently in use)
 const STRICT_KEYWORDS: &[&str] = &[
@@ -37,24 +39,83 @@ where
         Some(f) => m(f).collect::<String>() + c.as_str(),
     }
 }
+/// Turns schema names like `Pet`, `user.v1.Profile` or `search-results`
+/// into Rust type names (`Pet`, `UserV1Profile`, `SearchResults`).
+/// Characters that cannot appear in identifiers separate words, which are
+/// capitalized and joined. Names starting with a digit, like `3DModel`,
+/// are prefixed with `Schema`.
 pub(crate) fn schema_to_rust_typename(schema_name: &str) -> String {
-    avoid_reserved(&capitalize(schema_name))
+    let type_name = schema_name
+        .split(|c: char| !(c.is_alphanumeric() || c == '_'))
+        .map(capitalize)
+        .collect::<String>();
+    let type_name = match type_name.chars().next() {
+        None => "Schema".to_string(),
+        Some(c) if c.is_numeric() => format!("Schema{type_name}"),
+        Some(_) => type_name,
+    };
+    avoid_reserved(&type_name)
 }
 
-pub(crate) fn property_to_rust_fieldname(property_name: &str) -> String {
-    avoid_reserved(&decapitalize(property_name))
+/// Turns property names like `petId`, `PetId`, `pet-id`, `PET_ID` or
+/// `@type` into snake case field names (`pet_id`, `type_`), splitting
+/// words like [operation_id_to_rust_fn_name] does. Names starting with a
+/// digit are prefixed with `_`. The name avoids keywords and is made
+/// unique against `taken`.
+pub(crate) fn property_to_rust_fieldname(
+    property_name: &str,
+    taken: &impl ContainsPredicate,
+) -> String {
+    let field_name = snake_case_words(property_name, char::is_alphanumeric).join("_");
+    let field_name = match field_name.chars().next() {
+        None => "field".to_string(),
+        Some(c) if c.is_numeric() => format!("_{field_name}"),
+        Some(_) => field_name,
+    };
+    uncollide(taken, avoid_reserved(&field_name))
+}
+
+/// The casing convention for serde's `rename_all` that maps the given
+/// fields, as pairs of field and property name, to their property names.
+/// Fields named like their property are compatible with any convention,
+/// as they can be renamed individually. Yields `None` if all fields are
+/// named like their properties, or no single convention fits the others.
+pub(crate) fn container_rename_case(fields: &[(&str, &str)]) -> Option<SerdeRenameCase> {
+    let renamed = fields
+        .iter()
+        .filter(|(field_name, property_name)| field_name != property_name)
+        .collect::<Vec<_>>();
+    if renamed.is_empty() {
+        return None;
+    }
+    [
+        SerdeRenameCase::CamelCase,
+        SerdeRenameCase::PascalCase,
+        SerdeRenameCase::KebabCase,
+        SerdeRenameCase::ScreamingSnakeCase,
+    ]
+    .into_iter()
+    .find(|case| {
+        renamed
+            .iter()
+            .all(|(field_name, property_name)| case.apply(field_name) == *property_name)
+    })
 }
 
 pub(crate) fn parameter_to_rust_fn_param(param_name: &str) -> String {
     avoid_reserved(&decapitalize(param_name))
 }
 
-fn avoid_reserved(s: &str) -> String {
-    if STRICT_KEYWORDS
+/// Returns `true` if `s` is a strict or reserved Rust keyword
+pub(crate) fn is_keyword(s: &str) -> bool {
+    STRICT_KEYWORDS
         .iter()
         .chain(RESERVED_KEYWORDS.iter())
         .any(|e| (*e).eq(s))
-    {
+}
+
+fn avoid_reserved(s: &str) -> String {
+    if is_keyword(s) {
         s.to_string() + "_"
     } else {
         s.to_string()
@@ -62,13 +123,29 @@ fn avoid_reserved(s: &str) -> String {
 }
 
 /// Turns REST paths defined in an OpenAPI spec into Rust function names.
-/// For instance, /foo/bar should become foo_bar(), so '/' is replaced by underscore '_'
-/// . Non-Rust identifier characters like '{', '}', '$' will be replaced by '_'
-/// as well. Leading and consecutive underscores are eliminated (e.g when converting /foo/{bar}, which would
-/// otherwise yield _foo__bar, but are converted to foo_bar)
+/// For instance, `/foo/bar` should become `foo_bar()`. Words are separated
+/// at characters that are not ASCII letters or digits (like '/', '{', '-',
+/// '_' or '.') and at camel case humps, so `/petFindByStatus/{petId}`
+/// becomes `pet_find_by_status_pet_id`. The method is appended as the
+/// last word.
 pub(crate) fn path_method_to_rust_fn_name(
     method: &http::Method,
     path: &str,
+) -> anyhow::Result<String> {
+    let mut words = snake_case_words(path, |c| c.is_ascii_alphanumeric());
+    words.push(method.as_str().to_lowercase());
+    Ok(avoid_reserved(&words.join("_")))
+}
+
+/// Turns REST paths into Rust function names the way earlier versions did
+/// (see [crate::ApiConfig::legacy_path_names]). Non-Rust identifier
+/// characters like '/', '{', '}', '$' are replaced by '_', and the path is
+/// lowercased without separating camel case words, so
+/// `/petFindByStatus/{petId}` becomes `petfindbystatus_petid`. Leading and
+/// consecutive underscores are eliminated.
+pub(crate) fn legacy_path_method_to_rust_fn_name(
+    method: &http::Method,
+    path: &str,
 ) -> anyhow::Result<String> {
     // Convert HTTP method to lowercase
     let method_str = method.as_str().to_lowercase();
@@ -107,9 +184,107 @@ pub(crate) fn path_method_to_rust_fn_name(
     Ok(avoid_reserved(&function_name))
 }
 
-/// converts paths and methods like 'GET /foo/bar' into type names such as
-/// FooBarGet
+/// Normalizes a path template by removing empty segments, so `/pets/` and
+/// `//pets` both become `/pets`. The root path `/` stays as it is.
+pub(crate) fn normalize_path(path: &str) -> String {
+    let segments = path
+        .split('/')
+        .filter(|s| !s.is_empty())
+        .collect::<Vec<_>>();
+    "/".to_string() + &segments.join("/")
+}
+
+/// The shape of a path template, with the names of its parameters removed:
+/// `/users/{id}` and `/users/{userId}` both become `/users/{}`, as they
+/// match the same paths. Static segments are kept as they are, so neither
+/// casing nor trailing slashes are ignored. An unclosed `{` is kept as it
+/// is, too.
+pub(crate) fn path_template_shape(path: &str) -> String {
+    let mut shape = String::new();
+    let mut rest = path;
+    while let Some(start) = rest.find('{') {
+        let Some(len) = rest[start..].find('}') else {
+            break;
+        };
+        shape.push_str(&rest[..start]);
+        shape.push_str("{}");
+        rest = &rest[start + len + 1..];
+    }
+    shape.push_str(rest);
+    shape
+}
+
+/// Checks that the parameters of a path template like `/pets/{petId}` are
+/// well-formed and that `is_declared` accepts all of them. All undeclared
+/// parameters are reported at once; a malformed template is reported at the
+/// first broken parameter.
+pub(crate) fn validate_path_template(
+    path_template: &str,
+    is_declared: impl Fn(&str) -> bool,
+) -> Result<(), Vec<String>> {
+    let mut errors = Vec::new();
+    let mut chars = path_template.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '{' => {
+                let mut param_name = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some('{') | None => {
+                            errors.push(format!(
+                                "unterminated parameter in path template '{path_template}'"
+                            ));
+                            return Err(errors);
+                        }
+                        Some(c) => param_name.push(c),
+                    }
+                }
+                if param_name.is_empty() {
+                    errors.push(format!(
+                        "empty parameter in path template '{path_template}'"
+                    ));
+                    return Err(errors);
+                }
+                if !is_declared(&param_name) {
+                    errors.push(format!(
+                        "no variable for parameter '{param_name}' in path template '{path_template}'"
+                    ));
+                }
+            }
+            '}' => {
+                errors.push(format!("unmatched '}}' in path template '{path_template}'"));
+                return Err(errors);
+            }
+            _ => {}
+        }
+    }
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+/// Turns a snake case function name like `find_pets_by_status` into the
+/// camel case type name for the operation (`FindPetsByStatus`)
+pub(crate) fn fn_name_to_rust_typename(fn_name: &str) -> String {
+    fn_name.split('_').map(capitalize).collect()
+}
+
+/// Converts paths and methods like 'GET /foo/bar' into type names such as
+/// `FooBarGet`. Words are separated like in [path_method_to_rust_fn_name],
+/// so `GET /pet-find_by.status` becomes `PetFindByStatusGet`.
 pub(crate) fn path_method_to_rust_type_name(method: http::Method, path: &str) -> String {
+    let mut words = snake_case_words(path, |c| c.is_ascii_alphanumeric());
+    words.push(method.as_str().to_lowercase());
+    words.iter().map(|word| capitalize(word)).collect()
+}
+
+/// Converts paths and methods into type names the way earlier versions did
+/// (see [crate::ApiConfig::legacy_path_names]): path segments are only
+/// capitalized, keeping the case of their other characters.
+pub(crate) fn legacy_path_method_to_rust_type_name(method: http::Method, path: &str) -> String {
     let (l, r) = method.as_str().split_at(1);
     let method_str = l.to_uppercase() + &r.to_lowercase();
 
@@ -240,6 +415,170 @@ pub(crate) fn media_type_range_to_rust_type_name(media_type_key: &str) -> String
         .collect()
 }
 
+/// Turns HTTP header names like `X-RateLimit-Remaining` into Rust type
+/// names like `XRateLimitRemaining`. Non-alphanumeric characters separate
+/// words, which are capitalized and joined.
+pub(crate) fn header_to_rust_typename(header_name: &str) -> String {
+    let type_name = header_name
+        .split(|c: char| !c.is_alphanumeric())
+        .map(capitalize)
+        .collect::<String>();
+    avoid_reserved(&type_name)
+}
+
+/// Turns parameter names like `filter` or `X-Request-Id` into Rust type name
+/// fragments like `Filter` or `XRequestId`, following the rules of
+/// [header_to_rust_typename].
+pub(crate) fn parameter_to_rust_typename(param_name: &str) -> String {
+    header_to_rust_typename(param_name)
+}
+
+/// The suffix telling apart function parameters of the same name passed in
+/// different locations, like `query` for `id_query`
+pub(crate) fn parameter_location_to_suffix(location: ParameterLocation) -> &'static str {
+    match location {
+        ParameterLocation::Query => "query",
+        ParameterLocation::Header => "header",
+        ParameterLocation::Path => "path",
+        ParameterLocation::Cookie => "cookie",
+    }
+}
+
+/// Turns string values (like those in an `enum`) into Rust enum variant
+/// names, so `production` becomes `Production` and `us-east-1` becomes
+/// `UsEast1`. Values starting with a digit are prefixed with `V`.
+pub(crate) fn enum_value_to_rust_variant_name(value: &str) -> String {
+    let variant_name = value
+        .split(|c: char| !c.is_alphanumeric())
+        .map(capitalize)
+        .collect::<String>();
+    let variant_name = match variant_name.chars().next() {
+        None => "Empty".to_string(),
+        Some(c) if c.is_numeric() => format!("V{variant_name}"),
+        Some(_) => variant_name,
+    };
+    avoid_reserved(&variant_name)
+}
+
+/// Turns the values of an enum into variant names like
+/// [enum_value_to_rust_variant_name], unique within the enum. Values
+/// translating to the same name, like `in-progress` and `in_progress`, get
+/// numbered suffixes in order. Values made of punctuation only, like `*`,
+/// have no name to translate and become `Value1`, `Value2` and so on.
+pub(crate) fn enum_values_to_rust_variant_names(values: &[&str]) -> Vec<String> {
+    let mut names = HashSet::new();
+    let mut synthetic = 0;
+    let mut variant_names = Vec::new();
+    for value in values {
+        let candidate = if value.is_empty() || value.chars().any(char::is_alphanumeric) {
+            enum_value_to_rust_variant_name(value)
+        } else {
+            synthetic += 1;
+            format!("Value{synthetic}")
+        };
+        let variant_name = uncollide(&names, candidate);
+        names.insert(variant_name.clone());
+        variant_names.push(variant_name);
+    }
+    variant_names
+}
+
+/// Turns schema names like `AuthScheme` or `auth-scheme` into Rust constant
+/// names like `AUTH_SCHEME`. Words are separated at non-alphanumeric
+/// characters and where a lowercase letter or digit is followed by an
+/// uppercase letter.
+pub(crate) fn schema_to_rust_constname(schema_name: &str) -> String {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let mut prev_lower = false;
+    for c in schema_name.chars() {
+        if !c.is_alphanumeric() {
+            words.push(std::mem::take(&mut word));
+            prev_lower = false;
+            continue;
+        }
+        if c.is_uppercase() && prev_lower {
+            words.push(std::mem::take(&mut word));
+        }
+        prev_lower = c.is_lowercase() || c.is_ascii_digit();
+        word.extend(c.to_uppercase());
+    }
+    words.push(word);
+    let const_name = words
+        .into_iter()
+        .filter(|w| !w.is_empty())
+        .collect::<Vec<_>>()
+        .join("_");
+    avoid_reserved(&const_name)
+}
+
+/// Turns operationIds like `getUser`, `user.get` or `get-user (v2)` into
+/// snake case Rust function names (`get_user`, `user_get`, `get_user_v2`).
+/// Characters that are not letters or digits separate words, as do
+/// camel case humps. Names starting with a digit are prefixed with `op_`.
+/// The name avoids keywords and is made unique against `taken`.
+///
+/// Yields `None` if no word is left of the operationId (e.g. if it only
+/// consists of stripped non-ASCII characters), in which case callers
+/// fall back to the name derived from path and method.
+pub(crate) fn operation_id_to_rust_fn_name(
+    operation_id: &str,
+    non_ascii: NonAsciiHandling,
+    taken: &impl ContainsPredicate,
+) -> Option<String> {
+    let is_word_char = |c: char| match non_ascii {
+        NonAsciiHandling::Strip => c.is_ascii_alphanumeric(),
+        NonAsciiHandling::Keep => c.is_alphanumeric(),
+    };
+    // stripped letters and digits are dropped rather than separating words
+    let operation_id = match non_ascii {
+        NonAsciiHandling::Strip if !operation_id.is_ascii() => {
+            log::warn!("stripping non-ASCII characters from operationId '{operation_id}'");
+            operation_id
+                .chars()
+                .filter(|c| c.is_ascii() || !c.is_alphanumeric())
+                .collect()
+        }
+        _ => operation_id.to_string(),
+    };
+
+    let mut fn_name = snake_case_words(&operation_id, is_word_char).join("_");
+    if fn_name.is_empty() {
+        return None;
+    }
+    if fn_name.starts_with(|c: char| c.is_numeric()) {
+        fn_name = format!("op_{fn_name}");
+    }
+    Some(uncollide(taken, avoid_reserved(&fn_name)))
+}
+
+/// Splits a name into lowercase words. Characters that are not word
+/// characters separate words, as do camel case humps.
+fn snake_case_words(name: &str, is_word_char: impl Fn(char) -> bool) -> Vec<String> {
+    let chars = name.chars().collect::<Vec<_>>();
+    let mut words = Vec::new();
+    let mut word = String::new();
+    for (i, &c) in chars.iter().enumerate() {
+        if !is_word_char(c) {
+            words.push(std::mem::take(&mut word));
+            continue;
+        }
+        // a hump starts at an uppercase letter following a lowercase letter
+        // or digit ('getUser'), or at the last uppercase letter of an
+        // acronym followed by a lowercase letter ('HTTPServer')
+        if c.is_uppercase() && i > 0 {
+            let prev = chars[i - 1];
+            let next_lower = chars.get(i + 1).is_some_and(|n| n.is_lowercase());
+            if prev.is_lowercase() || prev.is_numeric() || (prev.is_uppercase() && next_lower) {
+                words.push(std::mem::take(&mut word));
+            }
+        }
+        word.extend(c.to_lowercase());
+    }
+    words.push(word);
+    words.into_iter().filter(|w| !w.is_empty()).collect()
+}
+
 pub trait ContainsPredicate {
     fn contains_str(&self, item: &str) -> bool;
 }
@@ -271,6 +610,37 @@ pub fn uncollide(predicate: &impl ContainsPredicate, name_candidate: String) ->
     candidate
 }
 
+/** Makes names unique across a namespace like [uncollide], remembering the
+last suffix chosen for each candidate. As namespaces only grow, probing
+resumes there instead of at the bare candidate, which would be quadratic
+for thousands of equal candidates. */
+#[derive(Debug, Default)]
+pub struct NameAllocator {
+    suffixes: HashMap<String, u32>,
+}
+
+impl NameAllocator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The first of `name_candidate`, `name_candidate1`, `name_candidate2`
+    /// ... not taken, given that `taken` only grew since the last call
+    pub fn uncollide(&mut self, taken: &impl ContainsPredicate, name_candidate: String) -> String {
+        let n = self.suffixes.entry(name_candidate.clone()).or_insert(0);
+        loop {
+            let candidate = match *n {
+                0 => name_candidate.clone(),
+                n => format!("{name_candidate}{n}"),
+            };
+            if !taken.contains_str(&candidate) {
+                return candidate;
+            }
+            *n += 1;
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -417,4 +787,424 @@ mod tests {
             "FooBarBarIdGet"
         );
     }
+
+    #[test]
+    fn test_path_method_names_word_boundaries() {
+        let cases = [
+            ("/pets", "pets_get", "PetsGet"),
+            (
+                "/petFindByStatus",
+                "pet_find_by_status_get",
+                "PetFindByStatusGet",
+            ),
+            (
+                "/pet/findByStatus",
+                "pet_find_by_status_get",
+                "PetFindByStatusGet",
+            ),
+            (
+                "/userProfileSettings/{settingId}",
+                "user_profile_settings_setting_id_get",
+                "UserProfileSettingsSettingIdGet",
+            ),
+            (
+                "/pet-find_by.status",
+                "pet_find_by_status_get",
+                "PetFindByStatusGet",
+            ),
+            ("/pets/{pet_id}", "pets_pet_id_get", "PetsPetIdGet"),
+            ("/api/v2/items", "api_v2_items_get", "ApiV2ItemsGet"),
+            (
+                "/HTTPServer/status",
+                "http_server_status_get",
+                "HttpServerStatusGet",
+            ),
+            ("/users/{userID}", "users_user_id_get", "UsersUserIdGet"),
+            (
+                "/files/{name}.{ext}",
+                "files_name_ext_get",
+                "FilesNameExtGet",
+            ),
+            ("/PETS", "pets_get", "PetsGet"),
+            ("/{a}{b}", "a_b_get", "ABGet"),
+            ("/", "get", "Get"),
+        ];
+        for (path, fn_name, type_name) in cases {
+            assert_eq!(
+                fn_name,
+                path_method_to_rust_fn_name(&Method::GET, path).unwrap(),
+                "{path}"
+            );
+            assert_eq!(
+                type_name,
+                path_method_to_rust_type_name(Method::GET, path),
+                "{path}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_legacy_path_method_names() {
+        let cases = [
+            (
+                "/pet/findByStatus",
+                "pet_findbystatus_get",
+                "PetFindByStatusGet",
+            ),
+            (
+                "/petFindByStatus",
+                "petfindbystatus_get",
+                "PetFindByStatusGet",
+            ),
+            ("/pets/{petId}", "pets_petid_get", "PetsPetIdGet"),
+            ("/HTTPServer", "httpserver_get", "HTTPServerGet"),
+        ];
+        for (path, fn_name, type_name) in cases {
+            assert_eq!(
+                fn_name,
+                legacy_path_method_to_rust_fn_name(&Method::GET, path).unwrap(),
+                "{path}"
+            );
+            assert_eq!(
+                type_name,
+                legacy_path_method_to_rust_type_name(Method::GET, path),
+                "{path}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_header_to_rust_typename() {
+        assert_eq!(
+            header_to_rust_typename("X-RateLimit-Remaining"),
+            "XRateLimitRemaining"
+        );
+        assert_eq!(header_to_rust_typename("etag"), "Etag");
+        assert_eq!(header_to_rust_typename("x-request_id"), "XRequestId");
+    }
+
+    #[test]
+    fn test_enum_value_to_rust_variant_name() {
+        assert_eq!(enum_value_to_rust_variant_name("production"), "Production");
+        assert_eq!(enum_value_to_rust_variant_name("us-east-1"), "UsEast1");
+        assert_eq!(enum_value_to_rust_variant_name("v2.beta"), "V2Beta");
+        assert_eq!(enum_value_to_rust_variant_name("8443"), "V8443");
+        assert_eq!(enum_value_to_rust_variant_name("self"), "Self_");
+        assert_eq!(enum_value_to_rust_variant_name(""), "Empty");
+    }
+
+    #[test]
+    fn test_enum_values_to_rust_variant_names() {
+        assert_eq!(
+            enum_values_to_rust_variant_names(&["in-progress", "in_progress", "done"]),
+            vec!["InProgress", "InProgress1", "Done"]
+        );
+        assert_eq!(
+            enum_values_to_rust_variant_names(&["A", "a"]),
+            vec!["A", "A1"]
+        );
+        assert_eq!(
+            enum_values_to_rust_variant_names(&["*", "-", "value1"]),
+            vec!["Value1", "Value2", "Value11"]
+        );
+    }
+
+    #[test]
+    fn test_schema_to_rust_constname() {
+        assert_eq!(schema_to_rust_constname("AuthScheme"), "AUTH_SCHEME");
+        assert_eq!(schema_to_rust_constname("auth-scheme"), "AUTH_SCHEME");
+        assert_eq!(schema_to_rust_constname("apiV2"), "API_V2");
+        assert_eq!(schema_to_rust_constname("HTTP"), "HTTP");
+    }
+
+    #[test]
+    fn test_normalize_path() {
+        assert_eq!(normalize_path("/pets"), "/pets");
+        assert_eq!(normalize_path("/pets/"), "/pets");
+        assert_eq!(normalize_path("//pets//{id}/"), "/pets/{id}");
+        assert_eq!(normalize_path("/"), "/");
+        assert_eq!(normalize_path(""), "/");
+        assert_eq!(normalize_path("/{a}{b}"), "/{a}{b}");
+    }
+
+    #[test]
+    fn test_path_template_shape() {
+        assert_eq!(path_template_shape("/users/{id}"), "/users/{}");
+        assert_eq!(
+            path_template_shape("/users/{id}"),
+            path_template_shape("/users/{userId}")
+        );
+        // parameters are told apart by position
+        assert_ne!(
+            path_template_shape("/users/{id}/posts"),
+            path_template_shape("/users/posts/{id}")
+        );
+        // static segments differing in case are different paths
+        assert_ne!(
+            path_template_shape("/Users/{id}"),
+            path_template_shape("/users/{id}")
+        );
+        // so are paths differing by a trailing slash, unlike their names
+        assert_ne!(
+            path_template_shape("/users/{id}/"),
+            path_template_shape("/users/{userId}")
+        );
+        assert_eq!(
+            path_template_shape("/users/{id}/"),
+            path_template_shape("/users/{userId}/")
+        );
+        assert_eq!(path_template_shape("/files/{name}.{ext}"), "/files/{}.{}");
+        assert_eq!(path_template_shape("/{a}{b}"), "/{}{}");
+        assert_eq!(path_template_shape("/users/{id"), "/users/{id");
+        assert_eq!(path_template_shape("/"), "/");
+    }
+
+    #[test]
+    fn test_validate_path_template() {
+        let is_declared = |name: &str| name != "ownerId" && name != "petId";
+
+        assert!(validate_path_template("/stores/{storeId}", is_declared).is_ok());
+        assert!(validate_path_template("/stores/", is_declared).is_ok());
+        assert!(validate_path_template("/{a}{b}/x.{c}", is_declared).is_ok());
+
+        // all undeclared parameters are reported
+        let errors =
+            validate_path_template("/owners/{ownerId}/pets/{petId}/{storeId}", is_declared)
+                .unwrap_err();
+        assert_eq!(2, errors.len());
+        assert!(errors[0].contains("'ownerId'"));
+        assert!(errors[1].contains("'petId'"));
+
+        // broken templates
+        for template in ["/stores/{storeId", "/stores/{}", "/stores/storeId}"] {
+            let errors = validate_path_template(template, is_declared).unwrap_err();
+            assert_eq!(1, errors.len(), "{template}");
+        }
+    }
+
+    #[test]
+    fn test_trailing_slash_and_adjacent_params() {
+        // a trailing slash does not change the derived names, so
+        // collisions must be resolved by the caller
+        assert_eq!(
+            path_method_to_rust_fn_name(&Method::GET, "/pets/").unwrap(),
+            path_method_to_rust_fn_name(&Method::GET, "/pets").unwrap()
+        );
+        assert_eq!(
+            path_method_to_rust_type_name(Method::GET, "/pets/"),
+            "PetsGet"
+        );
+        assert_eq!(
+            path_method_to_rust_fn_name(&Method::GET, "/{a}{b}").unwrap(),
+            "a_b_get"
+        );
+        assert_eq!(
+            path_method_to_rust_type_name(Method::GET, "/{a}{b}"),
+            "ABGet"
+        );
+        assert_eq!(
+            path_method_to_rust_type_name(Method::GET, "/foo//bar/"),
+            "FooBarGet"
+        );
+    }
+
+    #[test]
+    fn test_operation_id_to_rust_fn_name() {
+        use NonAsciiHandling::*;
+
+        let taken = vec!["get_user", "list_pets", "list_pets1"];
+        let cases: &[(&str, NonAsciiHandling, Option<&str>)] = &[
+            // plain and camel case
+            ("getUser", Strip, Some("get_user1")),
+            ("findPetsByStatus", Strip, Some("find_pets_by_status")),
+            ("GetUser", Strip, Some("get_user1")),
+            ("getUserV2", Strip, Some("get_user_v2")),
+            ("getHTTPServer", Strip, Some("get_http_server")),
+            ("HTTP", Strip, Some("http")),
+            ("already_snake", Strip, Some("already_snake")),
+            // separators become underscores, repeats collapse
+            ("user.get", Strip, Some("user_get")),
+            ("get-user (v2)", Strip, Some("get_user_v2")),
+            ("get--user__by..id", Strip, Some("get_user_by_id")),
+            ("__init__", Strip, Some("init")),
+            ("get user", Strip, Some("get_user1")),
+            // leading digits
+            ("1_getUser", Strip, Some("op_1_get_user")),
+            ("2fa.verify", Strip, Some("op_2fa_verify")),
+            ("42", Strip, Some("op_42")),
+            // keywords
+            ("type", Strip, Some("type_")),
+            ("self", Strip, Some("self_")),
+            ("Match", Strip, Some("match_")),
+            // collisions
+            ("listPets", Strip, Some("list_pets2")),
+            ("list-pets", Strip, Some("list_pets2")),
+            // non-ASCII
+            ("получитьПользователя", Strip, None),
+            ("getПользователя", Strip, Some("get")),
+            ("cafés", Strip, Some("cafs")),
+            ("café-menu", Strip, Some("caf_menu")),
+            ("получитьПользователя", Keep, Some("получить_пользователя")),
+            ("cafés", Keep, Some("cafés")),
+            ("1_получить", Keep, Some("op_1_получить")),
+            // nothing left
+            ("", Strip, None),
+            ("--", Strip, None),
+            ("(  )", Keep, None),
+        ];
+        for (operation_id, non_ascii, expected) in cases {
+            assert_eq!(
+                operation_id_to_rust_fn_name(operation_id, *non_ascii, &taken).as_deref(),
+                *expected,
+                "operationId '{operation_id}' ({non_ascii:?})"
+            );
+        }
+    }
+
+    #[test]
+    fn test_schema_to_rust_typename() {
+        let cases = [
+            ("Pet", "Pet"),
+            ("pet", "Pet"),
+            ("already_snake", "Already_snake"),
+            // separators join capitalized words
+            ("user.v1.Profile", "UserV1Profile"),
+            ("search-results", "SearchResults"),
+            ("Foo Bar", "FooBar"),
+            ("io.k8s.api.core.v1.Pod", "IoK8sApiCoreV1Pod"),
+            ("Pet[]", "Pet"),
+            ("Größe", "Größe"),
+            // leading digits
+            ("3DModel", "Schema3DModel"),
+            ("2fa-config", "Schema2faConfig"),
+            ("---", "Schema"),
+            // keywords
+            ("Self", "Self_"),
+            ("self", "Self_"),
+        ];
+        for (schema_name, expected) in cases {
+            assert_eq!(
+                schema_to_rust_typename(schema_name),
+                expected,
+                "schema name '{schema_name}'"
+            );
+        }
+    }
+
+    #[test]
+    fn test_property_to_rust_fieldname() {
+        let taken = vec!["name"];
+        let cases = [
+            ("id", "id"),
+            ("petId", "pet_id"),
+            ("PetId", "pet_id"),
+            ("pet-id", "pet_id"),
+            ("PET_ID", "pet_id"),
+            ("photoUrls", "photo_urls"),
+            ("HTTPServer", "http_server"),
+            ("_links", "links"),
+            ("@type", "type_"),
+            ("$ref", "ref_"),
+            ("2fa", "_2fa"),
+            ("größe", "größe"),
+            ("---", "field"),
+            // collisions
+            ("name", "name1"),
+            ("Name", "name1"),
+        ];
+        for (property_name, expected) in cases {
+            assert_eq!(
+                property_to_rust_fieldname(property_name, &taken),
+                expected,
+                "property name '{property_name}'"
+            );
+        }
+    }
+
+    #[test]
+    fn test_container_rename_case() {
+        // pairs of field name and property name
+        type Fields = &'static [(&'static str, &'static str)];
+        let cases: &[(Fields, Option<SerdeRenameCase>)] = &[
+            // fully camel case
+            (
+                &[("pet_id", "petId"), ("created_at", "createdAt")],
+                Some(SerdeRenameCase::CamelCase),
+            ),
+            // fields named like their property fit any convention
+            (
+                &[("pet_id", "petId"), ("name", "name")],
+                Some(SerdeRenameCase::CamelCase),
+            ),
+            (
+                &[("pet_id", "PetId"), ("name", "name")],
+                Some(SerdeRenameCase::PascalCase),
+            ),
+            (&[("pet_id", "pet-id")], Some(SerdeRenameCase::KebabCase)),
+            (
+                &[("pet_id", "PET_ID")],
+                Some(SerdeRenameCase::ScreamingSnakeCase),
+            ),
+            // serde drops the trailing underscore of `type_`
+            (
+                &[("type_", "type"), ("pet_id", "petId")],
+                Some(SerdeRenameCase::CamelCase),
+            ),
+            // nothing to rename
+            (&[], None),
+            (&[("pet_id", "pet_id"), ("name", "name")], None),
+            // mixed conventions
+            (&[("pet_id", "petId"), ("pet_name", "pet-name")], None),
+            (&[("pet_id", "petId"), ("name", "Name")], None),
+            (&[("http_server", "HTTPServer")], None),
+        ];
+        for (fields, expected) in cases {
+            assert_eq!(
+                container_rename_case(fields),
+                *expected,
+                "fields {fields:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_name_allocator() {
+        let mut taken = HashSet::new();
+        let mut names = NameAllocator::new();
+        taken.insert("Foo1".to_string());
+        for _ in 0..4 {
+            let name = names.uncollide(&taken, "Foo".to_string());
+            taken.insert(name);
+        }
+        assert!(
+            ["Foo", "Foo1", "Foo2", "Foo3", "Foo4"]
+                .iter()
+                .all(|n| taken.contains(*n))
+        );
+
+        // a name not inserted is handed out again, like by uncollide()
+        assert_eq!("Foo5", names.uncollide(&taken, "Foo".to_string()));
+        assert_eq!("Foo5", names.uncollide(&taken, "Foo".to_string()));
+        assert_eq!("Bar", names.uncollide(&taken, "Bar".to_string()));
+    }
+
+    #[test]
+    fn test_name_allocator_many_collisions() {
+        let start = std::time::Instant::now();
+        let mut taken = HashSet::new();
+        let mut names = NameAllocator::new();
+        let mut allocated = Vec::new();
+        for _ in 0..10_000 {
+            let name = names.uncollide(&taken, "InlineSchema".to_string());
+            taken.insert(name.clone());
+            allocated.push(name);
+        }
+        // generous, probing from the start each time takes ~50M lookups
+        assert!(start.elapsed() < std::time::Duration::from_secs(5));
+
+        assert_eq!("InlineSchema", allocated[0]);
+        for (n, name) in allocated.iter().enumerate().skip(1) {
+            assert_eq!(&format!("InlineSchema{n}"), name);
+        }
+    }
 }
@@ -1,4 +1,5 @@
-use std::{collections::HashMap, io, str::FromStr};
+use indexmap::IndexMap;
+use std::{io, str::FromStr};
 
 use json::JsonValue;
 
@@ -12,18 +13,193 @@ pub trait Spec: FromStr<Err = anyhow::Error> {
     type Operation: Operation<Self>;
     type RequestBody: RequestBody<Self>;
     type Response: Response<Self>;
+    type Header: Header<Self>;
 
     fn from_reader(r: impl io::Read) -> anyhow::Result<impl Spec>;
 
     fn components(&self) -> Option<Self::Components>;
 
+    /// The path items, by path. Path items given as `$ref`s are resolved
+    /// where the adapter supports it, and skipped otherwise (see
+    /// [Spec::unresolved_path_item_refs]).
     fn paths(&self) -> impl Iterator<Item = (String, Self::PathItem)>;
 
+    /// The path items given as `$ref`s that [Spec::paths] skips because
+    /// they cannot be resolved, like references to other documents, as
+    /// pairs of path and reference URI
+    fn unresolved_path_item_refs(&self) -> Vec<(String, String)>;
+
+    /// The operations declared under methods the adapter cannot represent
+    /// (like `query`), and which are therefore missing from the path
+    /// items, as pairs of path and method
+    fn unsupported_operations(&self) -> Vec<(String, String)>;
+
+    /// The schemas below `#/components/schemas`, by name. The order is
+    /// stable across runs, so that types are generated in the same order
+    /// each time: it is the order of declaration in the spec where the
+    /// underlying parser preserves it (OAS 3.0), and name order otherwise
+    /// (OAS 3.1, as the `oas3` crate keeps schemas in a `BTreeMap`).
     fn schemata_iter(&self) -> impl Iterator<Item = (String, RefOr<Self::Schema>)>;
+
+    /// The API maintainer's contact information from `info.contact`
+    fn contact(&self) -> Option<Contact>;
+
+    /// The API's license from `info.license`
+    fn license(&self) -> Option<License>;
+
+    /// The URL of the API's terms of service from `info.termsOfService`
+    fn terms_of_service(&self) -> Option<String>;
+
+    /// The security requirements applying to all operations not declaring
+    /// their own, from `security`
+    fn security(&self) -> Vec<SecurityRequirement>;
+
+    /// The URL template of the first entry in `servers`, like
+    /// `https://{env}.example.com/v1`
+    fn server_url(&self) -> Option<String>;
+
+    /// The variables of the first entry in `servers`, by name
+    fn server_variables(&self) -> IndexMap<String, ServerVariable>;
+
+    /// Checks that all `$ref` URIs in the spec point to existing objects.
+    /// By default, only the schema references found in the schemas
+    /// below `#/components/schemas` are checked; adapters override this
+    /// to check the references in parameters, request bodies and
+    /// responses as well.
+    fn validate_references(&self) -> Vec<ValidationError> {
+        let schema_uris: Vec<String> = self
+            .schemata_iter()
+            .map(|(name, _)| format!("#/components/schemas/{name}"))
+            .collect();
+        let mut errors = Vec::new();
+        let mut pending: Vec<(String, RefOr<Self::Schema>)> = self
+            .schemata_iter()
+            .map(|(name, schema)| (format!("#/components/schemas/{name}"), schema))
+            .collect();
+        while let Some((location, schema)) = pending.pop() {
+            match schema {
+                RefOr::Reference(r) => {
+                    if !schema_uris.iter().any(|uri| uri == r.uri()) {
+                        errors.push(ValidationError {
+                            location,
+                            reference: r.uri().to_string(),
+                        });
+                    }
+                }
+                RefOr::Object(schema) => {
+                    for (name, property) in schema.properties() {
+                        pending.push((format!("{location}/properties/{name}"), property));
+                    }
+                    for item in schema.items().unwrap_or_default() {
+                        pending.push((format!("{location}/items"), item));
+                    }
+                }
+            }
+        }
+        errors
+    }
+}
+
+/// A `$ref` URI in the spec that does not point to an existing object
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationError {
+    /// location of the `$ref` in the OAS document, e.g. `#/components/schemas/Pet/properties/owner`
+    pub location: String,
+    /// the unresolvable URI, e.g. `#/components/schemas/Owner`
+    pub reference: String,
+}
+
+impl std::fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "reference '{}' at {} does not resolve",
+            self.reference, self.location
+        )
+    }
+}
+
+impl std::error::Error for ValidationError {}
+
+/// A variable in a server's URL template, see
+/// https://spec.openapis.org/oas/v3.0.4.html#server-variable-object
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ServerVariable {
+    pub default: String,
+    /// The values the variable is restricted to. Empty if unrestricted.
+    pub enum_: Vec<String>,
+    pub description: Option<String>,
+}
+
+/// A link from a response to another operation, see
+/// https://spec.openapis.org/oas/v3.0.4.html#link-object
+#[derive(Debug, Clone, PartialEq)]
+pub struct Link {
+    /// The `operationId` of the linked operation. `None` for links using
+    /// `operationRef`.
+    pub operation_id: Option<String>,
+    /// The parameters to pass to the linked operation, by name (which may
+    /// be qualified with the location, like `path.id`). Values are either
+    /// constants or runtime expressions like `$response.body#/id`.
+    pub parameters: Vec<(String, JsonValue)>,
+}
+
+/// A security scheme an API may use, see
+/// https://spec.openapis.org/oas/v3.0.4.html#security-scheme-object
+#[derive(Debug, Clone, PartialEq)]
+pub enum SecurityScheme {
+    /// An API key passed in the header, query parameter or cookie `name`
+    ApiKey {
+        name: String,
+        location: ApiKeyLocation,
+    },
+    /// HTTP authentication, `scheme` is the authorization scheme as used in
+    /// the `Authorization` header (e.g. `basic` or `bearer`)
+    Http {
+        scheme: String,
+    },
+    OAuth2,
+    OpenIdConnect,
+}
+
+/// A security requirement, mapping the names of the security schemes that
+/// must all be satisfied to the scopes they require, see
+/// https://spec.openapis.org/oas/v3.0.4.html#security-requirement-object
+pub type SecurityRequirement = IndexMap<String, Vec<String>>;
+
+/// Where an API key is passed, see 'in' in
+/// https://spec.openapis.org/oas/v3.0.4.html#fixed-fields-23
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ApiKeyLocation {
+    Query,
+    Header,
+    Cookie,
+}
+
+/// Contact information of an API, see
+/// https://spec.openapis.org/oas/v3.0.4.html#contact-object
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Contact {
+    pub name: Option<String>,
+    pub url: Option<String>,
+    pub email: Option<String>,
+}
+
+/// License information of an API, see
+/// https://spec.openapis.org/oas/v3.0.4.html#license-object
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct License {
+    pub name: String,
+    pub url: Option<String>,
 }
 
 pub trait Components<S: Spec> {
+    /// see [Spec::schemata_iter] for the order of the schemas
     fn schemas(&self) -> impl Iterator<Item = (String, RefOr<S::Schema>)>;
+    // see 'headers' in https://spec.openapis.org/oas/v3.0.4.html#x4-7-7-1-fixed-fields
+    fn headers(&self) -> impl Iterator<Item = (String, RefOr<S::Header>)>;
+    // see 'securitySchemes' in https://spec.openapis.org/oas/v3.0.4.html#x4-7-7-1-fixed-fields
+    fn security_schemes(&self) -> impl Iterator<Item = (String, SecurityScheme)>;
 }
 
 /**
@@ -49,6 +225,16 @@ https://spec.openapis.org/oas/v3.0.4.html#data-type-format
 pub enum Format {
     Int32,
     Int64,
+    /// Not part of the OAS 3.0 format table, but commonly used for
+    /// unsigned 64 bit integers (e.g. by protobuf-JSON)
+    UInt64,
+    /// Like [Format::UInt64], the other fixed size integer formats are
+    /// not in the format table, but found in real specs
+    Int8,
+    Int16,
+    UInt8,
+    UInt16,
+    UInt32,
     Float,
     Double,
     Byte,
@@ -56,6 +242,36 @@ pub enum Format {
     Date,
     DateTime,
     Password,
+    /// Not in the format table either, but used by financial APIs for
+    /// amounts of money, on strings and numbers
+    Decimal,
+    /// A format we do not recognize, as given in the spec
+    Custom(String),
+}
+
+impl Format {
+    /// The format named by the value of a `format` keyword
+    pub fn parse(format: &str) -> Format {
+        match format {
+            "int8" => Format::Int8,
+            "int16" => Format::Int16,
+            "int32" => Format::Int32,
+            "int64" => Format::Int64,
+            "uint8" => Format::UInt8,
+            "uint16" => Format::UInt16,
+            "uint32" => Format::UInt32,
+            "uint64" => Format::UInt64,
+            "float" => Format::Float,
+            "double" => Format::Double,
+            "byte" => Format::Byte,
+            "binary" => Format::Binary,
+            "date" => Format::Date,
+            "date-time" => Format::DateTime,
+            "password" => Format::Password,
+            "decimal" => Format::Decimal,
+            _ => Format::Custom(format.to_string()),
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -83,6 +299,14 @@ pub trait Schema: Clone + std::fmt::Debug + std::hash::Hash + Eq + ByReference {
     */
     fn name(&self) -> Option<&str>;
 
+    /**
+    The JSON Schema dialect declared by the `$schema` keyword, or else by
+    the document's `jsonSchemaDialect`, if any. Only OAS 3.1 allows
+    declaring a dialect.
+    see https://spec.openapis.org/oas/v3.1.0#json-schema-keywords
+    */
+    fn schema_dialect(&self) -> Option<&str>;
+
     /**
      */
     fn type_(&self) -> Option<Vec<Type>>;
@@ -91,26 +315,84 @@ pub trait Schema: Clone + std::fmt::Debug + std::hash::Hash + Eq + ByReference {
     https://datatracker.ietf.org/doc/html/draft-wright-json-schema-validation-00#section-7
     */
     fn format(&self) -> Option<Format>;
+    /// Whether the schema's type is `integer`, which [Schema::type_]
+    /// reports as [Type::Number]
+    fn is_integer(&self) -> bool;
     /**
     https://datatracker.ietf.org/doc/html/draft-wright-json-schema-validation-00#section-6.1
      */
     fn title(&self) -> Option<&str>;
     fn description(&self) -> Option<&str>;
+    /** https://datatracker.ietf.org/doc/html/draft-wright-json-schema-validation-00#section-5.7 */
+    fn min_length(&self) -> Option<usize>;
+    /** https://datatracker.ietf.org/doc/html/draft-wright-json-schema-validation-00#section-5.6 */
+    fn max_length(&self) -> Option<usize>;
+    /** https://datatracker.ietf.org/doc/html/draft-wright-json-schema-validation-00#section-5.14 */
+    fn min_properties(&self) -> Option<usize>;
+    /** https://datatracker.ietf.org/doc/html/draft-wright-json-schema-validation-00#section-5.13 */
+    fn max_properties(&self) -> Option<usize>;
 
     fn required(&self) -> Option<Vec<&str>>;
 
-    fn all_of(&self) -> Option<Vec<impl Schema>>;
+    /** The schemas of `allOf`, which may be references, like a single
+    referenced schema wrapped only to annotate it */
+    fn all_of(&self) -> Option<Vec<RefOr<Self>>>;
     fn any_of(&self) -> Option<Vec<impl Schema>>;
     fn one_of(&self) -> Option<Vec<impl Schema>>;
     fn enum_(&self) -> Option<Vec<JsonValue>>;
+    /**
+    The single value allowed by the `const` keyword. Only OAS 3.1 supports it.
+    see https://json-schema.org/draft/2020-12/json-schema-validation#section-6.1.3
+    */
+    fn const_value(&self) -> Option<JsonValue>;
+    /**
+    The value assumed for a property if it is absent, from the `default` keyword.
+    see https://json-schema.org/draft/2020-12/json-schema-validation#section-9.2
+    */
+    fn default_value(&self) -> Option<JsonValue>;
+    /**
+    An example of a value of the schema, from the `example` keyword in OAS
+    3.0, or the first of the `examples` in OAS 3.1.
+    see https://spec.openapis.org/oas/v3.0.4.html#fixed-fields-20
+    */
+    fn example(&self) -> Option<JsonValue>;
+    /**
+    The value of a specification extension, by its full key (like `x-rust-type`).
+    see https://spec.openapis.org/oas/v3.0.4.html#specification-extensions
+    */
+    fn extension_value(&self, key: &str) -> Option<JsonValue>;
+    /**
+    Whether a property is only sent by the server, from the `readOnly` keyword.
+    see https://json-schema.org/draft/2020-12/json-schema-validation#section-9.4
+    */
+    fn read_only(&self) -> bool;
+    /**
+    Whether a property is only sent by the client, from the `writeOnly` keyword.
+    see https://json-schema.org/draft/2020-12/json-schema-validation#section-9.4
+    */
+    fn write_only(&self) -> bool;
+    /**
+    Whether `null` is a valid value, from the `nullable` keyword in OAS 3.0,
+    or a `null` type in OAS 3.1.
+    see https://spec.openapis.org/oas/v3.0.4.html#fixed-fields-20
+    */
+    fn nullable(&self) -> bool;
 
-    /** https://datatracker.ietf.org/doc/html/draft-wright-json-schema-validation-00#section-5.16 */
+    /**
+    https://datatracker.ietf.org/doc/html/draft-wright-json-schema-validation-00#section-5.16
+    The properties are in the order of declaration, so that the fields of
+    the generated structs are the same each time.
+    */
     // TODO: change 'impl Schema' to RefOr<impl Schema>
-    fn properties(&self) -> HashMap<String, RefOr<Self>>;
-    /** https://datatracker.ietf.org/doc/html/draft-wright-json-schema-validation-00#section-5.17 */
-    fn pattern_properties(&self) -> HashMap<String, RefOr<impl Schema>>;
+    fn properties(&self) -> IndexMap<String, RefOr<Self>>;
+    /**
+    Schemas for properties whose names match the regular expression used as key.
+    Only OAS 3.1 supports this keyword.
+    https://datatracker.ietf.org/doc/html/draft-wright-json-schema-validation-00#section-5.17
+    */
+    fn pattern_properties(&self) -> IndexMap<String, RefOr<Self>>;
     /** https://datatracker.ietf.org/doc/html/draft-wright-json-schema-validation-00#section-5.18 */
-    fn addtional_properties(&self) -> BooleanOrSchema<impl Schema>;
+    fn addtional_properties(&self) -> BooleanOrSchema<Self>;
 
     /**
     see 'items' following https://spec.openapis.org/oas/v3.0.4.html#x4-7-24-1-json-schema-keywords
@@ -133,8 +415,24 @@ pub trait Operation<S: Spec> {
     // see 'parameters' in  https://spec.openapis.org/oas/v3.0.4.html#x4-7-10-1-fixed-fields
     fn parameters(&self) -> impl Iterator<Item = RefOr<S::Parameter>>;
     fn operation_id(&self) -> Option<&str>;
+    /// see https://spec.openapis.org/oas/v3.0.4.html#operation-summary
+    fn summary(&self) -> Option<&str>;
+    /// see https://spec.openapis.org/oas/v3.0.4.html#operation-description
+    fn description(&self) -> Option<&str>;
+    /// see https://spec.openapis.org/oas/v3.0.4.html#operation-tags
+    fn tags(&self) -> impl Iterator<Item = &str>;
     fn request_body(&self) -> Option<RefOr<S::RequestBody>>;
     fn responses(&self) -> impl Iterator<Item = (StatusSpec, RefOr<S::Response>)>;
+    /// The alternative security requirements of the operation, see
+    /// https://spec.openapis.org/oas/v3.0.4.html#operation-security
+    /// `None` if the operation inherits [Spec::security], an empty `Vec` if
+    /// it requires no security at all.
+    fn security(&self) -> Option<Vec<SecurityRequirement>>;
+    /// The callbacks of the operation by name, each with the path items of
+    /// the requests the server makes, by the runtime expression yielding
+    /// their URL (like `{$request.body#/callbackUrl}`), see
+    /// https://spec.openapis.org/oas/v3.0.4.html#callback-object
+    fn callbacks(&self) -> impl Iterator<Item = (String, Vec<(String, S::PathItem)>)>;
 }
 
 #[derive(Clone, Copy, Debug, Hash, PartialEq)]
@@ -167,24 +465,47 @@ pub trait Parameter<S: Spec>: ByReference + Clone {
     /// see https://spec.openapis.org/oas/v3.0.4.html#parameter-in
     fn in_(&self) -> ParameterLocation;
     fn name(&self) -> &str;
+    /// see https://spec.openapis.org/oas/v3.0.4.html#parameter-required
+    fn required(&self) -> bool;
 
     /// `Parameter` must either contain a `schema` or a `content` field
     /// - so only either one of them can be `None`
     fn schema(&self) -> Option<RefOr<S::Schema>>;
 
-    fn content(&self) -> Option<HashMap<String, S::MediaType>>;
+    fn content(&self) -> Option<IndexMap<String, S::MediaType>>;
+
+    /// An example of the parameter's value, from `example`, or else the
+    /// first of `examples` with a value, see
+    /// https://spec.openapis.org/oas/v3.0.4.html#parameter-example
+    fn example(&self) -> Option<JsonValue>;
 }
 
 /// see https://spec.openapis.org/oas/v3.0.4.html#request-body-object
 #[allow(unused)]
 pub trait RequestBody<S: Spec>: ByReference + Clone {
-    fn content(&self) -> HashMap<String, S::MediaType>;
+    fn content(&self) -> IndexMap<String, S::MediaType>;
     fn required(&self) -> bool;
+    /// see https://spec.openapis.org/oas/v3.0.4.html#request-body-description
+    fn description(&self) -> Option<&str>;
 }
 
 // see https://spec.openapis.org/oas/v3.0.4.html#response-object
+#[allow(unused)]
 pub trait Response<S: Spec>: ByReference + Clone {
-    fn content(&self) -> HashMap<String, S::MediaType>;
+    fn content(&self) -> IndexMap<String, S::MediaType>;
+    fn headers(&self) -> IndexMap<String, RefOr<S::Header>>;
+    /// The links to other operations, by link name
+    fn links(&self) -> IndexMap<String, Link>;
+}
+
+/// see https://spec.openapis.org/oas/v3.0.4.html#header-object
+#[allow(unused)]
+pub trait Header<S: Spec>: ByReference + Clone {
+    fn required(&self) -> bool;
+
+    /// Like for [Parameter], a `Header` either contains a `schema`
+    /// or a `content` field. We only support `schema` for now.
+    fn schema(&self) -> Option<RefOr<S::Schema>>;
 }
 
 /// see https://spec.openapis.org/oas/v3.0.4.html#media-type-object
@@ -201,7 +522,8 @@ pub trait MediaType<S: Spec> {
 /// are meant to be infallible: Implemenations assume that the
 /// URIs they were constructed from can _always_ be resolved
 /// successfully.
-pub trait Reference<T>: PartialEq + Eq + Clone + std::fmt::Debug
+/// References are equal if their URIs are, and must hash accordingly
+pub trait Reference<T>: PartialEq + Eq + std::hash::Hash + Clone + std::fmt::Debug
 where
     T: ByReference,
 {
@@ -304,13 +626,20 @@ where
 
 impl<O> std::hash::Hash for RefOr<O>
 where
-    O: ByReference,
+    O: ByReference + std::hash::Hash,
 {
     fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
         core::mem::discriminant(self).hash(state);
+        match self {
+            RefOr::Reference(reference) => reference.hash(state),
+            RefOr::Object(object) => object.hash(state),
+        }
     }
 }
 
+/// A reference never equals an object, not even the one it resolves to:
+/// the type mapping keeps the types of named schemas by their object, and
+/// those of references to them (aliases) separately.
 impl<O: PartialEq> PartialEq for RefOr<O>
 where
     O: ByReference,
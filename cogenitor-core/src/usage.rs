@@ -0,0 +1,229 @@
+//! Analysis of the direction in which the schemas of an OAS document are
+//! used: in requests the client sends, in responses it receives, or both.
+//! Properties sent in one direction only (`readOnly` and `writeOnly`) only
+//! need special treatment in schemas used in the other direction.
+
+use std::collections::HashMap;
+
+use crate::types::{
+    BooleanOrSchema, Header, MediaType, Operation, Parameter, PathItem, RefOr, RequestBody,
+    Response, Schema, Spec,
+};
+
+/// The direction in which values of a schema are used
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Usage {
+    /// sent by the client only, like request bodies and parameters
+    Request,
+    /// received by the client only, like response bodies and headers
+    Response,
+    /// sent and received by the client
+    Both,
+}
+
+impl Usage {
+    /// Whether the client sends values of the schema
+    pub fn in_request(self) -> bool {
+        self != Usage::Response
+    }
+
+    /// Whether the client receives values of the schema
+    pub fn in_response(self) -> bool {
+        self != Usage::Request
+    }
+
+    fn merge(self, other: Usage) -> Usage {
+        if self == other { self } else { Usage::Both }
+    }
+}
+
+/// The usage of the schemas reachable from the operations' parameters,
+/// request bodies and responses, through properties, items and map values.
+/// Callbacks are requests the server sends, so their request bodies count
+/// as received and their responses as sent. Schemas reachable otherwise
+/// only (like via `allOf`), or not at all, are missing.
+pub(crate) fn schema_usage<S: Spec>(spec: &S) -> HashMap<RefOr<S::Schema>, Usage> {
+    let mut roots = Vec::new();
+    for (_, path_item) in spec.paths() {
+        for param in path_item.parameters() {
+            push_parameter_roots::<S>(&mut roots, &param.resolve_fully());
+        }
+        for (_, path_op) in path_item.operations_iter() {
+            push_operation_roots::<S>(&mut roots, &path_op, Usage::Request, Usage::Response);
+            for (_, callback_items) in path_op.callbacks() {
+                for (_, callback_item) in callback_items {
+                    for (_, callback_op) in callback_item.operations_iter() {
+                        push_operation_roots::<S>(
+                            &mut roots,
+                            &callback_op,
+                            Usage::Response,
+                            Usage::Request,
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    let mut usage: HashMap<RefOr<S::Schema>, Usage> = HashMap::new();
+    while let Some((schema, schema_usage)) = roots.pop() {
+        let schema = schema.resolve();
+        let known = usage.get(&schema).copied();
+        let merged = known.map_or(schema_usage, |known| known.merge(schema_usage));
+        if known == Some(merged) {
+            continue;
+        }
+        usage.insert(schema.clone(), merged);
+
+        let Some(object) = schema.as_object() else {
+            continue;
+        };
+        let children = object
+            .properties()
+            .into_values()
+            .chain(object.pattern_properties().into_values())
+            .chain(object.items().unwrap_or_default());
+        for child in children {
+            roots.push((child, merged));
+        }
+        if let BooleanOrSchema::Schema(values) = object.addtional_properties() {
+            roots.push((RefOr::Object(values), merged));
+        }
+    }
+    usage
+}
+
+fn push_operation_roots<S: Spec>(
+    roots: &mut Vec<(RefOr<S::Schema>, Usage)>,
+    operation: &S::Operation,
+    sent: Usage,
+    received: Usage,
+) {
+    if sent == Usage::Request {
+        for param in operation.parameters() {
+            push_parameter_roots::<S>(roots, &param.resolve_fully());
+        }
+    }
+    if let Some(request_body) = operation.request_body() {
+        for media_type in request_body.resolve_fully().content().values() {
+            roots.extend(media_type.schema().map(|schema| (schema, sent)));
+        }
+    }
+    for (_, response) in operation.responses() {
+        let response = response.resolve_fully();
+        for media_type in response.content().values() {
+            roots.extend(media_type.schema().map(|schema| (schema, received)));
+        }
+        for header in response.headers().values() {
+            roots.extend(
+                header
+                    .resolve_fully()
+                    .schema()
+                    .map(|schema| (schema, received)),
+            );
+        }
+    }
+}
+
+fn push_parameter_roots<S: Spec>(roots: &mut Vec<(RefOr<S::Schema>, Usage)>, param: &S::Parameter) {
+    roots.extend(param.schema().map(|schema| (schema, Usage::Request)));
+    for media_type in param.content().unwrap_or_default().values() {
+        roots.extend(media_type.schema().map(|schema| (schema, Usage::Request)));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+    use crate::adapters::oas30::OAS30Spec;
+
+    #[test]
+    fn test_schema_usage() {
+        let oas = r"
+openapi: 3.0.0
+info:
+    title: Schemas used in requests, responses and both
+    version: v1
+paths:
+    /pets:
+        post:
+            requestBody:
+                content:
+                    application/json:
+                        schema:
+                            $ref: '#/components/schemas/NewPet'
+            responses:
+                '201':
+                    description: the pet created
+                    content:
+                        application/json:
+                            schema:
+                                $ref: '#/components/schemas/Pet'
+    /pets/{id}:
+        get:
+            parameters:
+                -   {name: id, in: path, required: true, schema: {$ref: '#/components/schemas/PetId'}}
+            responses:
+                '200':
+                    description: the pet
+                    content:
+                        application/json:
+                            schema:
+                                $ref: '#/components/schemas/Pet'
+components:
+    schemas:
+        NewPet:
+            type: object
+            properties:
+                name:
+                    type: string
+                tags:
+                    type: array
+                    items:
+                        $ref: '#/components/schemas/Tag'
+        Pet:
+            type: object
+            properties:
+                id:
+                    $ref: '#/components/schemas/PetId'
+                tags:
+                    type: array
+                    items:
+                        $ref: '#/components/schemas/Tag'
+                owner:
+                    $ref: '#/components/schemas/Owner'
+        PetId:
+            type: integer
+        Tag:
+            type: string
+        Owner:
+            type: object
+            additionalProperties:
+                $ref: '#/components/schemas/Contact'
+        Contact:
+            type: string
+        Unused:
+            type: string
+";
+        let spec = OAS30Spec::from_str(oas).unwrap();
+        let usage = schema_usage(&spec);
+        let usage_of = |name: &str| {
+            let (_, schema) = spec.schemata_iter().find(|(n, _)| n == name).unwrap();
+            usage.get(&schema).copied()
+        };
+
+        assert_eq!(Some(Usage::Request), usage_of("NewPet"));
+        assert_eq!(Some(Usage::Response), usage_of("Pet"));
+        assert_eq!(Some(Usage::Response), usage_of("Owner"));
+        assert_eq!(Some(Usage::Response), usage_of("Contact"));
+        assert_eq!(Some(Usage::Both), usage_of("Tag"));
+        assert_eq!(Some(Usage::Both), usage_of("PetId"));
+        assert_eq!(None, usage_of("Unused"));
+
+        assert!(Usage::Both.in_request() && Usage::Both.in_response());
+        assert!(!Usage::Response.in_request());
+        assert!(!Usage::Request.in_response());
+    }
+}
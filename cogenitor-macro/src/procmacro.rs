@@ -34,6 +34,16 @@ impl ExprInto<String> for Expr {
     }
 }
 
+impl ExprInto<Vec<String>> for Expr {
+    fn expr_into(&self) -> Option<Vec<String>> {
+        if let Expr::Array(array) = self {
+            array.elems.iter().map(|elem| elem.expr_into()).collect()
+        } else {
+            None
+        }
+    }
+}
+
 impl ExprInto<bool> for Expr {
     fn expr_into(&self) -> Option<bool> {
         if let Expr::Lit(ExprLit {
@@ -48,6 +58,20 @@ impl ExprInto<bool> for Expr {
     }
 }
 
+impl ExprInto<usize> for Expr {
+    fn expr_into(&self) -> Option<usize> {
+        if let Expr::Lit(ExprLit {
+            attrs: _,
+            lit: syn::Lit::Int(lit_int),
+        }) = self
+        {
+            lit_int.base10_parse().ok()
+        } else {
+            None
+        }
+    }
+}
+
 impl Parse for MacroConfig {
     fn parse(input: ParseStream) -> syn::Result<Self> {
         let mut macro_config = Self::default();
@@ -66,6 +90,26 @@ impl Parse for MacroConfig {
                         "'path' expects a string literal as argument",
                     ))?);
                 }
+                "additional_paths" => {
+                    config.additional_paths =
+                        name_value.value.expr_into().ok_or(syn::Error::new(
+                            name_value.span(),
+                            "'additional_paths' expects an array of string literals as argument",
+                        ))?;
+                }
+                "url" => {
+                    config.url = Some(name_value.value.expr_into().ok_or(syn::Error::new(
+                        name_value.span(),
+                        "'url' expects a string literal as argument",
+                    ))?);
+                }
+                "spec_cache_ttl" => {
+                    let seconds: usize = name_value.value.expr_into().ok_or(syn::Error::new(
+                        name_value.span(),
+                        "'spec_cache_ttl' expects an integer literal of seconds as argument",
+                    ))?;
+                    config.spec_cache_ttl = Some(std::time::Duration::from_secs(seconds as u64));
+                }
                 "module_name" => {
                     config.module_name =
                         Some(name_value.value.clone().expr_into().ok_or(syn::Error::new(
@@ -73,6 +117,292 @@ impl Parse for MacroConfig {
                             "'module_name' expects a string literal as argument",
                         ))?);
                 }
+                "params_struct_threshold" => {
+                    config.params_struct_threshold =
+                        Some(name_value.value.expr_into().ok_or(syn::Error::new(
+                            name_value.span(),
+                            "'params_struct_threshold' expects an integer literal as argument",
+                        ))?);
+                }
+                "visibility" => {
+                    let visibility: String =
+                        name_value.value.expr_into().ok_or(syn::Error::new(
+                            name_value.span(),
+                            "'visibility' expects a string literal as argument",
+                        ))?;
+                    config.visibility = visibility
+                        .parse()
+                        .map_err(|e: String| syn::Error::new(name_value.span(), e))?;
+                }
+                "stringified_integers" => {
+                    config.stringified_integers =
+                        name_value.value.expr_into().ok_or(syn::Error::new(
+                            name_value.span(),
+                            "'stringified_integers' expects a boolean literal as argument",
+                        ))?;
+                }
+                "ignore_schema_titles" => {
+                    config.ignore_schema_titles =
+                        name_value.value.expr_into().ok_or(syn::Error::new(
+                            name_value.span(),
+                            "'ignore_schema_titles' expects a boolean literal as argument",
+                        ))?;
+                }
+                "generate_test_macro" => {
+                    config.generate_test_macro =
+                        name_value.value.expr_into().ok_or(syn::Error::new(
+                            name_value.span(),
+                            "'generate_test_macro' expects a boolean literal as argument",
+                        ))?;
+                }
+                "emit_examples" => {
+                    config.emit_examples = name_value.value.expr_into().ok_or(syn::Error::new(
+                        name_value.span(),
+                        "'emit_examples' expects a boolean literal as argument",
+                    ))?;
+                }
+                "roundtrip_tests" => {
+                    config.roundtrip_tests =
+                        name_value.value.expr_into().ok_or(syn::Error::new(
+                            name_value.span(),
+                            "'roundtrip_tests' expects a boolean literal as argument",
+                        ))?;
+                }
+                "fixed_size_bytes_limit" => {
+                    config.fixed_size_bytes_limit =
+                        Some(name_value.value.expr_into().ok_or(syn::Error::new(
+                            name_value.span(),
+                            "'fixed_size_bytes_limit' expects an integer literal as argument",
+                        ))?);
+                }
+                "max_operations" => {
+                    config.max_operations =
+                        Some(name_value.value.expr_into().ok_or(syn::Error::new(
+                            name_value.span(),
+                            "'max_operations' expects an integer literal as argument",
+                        ))?);
+                }
+                "max_schemas" => {
+                    config.max_schemas =
+                        Some(name_value.value.expr_into().ok_or(syn::Error::new(
+                            name_value.span(),
+                            "'max_schemas' expects an integer literal as argument",
+                        ))?);
+                }
+                "fail_on_limit" => {
+                    config.fail_on_limit = name_value.value.expr_into().ok_or(syn::Error::new(
+                        name_value.span(),
+                        "'fail_on_limit' expects a boolean literal as argument",
+                    ))?;
+                }
+                "generate_id_newtypes" => {
+                    config.generate_id_newtypes =
+                        name_value.value.expr_into().ok_or(syn::Error::new(
+                            name_value.span(),
+                            "'generate_id_newtypes' expects a boolean literal as argument",
+                        ))?;
+                }
+                "validation_newtypes" => {
+                    config.validation_newtypes =
+                        name_value.value.expr_into().ok_or(syn::Error::new(
+                            name_value.span(),
+                            "'validation_newtypes' expects a boolean literal as argument",
+                        ))?;
+                }
+                "operation_id_names" => {
+                    config.operation_id_names =
+                        name_value.value.expr_into().ok_or(syn::Error::new(
+                            name_value.span(),
+                            "'operation_id_names' expects a boolean literal as argument",
+                        ))?;
+                }
+                "legacy_path_names" => {
+                    config.legacy_path_names =
+                        name_value.value.expr_into().ok_or(syn::Error::new(
+                            name_value.span(),
+                            "'legacy_path_names' expects a boolean literal as argument",
+                        ))?;
+                }
+                "preferred_media_types" => {
+                    config.preferred_media_types =
+                        name_value.value.expr_into().ok_or(syn::Error::new(
+                            name_value.span(),
+                            "'preferred_media_types' expects an array of string literals as argument",
+                        ))?;
+                }
+                "json_value_conversions" => {
+                    config.json_value_conversions =
+                        name_value.value.expr_into().ok_or(syn::Error::new(
+                            name_value.span(),
+                            "'json_value_conversions' expects a boolean literal as argument",
+                        ))?;
+                }
+                "typed_formats" => {
+                    config.typed_formats = name_value.value.expr_into().ok_or(syn::Error::new(
+                        name_value.span(),
+                        "'typed_formats' expects a boolean literal as argument",
+                    ))?;
+                }
+                "strict_path_params" => {
+                    config.strict_path_params =
+                        name_value.value.expr_into().ok_or(syn::Error::new(
+                            name_value.span(),
+                            "'strict_path_params' expects a boolean literal as argument",
+                        ))?;
+                }
+                "strict_param_content" => {
+                    config.strict_param_content =
+                        name_value.value.expr_into().ok_or(syn::Error::new(
+                            name_value.span(),
+                            "'strict_param_content' expects a boolean literal as argument",
+                        ))?;
+                }
+                "strict_operations" => {
+                    config.strict_operations =
+                        name_value.value.expr_into().ok_or(syn::Error::new(
+                            name_value.span(),
+                            "'strict_operations' expects a boolean literal as argument",
+                        ))?;
+                }
+                "strict_duplicate_paths" => {
+                    config.strict_duplicate_paths =
+                        name_value.value.expr_into().ok_or(syn::Error::new(
+                            name_value.span(),
+                            "'strict_duplicate_paths' expects a boolean literal as argument",
+                        ))?;
+                }
+                "debug_secrets" => {
+                    config.debug_secrets = name_value.value.expr_into().ok_or(syn::Error::new(
+                        name_value.span(),
+                        "'debug_secrets' expects a boolean literal as argument",
+                    ))?;
+                }
+                "response_decoders" => {
+                    config.response_decoders =
+                        name_value.value.expr_into().ok_or(syn::Error::new(
+                            name_value.span(),
+                            "'response_decoders' expects a boolean literal as argument",
+                        ))?;
+                }
+                "emit_tracing" => {
+                    config.emit_tracing = name_value.value.expr_into().ok_or(syn::Error::new(
+                        name_value.span(),
+                        "'emit_tracing' expects a boolean literal as argument",
+                    ))?;
+                }
+                "tracing_feature" => {
+                    config.tracing_feature =
+                        Some(name_value.value.expr_into().ok_or(syn::Error::new(
+                            name_value.span(),
+                            "'tracing_feature' expects a string literal as argument",
+                        ))?);
+                }
+                "double_option_for_patch" => {
+                    config.double_option_for_patch =
+                        name_value.value.expr_into().ok_or(syn::Error::new(
+                            name_value.span(),
+                            "'double_option_for_patch' expects a boolean literal as argument",
+                        ))?;
+                }
+                "sse_events" => {
+                    config.sse_events = name_value.value.expr_into().ok_or(syn::Error::new(
+                        name_value.span(),
+                        "'sse_events' expects a boolean literal as argument",
+                    ))?;
+                }
+                "media_type_strategy" => {
+                    let strategy: String = name_value.value.expr_into().ok_or(syn::Error::new(
+                        name_value.span(),
+                        "'media_type_strategy' expects a string literal as argument",
+                    ))?;
+                    config.media_type_strategy = strategy
+                        .parse()
+                        .map_err(|e: String| syn::Error::new(name_value.span(), e))?;
+                }
+                "missing_responses" => {
+                    let missing_responses: String =
+                        name_value.value.expr_into().ok_or(syn::Error::new(
+                            name_value.span(),
+                            "'missing_responses' expects a string literal as argument",
+                        ))?;
+                    config.missing_responses = missing_responses
+                        .parse()
+                        .map_err(|e: String| syn::Error::new(name_value.span(), e))?;
+                }
+                "operation_type_aliases" => {
+                    let operation_type_aliases: String =
+                        name_value.value.expr_into().ok_or(syn::Error::new(
+                            name_value.span(),
+                            "'operation_type_aliases' expects a string literal as argument",
+                        ))?;
+                    config.operation_type_aliases = operation_type_aliases
+                        .parse()
+                        .map_err(|e: String| syn::Error::new(name_value.span(), e))?;
+                }
+                "server_framework" => {
+                    let server_framework: String =
+                        name_value.value.expr_into().ok_or(syn::Error::new(
+                            name_value.span(),
+                            "'server_framework' expects a string literal as argument",
+                        ))?;
+                    config.server_framework = server_framework
+                        .parse()
+                        .map_err(|e: String| syn::Error::new(name_value.span(), e))?;
+                }
+                "non_ascii_names" => {
+                    let non_ascii_names: String =
+                        name_value.value.expr_into().ok_or(syn::Error::new(
+                            name_value.span(),
+                            "'non_ascii_names' expects a string literal as argument",
+                        ))?;
+                    config.non_ascii_names = non_ascii_names
+                        .parse()
+                        .map_err(|e: String| syn::Error::new(name_value.span(), e))?;
+                }
+                "callback_parsers" => {
+                    config.callback_parsers =
+                        name_value.value.expr_into().ok_or(syn::Error::new(
+                            name_value.span(),
+                            "'callback_parsers' expects a boolean literal as argument",
+                        ))?;
+                }
+                "link_helpers" => {
+                    config.link_helpers = name_value.value.expr_into().ok_or(syn::Error::new(
+                        name_value.span(),
+                        "'link_helpers' expects a boolean literal as argument",
+                    ))?;
+                }
+                "emit_exists_helpers" => {
+                    config.emit_exists_helpers =
+                        name_value.value.expr_into().ok_or(syn::Error::new(
+                            name_value.span(),
+                            "'emit_exists_helpers' expects a boolean literal as argument",
+                        ))?;
+                }
+                "env_constructor" => {
+                    config.env_constructor =
+                        name_value.value.expr_into().ok_or(syn::Error::new(
+                            name_value.span(),
+                            "'env_constructor' expects a boolean literal as argument",
+                        ))?;
+                }
+                "env_prefix" => {
+                    config.env_prefix =
+                        Some(name_value.value.expr_into().ok_or(syn::Error::new(
+                            name_value.span(),
+                            "'env_prefix' expects a string literal as argument",
+                        ))?);
+                }
+                "single_value_enums" => {
+                    let single_value_enums: String =
+                        name_value.value.expr_into().ok_or(syn::Error::new(
+                            name_value.span(),
+                            "'single_value_enums' expects a string literal as argument",
+                        ))?;
+                    config.single_value_enums = single_value_enums
+                        .parse()
+                        .map_err(|e: String| syn::Error::new(name_value.span(), e))?;
+                }
                 _ => {
                     return Err(syn::Error::new(
                         name_value.span(),
@@ -88,8 +418,12 @@ impl Parse for MacroConfig {
 
 // Main macro implementation
 pub(super) fn generate_macro_code(config: ApiConfig) -> TokenStream {
-    match cogenitor_core::generate_mod(&config) {
-        Ok(ts) => ts,
+    match cogenitor_core::generate_mod_with_stats(&config) {
+        Ok((ts, stats)) => {
+            let source = config.path.as_deref().or(config.url.as_deref());
+            log::info!("generated code for {}: {stats}", source.unwrap_or_default());
+            ts
+        }
         Err(e) => match e.downcast_ref::<syn::Error>() {
             Some(e) => e.to_compile_error(),
             None => {
@@ -104,7 +438,12 @@ pub(crate) fn parse_config(input: TokenStream) -> syn::Result<ApiConfig> {
     // Handle single argument case
     let config;
     if let Ok(path) = syn::parse2::<LitStr>(input.clone()) {
-        config = ApiConfig::new_from_path(path.value());
+        let path = path.value();
+        config = if path.starts_with("http://") || path.starts_with("https://") {
+            ApiConfig::new_from_url(path)
+        } else {
+            ApiConfig::new_from_path(path)
+        };
     } else {
         // Handle key-value pairs case
         let macro_config: MacroConfig = syn::parse2(input)?;
@@ -125,6 +464,24 @@ pub fn test_parse_config() {
         config
     );
 
+    let macro_args = quote::quote!("https://example.com/openapi.yaml");
+    let config = parse_config(macro_args).unwrap();
+    assert_eq!(
+        ApiConfig::new_from_url("https://example.com/openapi.yaml".to_string()),
+        config
+    );
+
+    let macro_args = quote::quote!(url = "https://example.com/openapi.yaml");
+    let config = parse_config(macro_args).unwrap();
+    assert_eq!(
+        Some("https://example.com/openapi.yaml".to_string()),
+        config.url
+    );
+
+    let macro_args = quote::quote!(url = "https://example.com/openapi.yaml", spec_cache_ttl = 0);
+    let config = parse_config(macro_args).unwrap();
+    assert_eq!(Some(std::time::Duration::ZERO), config.spec_cache_ttl);
+
     let macro_args = quote::quote!(path = "/path/to/openapi.yaml");
     let config = parse_config(macro_args).unwrap();
     assert_eq!(
@@ -135,6 +492,255 @@ pub fn test_parse_config() {
         config
     );
 
+    let macro_args = quote::quote!(
+        path = "/path/to/openapi.yaml",
+        additional_paths = ["/path/to/pets.yaml", "/path/to/orders.yaml"]
+    );
+    let config = parse_config(macro_args).unwrap();
+    assert_eq!(
+        vec!["/path/to/pets.yaml", "/path/to/orders.yaml"],
+        config.additional_paths
+    );
+
+    let macro_args = quote::quote!(path = "/path/to/openapi.yaml", params_struct_threshold = 8);
+    let config = parse_config(macro_args).unwrap();
+    assert_eq!(
+        ApiConfig {
+            path: Some("/path/to/openapi.yaml".to_string()),
+            params_struct_threshold: Some(8),
+            ..Default::default()
+        },
+        config
+    );
+
+    let macro_args = quote::quote!(path = "/path/to/openapi.yaml", visibility = "crate");
+    let config = parse_config(macro_args).unwrap();
+    assert_eq!(cogenitor_core::Visibility::Crate, config.visibility);
+
+    let macro_args = quote::quote!(path = "/path/to/openapi.yaml", visibility = "super");
+    parse_config(macro_args).unwrap_err();
+
+    let macro_args = quote::quote!(path = "/path/to/openapi.yaml", stringified_integers = true);
+    let config = parse_config(macro_args).unwrap();
+    assert!(config.stringified_integers);
+
+    let macro_args = quote::quote!(path = "/path/to/openapi.yaml", ignore_schema_titles = true);
+    let config = parse_config(macro_args).unwrap();
+    assert!(config.ignore_schema_titles);
+
+    let macro_args = quote::quote!(path = "/path/to/openapi.yaml", generate_test_macro = true);
+    let config = parse_config(macro_args).unwrap();
+    assert!(config.generate_test_macro);
+
+    let macro_args = quote::quote!(path = "/path/to/openapi.yaml", emit_examples = true);
+    let config = parse_config(macro_args).unwrap();
+    assert!(config.emit_examples);
+
+    let macro_args = quote::quote!(path = "/path/to/openapi.yaml", roundtrip_tests = true);
+    let config = parse_config(macro_args).unwrap();
+    assert!(config.roundtrip_tests);
+
+    let macro_args = quote::quote!(path = "/path/to/openapi.yaml", fixed_size_bytes_limit = 64);
+    let config = parse_config(macro_args).unwrap();
+    assert_eq!(Some(64), config.fixed_size_bytes_limit);
+
+    let macro_args = quote::quote!(
+        path = "/path/to/openapi.yaml",
+        preferred_media_types = ["application/json", "application/xml"],
+        media_type_strategy = "prefer_single"
+    );
+    let config = parse_config(macro_args).unwrap();
+    assert_eq!(
+        vec!["application/json", "application/xml"],
+        config.preferred_media_types
+    );
+    assert_eq!(
+        cogenitor_core::MediaTypeStrategy::PreferSingle,
+        config.media_type_strategy
+    );
+
+    let macro_args = quote::quote!(
+        path = "/path/to/openapi.yaml",
+        json_value_conversions = true
+    );
+    let config = parse_config(macro_args).unwrap();
+    assert!(config.json_value_conversions);
+
+    let macro_args = quote::quote!(path = "/path/to/openapi.yaml", typed_formats = true);
+    let config = parse_config(macro_args).unwrap();
+    assert!(config.typed_formats);
+
+    let macro_args = quote::quote!(path = "/path/to/openapi.yaml", strict_path_params = true);
+    let config = parse_config(macro_args).unwrap();
+    assert!(config.strict_path_params);
+
+    let macro_args = quote::quote!(path = "/path/to/openapi.yaml", strict_param_content = true);
+    let config = parse_config(macro_args).unwrap();
+    assert!(config.strict_param_content);
+
+    let macro_args = quote::quote!(path = "/path/to/openapi.yaml", strict_operations = true);
+    let config = parse_config(macro_args).unwrap();
+    assert!(config.strict_operations);
+
+    let macro_args = quote::quote!(
+        path = "/path/to/openapi.yaml",
+        strict_duplicate_paths = true
+    );
+    let config = parse_config(macro_args).unwrap();
+    assert!(config.strict_duplicate_paths);
+
+    let macro_args = quote::quote!(path = "/path/to/openapi.yaml", debug_secrets = true);
+    let config = parse_config(macro_args).unwrap();
+    assert!(config.debug_secrets);
+
+    let macro_args = quote::quote!(path = "/path/to/openapi.yaml", response_decoders = true);
+    let config = parse_config(macro_args).unwrap();
+    assert!(config.response_decoders);
+
+    let macro_args = quote::quote!(
+        path = "/path/to/openapi.yaml",
+        response_decoders = true,
+        emit_tracing = true,
+        tracing_feature = "tracing"
+    );
+    let config = parse_config(macro_args).unwrap();
+    assert!(config.emit_tracing);
+    assert_eq!(Some("tracing".to_string()), config.tracing_feature);
+
+    let macro_args = quote::quote!(
+        path = "/path/to/openapi.yaml",
+        double_option_for_patch = true
+    );
+    let config = parse_config(macro_args).unwrap();
+    assert!(config.double_option_for_patch);
+
+    let macro_args = quote::quote!(path = "/path/to/openapi.yaml", sse_events = true);
+    let config = parse_config(macro_args).unwrap();
+    assert!(config.sse_events);
+
+    let macro_args = quote::quote!(
+        path = "/path/to/openapi.yaml",
+        max_operations = 1000,
+        max_schemas = 500,
+        fail_on_limit = true
+    );
+    let config = parse_config(macro_args).unwrap();
+    assert_eq!(Some(1000), config.max_operations);
+    assert_eq!(Some(500), config.max_schemas);
+    assert!(config.fail_on_limit);
+
+    let macro_args = quote::quote!(
+        path = "/path/to/openapi.yaml",
+        media_type_strategy = "collapse_identical"
+    );
+    let config = parse_config(macro_args).unwrap();
+    assert_eq!(
+        cogenitor_core::MediaTypeStrategy::CollapseIdentical,
+        config.media_type_strategy
+    );
+
+    let macro_args = quote::quote!(path = "/path/to/openapi.yaml", generate_id_newtypes = true);
+    let config = parse_config(macro_args).unwrap();
+    assert!(config.generate_id_newtypes);
+
+    let macro_args = quote::quote!(path = "/path/to/openapi.yaml", validation_newtypes = true);
+    let config = parse_config(macro_args).unwrap();
+    assert!(config.validation_newtypes);
+
+    let macro_args = quote::quote!(path = "/path/to/openapi.yaml", operation_id_names = true);
+    let config = parse_config(macro_args).unwrap();
+    assert!(config.operation_id_names);
+
+    let macro_args = quote::quote!(path = "/path/to/openapi.yaml", legacy_path_names = true);
+    let config = parse_config(macro_args).unwrap();
+    assert!(config.legacy_path_names);
+
+    let macro_args = quote::quote!(
+        path = "/path/to/openapi.yaml",
+        missing_responses = "raw_response"
+    );
+    let config = parse_config(macro_args).unwrap();
+    assert_eq!(
+        cogenitor_core::MissingResponses::RawResponse,
+        config.missing_responses
+    );
+
+    let macro_args = quote::quote!(path = "/path/to/openapi.yaml", missing_responses = "none");
+    parse_config(macro_args).unwrap_err();
+
+    let macro_args = quote::quote!(path = "/path/to/openapi.yaml", media_type_strategy = "all");
+    parse_config(macro_args).unwrap_err();
+
+    let macro_args = quote::quote!(
+        path = "/path/to/openapi.yaml",
+        single_value_enums = "string"
+    );
+    let config = parse_config(macro_args).unwrap();
+    assert_eq!(
+        cogenitor_core::SingleValueEnums::String,
+        config.single_value_enums
+    );
+
+    let macro_args = quote::quote!(path = "/path/to/openapi.yaml", single_value_enums = "enum");
+    parse_config(macro_args).unwrap_err();
+
+    let macro_args = quote::quote!(
+        path = "/path/to/openapi.yaml",
+        operation_type_aliases = "modules"
+    );
+    let config = parse_config(macro_args).unwrap();
+    assert_eq!(
+        cogenitor_core::OperationTypeAliases::Modules,
+        config.operation_type_aliases
+    );
+
+    let macro_args = quote::quote!(
+        path = "/path/to/openapi.yaml",
+        operation_type_aliases = "aliases"
+    );
+    parse_config(macro_args).unwrap_err();
+
+    let macro_args = quote::quote!(path = "/path/to/openapi.yaml", server_framework = "axum");
+    let config = parse_config(macro_args).unwrap();
+    assert_eq!(
+        cogenitor_core::ServerFramework::Axum,
+        config.server_framework
+    );
+
+    let macro_args = quote::quote!(path = "/path/to/openapi.yaml", server_framework = "warp");
+    parse_config(macro_args).unwrap_err();
+
+    let macro_args = quote::quote!(path = "/path/to/openapi.yaml", non_ascii_names = "keep");
+    let config = parse_config(macro_args).unwrap();
+    assert_eq!(
+        cogenitor_core::NonAsciiHandling::Keep,
+        config.non_ascii_names
+    );
+
+    let macro_args = quote::quote!(path = "/path/to/openapi.yaml", non_ascii_names = "ascii");
+    parse_config(macro_args).unwrap_err();
+
+    let macro_args = quote::quote!(path = "/path/to/openapi.yaml", callback_parsers = true);
+    let config = parse_config(macro_args).unwrap();
+    assert!(config.callback_parsers);
+
+    let macro_args = quote::quote!(path = "/path/to/openapi.yaml", link_helpers = true);
+    let config = parse_config(macro_args).unwrap();
+    assert!(config.link_helpers);
+
+    let macro_args = quote::quote!(path = "/path/to/openapi.yaml", emit_exists_helpers = true);
+    let config = parse_config(macro_args).unwrap();
+    assert!(config.emit_exists_helpers);
+
+    let macro_args = quote::quote!(
+        path = "/path/to/openapi.yaml",
+        env_constructor = true,
+        env_prefix = "petstore"
+    );
+    let config = parse_config(macro_args).unwrap();
+    assert!(config.env_constructor);
+    assert_eq!(Some("petstore".to_string()), config.env_prefix);
+
     // error on unknown params
     let macro_args = quote::quote!(xxx = "/path/to/openapi.yaml");
     parse_config(macro_args).unwrap_err();
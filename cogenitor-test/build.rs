@@ -28,10 +28,11 @@ fn build() -> anyhow::Result<()> {
         "cargo::warning=output_path: {} ",
         output_path.to_owned().to_string_lossy()
     );
-    cogenitor::generate_file(
-        &ApiConfig::new_from_path(input_path.as_os_str().to_string_lossy().into_owned()),
-        &output_path,
-    )?;
+    let config = ApiConfig {
+        generate_test_macro: true,
+        ..ApiConfig::new_from_path(input_path.as_os_str().to_string_lossy().into_owned())
+    };
+    cogenitor::generate_file_if_changed(&config, &output_path)?;
 
     Ok(())
 }
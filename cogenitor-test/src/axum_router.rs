@@ -0,0 +1,197 @@
+#![allow(dead_code)]
+
+cogenitor::generate_api!(
+    path = "test-data/petstore.yaml",
+    module_name = "axum_router_api",
+    server_framework = "axum"
+);
+
+// status ranges, rate limits and binary content are served as well
+cogenitor::generate_api!(
+    path = "test-data/response-decoders.yaml",
+    module_name = "axum_router_responses_api",
+    server_framework = "axum"
+);
+
+#[cfg(test)]
+mod tests {
+    use super::axum_router_api::*;
+
+    /// Serves the pets `1` (available) and `2` (sold)
+    #[derive(Clone)]
+    struct StubApi;
+
+    fn pet(id: i64, status: &str) -> Pet {
+        Pet {
+            tags: None,
+            status: Some(status.to_string()),
+            photo_urls: vec![],
+            category: None,
+            id: Some(id),
+            name: format!("pet {id}"),
+        }
+    }
+
+    impl Client for StubApi {
+        fn pet_put(&self, _body: PetPutContent) -> Result<PetPutOk200, PetPutError> {
+            unimplemented!()
+        }
+        fn pet_post(&self, _body: PetPostContent) -> Result<PetPostOk200, PetPostError> {
+            unimplemented!()
+        }
+        fn pet_find_by_status_get(
+            &self,
+            status: String,
+        ) -> Result<PetFindByStatusGetOk200, PetFindByStatusGetError> {
+            let pets = [pet(1, "available"), pet(2, "sold")]
+                .into_iter()
+                .filter(|pet| pet.status.as_ref() == Some(&status))
+                .collect();
+            Ok(PetFindByStatusGetOk200::ApplicationJson(pets))
+        }
+        fn pet_find_by_tags_get(
+            &self,
+            _tags: Vec<String>,
+        ) -> Result<PetFindByTagsGetOk200, PetFindByTagsGetError> {
+            unimplemented!()
+        }
+        fn pet_pet_id_get(&self, pet_id: i64) -> Result<PetPetIdGetOk200, PetPetIdGetError> {
+            match pet_id {
+                1 => Ok(PetPetIdGetOk200::ApplicationJson(pet(1, "available"))),
+                2 => Ok(PetPetIdGetOk200::ApplicationJson(pet(2, "sold"))),
+                _ => Err(PetPetIdGetError::NotFound404(())),
+            }
+        }
+        fn pet_pet_id_post(
+            &self,
+            _pet_id: i64,
+            _name: String,
+            _status: String,
+        ) -> Result<PetPetIdPostOk200, PetPetIdPostError> {
+            unimplemented!()
+        }
+        fn pet_pet_id_delete(
+            &self,
+            _api_key: String,
+            _pet_id: i64,
+        ) -> Result<(), PetPetIdDeleteError> {
+            unimplemented!()
+        }
+        fn pet_pet_id_upload_image_post(
+            &self,
+            _pet_id: i64,
+            _additional_metadata: String,
+            _body: Vec<u8>,
+        ) -> Result<ApiResponse, PetPetIdUploadImagePostError> {
+            unimplemented!()
+        }
+        fn store_inventory_get(
+            &self,
+        ) -> Result<std::collections::HashMap<String, i32>, StoreInventoryGetError> {
+            unimplemented!()
+        }
+        fn store_order_post(
+            &self,
+            _body: StoreOrderPostContent,
+        ) -> Result<Order, StoreOrderPostError> {
+            unimplemented!()
+        }
+        fn store_order_order_id_get(
+            &self,
+            _order_id: i64,
+        ) -> Result<StoreOrderOrderIdGetOk200, StoreOrderOrderIdGetError> {
+            unimplemented!()
+        }
+        fn store_order_order_id_delete(
+            &self,
+            _order_id: i64,
+        ) -> Result<(), StoreOrderOrderIdDeleteError> {
+            unimplemented!()
+        }
+        fn user_post(&self, _body: UserPostContent) -> Result<UserPostOk200, UserPostError> {
+            unimplemented!()
+        }
+        fn user_create_with_list_post(
+            &self,
+            _body: Vec<User>,
+        ) -> Result<UserCreateWithListPostOk200, UserCreateWithListPostError> {
+            unimplemented!()
+        }
+        fn user_login_get(
+            &self,
+            _username: String,
+            _password: String,
+        ) -> Result<UserLoginGetOk200, UserLoginGetError> {
+            unimplemented!()
+        }
+        fn user_logout_get(&self) -> Result<(), UserLogoutGetError> {
+            unimplemented!()
+        }
+        fn user_username_get(
+            &self,
+            _username: String,
+        ) -> Result<UserUsernameGetOk200, UserUsernameGetError> {
+            unimplemented!()
+        }
+        fn user_username_put(
+            &self,
+            _username: String,
+            _body: UserUsernamePutContent,
+        ) -> Result<(), UserUsernamePutError> {
+            unimplemented!()
+        }
+        fn user_username_delete(&self, _username: String) -> Result<(), UserUsernameDeleteError> {
+            unimplemented!()
+        }
+    }
+
+    /// Serves the router on a port of its own, returning the base URL
+    fn serve(router: axum::Router) -> String {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        listener.set_nonblocking(true).unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        std::thread::spawn(move || {
+            let runtime = tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .unwrap();
+            runtime.block_on(async {
+                let listener = tokio::net::TcpListener::from_std(listener).unwrap();
+                axum::serve(listener, router).await.unwrap();
+            });
+        });
+        base_url
+    }
+
+    #[test]
+    pub fn test_serve_petstore() {
+        let base_url = serve(router(StubApi));
+
+        let response = reqwest::blocking::get(format!("{base_url}/pet/2")).unwrap();
+        assert_eq!(200, response.status().as_u16());
+        assert_eq!(pet(2, "sold"), response.json::<Pet>().unwrap());
+
+        let response = reqwest::blocking::get(format!("{base_url}/pet/3")).unwrap();
+        assert_eq!(404, response.status().as_u16());
+
+        // malformed parameters are rejected before reaching the API
+        let response = reqwest::blocking::get(format!("{base_url}/pet/two")).unwrap();
+        assert_eq!(400, response.status().as_u16());
+
+        let response =
+            reqwest::blocking::get(format!("{base_url}/pet/findByStatus?status=available"))
+                .unwrap();
+        assert_eq!(200, response.status().as_u16());
+        assert_eq!(
+            vec![pet(1, "available")],
+            response.json::<Vec<Pet>>().unwrap()
+        );
+
+        // string parameters are taken as they are, even if they are JSON
+        let response =
+            reqwest::blocking::get(format!("{base_url}/pet/findByStatus?status=%22sold%22"))
+                .unwrap();
+        assert_eq!(200, response.status().as_u16());
+        assert_eq!(Vec::<Pet>::new(), response.json::<Vec<Pet>>().unwrap());
+    }
+}
@@ -0,0 +1,65 @@
+#![allow(dead_code)]
+
+cogenitor::generate_api!(
+    path = "test-data/binary-download.yaml",
+    module_name = "binary_download_api",
+    operation_id_names = true
+);
+
+#[cfg(test)]
+mod tests {
+    use super::binary_download_api::*;
+    use httpmock::prelude::*;
+
+    /// Downloads reports from a server, leaving the variant writing into a
+    /// writer to the trait's default
+    struct HttpClient {
+        base_url: String,
+    }
+
+    impl Client for HttpClient {
+        fn get_report(&self, report_id: i64) -> Result<Vec<u8>, GetReportError> {
+            let url = format!("{}/reports/{report_id}", self.base_url);
+            let response =
+                reqwest::blocking::get(url).map_err(|e| GetReportError::OtherError(Box::new(e)))?;
+            match response.status().as_u16() {
+                200 => response
+                    .bytes()
+                    .map(|b| b.to_vec())
+                    .map_err(|e| GetReportError::OtherError(Box::new(e))),
+                404 => Err(GetReportError::NotFound404(())),
+                status => panic!("unexpected status {status}"),
+            }
+        }
+        fn get_report_to(&self, _report_id: i64) -> Result<String, GetReportToError> {
+            unimplemented!()
+        }
+    }
+
+    #[test]
+    pub fn test_download_to_writer() {
+        let server = MockServer::start();
+        let get_report = server.mock(|when, then| {
+            when.method(GET).path("/reports/42");
+            then.status(200)
+                .header("content-type", "application/pdf")
+                .body(b"%PDF-1.7");
+        });
+        let client = HttpClient {
+            base_url: server.base_url(),
+        };
+
+        // `get_report_to` is taken by the other operation
+        let mut writer = Vec::new();
+        let written = client.get_report_to1(42, &mut writer);
+        get_report.assert();
+        assert!(matches!(written, Ok(8)));
+        assert_eq!(b"%PDF-1.7".to_vec(), writer);
+
+        // errors are returned without writing
+        let mut writer = Vec::new();
+        let written = client.get_report_to1(43, &mut writer);
+        assert!(matches!(written, Err(GetReportError::NotFound404(()))));
+        assert!(writer.is_empty());
+    }
+}
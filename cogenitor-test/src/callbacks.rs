@@ -0,0 +1,33 @@
+#![allow(dead_code)]
+
+cogenitor::generate_api!(
+    path = "test-data/callbacks.yaml",
+    module_name = "callbacks_api",
+    callback_parsers = true
+);
+
+#[cfg(test)]
+mod tests {
+    use super::callbacks_api::{OrderStatusChanged, callbacks};
+
+    #[test]
+    pub fn test_parse_callback_round_trip() {
+        let status_changed = OrderStatusChanged {
+            order_id: 42,
+            status: "shipped".to_string(),
+        };
+        let body = serde_json::to_vec(&status_changed).unwrap();
+        assert_eq!(
+            status_changed,
+            callbacks::parse_order_status_changed(&body).unwrap()
+        );
+    }
+
+    #[test]
+    pub fn test_parse_callback_error() {
+        let error =
+            callbacks::parse_order_status_changed(br#"{"orderId": "42", "status": "shipped"}"#)
+                .unwrap_err();
+        assert_eq!("orderId", error.path);
+    }
+}
@@ -0,0 +1,85 @@
+//! The response decoders instrumented with `tracing` spans and events.
+//! `tracing` is a dev-dependency of this crate only, so is the module.
+
+#[cfg(test)]
+cogenitor::generate_api!(
+    path = "test-data/response-decoders.yaml",
+    module_name = "traced_api",
+    response_decoders = true,
+    emit_tracing = true
+);
+
+#[cfg(test)]
+mod tests {
+    use super::traced_api::ClientImpl;
+    use std::io::Write;
+    use std::sync::{Arc, Mutex};
+
+    /// Collects everything written to it, so the trace can be inspected
+    #[derive(Clone, Default)]
+    struct TraceBuffer(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for TraceBuffer {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn traced<T>(f: impl FnOnce() -> T) -> (T, String) {
+        let buffer = TraceBuffer::default();
+        let writer = buffer.clone();
+        let subscriber = tracing_subscriber::fmt()
+            .with_max_level(tracing::Level::TRACE)
+            .with_ansi(false)
+            .with_writer(move || writer.clone())
+            .finish();
+        let result = tracing::subscriber::with_default(subscriber, f);
+        let trace = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+        (result, trace)
+    }
+
+    fn response(status: u16, body: &[u8]) -> http::Response<Vec<u8>> {
+        http::Response::builder()
+            .status(status)
+            .header(http::header::CONTENT_TYPE, "application/json")
+            .body(body.to_vec())
+            .unwrap()
+    }
+
+    #[test]
+    pub fn test_traced_success() {
+        let (pet, trace) = traced(|| {
+            ClientImpl::decode_pets_pet_id_get_response(response(200, br#"{"name": "Doggy"}"#))
+        });
+        assert!(pet.is_ok());
+        assert!(
+            trace.contains(
+                r#"operation{operation="pets_pet_id_get" method="GET" path="/pets/{petId}"}"#
+            ),
+            "{trace}"
+        );
+        assert!(trace.contains("cogenitor::client"), "{trace}");
+        assert!(trace.contains("status=200"), "{trace}");
+        assert!(!trace.contains("operation failed"), "{trace}");
+    }
+
+    #[test]
+    pub fn test_traced_error() {
+        let (e, trace) = traced(|| ClientImpl::decode_pets_pet_id_get_response(response(404, b"")));
+        assert!(e.is_err());
+        assert!(trace.contains("status=404"), "{trace}");
+        assert!(
+            trace.contains(r#"operation failed error="NotFound404""#),
+            "{trace}"
+        );
+
+        let (e, trace) =
+            traced(|| ClientImpl::decode_pets_pet_id_get_response(response(200, b"not json")));
+        assert!(e.is_err());
+        assert!(trace.contains(r#"error="OtherError""#), "{trace}");
+    }
+}
@@ -0,0 +1,132 @@
+#![allow(dead_code)]
+
+cogenitor::generate_api!(
+    path = "test-data/security.yaml",
+    module_name = "env_secured_api",
+    env_constructor = true,
+    env_prefix = "secured"
+);
+
+cogenitor::generate_api!(
+    path = "test-data/servers.yaml",
+    module_name = "env_plain_api",
+    env_constructor = true
+);
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Mutex, MutexGuard};
+
+    use super::env_plain_api;
+    use super::env_secured_api::{ApiKeyLocation, ClientImpl, EnvConfigError, SecurityMiddleware};
+
+    /// The environment is shared by all tests, so tests modifying it must
+    /// not run concurrently
+    static ENV_LOCK: Mutex<()> = Mutex::new(());
+
+    const VARS: [&str; 4] = [
+        "SECURED_BASE_URL",
+        "SECURED_API_KEY",
+        "SECURED_BEARER_TOKEN",
+        "ENV_PLAIN_API_BASE_URL",
+    ];
+
+    /// Locks the environment and sets the given variables, unsetting the
+    /// other ones the tests use
+    fn set_env(vars: &[(&str, &str)]) -> MutexGuard<'static, ()> {
+        let guard = ENV_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        for name in VARS {
+            // SAFETY: other threads touching the environment hold ENV_LOCK
+            unsafe { std::env::remove_var(name) };
+        }
+        for (name, value) in vars {
+            // SAFETY: see above
+            unsafe { std::env::set_var(name, value) };
+        }
+        guard
+    }
+
+    #[test]
+    pub fn test_from_env_api_key() {
+        let _guard = set_env(&[
+            ("SECURED_BASE_URL", "https://example.com"),
+            ("SECURED_API_KEY", "s3cr3t"),
+            ("SECURED_BEARER_TOKEN", "abc"),
+        ]);
+        let client = ClientImpl::from_env().unwrap();
+        assert_eq!("https://example.com", client.base_url);
+        assert_eq!(
+            Some(SecurityMiddleware::ApiKey {
+                name: "X-API-Key".to_string(),
+                key: "s3cr3t".to_string(),
+                location: ApiKeyLocation::Header,
+            }),
+            client.auth
+        );
+    }
+
+    #[test]
+    pub fn test_from_env_bearer_token() {
+        let _guard = set_env(&[
+            ("SECURED_BASE_URL", "https://example.com"),
+            ("SECURED_BEARER_TOKEN", "abc"),
+        ]);
+        let client = ClientImpl::from_env().unwrap();
+        assert_eq!(
+            Some(SecurityMiddleware::BearerToken("abc".to_string())),
+            client.auth
+        );
+    }
+
+    #[test]
+    pub fn test_from_env_missing() {
+        let guard = set_env(&[("SECURED_API_KEY", "")]);
+        let error = ClientImpl::from_env().unwrap_err();
+        assert_eq!(
+            EnvConfigError {
+                missing: vec!["SECURED_BASE_URL"],
+                missing_credentials: vec!["SECURED_API_KEY", "SECURED_BEARER_TOKEN"],
+            },
+            error
+        );
+        assert_eq!(
+            "missing environment variables: SECURED_BASE_URL; \
+             missing credentials, set one of: SECURED_API_KEY, SECURED_BEARER_TOKEN",
+            error.to_string()
+        );
+
+        drop(guard);
+
+        let guard = set_env(&[("SECURED_API_KEY", "s3cr3t")]);
+        let error = ClientImpl::from_env().unwrap_err();
+        assert_eq!(
+            "missing environment variables: SECURED_BASE_URL",
+            error.to_string()
+        );
+
+        drop(guard);
+
+        let _guard = set_env(&[("SECURED_BASE_URL", "https://example.com")]);
+        let error = ClientImpl::from_env().unwrap_err();
+        assert!(error.missing.is_empty());
+        assert_eq!(
+            "missing credentials, set one of: SECURED_API_KEY, SECURED_BEARER_TOKEN",
+            error.to_string()
+        );
+    }
+
+    #[test]
+    pub fn test_from_env_without_security() {
+        let guard = set_env(&[]);
+        let error = env_plain_api::ClientImpl::from_env().unwrap_err();
+        assert_eq!(
+            "missing environment variables: ENV_PLAIN_API_BASE_URL",
+            error.to_string()
+        );
+        drop(guard);
+
+        let _guard = set_env(&[("ENV_PLAIN_API_BASE_URL", "https://dev.example.com/v1")]);
+        let client = env_plain_api::ClientImpl::from_env().unwrap();
+        assert_eq!("https://dev.example.com/v1", client.base_url);
+    }
+}
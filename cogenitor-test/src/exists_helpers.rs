@@ -0,0 +1,156 @@
+#![allow(dead_code)]
+
+cogenitor::generate_api!(
+    path = "test-data/petstore.yaml",
+    module_name = "exists_helpers_api",
+    emit_exists_helpers = true
+);
+
+#[cfg(test)]
+mod tests {
+    use super::exists_helpers_api::*;
+    use httpmock::prelude::*;
+
+    /// Fetches pets from a server, implementing the checked operation only
+    struct HttpClient {
+        base_url: String,
+    }
+
+    impl Client for HttpClient {
+        fn pet_put(&self, _body: PetPutContent) -> Result<PetPutOk200, PetPutError> {
+            unimplemented!()
+        }
+        fn pet_post(&self, _body: PetPostContent) -> Result<PetPostOk200, PetPostError> {
+            unimplemented!()
+        }
+        fn pet_find_by_status_get(
+            &self,
+            _status: String,
+        ) -> Result<PetFindByStatusGetOk200, PetFindByStatusGetError> {
+            unimplemented!()
+        }
+        fn pet_find_by_tags_get(
+            &self,
+            _tags: Vec<String>,
+        ) -> Result<PetFindByTagsGetOk200, PetFindByTagsGetError> {
+            unimplemented!()
+        }
+        fn pet_pet_id_get(&self, pet_id: i64) -> Result<PetPetIdGetOk200, PetPetIdGetError> {
+            let url = format!("{}/pet/{pet_id}", self.base_url);
+            let response = reqwest::blocking::get(url)
+                .map_err(|e| PetPetIdGetError::OtherError(Box::new(e)))?;
+            match response.status().as_u16() {
+                200 => response
+                    .json()
+                    .map(PetPetIdGetOk200::ApplicationJson)
+                    .map_err(|e| PetPetIdGetError::OtherError(Box::new(e))),
+                404 => Err(PetPetIdGetError::NotFound404(())),
+                status => panic!("unexpected status {status}"),
+            }
+        }
+        fn pet_pet_id_post(
+            &self,
+            _pet_id: i64,
+            _name: String,
+            _status: String,
+        ) -> Result<PetPetIdPostOk200, PetPetIdPostError> {
+            unimplemented!()
+        }
+        fn pet_pet_id_delete(
+            &self,
+            _api_key: String,
+            _pet_id: i64,
+        ) -> Result<(), PetPetIdDeleteError> {
+            unimplemented!()
+        }
+        fn pet_pet_id_upload_image_post(
+            &self,
+            _pet_id: i64,
+            _additional_metadata: String,
+            _body: Vec<u8>,
+        ) -> Result<ApiResponse, PetPetIdUploadImagePostError> {
+            unimplemented!()
+        }
+        fn store_inventory_get(
+            &self,
+        ) -> Result<std::collections::HashMap<String, i32>, StoreInventoryGetError> {
+            unimplemented!()
+        }
+        fn store_order_post(
+            &self,
+            _body: StoreOrderPostContent,
+        ) -> Result<Order, StoreOrderPostError> {
+            unimplemented!()
+        }
+        fn store_order_order_id_get(
+            &self,
+            _order_id: i64,
+        ) -> Result<StoreOrderOrderIdGetOk200, StoreOrderOrderIdGetError> {
+            unimplemented!()
+        }
+        fn store_order_order_id_delete(
+            &self,
+            _order_id: i64,
+        ) -> Result<(), StoreOrderOrderIdDeleteError> {
+            unimplemented!()
+        }
+        fn user_post(&self, _body: UserPostContent) -> Result<UserPostOk200, UserPostError> {
+            unimplemented!()
+        }
+        fn user_create_with_list_post(
+            &self,
+            _body: Vec<User>,
+        ) -> Result<UserCreateWithListPostOk200, UserCreateWithListPostError> {
+            unimplemented!()
+        }
+        fn user_login_get(
+            &self,
+            _username: String,
+            _password: String,
+        ) -> Result<UserLoginGetOk200, UserLoginGetError> {
+            unimplemented!()
+        }
+        fn user_logout_get(&self) -> Result<(), UserLogoutGetError> {
+            unimplemented!()
+        }
+        fn user_username_get(
+            &self,
+            _username: String,
+        ) -> Result<UserUsernameGetOk200, UserUsernameGetError> {
+            unimplemented!()
+        }
+        fn user_username_put(
+            &self,
+            _username: String,
+            _body: UserUsernamePutContent,
+        ) -> Result<(), UserUsernamePutError> {
+            unimplemented!()
+        }
+        fn user_username_delete(&self, _username: String) -> Result<(), UserUsernameDeleteError> {
+            unimplemented!()
+        }
+    }
+
+    #[test]
+    pub fn test_exists() {
+        let server = MockServer::start();
+        let existing = server.mock(|when, then| {
+            when.method(GET).path("/pet/1");
+            then.status(200)
+                .header("content-type", "application/json")
+                .body(r#"{"id": 1, "name": "doggie", "photoUrls": []}"#);
+        });
+        let missing = server.mock(|when, then| {
+            when.method(GET).path("/pet/2");
+            then.status(404);
+        });
+        let client = HttpClient {
+            base_url: server.base_url(),
+        };
+
+        assert!(matches!(client.pet_pet_id_exists(1), Ok(true)));
+        assert!(matches!(client.pet_pet_id_exists(2), Ok(false)));
+        existing.assert();
+        missing.assert();
+    }
+}
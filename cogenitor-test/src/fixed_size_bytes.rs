@@ -0,0 +1,60 @@
+#![allow(dead_code)]
+
+cogenitor::generate_api!(
+    path = "test-data/fixed-size-bytes.yaml",
+    module_name = "fixed_size_bytes_api",
+    fixed_size_bytes_limit = 64
+);
+
+#[cfg(test)]
+mod tests {
+    use super::fixed_size_bytes_api::File;
+    use serde_json::json;
+
+    #[test]
+    pub fn test_fixed_size_bytes_roundtrip() {
+        // 48 bytes are beyond the array sizes serde supports natively
+        let file = File {
+            md5: [1; 16],
+            sha384: Some([2; 48]),
+            certificate: Some(vec![4; 75]),
+            signature: Some(vec![3; 128]),
+            name: Some("sixteen chars...".to_string()),
+        };
+
+        // byte strings are base64 encoded without padding, binary strings
+        // are not encoded
+        let value = serde_json::to_value(&file).unwrap();
+        assert_eq!(json!("AQEBAQEBAQEBAQEBAQEBAQ"), value["md5"]);
+        assert_eq!(json!("AgIC".repeat(16)), value["sha384"]);
+        assert_eq!(json!("BAQE".repeat(25)), value["certificate"]);
+        assert_eq!(json!(vec![3; 128]), value["signature"]);
+
+        let parsed: File = serde_json::from_value(value).unwrap();
+        assert_eq!(file, parsed);
+
+        // padding is accepted, and absent optional arrays are None
+        let parsed: File =
+            serde_json::from_value(json!({ "md5": "AQEBAQEBAQEBAQEBAQEBAQ==" })).unwrap();
+        assert_eq!([1; 16], parsed.md5);
+        assert_eq!(None, parsed.sha384);
+    }
+
+    #[test]
+    pub fn test_fixed_size_bytes_wrong_length() {
+        // 15 and 17 bytes
+        serde_json::from_value::<File>(json!({ "md5": "AQEBAQEBAQEBAQEBAQEB" })).unwrap_err();
+        serde_json::from_value::<File>(json!({ "md5": "AQEBAQEBAQEBAQEBAQEBAQE" })).unwrap_err();
+        serde_json::from_value::<File>(
+            json!({ "md5": "AQEBAQEBAQEBAQEBAQEBAQ", "sha384": "AgIC" }),
+        )
+        .unwrap_err();
+    }
+
+    #[test]
+    pub fn test_fixed_size_bytes_invalid_base64() {
+        serde_json::from_value::<File>(json!({ "md5": vec![1; 16] })).unwrap_err();
+        serde_json::from_value::<File>(json!({ "md5": "AQEBAQEBAQEBAQEBAQEBA!" })).unwrap_err();
+        serde_json::from_value::<File>(json!({ "md5": "AQEBAQEBAQEBAQEBAQEBAQE=B" })).unwrap_err();
+    }
+}
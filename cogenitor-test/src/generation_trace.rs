@@ -0,0 +1,107 @@
+//! Records what the generator logs while generating the petstore API,
+//! which documents the steps of the generation pipeline. The generator
+//! logs via the `log` crate, whose records are forwarded to `tracing`.
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+    use std::sync::{Arc, Mutex};
+
+    /// Collects everything written to it, so the trace can be inspected
+    #[derive(Clone, Default)]
+    struct TraceBuffer(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for TraceBuffer {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    pub fn test_generation_trace() {
+        // another test may have installed the forwarding already
+        let _ = tracing_log::LogTracer::init();
+        let buffer = TraceBuffer::default();
+        let writer = buffer.clone();
+        let subscriber = tracing_subscriber::fmt()
+            .with_max_level(tracing::Level::TRACE)
+            .with_ansi(false)
+            .with_writer(move || writer.clone())
+            .finish();
+
+        let config = cogenitor::ApiConfig {
+            path: Some(concat!(env!("CARGO_MANIFEST_DIR"), "/../test-data/petstore.yaml").into()),
+            ..Default::default()
+        };
+        tracing::subscriber::with_default(subscriber, || {
+            cogenitor::generate_token_stream(&config).unwrap();
+        });
+
+        let trace = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+        assert!(
+            trace.contains("types stubs from schemas section constructed"),
+            "{trace}"
+        );
+        for schema in ["Order", "Category", "User", "Tag", "Pet", "ApiResponse"] {
+            assert!(
+                trace.contains(&format!("creating type for schema '{schema}'")),
+                "{trace}"
+            );
+            assert!(
+                trace.contains(&format!("parsing schema Some(\"{schema}\") as Struct")),
+                "{trace}"
+            );
+        }
+        assert!(
+            trace.contains("creating method for GET /pet/{petId}"),
+            "{trace}"
+        );
+        assert!(
+            trace.contains("the generated ClientImpl::url_for() requires the 'url' crate"),
+            "{trace}"
+        );
+        assert!(trace.contains("token stream:"), "{trace}");
+    }
+
+    #[test]
+    pub fn test_unrecognized_format_warning() {
+        let _ = tracing_log::LogTracer::init();
+        let buffer = TraceBuffer::default();
+        let writer = buffer.clone();
+        let subscriber = tracing_subscriber::fmt()
+            .with_max_level(tracing::Level::WARN)
+            .with_ansi(false)
+            .with_writer(move || writer.clone())
+            .finish();
+
+        let config = cogenitor::ApiConfig {
+            path: Some(
+                concat!(
+                    env!("CARGO_MANIFEST_DIR"),
+                    "/../test-data/integer-formats.yaml"
+                )
+                .into(),
+            ),
+            ..Default::default()
+        };
+        tracing::subscriber::with_default(subscriber, || {
+            cogenitor::generate_token_stream(&config).unwrap();
+        });
+
+        let trace = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+        assert!(
+            trace.contains("unrecognized integer format 'bogus', mapping to i64"),
+            "{trace}"
+        );
+        // decimals are recognized, but only mapped to a decimal type with
+        // 'typed_formats'
+        assert!(
+            trace.contains("mapping decimal number to f64, which may lose precision"),
+            "{trace}"
+        );
+    }
+}
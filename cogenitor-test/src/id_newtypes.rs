@@ -0,0 +1,31 @@
+#![allow(dead_code)]
+
+cogenitor::generate_api!(
+    path = "test-data/petstore.yaml",
+    module_name = "id_newtypes_api",
+    generate_id_newtypes = true
+);
+
+#[cfg(test)]
+mod tests {
+    use super::id_newtypes_api::{Order, PetId};
+    use serde_json::json;
+
+    #[test]
+    pub fn test_id_newtype_roundtrip() {
+        let order: Order = serde_json::from_value(json!({"id": 10, "petId": 198772})).unwrap();
+        assert_eq!(Some(PetId(198772)), order.pet_id);
+        assert_eq!(
+            json!(198772),
+            serde_json::to_value(order.pet_id.unwrap()).unwrap()
+        );
+    }
+
+    #[test]
+    pub fn test_id_newtype_conversions() {
+        let pet_id = PetId::from(42);
+        assert_eq!(42, i64::from(pet_id));
+        assert_eq!("42", pet_id.to_string());
+        assert!(PetId(1) < PetId(2));
+    }
+}
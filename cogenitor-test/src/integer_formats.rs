@@ -0,0 +1,28 @@
+#![allow(dead_code)]
+
+cogenitor::generate_api!(
+    path = "test-data/integer-formats.yaml",
+    module_name = "integer_formats_api"
+);
+
+#[cfg(test)]
+mod tests {
+    use super::integer_formats_api::Counters;
+
+    #[test]
+    pub fn test_integer_formats() {
+        // the field types are checked by the compiler
+        let counters = Counters {
+            id: u64::MAX,
+            level: i8::MIN,
+            small: u16::MAX,
+            port: u32::MAX,
+            // unrecognized integer formats do not round to a float
+            legacy: i64::MAX,
+            ratio: 0.5f64,
+        };
+        let json = serde_json::to_value(&counters).unwrap();
+        assert_eq!(u64::MAX, json["id"].as_u64().unwrap());
+        assert_eq!(i64::MAX, json["legacy"].as_i64().unwrap());
+    }
+}
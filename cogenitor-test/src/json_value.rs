@@ -0,0 +1,51 @@
+#![allow(dead_code)]
+
+cogenitor::generate_api!(
+    path = "test-data/petstore.yaml",
+    module_name = "json_value_api",
+    json_value_conversions = true
+);
+
+#[cfg(test)]
+mod tests {
+    use super::json_value_api::{Category, Pet, decode_with_path};
+    use serde_json::json;
+
+    #[test]
+    pub fn test_json_value_conversions() {
+        let pet = Pet {
+            id: Some(1),
+            name: "Doggy".to_string(),
+            category: Some(Category {
+                id: Some(1000),
+                name: Some("Dogs".to_string()),
+            }),
+            status: None,
+            photo_urls: vec![],
+            tags: None,
+        };
+
+        let value = serde_json::Value::from(pet);
+        assert_eq!(json!("Dogs"), value["category"]["name"]);
+        let pet = Pet::try_from(value).unwrap();
+        assert_eq!("Doggy", pet.name);
+
+        Pet::try_from(json!({ "name": 42 })).unwrap_err();
+    }
+
+    #[test]
+    pub fn test_decode_with_path() {
+        let json = br#"{"name": "Doggy", "photoUrls": [], "category": {"id": "one"}}"#;
+        let e = decode_with_path::<Pet>(json).unwrap_err();
+        assert_eq!("category.id", e.path);
+
+        let json = br#"{"name": "Doggy", "photoUrls": ["a", 2]}"#;
+        let e = decode_with_path::<Pet>(json).unwrap_err();
+        assert_eq!("photoUrls[1]", e.path);
+        assert!(e.to_string().starts_with("photoUrls[1]: invalid type"));
+
+        let json = br#"{"name": "Doggy", "photoUrls": []}"#;
+        let pet = decode_with_path::<Pet>(json).unwrap();
+        assert_eq!("Doggy", pet.name);
+    }
+}
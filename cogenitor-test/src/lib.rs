@@ -1,2 +1,30 @@
+mod axum_router;
+mod binary_download;
+mod callbacks;
+mod client_tracing;
+mod env_constructor;
+mod exists_helpers;
+mod fixed_size_bytes;
+mod generation_trace;
+mod id_newtypes;
+mod integer_formats;
+mod json_value;
+mod links;
+mod map_size;
+mod missing_responses;
+mod nullable;
+mod operation_type_aliases;
+mod parameter_examples;
 mod petstore_file;
 mod petstore_macro;
+mod read_write_only;
+mod response_decoders;
+mod roundtrip_tests;
+mod schema_names;
+mod security;
+mod security_override;
+mod servers;
+mod single_value_enums;
+mod sse;
+mod stringified_integers;
+mod typed_formats;
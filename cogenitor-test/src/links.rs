@@ -0,0 +1,68 @@
+#![allow(dead_code)]
+
+cogenitor::generate_api!(
+    path = "test-data/links.yaml",
+    module_name = "links_api",
+    link_helpers = true
+);
+
+#[cfg(test)]
+mod tests {
+    use super::links_api::*;
+    use httpmock::prelude::*;
+
+    /// Fetches orders from a server, implementing the linked operation only
+    struct HttpClient {
+        base_url: String,
+    }
+
+    impl Client for HttpClient {
+        fn store_order_post(&self, _body: Order) -> Result<Order, StoreOrderPostError> {
+            unimplemented!()
+        }
+        fn store_order_order_id_get(
+            &self,
+            order_id: i64,
+        ) -> Result<Order, StoreOrderOrderIdGetError> {
+            let url = format!("{}/store/order/{order_id}", self.base_url);
+            let response = reqwest::blocking::get(url)
+                .map_err(|e| StoreOrderOrderIdGetError::OtherError(Box::new(e)))?;
+            match response.status().as_u16() {
+                200 => response
+                    .json()
+                    .map_err(|e| StoreOrderOrderIdGetError::OtherError(Box::new(e))),
+                404 => Err(StoreOrderOrderIdGetError::NotFound404(())),
+                status => panic!("unexpected status {status}"),
+            }
+        }
+        fn store_order_order_id_delete(
+            &self,
+            _order_id: i64,
+        ) -> Result<(), StoreOrderOrderIdDeleteError> {
+            unimplemented!()
+        }
+    }
+
+    #[test]
+    pub fn test_follow_link() {
+        let server = MockServer::start();
+        let get_order = server.mock(|when, then| {
+            when.method(GET).path("/store/order/42");
+            then.status(200)
+                .header("content-type", "application/json")
+                .body(r#"{"id": 42, "quantity": 3}"#);
+        });
+        let client = HttpClient {
+            base_url: server.base_url(),
+        };
+
+        // the order as the server responded to its creation
+        let created = Order {
+            id: 42,
+            quantity: None,
+        };
+        let order = created.follow_get_order_by_id(&client);
+        get_order.assert();
+        assert!(matches!(order, Ok(o) if o.id == 42 && o.quantity == Some(3)));
+    }
+}
@@ -0,0 +1,54 @@
+#![allow(dead_code)]
+
+cogenitor::generate_api!(
+    path = "test-data/map-size.yaml",
+    module_name = "map_size_api",
+    validation_newtypes = true
+);
+
+cogenitor::generate_api!(
+    path = "test-data/map-size.yaml",
+    module_name = "unchecked_map_size_api"
+);
+
+#[cfg(test)]
+mod tests {
+    use super::map_size_api::Labels;
+    use serde_json::json;
+    use std::collections::HashMap;
+
+    fn labels(count: usize) -> HashMap<String, String> {
+        (0..count)
+            .map(|i| (format!("key{i}"), format!("value{i}")))
+            .collect()
+    }
+
+    #[test]
+    pub fn test_map_size_conversions() {
+        assert!(Labels::try_from(labels(1)).is_err());
+        assert!(Labels::try_from(labels(6)).is_err());
+        for count in 2..=5 {
+            let map = Labels::try_from(labels(count)).unwrap();
+            assert_eq!(count, map.as_ref().len());
+            assert_eq!(labels(count), HashMap::from(map));
+        }
+    }
+
+    #[test]
+    pub fn test_map_size_deserialization() {
+        let result = serde_json::from_value::<Labels>(json!({"a": "1"}));
+        assert!(result.is_err());
+
+        let map: Labels = serde_json::from_value(json!({"a": "1", "b": "2"})).unwrap();
+        assert_eq!(
+            json!({"a": "1", "b": "2"}),
+            serde_json::to_value(map).unwrap()
+        );
+    }
+
+    #[test]
+    pub fn test_unchecked_map_size() {
+        let map: super::unchecked_map_size_api::Labels = labels(1);
+        assert_eq!(1, map.len());
+    }
+}
@@ -0,0 +1,28 @@
+#![allow(dead_code)]
+
+cogenitor::generate_api!(
+    path = "test-data/missing-responses.yaml",
+    module_name = "missing_responses_api",
+    missing_responses = "raw_response",
+    response_decoders = true
+);
+
+#[cfg(test)]
+mod tests {
+    use super::missing_responses_api::ClientImpl;
+
+    #[test]
+    pub fn test_decode_raw_response() {
+        for status in [200, 404] {
+            let response = http::Response::builder()
+                .status(status)
+                .body(b"pong".to_vec())
+                .unwrap();
+            let Ok(response) = ClientImpl::decode_ping_get_response(response) else {
+                panic!("raw response expected");
+            };
+            assert_eq!(status, response.status().as_u16());
+            assert_eq!(b"pong", &response.body()[..]);
+        }
+    }
+}
@@ -0,0 +1,74 @@
+#![allow(dead_code)]
+
+cogenitor::generate_api!(
+    path = "test-data/nullable.yaml",
+    module_name = "nullable_api"
+);
+
+cogenitor::generate_api!(
+    path = "test-data/nullable.yaml",
+    module_name = "patch_api",
+    double_option_for_patch = true
+);
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    #[test]
+    pub fn test_nullable_properties() {
+        let pet: super::nullable_api::PetPatch =
+            serde_json::from_value(json!({"name": "Doggy", "owner": null})).unwrap();
+        assert_eq!("Doggy", pet.name);
+        assert_eq!(None, pet.owner);
+        assert_eq!(None, pet.tag);
+        assert_eq!(None, pet.nickname);
+
+        // absent and null values are the same without double options
+        let pet: super::nullable_api::PetPatch =
+            serde_json::from_value(json!({"name": "Doggy", "owner": "Alice", "nickname": null}))
+                .unwrap();
+        assert_eq!(Some("Alice".to_string()), pet.owner);
+        assert_eq!(None, pet.nickname);
+    }
+
+    #[test]
+    pub fn test_double_option_for_patch() {
+        use super::patch_api::PetPatch;
+
+        let pet: PetPatch =
+            serde_json::from_value(json!({"name": "Doggy", "owner": null})).unwrap();
+        assert_eq!(None, pet.owner);
+        assert_eq!(None, pet.nickname);
+
+        let pet: PetPatch =
+            serde_json::from_value(json!({"name": "Doggy", "owner": null, "nickname": null}))
+                .unwrap();
+        assert_eq!(Some(None), pet.nickname);
+
+        let pet: PetPatch =
+            serde_json::from_value(json!({"name": "Doggy", "owner": null, "nickname": "Doggo"}))
+                .unwrap();
+        assert_eq!(Some(Some("Doggo".to_string())), pet.nickname);
+
+        // absent values stay absent, null values are sent as null
+        let pet = PetPatch {
+            name: "Doggy".to_string(),
+            owner: None,
+            tag: None,
+            nickname: Some(None),
+        };
+        assert_eq!(
+            json!({"name": "Doggy", "owner": null, "tag": null, "nickname": null}),
+            serde_json::to_value(&pet).unwrap()
+        );
+        let pet = PetPatch {
+            nickname: None,
+            ..pet
+        };
+        assert_eq!(
+            json!({"name": "Doggy", "owner": null, "tag": null}),
+            serde_json::to_value(&pet).unwrap()
+        );
+    }
+}
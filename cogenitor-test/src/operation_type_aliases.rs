@@ -0,0 +1,57 @@
+#![allow(dead_code)]
+
+cogenitor::generate_api!(
+    path = "test-data/petstore.yaml",
+    module_name = "alias_modules_api",
+    operation_type_aliases = "modules"
+);
+
+cogenitor::generate_api!(
+    path = "test-data/petstore.yaml",
+    module_name = "alias_prefixed_api",
+    operation_type_aliases = "prefixed"
+);
+
+#[cfg(test)]
+mod tests {
+    use super::{alias_modules_api, alias_prefixed_api};
+
+    // the aliases compile only if they are the types of the signatures
+
+    #[test]
+    pub fn test_module_aliases() {
+        use alias_modules_api::{Client, pet_pet_id_get, pet_put};
+
+        fn pet_put_fn<C: Client>()
+        -> fn(&C, pet_put::Request) -> Result<pet_put::Response, pet_put::Error> {
+            C::pet_put
+        }
+        fn pet_pet_id_get_fn<C: Client>()
+        -> fn(&C, i64) -> Result<pet_pet_id_get::Response, pet_pet_id_get::Error> {
+            C::pet_pet_id_get
+        }
+
+        // operations without request body take the unit type
+        let request: pet_pet_id_get::Request = ();
+        assert_eq!((), request);
+    }
+
+    #[test]
+    pub fn test_prefixed_aliases() {
+        use alias_prefixed_api::{
+            Client, PetPetIdGetError, PetPetIdGetRequest, PetPetIdGetResponse, PetPutError,
+            PetPutRequest, PetPutResponse,
+        };
+
+        fn pet_put_fn<C: Client>() -> fn(&C, PetPutRequest) -> Result<PetPutResponse, PetPutError> {
+            C::pet_put
+        }
+        fn pet_pet_id_get_fn<C: Client>()
+        -> fn(&C, i64) -> Result<PetPetIdGetResponse, PetPetIdGetError> {
+            C::pet_pet_id_get
+        }
+
+        let request: PetPetIdGetRequest = ();
+        assert_eq!((), request);
+    }
+}
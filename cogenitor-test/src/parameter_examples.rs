@@ -0,0 +1,41 @@
+#![allow(dead_code)]
+
+cogenitor::generate_api!(
+    path = "test-data/parameter-examples.yaml",
+    module_name = "parameter_examples_api",
+    emit_examples = true
+);
+
+// the generated example tests run with `--ignored`, and
+// PARAMETER_EXAMPLES_API_BASE_URL set
+#[cfg(test)]
+mod tests {
+    use super::parameter_examples_api::*;
+
+    /// The example tests call `ClientImpl`, which the generated code leaves
+    /// to us to implement. Instead of calling an API, we check that the
+    /// tests pass the examples.
+    impl Client for ClientImpl {
+        fn pets_get(&self, status: String, limit: i32) -> Result<Vec<Pet>, PetsGetError> {
+            assert_eq!(("available", 10), (status.as_str(), limit));
+            Ok(Vec::new())
+        }
+        fn pets_post(&self, _body: Pet) -> Result<(), PetsPostError> {
+            unimplemented!()
+        }
+        fn pets_pet_id_get(&self, pet_id: i64, verbose: bool) -> Result<Pet, PetsPetIdGetError> {
+            assert_eq!((1, true), (pet_id, verbose));
+            Ok(Pet {
+                id: Some(pet_id),
+                name: "Rex".to_string(),
+            })
+        }
+        fn pets_pet_id_photos_photo_id_get(
+            &self,
+            _pet_id: i64,
+            _photo_id: String,
+        ) -> Result<Vec<u8>, PetsPetIdPhotosPhotoIdGetError> {
+            unimplemented!()
+        }
+    }
+}
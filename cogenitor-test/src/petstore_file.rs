@@ -7,3 +7,85 @@ pub fn test_pet_present() {
 
     // this test does not fail; if it compiles it means that the Pet type is available
 }
+
+#[test]
+pub fn test_operations_listing() {
+    use generated_api::{OperationInfo, operations};
+
+    let operations = operations();
+    assert_eq!(19, operations.len());
+    assert_eq!(
+        OperationInfo {
+            name: "pet_pet_id_get",
+            method: "GET",
+            path: "/pet/{petId}",
+            summary: "Find pet by ID.",
+            tag: "pet",
+        },
+        operations[4]
+    );
+    assert!(
+        operations
+            .iter()
+            .any(|op| op.method == "DELETE" && op.path == "/user/{username}" && op.tag == "user")
+    );
+}
+
+#[test]
+pub fn test_client_impl_display() {
+    use generated_api::ClientImpl;
+
+    let client = ClientImpl {
+        base_url: "https://petstore3.swagger.io/api/v3".to_string(),
+        timeout: Some(std::time::Duration::from_secs(5)),
+        auth: None,
+    };
+    assert_eq!(
+        "ClientImpl { base_url: https://petstore3.swagger.io/api/v3, timeout: Some(5s) }",
+        client.to_string()
+    );
+    assert!(format!("{client:?}").starts_with("ClientImpl {"));
+}
+
+#[test]
+pub fn test_make_test_client() {
+    use generated_api::ClientImpl;
+
+    let client = generated_api::make_test_client!("http://localhost:8080/api/v3");
+    assert_eq!("http://localhost:8080/api/v3", client.base_url);
+    assert_eq!(None, client.timeout);
+}
+
+#[test]
+pub fn test_lookup_operation() {
+    use generated_api::{lookup_operation, operation_of};
+    use http::Method;
+
+    assert_eq!(
+        Some("pet_pet_id_get"),
+        lookup_operation(&Method::GET, "/pet/{petId}")
+    );
+    assert_eq!(Some("pet_post"), lookup_operation(&Method::POST, "/pet"));
+    assert_eq!(
+        Some("user_username_delete"),
+        lookup_operation(&Method::DELETE, "/user/{username}")
+    );
+    // templates only, concrete paths are not matched
+    assert_eq!(None, lookup_operation(&Method::GET, "/pet/42"));
+    assert_eq!(None, lookup_operation(&Method::PATCH, "/pet/{petId}"));
+    assert_eq!(None, lookup_operation(&Method::GET, "/unknown"));
+
+    assert_eq!(
+        Some((Method::GET, "/pet/{petId}")),
+        operation_of("pet_pet_id_get")
+    );
+    assert_eq!(Some((Method::POST, "/pet")), operation_of("pet_post"));
+    assert_eq!(None, operation_of("unknown_get"));
+
+    for op in generated_api::operations() {
+        let (method, path) = operation_of(op.name).unwrap();
+        assert_eq!(op.method, method.as_str());
+        assert_eq!(op.path, path);
+        assert_eq!(Some(op.name), lookup_operation(&method, path));
+    }
+}
@@ -21,7 +21,7 @@ mod tests {
                 name: Some("Dogs".to_string()),
             }),
             status: Some("placed".to_string()),
-            photoUrls: vec![],
+            photo_urls: vec![],
             tags: Some(vec![]),
         };
 
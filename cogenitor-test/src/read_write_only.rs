@@ -0,0 +1,50 @@
+#![allow(dead_code)]
+
+cogenitor::generate_api!(
+    path = "test-data/read-write-only.yaml",
+    module_name = "read_write_only_api"
+);
+
+#[cfg(test)]
+mod tests {
+    use super::read_write_only_api::Account;
+    use serde_json::json;
+
+    #[test]
+    pub fn test_read_only_not_serialized() {
+        let account = Account {
+            id: Some(1),
+            name: "jane".to_string(),
+            password: Some("s3cr3t".to_string()),
+        };
+        assert_eq!(
+            json!({"name": "jane", "password": "s3cr3t"}),
+            serde_json::to_value(&account).unwrap()
+        );
+    }
+
+    #[test]
+    pub fn test_write_only_not_deserialized() {
+        let account: Account =
+            serde_json::from_value(json!({"id": 1, "name": "jane", "password": "s3cr3t"})).unwrap();
+        assert_eq!(Some(1), account.id);
+        assert_eq!(None, account.password);
+
+        // required read-only and write-only properties may be absent
+        let account: Account = serde_json::from_value(json!({"name": "jane"})).unwrap();
+        assert_eq!(None, account.id);
+    }
+
+    #[test]
+    pub fn test_write_only_redacted_in_debug() {
+        let account = Account {
+            id: Some(1),
+            name: "jane".to_string(),
+            password: Some("s3cr3t".to_string()),
+        };
+        let debug = format!("{account:?}");
+        assert!(debug.contains(r#"password: "***""#), "{debug}");
+        assert!(debug.contains(r#"name: "jane""#), "{debug}");
+        assert!(!debug.contains("s3cr3t"), "{debug}");
+    }
+}
@@ -0,0 +1,252 @@
+#![allow(dead_code)]
+
+cogenitor::generate_api!(
+    path = "test-data/response-decoders.yaml",
+    module_name = "response_decoders_api",
+    response_decoders = true
+);
+
+cogenitor::generate_api!(
+    path = "test-data/response-decoders.yaml",
+    module_name = "collapsed_response_decoders_api",
+    media_type_strategy = "collapse_identical",
+    response_decoders = true
+);
+
+#[cfg(test)]
+mod tests {
+    use super::response_decoders_api::{
+        ClientImpl, DecodeError, PetsPetIdDeleteError, PetsPetIdGetError, PetsPetIdNameGetError,
+        PetsPetIdOwnerGetError, PetsPetIdPhotoGetOk200, PetsPetIdPhotoGetSuccess,
+    };
+
+    fn response(status: u16, content_type: &str, body: &[u8]) -> http::Response<Vec<u8>> {
+        http::Response::builder()
+            .status(status)
+            .header(http::header::CONTENT_TYPE, content_type)
+            .body(body.to_vec())
+            .unwrap()
+    }
+
+    #[test]
+    pub fn test_decode_status_codes() {
+        let pet = ClientImpl::decode_pets_pet_id_get_response(response(
+            200,
+            "application/json; charset=utf-8",
+            br#"{"name": "Doggy"}"#,
+        ));
+        assert!(matches!(pet, Ok(p) if p.name == "Doggy"));
+
+        // exact codes take precedence over ranges
+        let e = ClientImpl::decode_pets_pet_id_get_response(response(404, "text/plain", b""));
+        assert!(matches!(e, Err(PetsPetIdGetError::NotFound404(()))));
+        let e = ClientImpl::decode_pets_pet_id_get_response(response(
+            409,
+            "application/json",
+            br#"{"title": "Conflict"}"#,
+        ));
+        assert!(matches!(e, Err(PetsPetIdGetError::Status4XX(p)) if p.title == "Conflict"));
+        let e = ClientImpl::decode_pets_pet_id_get_response(response(503, "text/plain", b""));
+        assert!(matches!(
+            e,
+            Err(PetsPetIdGetError::Status5XX(
+                http::StatusCode::SERVICE_UNAVAILABLE
+            ))
+        ));
+
+        // undeclared statuses and malformed bodies are reported as well
+        let e = ClientImpl::decode_pets_pet_id_get_response(response(302, "text/plain", b"moved"));
+        match e {
+            Err(PetsPetIdGetError::UnknownResponse(r)) => assert_eq!(b"moved", &r.body()[..]),
+            _ => panic!("unknown response expected"),
+        }
+        let e = ClientImpl::decode_pets_pet_id_get_response(response(
+            200,
+            "application/json",
+            br#"{"name": 42}"#,
+        ));
+        match e {
+            Err(PetsPetIdGetError::OtherError(e)) => {
+                let e = e.downcast_ref::<DecodeError>().unwrap();
+                assert_eq!("name", e.path);
+            }
+            _ => panic!("decode error expected"),
+        }
+    }
+
+    #[test]
+    pub fn test_decode_default_response() {
+        let r = ClientImpl::decode_pets_pet_id_delete_response(response(204, "text/plain", b""));
+        assert!(matches!(r, Ok(())));
+        let e = ClientImpl::decode_pets_pet_id_delete_response(response(
+            500,
+            "application/json",
+            br#"{"title": "Oops"}"#,
+        ));
+        assert!(matches!(e, Err(PetsPetIdDeleteError::Default(p)) if p.title == "Oops"));
+    }
+
+    #[test]
+    pub fn test_decode_media_types() {
+        let photo = ClientImpl::decode_pets_pet_id_photo_get_response(response(
+            200,
+            "image/png",
+            b"\x89PNG",
+        ));
+        assert!(matches!(
+            photo,
+            Ok(PetsPetIdPhotoGetSuccess::Ok200(PetsPetIdPhotoGetOk200::ImageAny(bytes)))
+                if bytes == b"\x89PNG"
+        ));
+        let photo = ClientImpl::decode_pets_pet_id_photo_get_response(response(
+            200,
+            "application/json",
+            br#"{"url": "https://example.com/doggy.png"}"#,
+        ));
+        assert!(matches!(
+            photo,
+            Ok(PetsPetIdPhotoGetSuccess::Ok200(PetsPetIdPhotoGetOk200::ApplicationJson(p)))
+                if p.url == "https://example.com/doggy.png"
+        ));
+        let photo = ClientImpl::decode_pets_pet_id_photo_get_response(response(202, "", b""));
+        assert!(matches!(
+            photo,
+            Ok(PetsPetIdPhotoGetSuccess::Accepted202(()))
+        ));
+
+        // media types not declared are unknown
+        let photo =
+            ClientImpl::decode_pets_pet_id_photo_get_response(response(200, "text/html", b""));
+        assert!(photo.is_err());
+    }
+
+    #[test]
+    pub fn test_decode_text() {
+        let name = ClientImpl::decode_pets_pet_id_name_get_response(response(
+            200,
+            "text/plain; charset=utf-8",
+            "Snoopy 🐶".as_bytes(),
+        ));
+        assert!(matches!(name, Ok(n) if n == "Snoopy 🐶"));
+
+        let name =
+            ClientImpl::decode_pets_pet_id_name_get_response(response(200, "text/plain", b"\xff"));
+        assert!(matches!(name, Err(PetsPetIdNameGetError::OtherError(_))));
+    }
+
+    #[test]
+    pub fn test_decode_collapsed_media_types() {
+        use super::collapsed_response_decoders_api::ClientImpl;
+
+        for content_type in ["application/json", "application/vnd.petstore.owner+json"] {
+            let owner = ClientImpl::decode_pets_pet_id_owner_get_response(response(
+                200,
+                content_type,
+                br#"{"name": "Jane"}"#,
+            ));
+            assert!(matches!(owner, Ok(o) if o.name == "Jane"));
+        }
+        let owner = ClientImpl::decode_pets_pet_id_owner_get_response(response(
+            200,
+            "application/xml",
+            b"<owner/>",
+        ));
+        assert!(owner.is_err());
+    }
+
+    fn too_many_requests(headers: &[(&'static str, &str)]) -> http::Response<Vec<u8>> {
+        let mut response = response(429, "application/json", br#"{"title": "Slow down"}"#);
+        for (name, value) in headers {
+            response
+                .headers_mut()
+                .insert(*name, http::HeaderValue::from_str(value).unwrap());
+        }
+        response
+    }
+
+    #[test]
+    pub fn test_decode_retry_after() {
+        let e = ClientImpl::decode_pets_pet_id_owner_get_response(too_many_requests(&[(
+            "retry-after",
+            "120",
+        )]));
+        match e {
+            Err(PetsPetIdOwnerGetError::TooManyRequests429 {
+                body, retry_after, ..
+            }) => {
+                assert_eq!("Slow down", body.title);
+                assert_eq!(Some(std::time::Duration::from_secs(120)), retry_after);
+            }
+            _ => panic!("too many requests expected"),
+        }
+
+        // 2100-01-01T00:00:00Z
+        let e = ClientImpl::decode_pets_pet_id_owner_get_response(too_many_requests(&[(
+            "retry-after",
+            "Fri, 01 Jan 2100 00:00:00 GMT",
+        )]));
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        match e {
+            Err(PetsPetIdOwnerGetError::TooManyRequests429 { retry_after, .. }) => {
+                let expected = 4102444800 - now;
+                let actual = retry_after.unwrap().as_secs();
+                assert!(actual.abs_diff(expected) <= 5, "{actual} != {expected}");
+            }
+            _ => panic!("too many requests expected"),
+        }
+
+        // dates in the past don't delay at all
+        let e = ClientImpl::decode_pets_pet_id_owner_get_response(too_many_requests(&[(
+            "retry-after",
+            "Sun, 06 Nov 1994 08:49:37 GMT",
+        )]));
+        assert!(matches!(
+            e,
+            Err(PetsPetIdOwnerGetError::TooManyRequests429 {
+                retry_after: Some(d),
+                ..
+            }) if d.is_zero()
+        ));
+
+        // absent or malformed headers yield no delay
+        for headers in [&[][..], &[("retry-after", "tomorrow")][..]] {
+            let e = ClientImpl::decode_pets_pet_id_owner_get_response(too_many_requests(headers));
+            assert!(matches!(
+                e,
+                Err(PetsPetIdOwnerGetError::TooManyRequests429 {
+                    retry_after: None,
+                    ..
+                })
+            ));
+        }
+    }
+
+    #[test]
+    pub fn test_decode_rate_limit_headers() {
+        let e = ClientImpl::decode_pets_pet_id_owner_get_response(too_many_requests(&[
+            ("x-ratelimit-remaining", "0"),
+            ("x-ratelimit-reset", "1700000000"),
+        ]));
+        assert!(matches!(
+            e,
+            Err(PetsPetIdOwnerGetError::TooManyRequests429 {
+                rate_limit_remaining: Some(0),
+                rate_limit_reset: Some(1700000000),
+                ..
+            })
+        ));
+
+        let e = ClientImpl::decode_pets_pet_id_owner_get_response(too_many_requests(&[]));
+        assert!(matches!(
+            e,
+            Err(PetsPetIdOwnerGetError::TooManyRequests429 {
+                rate_limit_remaining: None,
+                rate_limit_reset: None,
+                ..
+            })
+        ));
+    }
+}
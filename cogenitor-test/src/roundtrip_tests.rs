@@ -0,0 +1,8 @@
+#![allow(dead_code)]
+
+// the generated module brings its own round-trip tests for each type
+cogenitor::generate_api!(
+    path = "test-data/petstore.yaml",
+    module_name = "roundtrip_api",
+    roundtrip_tests = true
+);
@@ -0,0 +1,29 @@
+#![allow(dead_code)]
+
+cogenitor::generate_api!(
+    path = "test-data/schema-names.yaml",
+    module_name = "schema_names_api"
+);
+
+#[cfg(test)]
+mod tests {
+    use super::schema_names_api::{Schema3DModel, SearchResults, UserV1Profile, UserV1Profile1};
+    use serde_json::json;
+
+    #[test]
+    pub fn test_schema_names() {
+        let profile: UserV1Profile = serde_json::from_value(json!({
+            "name": "Alice",
+            "legacy": {"nickname": "al"}
+        }))
+        .unwrap();
+        assert_eq!("Alice", profile.name);
+        let legacy: UserV1Profile1 = profile.legacy.unwrap();
+        assert_eq!(Some("al".to_string()), legacy.nickname);
+
+        let results: SearchResults =
+            serde_json::from_value(json!({"models": [{"vertices": 8}]})).unwrap();
+        let models: Vec<Schema3DModel> = results.models.unwrap();
+        assert_eq!(Some(8.0), models[0].vertices);
+    }
+}
@@ -0,0 +1,72 @@
+#![allow(dead_code)]
+
+cogenitor::generate_api!(
+    path = "test-data/security.yaml",
+    module_name = "security_api"
+);
+
+#[cfg(test)]
+mod tests {
+    use super::security_api::{ApiKeyLocation, SecurityMiddleware};
+
+    fn apply(auth: &SecurityMiddleware) -> http::Request<()> {
+        let rb = http::Request::builder().uri("https://example.com/pets?limit=10");
+        SecurityMiddleware::apply_security(rb, auth)
+            .body(())
+            .unwrap()
+    }
+
+    #[test]
+    pub fn test_bearer_token() {
+        let request = apply(&SecurityMiddleware::BearerToken("abc".to_string()));
+        assert_eq!("Bearer abc", request.headers()[http::header::AUTHORIZATION]);
+    }
+
+    #[test]
+    pub fn test_basic_auth() {
+        let request = apply(&SecurityMiddleware::BasicAuth {
+            username: "Aladdin".to_string(),
+            password: "open sesame".to_string(),
+        });
+        // example from RFC 7617, section 2
+        assert_eq!(
+            "Basic QWxhZGRpbjpvcGVuIHNlc2FtZQ==",
+            request.headers()[http::header::AUTHORIZATION]
+        );
+    }
+
+    #[test]
+    pub fn test_debug_hides_credentials() {
+        let token = SecurityMiddleware::BearerToken("abc".to_string());
+        assert_eq!(r#"BearerToken("***")"#, format!("{token:?}"));
+        let basic = SecurityMiddleware::BasicAuth {
+            username: "Aladdin".to_string(),
+            password: "open sesame".to_string(),
+        };
+        assert_eq!(
+            r#"BasicAuth { username: "Aladdin", password: "***" }"#,
+            format!("{basic:?}")
+        );
+    }
+
+    #[test]
+    pub fn test_api_key() {
+        let key = |location| SecurityMiddleware::ApiKey {
+            name: "X-API-Key".to_string(),
+            key: "s3cr3t&".to_string(),
+            location,
+        };
+
+        let request = apply(&key(ApiKeyLocation::Header));
+        assert_eq!("s3cr3t&", request.headers()["X-API-Key"]);
+
+        let request = apply(&key(ApiKeyLocation::Query));
+        assert_eq!(
+            "https://example.com/pets?limit=10&X-API-Key=s3cr3t%26",
+            request.uri().to_string()
+        );
+
+        let request = apply(&key(ApiKeyLocation::Cookie));
+        assert_eq!("X-API-Key=s3cr3t&", request.headers()[http::header::COOKIE]);
+    }
+}
@@ -0,0 +1,72 @@
+#![allow(dead_code)]
+
+cogenitor::generate_api!(
+    path = "test-data/security-override.yaml",
+    module_name = "security_override_api"
+);
+
+#[cfg(test)]
+mod tests {
+    use super::security_override_api::{ApiKeyLocation, ClientImpl, SecurityMiddleware};
+
+    fn client_with(auth: SecurityMiddleware) -> ClientImpl {
+        ClientImpl {
+            base_url: "https://example.com".to_string(),
+            timeout: None,
+            auth: Some(auth),
+        }
+    }
+
+    fn api_key() -> SecurityMiddleware {
+        SecurityMiddleware::ApiKey {
+            name: "X-API-Key".to_string(),
+            key: "s3cr3t".to_string(),
+            location: ApiKeyLocation::Header,
+        }
+    }
+
+    fn build(rb: http::request::Builder) -> http::Request<()> {
+        rb.body(()).unwrap()
+    }
+
+    #[test]
+    pub fn test_debug_hides_credentials() {
+        let client = client_with(api_key());
+        let debug = format!("{client:?}");
+        assert!(!debug.contains("s3cr3t"), "{debug}");
+        assert!(
+            debug.contains(r#"ApiKey { name: "X-API-Key", key: "***", location: Header }"#),
+            "{debug}"
+        );
+    }
+
+    #[test]
+    pub fn test_global_security() {
+        let client = client_with(api_key());
+        let request = build(client.apply_pets_get_security(http::Request::builder()));
+        assert_eq!("s3cr3t", request.headers()["X-API-Key"]);
+    }
+
+    #[test]
+    pub fn test_security_override_without_auth() {
+        let client = client_with(api_key());
+        let request = build(client.apply_login_post_security(http::Request::builder()));
+        assert!(request.headers().is_empty());
+    }
+
+    #[test]
+    pub fn test_alternative_security_requirements() {
+        let client = client_with(SecurityMiddleware::BearerToken("abc".to_string()));
+        let request = build(client.apply_pets_pet_id_delete_security(http::Request::builder()));
+        assert_eq!("Bearer abc", request.headers()[http::header::AUTHORIZATION]);
+
+        let client = client_with(api_key());
+        let request = build(client.apply_pets_pet_id_delete_security(http::Request::builder()));
+        assert_eq!("s3cr3t", request.headers()["X-API-Key"]);
+
+        // credentials satisfying none of the requirements are not sent
+        let client = client_with(SecurityMiddleware::BearerToken("abc".to_string()));
+        let request = build(client.apply_pets_get_security(http::Request::builder()));
+        assert!(request.headers().is_empty());
+    }
+}
@@ -0,0 +1,49 @@
+#![allow(dead_code)]
+
+cogenitor::generate_api!(path = "test-data/servers.yaml", module_name = "servers_api");
+
+#[cfg(test)]
+mod tests {
+    use super::servers_api::{ClientImpl, ServerEnv, server_url};
+
+    #[test]
+    pub fn test_server_url() {
+        assert_eq!(
+            "https://production.example.com/v1",
+            server_url(ServerEnv::Production, "443").unwrap().as_str()
+        );
+        assert_eq!(
+            "https://staging.example.com:8443/v1",
+            server_url(ServerEnv::Staging, "8443").unwrap().as_str()
+        );
+
+        // free-form variables may make the URL invalid
+        assert_eq!(
+            Err(url::ParseError::InvalidPort),
+            server_url(ServerEnv::Staging, "port")
+        );
+    }
+
+    #[test]
+    pub fn test_url_for_keeps_base_path() {
+        let client = ClientImpl {
+            base_url: server_url(ServerEnv::Production, "443")
+                .unwrap()
+                .to_string(),
+            timeout: None,
+        };
+        assert_eq!(
+            "https://production.example.com/v1/pets",
+            client.url_for("/pets").unwrap().as_str()
+        );
+
+        for base_url in ["https://example.com", "https://example.com/v1/"] {
+            let client = ClientImpl {
+                base_url: base_url.to_string(),
+                timeout: None,
+            };
+            let expected = format!("{}/pets", base_url.trim_end_matches('/'));
+            assert_eq!(expected, client.url_for("/pets").unwrap().as_str());
+        }
+    }
+}
@@ -0,0 +1,69 @@
+#![allow(dead_code)]
+
+cogenitor::generate_api!(
+    path = "test-data/single-value-enums.yaml",
+    module_name = "marker_api"
+);
+
+cogenitor::generate_api!(
+    path = "test-data/single-value-enums.yaml",
+    module_name = "string_api",
+    single_value_enums = "string"
+);
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    #[test]
+    pub fn test_marker_serializes_value() {
+        use super::marker_api::{CatMarker, Dog, DogMarker, Puppy};
+
+        let dog = Dog {
+            pet_type: DogMarker,
+            name: "Rex".to_string(),
+            kind: None,
+        };
+        assert_eq!(
+            json!({"petType": "dog", "name": "Rex", "kind": null}),
+            serde_json::to_value(&dog).unwrap()
+        );
+        assert_eq!("cat", CatMarker::VALUE);
+
+        // properties with the same value share their marker
+        let puppy = Puppy {
+            pet_type: DogMarker,
+        };
+        assert_eq!(
+            json!({"petType": "dog"}),
+            serde_json::to_value(&puppy).unwrap()
+        );
+    }
+
+    #[test]
+    pub fn test_marker_rejects_other_values() {
+        use super::marker_api::Dog;
+
+        let dog: Dog =
+            serde_json::from_value(json!({"petType": "dog", "name": "Rex", "kind": "mammal"}))
+                .unwrap();
+        assert_eq!("Rex", dog.name);
+        assert_eq!(Some("mammal".to_string()), dog.kind);
+
+        let error =
+            serde_json::from_value::<Dog>(json!({"petType": "cat", "name": "Rex"})).unwrap_err();
+        assert!(
+            error.to_string().contains("invalid value: string \"cat\""),
+            "{error}"
+        );
+        serde_json::from_value::<Dog>(json!({"name": "Rex"})).unwrap_err();
+    }
+
+    #[test]
+    pub fn test_string_mapping() {
+        use super::string_api::Dog;
+
+        let dog: Dog = serde_json::from_value(json!({"petType": "cat", "name": "Rex"})).unwrap();
+        assert_eq!("cat", dog.pet_type);
+    }
+}
@@ -0,0 +1,74 @@
+#![allow(dead_code)]
+
+cogenitor::generate_api!(
+    path = "test-data/sse.yaml",
+    module_name = "sse_api",
+    sse_events = true
+);
+
+#[cfg(test)]
+mod tests {
+    use super::sse_api::{Client, NotificationsGetError, SseEvent, SseEvents};
+    use std::io::Cursor;
+
+    static STREAM: &str = ": connected\n\
+        \n\
+        data: first\n\
+        \n\
+        event: alert\r\n\
+        id: 2\r\n\
+        retry: 1000\r\n\
+        data: second,\r\n\
+        data:  indented\r\n\
+        \r\n\
+        id: 3\n\
+        data\n\
+        \n\
+        data: incomplete\n";
+
+    struct StreamingClient;
+
+    impl Client for StreamingClient {
+        fn notifications_get(&self, _topic: String) -> Result<Vec<u8>, NotificationsGetError> {
+            Ok(STREAM.as_bytes().to_vec())
+        }
+
+        fn notifications_get_events(
+            &self,
+            _topic: String,
+        ) -> Result<impl Iterator<Item = Result<SseEvent, std::io::Error>>, NotificationsGetError>
+        {
+            Ok(SseEvents::new(Cursor::new(STREAM)))
+        }
+    }
+
+    #[test]
+    pub fn test_sse_events() {
+        let events = StreamingClient
+            .notifications_get_events("pets".to_string())
+            .ok()
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(
+            vec![
+                SseEvent {
+                    event: None,
+                    data: "first".to_string(),
+                    id: None,
+                },
+                SseEvent {
+                    event: Some("alert".to_string()),
+                    data: "second,\n indented".to_string(),
+                    id: Some("2".to_string()),
+                },
+                SseEvent {
+                    event: None,
+                    data: "".to_string(),
+                    id: Some("3".to_string()),
+                },
+            ],
+            events
+        );
+    }
+}
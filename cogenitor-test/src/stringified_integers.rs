@@ -0,0 +1,43 @@
+#![allow(dead_code)]
+
+cogenitor::generate_api!(
+    path = "test-data/stringified-integers.yaml",
+    module_name = "stringified_integers_api",
+    stringified_integers = true
+);
+
+#[cfg(test)]
+mod tests {
+    use super::stringified_integers_api::{Account, Int64String, UInt64String};
+    use serde_json::json;
+
+    #[test]
+    pub fn test_stringified_integers_roundtrip() {
+        // both values are above 2^53, which JSON numbers (as f64) cannot represent precisely
+        let account = Account {
+            id: Int64String(9_007_199_254_740_993),
+            balance: UInt64String(u64::MAX),
+            parent_id: Some(Int64String(-9_007_199_254_740_993)),
+        };
+
+        let value = serde_json::to_value(&account).unwrap();
+        let expected_value = json!({
+            "id": "9007199254740993",
+            "balance": "18446744073709551615",
+            "parent_id": "-9007199254740993",
+        });
+        assert_eq!(expected_value, value);
+
+        let parsed: Account = serde_json::from_value(value).unwrap();
+        assert_eq!(account, parsed);
+    }
+
+    #[test]
+    pub fn test_stringified_integers_invalid() {
+        // the JSON representation must be a string...
+        serde_json::from_value::<Int64String>(json!(42)).unwrap_err();
+        // ...holding an integer in range
+        serde_json::from_value::<Int64String>(json!("forty-two")).unwrap_err();
+        serde_json::from_value::<UInt64String>(json!("-1")).unwrap_err();
+    }
+}
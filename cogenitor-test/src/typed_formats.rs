@@ -0,0 +1,50 @@
+#![allow(dead_code)]
+
+// the generated round-trip tests build a payment from its required
+// properties, which need to parse as decimals
+cogenitor::generate_api!(
+    path = "test-data/decimals.yaml",
+    module_name = "typed_formats_api",
+    typed_formats = true,
+    roundtrip_tests = true
+);
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::typed_formats_api::Payment;
+    use rust_decimal::Decimal;
+    use serde_json::json;
+
+    #[test]
+    pub fn test_decimal_roundtrip() {
+        let payment = Payment {
+            amount: Decimal::from_str("1234.10").unwrap(),
+            rate: Decimal::from_str("0.25").unwrap(),
+            fee: Some(Decimal::from_str("0.99").unwrap()),
+            tax: None,
+        };
+
+        // decimal strings keep their digits, decimal numbers stay numbers
+        let value = serde_json::to_value(&payment).unwrap();
+        assert_eq!(json!("1234.10"), value["amount"]);
+        assert_eq!(json!(0.25), value["rate"]);
+        assert_eq!(json!("0.99"), value["fee"]);
+
+        let parsed: Payment = serde_json::from_value(value).unwrap();
+        assert_eq!(payment, parsed);
+
+        // absent optional decimals are None
+        let parsed: Payment =
+            serde_json::from_value(json!({ "amount": "0.10", "rate": 1.5 })).unwrap();
+        assert_eq!(Decimal::from_str("0.10").unwrap(), parsed.amount);
+        assert_eq!(None, parsed.fee);
+        assert_eq!(None, parsed.tax);
+    }
+
+    #[test]
+    pub fn test_decimal_invalid() {
+        serde_json::from_value::<Payment>(json!({ "amount": "ten", "rate": 1.5 })).unwrap_err();
+    }
+}
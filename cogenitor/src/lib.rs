@@ -1,4 +1,25 @@
 pub use cogenitor_core::ApiConfig;
+pub use cogenitor_core::CorpusOutcome;
+pub use cogenitor_core::CorpusReport;
+pub use cogenitor_core::DefaultNamingPolicy;
+pub use cogenitor_core::GenerateCheck;
+pub use cogenitor_core::GenerationStats;
+pub use cogenitor_core::ModelDiff;
+pub use cogenitor_core::NamingPolicy;
+pub use cogenitor_core::OperationSelector;
+pub use cogenitor_core::StatusSpec;
+pub use cogenitor_core::Visibility;
+pub use cogenitor_core::diff;
+pub use cogenitor_core::generate_batch;
+#[cfg(feature = "parallel")]
+pub use cogenitor_core::generate_batch_parallel;
+pub use cogenitor_core::generate_codemodel;
 pub use cogenitor_core::generate_file;
+pub use cogenitor_core::generate_file_check;
+pub use cogenitor_core::generate_file_if_changed;
+pub use cogenitor_core::generate_mod_with_stats;
+pub use cogenitor_core::generate_string;
 pub use cogenitor_core::generate_token_stream;
+pub use cogenitor_core::run_corpus;
+pub use cogenitor_core::show_operation;
 pub use cogenitor_macro::generate_api;
@@ -0,0 +1,228 @@
+//! Generates the code for a spec into a file:
+//!
+//! ```text
+//! cogenitor [--check] <spec> <output>
+//! ```
+//!
+//! With `--check`, the file is not written. Instead, the command fails if
+//! the file is missing or stale relative to the spec, printing a diff, so
+//! that CI can detect checked-in generated files needing regeneration.
+//!
+//! Generates the code for all specs below a directory, reporting the specs
+//! that fail as a table, or as JSON with `--json`:
+//!
+//! ```text
+//! cogenitor corpus [--timeout <seconds>] [--json] <dir>
+//! ```
+//!
+//! The command fails if generation fails for any of the specs.
+//!
+//! Shows what is generated for a single operation of a spec, selected by
+//! its path template and method or by its `operationId`:
+//!
+//! ```text
+//! cogenitor show <spec> (--operation <path> <method> | --operation-id <id>)
+//! ```
+
+use std::path::Path;
+use std::process::ExitCode;
+use std::time::Duration;
+
+use cogenitor::{ApiConfig, CorpusOutcome, CorpusReport, GenerateCheck, OperationSelector};
+
+const USAGE: &str = "usage: cogenitor [--check] <spec> <output>
+       cogenitor corpus [--timeout <seconds>] [--json] <dir>
+       cogenitor show <spec> (--operation <path> <method> | --operation-id <id>)";
+
+/// The time generating code for a single spec of a corpus may take, unless
+/// given with `--timeout`
+const DEFAULT_CORPUS_TIMEOUT: Duration = Duration::from_secs(60);
+
+fn main() -> ExitCode {
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+    match run(&args) {
+        Ok(code) => code,
+        Err(e) => {
+            eprintln!("{e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(args: &[String]) -> Result<ExitCode, String> {
+    match args.split_first().map(|(cmd, args)| (cmd.as_str(), args)) {
+        Some(("corpus", args)) => return run_corpus(args),
+        Some(("show", args)) => return run_show(args),
+        _ => {}
+    }
+    let check = args.iter().any(|arg| arg == "--check");
+    let [spec_path, output_path] = args
+        .iter()
+        .filter(|arg| *arg != "--check")
+        .collect::<Vec<_>>()[..]
+    else {
+        return Err(USAGE.to_string());
+    };
+    let config = ApiConfig::new_from_path(spec_path.clone());
+    let output_path = Path::new(output_path);
+
+    if !check {
+        cogenitor::generate_file(&config, output_path).map_err(|e| e.to_string())?;
+        return Ok(ExitCode::SUCCESS);
+    }
+    match cogenitor::generate_file_check(&config, output_path).map_err(|e| e.to_string())? {
+        GenerateCheck::Unchanged => Ok(ExitCode::SUCCESS),
+        GenerateCheck::Changed { diff_summary } => {
+            println!(
+                "--- {}\n+++ generated from {spec_path}",
+                output_path.display()
+            );
+            print!("{diff_summary}");
+            Ok(ExitCode::FAILURE)
+        }
+        GenerateCheck::Missing => {
+            println!("{} is missing", output_path.display());
+            Ok(ExitCode::FAILURE)
+        }
+    }
+}
+
+fn run_corpus(args: &[String]) -> Result<ExitCode, String> {
+    let mut timeout = DEFAULT_CORPUS_TIMEOUT;
+    let mut json = false;
+    let mut dir = None;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--json" => json = true,
+            "--timeout" => {
+                let seconds = args.next().ok_or(USAGE)?;
+                let seconds = seconds
+                    .parse::<f64>()
+                    .map_err(|e| format!("invalid timeout '{seconds}': {e}"))?;
+                timeout = Duration::try_from_secs_f64(seconds)
+                    .map_err(|e| format!("invalid timeout '{seconds}': {e}"))?;
+            }
+            _ if dir.is_none() => dir = Some(arg),
+            _ => return Err(USAGE.to_string()),
+        }
+    }
+    let dir = dir.ok_or(USAGE)?;
+
+    let report = cogenitor::run_corpus(Path::new(dir), &ApiConfig::default(), timeout)
+        .map_err(|e| e.to_string())?;
+    if json {
+        println!("{:#}", report.to_json());
+    } else {
+        println!("{report}");
+    }
+    Ok(if all_succeeded(&report) {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    })
+}
+
+fn run_show(args: &[String]) -> Result<ExitCode, String> {
+    let (spec_path, selector) = match args {
+        [spec_path, option, path, method] if option == "--operation" => (
+            spec_path,
+            format!("{method} {path}").parse::<OperationSelector>()?,
+        ),
+        [spec_path, option, operation_id] if option == "--operation-id" => (
+            spec_path,
+            OperationSelector::OperationId(operation_id.clone()),
+        ),
+        _ => return Err(USAGE.to_string()),
+    };
+    let config = ApiConfig::new_from_path(spec_path.clone());
+    let shown = cogenitor::show_operation(&config, &selector).map_err(|e| e.to_string())?;
+    print!("{shown}");
+    Ok(ExitCode::SUCCESS)
+}
+
+fn all_succeeded(report: &CorpusReport) -> bool {
+    report.count(&CorpusOutcome::Success) == report.entries.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::process::ExitCode;
+
+    #[test]
+    fn test_check() {
+        let spec_path = concat!(env!("CARGO_MANIFEST_DIR"), "/../test-data/petstore.yaml");
+        let output_path = std::env::temp_dir().join("cogenitor_test_check.rs");
+        let _ = std::fs::remove_file(&output_path);
+        let args = |check: bool| {
+            let mut args = vec![
+                spec_path.to_string(),
+                output_path.to_string_lossy().into_owned(),
+            ];
+            if check {
+                args.insert(0, "--check".to_string());
+            }
+            args
+        };
+
+        assert_eq!(Ok(ExitCode::FAILURE), super::run(&args(true)));
+        assert_eq!(Ok(ExitCode::SUCCESS), super::run(&args(false)));
+        assert_eq!(Ok(ExitCode::SUCCESS), super::run(&args(true)));
+        std::fs::write(&output_path, "// stale\n").unwrap();
+        assert_eq!(Ok(ExitCode::FAILURE), super::run(&args(true)));
+
+        assert!(super::run(&["--check".to_string()]).is_err());
+        std::fs::remove_file(&output_path).unwrap();
+    }
+
+    #[test]
+    fn test_corpus() {
+        let corpus_dir = std::env::temp_dir().join("cogenitor_test_corpus_cli");
+        let _ = std::fs::remove_dir_all(&corpus_dir);
+        std::fs::create_dir_all(&corpus_dir).unwrap();
+        let petstore = concat!(env!("CARGO_MANIFEST_DIR"), "/../test-data/petstore.yaml");
+        std::fs::copy(petstore, corpus_dir.join("petstore.yaml")).unwrap();
+        let args = |extra: &[&str]| {
+            let mut args = vec!["corpus".to_string()];
+            args.extend(extra.iter().map(|arg| arg.to_string()));
+            args.push(corpus_dir.to_string_lossy().into_owned());
+            args
+        };
+
+        assert_eq!(Ok(ExitCode::SUCCESS), super::run(&args(&[])));
+        assert_eq!(
+            Ok(ExitCode::SUCCESS),
+            super::run(&args(&["--json", "--timeout", "30"]))
+        );
+        std::fs::write(corpus_dir.join("broken.yaml"), "openapi: 3.0.0\n").unwrap();
+        assert_eq!(Ok(ExitCode::FAILURE), super::run(&args(&[])));
+
+        assert!(super::run(&args(&["--timeout", "soon"])).is_err());
+        assert!(super::run(&["corpus".to_string()]).is_err());
+        std::fs::remove_dir_all(&corpus_dir).unwrap();
+    }
+
+    #[test]
+    fn test_show() {
+        let spec_path = concat!(env!("CARGO_MANIFEST_DIR"), "/../test-data/petstore.yaml");
+        let args = |args: &[&str]| {
+            std::iter::once("show")
+                .chain(std::iter::once(spec_path))
+                .chain(args.iter().copied())
+                .map(str::to_string)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            Ok(ExitCode::SUCCESS),
+            super::run(&args(&["--operation", "/pet/{petId}", "get"]))
+        );
+        assert_eq!(
+            Ok(ExitCode::SUCCESS),
+            super::run(&args(&["--operation-id", "addPet"]))
+        );
+        assert!(super::run(&args(&["--operation", "/nowhere", "delete"])).is_err());
+        assert!(super::run(&args(&["--operation", "/pet", "g{et"])).is_err());
+        assert!(super::run(&args(&["--operation-id"])).is_err());
+    }
+}